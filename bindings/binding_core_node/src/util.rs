@@ -61,7 +61,7 @@ pub fn init_custom_trace_subscriber(
 pub fn try_with<F, Ret>(
     cm: Lrc<SourceMap>,
     skip_filename: bool,
-    _error_format: ErrorFormat,
+    error_format: ErrorFormat,
     op: F,
 ) -> Result<Ret, Error>
 where
@@ -72,6 +72,7 @@ where
             cm,
             swc_core::base::HandlerOpts {
                 skip_filename,
+                format: error_format.diagnostic_format(),
                 ..Default::default()
             },
             |handler| {
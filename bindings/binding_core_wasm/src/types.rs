@@ -671,8 +671,23 @@ export interface JscConfig {
      * a name of the npm package can be resolved, or absolute path to .wasm binary.
      *
      * Second parameter of tuple is JSON based configuration for the plugin.
+     *
+     * Optional third parameter is a list of files or directories the plugin can
+     * resolve and read through the host.
+     *
+     * Optional fourth parameter limits the resources the plugin can use.
+     * `memory` is in bytes, `fuel` is the number of wasm instructions and
+     * `timeout` is in milliseconds. The transform fails if the plugin runs
+     * out of them.
      */
-    plugins?: Array<[string, Record<string, any>]>
+    plugins?: Array<
+      [
+        string,
+        Record<string, any>,
+        string[]?,
+        { memory?: number; fuel?: number; timeout?: number }?
+      ]
+    >
   },
 
   baseUrl?: string
@@ -681,6 +696,18 @@ export interface JscConfig {
     [from: string]: string[]
   }
 
+  /**
+   * Rules to rewrite module specifiers of imports, exports, `import()` and
+   * `require()` calls. Rules are applied in order, before `paths`, and each
+   * rule is applied to the result of the previous ones.
+   *
+   * `replacement` can refer to capture groups of `pattern` using `$1`.
+   */
+  rewriteImports?: Array<{
+    pattern: string,
+    replacement: string
+  }>
+
   minify?: JsMinifyOptions;
 
   preserveAllComments?: boolean;
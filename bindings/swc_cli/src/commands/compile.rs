@@ -371,6 +371,7 @@ impl CompileOptions {
                 HandlerOpts {
                     color: ColorConfig::Always,
                     skip_filename: false,
+                    ..Default::default()
                 },
                 |handler| {
                     GLOBALS.set(&Default::default(), || {
@@ -9,11 +9,11 @@ use once_cell::sync::Lazy;
 #[doc(hidden)]
 pub use serde_wasm_bindgen;
 use serde_wasm_bindgen::Serializer;
-use swc::{config::ErrorFormat, Compiler, HandlerOpts};
+use swc::{config::ErrorFormat, Compiler};
 #[doc(hidden)]
 pub use swc::{
     config::{Options, ParseOptions, SourceMapsConfig},
-    try_with_handler,
+    try_with_handler, HandlerOpts,
 };
 #[doc(hidden)]
 pub use swc_common::{
@@ -188,7 +188,7 @@ macro_rules! build_parse_sync {
                   program.visit_mut_with(&mut $crate::wasm::resolver(
                     $crate::wasm::Mark::new(),
                     $crate::wasm::Mark::new(),
-                    opts.syntax.typescript(),
+                    opts.syntax.typescript() || opts.syntax.flow(),
                   ));
 
                   program
@@ -358,7 +358,10 @@ macro_rules! build_transform_sync {
         let error_format = opts.experimental.error_format.unwrap_or_default();
         $crate::wasm::try_with_handler_globals(
             c.cm.clone(),
-            $opt,
+            $crate::wasm::HandlerOpts {
+                format: error_format.diagnostic_format(),
+                ..$opt
+            },
             |handler| {
                 c.run(|| {
                   let s = $crate::wasm::JsCast::dyn_into::<$crate::wasm::js_sys::JsString>(s);
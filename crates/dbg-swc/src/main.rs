@@ -62,6 +62,7 @@ fn main() -> Result<()> {
             HandlerOpts {
                 color: ColorConfig::Always,
                 skip_filename: false,
+                ..Default::default()
             },
             |handler| {
                 GLOBALS.set(&Globals::default(), || {
@@ -114,6 +115,7 @@ fn main() -> Result<()> {
         HandlerOpts {
             color: ColorConfig::Always,
             skip_filename: false,
+            ..Default::default()
         },
         |handler| {
             GLOBALS.set(&Globals::default(), || {
@@ -7,11 +7,11 @@ use anyhow::{Context, Error};
 use dashmap::DashMap;
 use from_variant::FromVariant;
 use once_cell::sync::Lazy;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{version::Version, BrowserData, Versions};
 
-#[derive(Debug, Clone, Deserialize, FromVariant)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromVariant)]
 #[serde(untagged)]
 #[allow(clippy::large_enum_variant)]
 pub enum Targets {
@@ -24,20 +24,22 @@ pub enum Targets {
     HashMap(HashMap<String, QueryOrVersion, ahash::RandomState>),
 }
 
-#[derive(Debug, Clone, Copy, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct EsModules {
     #[allow(dead_code)]
     esmodules: bool,
 }
 
-#[derive(Debug, Clone, Deserialize, FromVariant)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromVariant)]
 #[serde(untagged)]
 pub enum QueryOrVersion {
     Query(Query),
     Version(Version),
 }
 
-#[derive(Debug, Clone, Deserialize, FromVariant, Eq, PartialEq, PartialOrd, Ord, Hash)]
+#[derive(
+    Debug, Clone, Serialize, Deserialize, FromVariant, Eq, PartialEq, PartialOrd, Ord, Hash,
+)]
 #[serde(untagged)]
 pub enum Query {
     Single(String),
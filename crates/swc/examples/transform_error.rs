@@ -16,6 +16,7 @@ fn main() {
             // You can use the env var named `NO_COLOR` to control this.
             color: ColorConfig::Auto,
             skip_filename: false,
+            ..Default::default()
         },
         |handler| {
             let fm = cm.new_source_file(FileName::Custom("foo.js".into()), "this ?= foo".into());
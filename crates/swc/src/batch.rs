@@ -0,0 +1,85 @@
+//! Compiling multiple files in parallel.
+//!
+//! This works like the directory mode of the `swc` cli. Each file is processed
+//! on a [rayon] thread pool, and the results are returned in the order of
+//! input files, so the output does not depend on the scheduling of threads.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Error};
+use rayon::prelude::*;
+use swc_common::{errors::Handler, FileName, Globals, GLOBALS};
+
+use crate::{config::Options, try_with_handler, Compiler, HandlerOpts, TransformOutput};
+
+/// Result of compiling a file with [Compiler::process_js_files].
+#[derive(Debug)]
+pub struct FileOutput {
+    pub path: PathBuf,
+    /// [None] if the file is ignored by `.swcrc`.
+    ///
+    /// On failure, the error contains the rendered diagnostics.
+    pub output: Result<Option<TransformOutput>, Error>,
+}
+
+impl Compiler {
+    /// Compiles `files` in parallel.
+    ///
+    /// `.swcrc` files are resolved once per directory while compiling the
+    /// batch, and each thread of the pool uses its own [Globals].
+    ///
+    /// The returned outputs are in the same order as `files`.
+    pub fn process_js_files(
+        &self,
+        files: &[PathBuf],
+        opts: &Options,
+        handler_opts: HandlerOpts,
+    ) -> Vec<FileOutput> {
+        let c = Compiler {
+            cm: self.cm.clone(),
+            comments: Default::default(),
+            swcrc_cache: Some(Default::default()),
+        };
+
+        files
+            .par_iter()
+            .map_init(Globals::new, |globals, path| {
+                let output = GLOBALS.set(globals, || {
+                    try_with_handler(c.cm.clone(), handler_opts.clone(), |handler| {
+                        c.process_js_file_in_batch(path, handler, opts)
+                    })
+                });
+
+                FileOutput {
+                    path: path.clone(),
+                    output,
+                }
+            })
+            .collect()
+    }
+
+    fn process_js_file_in_batch(
+        &self,
+        path: &Path,
+        handler: &Handler,
+        opts: &Options,
+    ) -> Result<Option<TransformOutput>, Error> {
+        if !opts.config.matches(path)? {
+            return Ok(None);
+        }
+
+        if self
+            .read_config(opts, &FileName::Real(path.to_path_buf()))?
+            .is_none()
+        {
+            return Ok(None);
+        }
+
+        let fm = self
+            .cm
+            .load_file(path)
+            .with_context(|| format!("failed to load file ({})", path.display()))?;
+
+        self.process_js_file(fm, handler, opts).map(Some)
+    }
+}
@@ -21,7 +21,12 @@ use swc_ecma_transforms::{
     fixer::{fixer, paren_remover},
     helpers, hygiene,
     hygiene::hygiene_with_config,
-    modules,
+    modules::{
+        self,
+        node_next::ModuleFormat,
+        rewrite_rules::{RewriteRule, RuleImportResolver},
+        rewriter::import_rewriter_with_require,
+    },
     optimization::const_modules,
     pass::Optional,
     Assumptions,
@@ -50,6 +55,8 @@ pub struct PassBuilder<'a, 'b, P: swc_ecma_visit::Fold> {
     inject_helpers: bool,
     minify: Option<JsMinifyOptions>,
     regenerator: regenerator::Config,
+    rewrite_imports: Vec<RewriteRule>,
+    module_format: Option<ModuleFormat>,
 }
 
 impl<'a, 'b, P: swc_ecma_visit::Fold> PassBuilder<'a, 'b, P> {
@@ -77,6 +84,8 @@ impl<'a, 'b, P: swc_ecma_visit::Fold> PassBuilder<'a, 'b, P> {
             inject_helpers: true,
             minify: None,
             regenerator: Default::default(),
+            rewrite_imports: Default::default(),
+            module_format: None,
         }
     }
 
@@ -100,6 +109,8 @@ impl<'a, 'b, P: swc_ecma_visit::Fold> PassBuilder<'a, 'b, P> {
             inject_helpers: self.inject_helpers,
             minify: self.minify,
             regenerator: self.regenerator,
+            rewrite_imports: self.rewrite_imports,
+            module_format: self.module_format,
         }
     }
 
@@ -158,6 +169,21 @@ impl<'a, 'b, P: swc_ecma_visit::Fold> PassBuilder<'a, 'b, P> {
         self
     }
 
+    /// Rules to rewrite module specifiers with. Rules are applied in order,
+    /// before the module pass.
+    pub fn rewrite_imports(mut self, rules: Vec<RewriteRule>) -> Self {
+        self.rewrite_imports = rules;
+        self
+    }
+
+    /// The module format of the file for `"module": "nodenext"`.
+    ///
+    /// If this is [None], it's detected from the file name.
+    pub fn module_format(mut self, format: Option<ModuleFormat>) -> Self {
+        self.module_format = format;
+        self
+    }
+
     /// # Arguments
     /// ## module
     ///  - Use `None` if you want swc to emit import statements.
@@ -182,8 +208,21 @@ impl<'a, 'b, P: swc_ecma_visit::Fold> PassBuilder<'a, 'b, P> {
     where
         P: 'cmt,
     {
+        let module_format = match module {
+            Some(ModuleConfig::NodeNext(..)) => Some(
+                self.module_format
+                    .unwrap_or_else(|| ModuleFormat::detect(base)),
+            ),
+            _ => None,
+        };
+
         let (need_analyzer, import_interop, ignore_dynamic) = match module {
             Some(ModuleConfig::CommonJs(ref c)) => (true, c.import_interop(), c.ignore_dynamic),
+            Some(ModuleConfig::NodeNext(ref c))
+                if module_format == Some(ModuleFormat::CommonJs) =>
+            {
+                (true, c.import_interop(), c.ignore_dynamic)
+            }
             Some(ModuleConfig::Amd(ref c)) => {
                 (true, c.config.import_interop(), c.config.ignore_dynamic)
             }
@@ -192,7 +231,7 @@ impl<'a, 'b, P: swc_ecma_visit::Fold> PassBuilder<'a, 'b, P> {
             }
             Some(ModuleConfig::SystemJs(_))
             | Some(ModuleConfig::Es6)
-            | Some(ModuleConfig::NodeNext)
+            | Some(ModuleConfig::NodeNext(..))
             | None => (false, true.into(), true),
         };
 
@@ -317,6 +356,8 @@ impl<'a, 'b, P: swc_ecma_visit::Fold> PassBuilder<'a, 'b, P> {
             ))
         };
 
+        let need_rewrite = !self.rewrite_imports.is_empty();
+
         let is_mangler_enabled = self
             .minify
             .as_ref()
@@ -331,6 +372,14 @@ impl<'a, 'b, P: swc_ecma_visit::Fold> PassBuilder<'a, 'b, P> {
             ),
             compat_pass,
             // module / helper
+            Optional::new(
+                import_rewriter_with_require(
+                    base.clone(),
+                    RuleImportResolver::new(self.rewrite_imports),
+                    self.unresolved_mark
+                ),
+                need_rewrite
+            ),
             Optional::new(
                 modules::import_analysis::import_analyzer(import_interop, ignore_dynamic),
                 need_analyzer
@@ -345,6 +394,7 @@ impl<'a, 'b, P: swc_ecma_visit::Fold> PassBuilder<'a, 'b, P> {
                 base,
                 self.unresolved_mark,
                 module,
+                module_format,
                 feature_flag
             ),
             as_folder(MinifierPass {
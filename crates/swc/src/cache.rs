@@ -0,0 +1,444 @@
+//! On-disk cache of transform outputs.
+//!
+//! Entries are addressed by a hash of the inputs of
+//! [Compiler::process_js_file], which are the source, the options, the resolved
+//! `.swcrc`, the input source map, the plugins and the browserslist targets of
+//! `env`. Other files read by transforms, like modules resolved with
+//! `jsc.paths`, are not hashed, so the cache should be cleared if they change.
+//! Entries are written to a temporary file and renamed into place, so multiple
+//! processes can share a cache directory. Reading an entry bumps its
+//! modification time, which is used to evict the least recently used entries
+//! once the size of the cache exceeds the limit.
+
+use std::{
+    fs,
+    io::ErrorKind,
+    mem,
+    path::{Path, PathBuf},
+    process,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{bail, Context, Error};
+use dashmap::DashMap;
+use filetime::FileTime;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha1::{Digest, Sha1};
+use swc_common::{errors::Handler, FileName, SourceFile};
+use swc_config::merge::Merge;
+
+use crate::{config::Options, Compiler, TransformOutput};
+
+/// Name of the directory for files being written.
+const TMP_DIR: &str = "tmp";
+
+/// Length of keys, which are hex encoded SHA-1 hashes.
+const KEY_LEN: usize = 40;
+
+/// Temporary files older than this are left by writers which crashed, and
+/// are removed on eviction.
+const STALE_TMP_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// A directory storing transform outputs.
+///
+/// See [Compiler::process_js_file_with_cache].
+pub struct TransformCache {
+    dir: PathBuf,
+    max_size: u64,
+    /// Approximate size of entries in bytes.
+    ///
+    /// Other processes may add or remove entries, so this is recomputed on
+    /// eviction.
+    size: AtomicU64,
+    /// Used to create unique names for temporary files.
+    tmp_id: AtomicUsize,
+    /// Hashes of plugin Wasm files, with the modification time and the length
+    /// of the file they are computed from.
+    #[cfg_attr(
+        not(all(
+            any(feature = "plugin", feature = "plugin-bytecheck"),
+            not(target_arch = "wasm32")
+        )),
+        allow(unused)
+    )]
+    plugin_hashes: DashMap<PathBuf, (SystemTime, u64, Arc<str>)>,
+}
+
+#[derive(Deserialize)]
+struct Entry {
+    code: String,
+    #[serde(default)]
+    map: Option<String>,
+}
+
+impl TransformCache {
+    /// Opens or creates a cache in `dir`.
+    ///
+    /// Least recently used entries are removed when the total size of entries
+    /// exceeds `max_size` bytes.
+    pub fn new(dir: impl Into<PathBuf>, max_size: u64) -> Result<Self, Error> {
+        let dir = dir.into();
+        fs::create_dir_all(dir.join(TMP_DIR))
+            .with_context(|| format!("failed to create transform cache at {}", dir.display()))?;
+
+        let cache = TransformCache {
+            dir,
+            max_size,
+            size: Default::default(),
+            tmp_id: Default::default(),
+            plugin_hashes: Default::default(),
+        };
+        cache.evict()?;
+
+        Ok(cache)
+    }
+
+    fn entry_path(&self, key: &str) -> Result<PathBuf, Error> {
+        if key.len() != KEY_LEN || !key.bytes().all(|b| b.is_ascii_hexdigit()) {
+            bail!("invalid transform cache key `{}`", key)
+        }
+
+        Ok(self.dir.join(&key[..2]).join(key))
+    }
+
+    /// Returns the output stored for `key`, if any.
+    ///
+    /// Keys are SHA-1 hashes encoded as hex, and an invalid key is a miss.
+    pub fn get(&self, key: &str) -> Option<TransformOutput> {
+        let path = self.entry_path(key).ok()?;
+        // Another process may remove the entry at any time, and a broken entry
+        // is treated as a miss.
+        let content = fs::read(&path).ok()?;
+        let entry: Entry = serde_json::from_slice(&content).ok()?;
+
+        let _ = filetime::set_file_mtime(&path, FileTime::now());
+
+        Some(TransformOutput {
+            code: entry.code,
+            map: entry.map,
+        })
+    }
+
+    /// Stores `output` for `key`, evicting old entries if required.
+    ///
+    /// Returns an error if `key` is not a SHA-1 hash encoded as hex.
+    pub fn put(&self, key: &str, output: &TransformOutput) -> Result<(), Error> {
+        let path = self.entry_path(key)?;
+        let content = serde_json::to_vec(output).context("failed to serialize transform output")?;
+
+        let tmp = self.dir.join(TMP_DIR).join(format!(
+            "{}.{}.{}",
+            key,
+            process::id(),
+            self.tmp_id.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::write(&tmp, &content)
+            .with_context(|| format!("failed to write cache entry to {}", tmp.display()))?;
+
+        let res = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::rename(&tmp, &path));
+        if let Err(err) = res {
+            let _ = fs::remove_file(&tmp);
+            return Err(Error::new(err)
+                .context(format!("failed to store cache entry to {}", path.display())));
+        }
+
+        let size =
+            self.size.fetch_add(content.len() as u64, Ordering::Relaxed) + content.len() as u64;
+        if size > self.max_size {
+            self.evict()?;
+        }
+
+        Ok(())
+    }
+
+    /// Removes least recently used entries until the size of the cache is
+    /// below the limit, and stale temporary files.
+    fn evict(&self) -> Result<(), Error> {
+        self.remove_stale_tmp_files()?;
+
+        let mut entries = vec![];
+        for dir in read_dir(&self.dir)? {
+            if dir.file_name() == TMP_DIR {
+                continue;
+            }
+
+            for entry in read_dir(&dir.path())? {
+                let metadata = match entry.metadata() {
+                    Ok(v) => v,
+                    Err(err) if err.kind() == ErrorKind::NotFound => continue,
+                    Err(err) => return Err(err.into()),
+                };
+                if metadata.is_file() {
+                    entries.push((
+                        entry.path(),
+                        metadata.len(),
+                        FileTime::from_last_modification_time(&metadata),
+                    ));
+                }
+            }
+        }
+
+        let mut size = entries.iter().map(|(_, len, _)| len).sum::<u64>();
+        if size > self.max_size {
+            // Leave some room so we don't evict on every store.
+            let target = self.max_size / 4 * 3;
+
+            entries.sort_by_key(|(_, _, mtime)| *mtime);
+            for (path, len, _) in entries {
+                if size <= target {
+                    break;
+                }
+
+                remove_file(&path)?;
+                size -= len;
+            }
+        }
+
+        self.size.store(size, Ordering::Relaxed);
+
+        Ok(())
+    }
+
+    /// Removes temporary files which are not renamed into place for
+    /// [STALE_TMP_AGE].
+    ///
+    /// Writers rename or remove their temporary file right after writing it,
+    /// so an old one is left by a process which crashed.
+    fn remove_stale_tmp_files(&self) -> Result<(), Error> {
+        let now = SystemTime::now();
+
+        for entry in read_dir(&self.dir.join(TMP_DIR))? {
+            let mtime = match entry.metadata().and_then(|v| v.modified()) {
+                Ok(v) => v,
+                Err(err) if err.kind() == ErrorKind::NotFound => continue,
+                Err(err) => return Err(err.into()),
+            };
+
+            // Modification times in the future are not stale.
+            if now
+                .duration_since(mtime)
+                .map_or(false, |age| age >= STALE_TMP_AGE)
+            {
+                remove_file(&entry.path())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the hash of the plugin Wasm file at `path`.
+    #[cfg(all(
+        any(feature = "plugin", feature = "plugin-bytecheck"),
+        not(target_arch = "wasm32")
+    ))]
+    fn plugin_hash(&self, path: &Path) -> Result<Arc<str>, Error> {
+        let metadata = fs::metadata(path)
+            .with_context(|| format!("failed to read plugin at {}", path.display()))?;
+        let mtime = metadata.modified()?;
+
+        if let Some(v) = self.plugin_hashes.get(path) {
+            if v.0 == mtime && v.1 == metadata.len() {
+                return Ok(v.2.clone());
+            }
+        }
+
+        let content = fs::read(path)
+            .with_context(|| format!("failed to read plugin at {}", path.display()))?;
+        let hash: Arc<str> = format!("{:x}", Sha1::digest(&content)).into();
+        self.plugin_hashes
+            .insert(path.to_path_buf(), (mtime, metadata.len(), hash.clone()));
+
+        Ok(hash)
+    }
+}
+
+fn remove_file(path: &Path) -> Result<(), Error> {
+    match fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(Error::new(err).context(format!(
+            "failed to remove cache entry at {}",
+            path.display()
+        ))),
+    }
+}
+
+fn read_dir(dir: &Path) -> Result<Vec<fs::DirEntry>, Error> {
+    let entries = match fs::read_dir(dir) {
+        Ok(v) => v,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(vec![]),
+        Err(err) => {
+            return Err(Error::new(err).context(format!(
+                "failed to read cache directory at {}",
+                dir.display()
+            )))
+        }
+    };
+
+    entries
+        .filter_map(|entry| match entry {
+            Err(err) if err.kind() == ErrorKind::NotFound => None,
+            entry => Some(entry.context("failed to read cache directory")),
+        })
+        .collect()
+}
+
+/// Writes `bytes` with its length, so the boundaries between parts of the key
+/// are not ambiguous.
+fn hash_part(hasher: &mut Sha1, bytes: &[u8]) {
+    hasher.update((bytes.len() as u64).to_le_bytes());
+    hasher.update(bytes);
+}
+
+/// Serializes `value` as JSON with sorted keys, so hash maps are hashed in the
+/// same order by all processes.
+fn canonical_json<T: Serialize>(value: &T) -> Result<Vec<u8>, Error> {
+    let mut value = serde_json::to_value(value)?;
+    sort_keys(&mut value);
+
+    Ok(serde_json::to_vec(&value)?)
+}
+
+fn sort_keys(value: &mut Value) {
+    match value {
+        Value::Array(items) => items.iter_mut().for_each(sort_keys),
+        Value::Object(map) => {
+            let mut entries = mem::take(map).into_iter().collect::<Vec<_>>();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+            for (key, mut value) in entries {
+                sort_keys(&mut value);
+                map.insert(key, value);
+            }
+        }
+        _ => {}
+    }
+}
+
+impl Compiler {
+    /// Computes the key of `fm` in [TransformCache].
+    ///
+    /// The key is a hash of the version of swc, the input file, the options,
+    /// the resolved config, the targets of `env`, the input source map and
+    /// the plugin Wasm files.
+    ///
+    /// Returns [None] if the file is ignored by `.swcrc`.
+    fn transform_cache_key(
+        &self,
+        cache: &TransformCache,
+        fm: &SourceFile,
+        opts: &Options,
+    ) -> Result<Option<String>, Error> {
+        if let FileName::Real(path) = &fm.name {
+            if !opts.config.matches(path)? {
+                return Ok(None);
+            }
+        }
+
+        let config = match self.read_config(opts, &fm.name)? {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+
+        // Same as `Options::build_as_input`
+        let mut cfg = opts.config.clone();
+        cfg.merge(config);
+
+        let mut hasher = Sha1::new();
+        hash_part(&mut hasher, env!("CARGO_PKG_VERSION").as_bytes());
+        hash_part(&mut hasher, fm.name.to_string().as_bytes());
+        hash_part(&mut hasher, fm.src.as_bytes());
+        // Marks are not serialized, as they differ between processes.
+        hash_part(
+            &mut hasher,
+            &canonical_json(opts).context("failed to serialize options")?,
+        );
+        hash_part(
+            &mut hasher,
+            &canonical_json(&cfg).context("failed to serialize config")?,
+        );
+
+        // Queries are resolved using browserslist config files.
+        if let Some(env) = &cfg.env {
+            let targets = swc_ecma_preset_env::targets_to_versions(env.targets.clone())?;
+            hash_part(
+                &mut hasher,
+                &canonical_json(&targets).context("failed to serialize targets")?,
+            );
+        }
+
+        let mut source_maps = opts.source_maps.clone();
+        source_maps.merge(cfg.source_maps.clone());
+        if source_maps.map_or(false, |v| v.enabled()) {
+            let input_source_map = cfg.input_source_map.clone().unwrap_or_default();
+            if let Some(orig) = self.get_orig_src_map(fm, &input_source_map, true)? {
+                let mut buf = vec![];
+                orig.to_writer(&mut buf)
+                    .context("failed to serialize input source map")?;
+                hash_part(&mut hasher, &buf);
+            }
+        }
+
+        #[cfg(all(
+            any(feature = "plugin", feature = "plugin-bytecheck"),
+            not(target_arch = "wasm32")
+        ))]
+        if let Some(plugins) = &cfg.jsc.experimental.plugins {
+            for path in crate::plugin::resolve_plugin_paths(plugins)? {
+                hash_part(&mut hasher, cache.plugin_hash(&path)?.as_bytes());
+            }
+        }
+        #[cfg(not(all(
+            any(feature = "plugin", feature = "plugin-bytecheck"),
+            not(target_arch = "wasm32")
+        )))]
+        let _ = cache;
+
+        Ok(Some(format!("{:x}", hasher.finalize())))
+    }
+
+    /// Same as [Compiler::process_js_file], but the output is read from
+    /// `cache` if the same file was compiled with the same config before.
+    ///
+    /// Outputs are stored only if there's no error, and failing to store an
+    /// output is not an error.
+    pub fn process_js_file_with_cache(
+        &self,
+        cache: &TransformCache,
+        fm: Arc<SourceFile>,
+        handler: &Handler,
+        opts: &Options,
+    ) -> Result<TransformOutput, Error> {
+        let key = self
+            .run(|| self.transform_cache_key(cache, &fm, opts))
+            .context("failed to compute the key of transform cache")?;
+
+        let key = match key {
+            Some(v) => v,
+            // Let `process_js_file` report it.
+            None => return self.process_js_file(fm, handler, opts),
+        };
+
+        if let Some(output) = cache.get(&key) {
+            return Ok(output);
+        }
+
+        let output = self.process_js_file(fm, handler, opts)?;
+
+        if !handler.has_errors() {
+            if let Err(err) = cache.put(&key, &output) {
+                tracing::warn!("failed to store transform output: {:?}", err);
+            }
+        }
+
+        Ok(output)
+    }
+}
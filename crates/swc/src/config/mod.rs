@@ -57,7 +57,12 @@ use swc_ecma_parser::{parse_file_as_expr, Syntax, TsConfig};
 use swc_ecma_transforms::{
     feature::FeatureFlag,
     hygiene, modules,
-    modules::{path::NodeImportResolver, rewriter::import_rewriter},
+    modules::{
+        node_next::{ModuleFormat, NodeNextImportResolver},
+        path::{ImportResolver, NodeImportResolver, NoopImportResolver},
+        rewrite_rules::RewriteRule,
+        rewriter::import_rewriter,
+    },
     optimization::{const_modules, json_parse, simplifier},
     pass::{noop, Optional},
     proposals::{decorators, export_default_from, import_assertions},
@@ -72,7 +77,7 @@ use swc_ecma_visit::{Fold, VisitMutWith};
 
 use crate::{
     builder::PassBuilder, dropped_comments_preserver::dropped_comments_preserver,
-    plugin::PluginConfig, SwcImportResolver,
+    plugin::PluginConfig, DiagnosticFormat, SwcImportResolver,
 };
 
 #[cfg(test)]
@@ -161,7 +166,7 @@ pub struct ParseOptions {
     pub target: EsVersion,
 }
 
-#[derive(Debug, Clone, Default, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
 pub struct Options {
     #[serde(flatten)]
@@ -176,10 +181,10 @@ pub struct Options {
     #[serde(skip_deserializing, default)]
     pub disable_fixer: bool,
 
-    #[serde(skip_deserializing, default)]
+    #[serde(skip, default)]
     pub top_level_mark: Option<Mark>,
 
-    #[serde(skip_deserializing, default)]
+    #[serde(skip, default)]
     pub unresolved_mark: Option<Mark>,
 
     #[cfg(not(target_arch = "wasm32"))]
@@ -227,7 +232,7 @@ pub struct Options {
     pub experimental: ExperimentalOptions,
 }
 
-#[derive(Debug, Clone, Default, Deserialize, Merge)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Merge)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
 pub struct ExperimentalOptions {
     #[serde(default)]
@@ -322,6 +327,7 @@ impl Options {
             keep_class_names,
             base_url,
             paths,
+            rewrite_imports,
             minify: mut js_minify,
             experimental,
             lints,
@@ -362,10 +368,11 @@ impl Options {
                 !transform.use_define_for_class_fields.into_bool();
         }
 
+        // Flow types are parsed into typescript nodes, which are stripped later
         program.visit_mut_with(&mut resolver(
             unresolved_mark,
             top_level_mark,
-            syntax.typescript(),
+            syntax.typescript() || syntax.flow(),
         ));
 
         if program.is_module() {
@@ -476,12 +483,20 @@ impl Options {
             json_parse_pass
         );
 
+        let module_format = match cfg.module {
+            Some(ModuleConfig::NodeNext(..)) => Some(ModuleFormat::detect(base)),
+            _ => None,
+        };
+
         let import_export_assign_config = match cfg.module {
             Some(ModuleConfig::Es6) => TsImportExportAssignConfig::EsNext,
             Some(ModuleConfig::CommonJs(..))
             | Some(ModuleConfig::Amd(..))
             | Some(ModuleConfig::Umd(..)) => TsImportExportAssignConfig::Preserve,
-            Some(ModuleConfig::NodeNext) => TsImportExportAssignConfig::NodeNext,
+            Some(ModuleConfig::NodeNext(..)) => match module_format {
+                Some(ModuleFormat::CommonJs) => TsImportExportAssignConfig::Preserve,
+                _ => TsImportExportAssignConfig::NodeNext,
+            },
             // TODO: should Preserve for SystemJS
             _ => TsImportExportAssignConfig::Classic,
         };
@@ -506,6 +521,8 @@ impl Options {
         .fixer(!self.disable_fixer)
         .preset_env(cfg.env)
         .regenerator(regenerator)
+        .rewrite_imports(rewrite_imports)
+        .module_format(module_format)
         .finalize(
             base_url,
             paths.into_iter().collect(),
@@ -609,6 +626,7 @@ impl Options {
                 decorators(decorators::Config {
                     legacy: transform.legacy_decorator.into_bool(),
                     emit_metadata: transform.decorator_metadata.into_bool(),
+                    strict_null_checks: transform.strict_null_checks.into_bool(),
                     use_define_for_class_fields: !assumptions.set_public_class_fields
                 }),
                 syntax.decorators()
@@ -649,6 +667,12 @@ impl Options {
                 ),
                 syntax.typescript()
             ),
+            Optional::new(
+                typescript::flow::strip_with_config(typescript::flow::Config {
+                    allow_declare_fields: transform.use_define_for_class_fields.into_bool(),
+                }),
+                syntax.flow()
+            ),
             plugins,
             custom_before_pass(&program),
             // handle jsx
@@ -818,7 +842,7 @@ impl Rc {
 }
 
 /// A single object in the `.swcrc` file
-#[derive(Debug, Default, Clone, Deserialize, Merge)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Merge)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
 pub struct Config {
     #[serde(default)]
@@ -1178,6 +1202,11 @@ pub struct JscConfig {
     #[serde(default)]
     pub paths: Paths,
 
+    /// Rules to rewrite module specifiers of imports, exports, `import()` and
+    /// `require()`. Rules are applied in order, before `paths`.
+    #[serde(default)]
+    pub rewrite_imports: Vec<RewriteRule>,
+
     #[serde(default)]
     pub minify: Option<JsMinifyOptions>,
 
@@ -1241,12 +1270,29 @@ pub enum ErrorFormat {
     Json,
     #[serde(rename = "normal")]
     Normal,
+    /// A SARIF 2.1.0 log.
+    #[serde(rename = "sarif")]
+    Sarif,
+    /// A JSON array of LSP `PublishDiagnosticsParams`.
+    #[serde(rename = "lsp")]
+    Lsp,
 }
 
 impl ErrorFormat {
+    /// The format of the diagnostics emitted by [crate::try_with_handler].
+    pub fn diagnostic_format(&self) -> DiagnosticFormat {
+        match self {
+            ErrorFormat::Json | ErrorFormat::Normal => DiagnosticFormat::Pretty,
+            ErrorFormat::Sarif => DiagnosticFormat::Sarif,
+            ErrorFormat::Lsp => DiagnosticFormat::Lsp,
+        }
+    }
+
     pub fn format(&self, err: &Error) -> String {
         match self {
             ErrorFormat::Normal => format!("{:?}", err),
+            // The message is the document rendered by `try_with_handler`
+            ErrorFormat::Sarif | ErrorFormat::Lsp => err.to_string(),
             ErrorFormat::Json => {
                 let mut map = serde_json::Map::new();
 
@@ -1293,8 +1339,11 @@ pub enum ModuleConfig {
     SystemJs(modules::system_js::Config),
     #[serde(rename = "es6")]
     Es6,
+    /// Emits each file as an ES module or a CommonJS module depending on its
+    /// extension and the nearest `package.json`, like `tsc` with `"module":
+    /// "nodenext"`. The config is used for files emitted as CommonJS.
     #[serde(rename = "nodenext")]
-    NodeNext,
+    NodeNext(modules::common_js::Config),
 }
 
 impl ModuleConfig {
@@ -1306,6 +1355,7 @@ impl ModuleConfig {
         base: &FileName,
         unresolved_mark: Mark,
         config: Option<ModuleConfig>,
+        module_format: Option<ModuleFormat>,
         available_features: FeatureFlag,
     ) -> Box<dyn swc_ecma_visit::Fold + 'cmt> {
         let base = match base {
@@ -1316,7 +1366,7 @@ impl ModuleConfig {
         };
 
         match config {
-            None | Some(ModuleConfig::Es6) | Some(ModuleConfig::NodeNext) => {
+            None | Some(ModuleConfig::Es6) => {
                 if paths.is_empty() {
                     Box::new(noop())
                 } else {
@@ -1325,6 +1375,28 @@ impl ModuleConfig {
                     Box::new(import_rewriter(base, resolver))
                 }
             }
+            Some(ModuleConfig::NodeNext(config)) => {
+                let resolver: Box<dyn ImportResolver> = if paths.is_empty() {
+                    Box::new(NoopImportResolver)
+                } else {
+                    build_resolver(base_url, paths)
+                };
+                let resolver = NodeNextImportResolver::new(resolver);
+
+                match module_format.unwrap_or_else(|| ModuleFormat::detect(&base)) {
+                    ModuleFormat::Esm => Box::new(import_rewriter(base, resolver)),
+                    ModuleFormat::CommonJs => {
+                        Box::new(modules::common_js::common_js_with_resolver(
+                            Box::new(resolver),
+                            base,
+                            unresolved_mark,
+                            config,
+                            available_features,
+                            comments,
+                        ))
+                    }
+                }
+            }
             Some(ModuleConfig::CommonJs(config)) => {
                 if paths.is_empty() {
                     Box::new(modules::common_js::common_js(
@@ -1425,6 +1497,11 @@ pub struct TransformConfig {
     #[serde(default)]
     pub decorator_metadata: BoolConfig<false>,
 
+    /// Serializes `null` and `undefined` in union types for decorator
+    /// metadata, like `tsc` does with `strictNullChecks`.
+    #[serde(default)]
+    pub strict_null_checks: BoolConfig<false>,
+
     #[serde(default)]
     pub hidden: HiddenTransformConfig,
 
@@ -1497,7 +1574,7 @@ pub struct GlobalPassOption {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum GlobalInliningPassEnvs {
-    List(AHashSet<String>),
+    List(#[serde(serialize_with = "serialize_sorted")] AHashSet<String>),
     Map(AHashMap<JsWord, JsWord>),
 }
 
@@ -1511,6 +1588,17 @@ impl Default for GlobalInliningPassEnvs {
     }
 }
 
+/// Serializes `set` in a stable order, as the order of a hash set differs
+/// between processes.
+fn serialize_sorted<S>(set: &AHashSet<String>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let mut items = set.iter().collect::<Vec<_>>();
+    items.sort();
+    items.serialize(serializer)
+}
+
 impl GlobalPassOption {
     pub fn build(self, cm: &SourceMap, handler: &Handler) -> impl 'static + Fold {
         type ValuesMap = Arc<AHashMap<JsWord, Expr>>;
@@ -122,6 +122,7 @@ use anyhow::{bail, Context, Error};
 use atoms::JsWord;
 use common::{collections::AHashMap, comments::SingleThreadedComments, errors::HANDLER};
 use config::{IsModule, JsMinifyCommentOption, JsMinifyOptions, OutputCharset};
+use dashmap::DashMap;
 use jsonc_parser::{parse_to_serde_value, ParseOptions};
 use once_cell::sync::Lazy;
 use serde::Serialize;
@@ -155,7 +156,7 @@ use swc_ecma_transforms::{
     resolver,
 };
 use swc_ecma_visit::{noop_visit_type, FoldWith, Visit, VisitMutWith, VisitWith};
-pub use swc_error_reporters::handler::{try_with_handler, HandlerOpts};
+pub use swc_error_reporters::handler::{try_with_handler, DiagnosticFormat, HandlerOpts};
 pub use swc_node_comments::SwcComments;
 use swc_timer::timer;
 use url::Url;
@@ -165,7 +166,11 @@ use crate::config::{
     BuiltInput, Config, ConfigFile, InputSourceMap, Options, Rc, RootMode, SourceMapsConfig,
 };
 
+#[cfg(feature = "concurrent")]
+pub mod batch;
 mod builder;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod cache;
 pub mod config;
 mod dropped_comments_preserver;
 mod plugin;
@@ -220,8 +225,17 @@ pub struct Compiler {
     /// CodeMap
     pub cm: Arc<SourceMap>,
     comments: SwcComments,
+    /// `.swcrc` files loaded by [Compiler::read_config], keyed by the
+    /// directory containing them.
+    ///
+    /// This is only used while compiling a batch of files, as a long-lived
+    /// compiler should see the changes made to `.swcrc` files.
+    swcrc_cache: Option<Arc<SwcrcCache>>,
 }
 
+/// `None` means there's no `.swcrc` file in the directory.
+type SwcrcCache = DashMap<PathBuf, Option<Rc>>;
+
 #[cfg(feature = "node")]
 #[napi_derive::napi(object)]
 #[derive(Debug, Serialize)]
@@ -660,6 +674,7 @@ impl Compiler {
         Compiler {
             cm,
             comments: Default::default(),
+            swcrc_cache: None,
         }
     }
 
@@ -693,11 +708,7 @@ impl Compiler {
                 if *swcrc {
                     let mut parent = path.parent();
                     while let Some(dir) = parent {
-                        let swcrc = dir.join(".swcrc");
-
-                        if swcrc.exists() {
-                            let config = load_swcrc(&swcrc)?;
-
+                        if let Some(config) = self.load_swcrc_in(dir)? {
                             let mut config = config
                                 .into_config(Some(path))
                                 .context("failed to process config file")?;
@@ -764,6 +775,34 @@ impl Compiler {
         .with_context(|| format!("failed to read swcrc file ({})", name))
     }
 
+    /// Loads `.swcrc` in `dir`, if it exists.
+    fn load_swcrc_in(&self, dir: &Path) -> Result<Option<Rc>, Error> {
+        let cache = match &self.swcrc_cache {
+            Some(v) => v,
+            None => {
+                let swcrc = dir.join(".swcrc");
+                if !swcrc.exists() {
+                    return Ok(None);
+                }
+                return load_swcrc(&swcrc).map(Some);
+            }
+        };
+
+        if let Some(rc) = cache.get(dir) {
+            return Ok(rc.clone());
+        }
+
+        let swcrc = dir.join(".swcrc");
+        let rc = if swcrc.exists() {
+            Some(load_swcrc(&swcrc)?)
+        } else {
+            None
+        };
+        cache.insert(dir.to_path_buf(), rc.clone());
+
+        Ok(rc)
+    }
+
     /// This method returns [None] if a file should be skipped.
     ///
     /// This method handles merging of config.
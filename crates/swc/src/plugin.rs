@@ -10,6 +10,7 @@
 )]
 
 use serde::{Deserialize, Serialize};
+use swc_common::plugin::limits::PluginLimits;
 #[cfg(any(feature = "plugin", feature = "plugin-bytecheck"))]
 use swc_ecma_ast::*;
 use swc_ecma_loader::resolvers::{lru::CachingResolver, node::NodeModulesResolver};
@@ -23,9 +24,21 @@ use swc_ecma_visit::{noop_fold_type, Fold};
 /// Type of plugin's configuration is up to each plugin - swc/core does not have
 /// strong type and it'll be serialized into plain string when it's passed to
 /// plugin's entrypoint function.
+///
+/// Optional third element is a list of files or directories the plugin can
+/// resolve and read through the host. Plugin cannot access any other module if
+/// it's empty.
+///
+/// Optional fourth element is the memory, fuel and timeout limits of the
+/// plugin.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
-pub struct PluginConfig(String, serde_json::Value);
+pub struct PluginConfig(
+    String,
+    serde_json::Value,
+    #[serde(default)] Vec<String>,
+    #[serde(default, skip_serializing_if = "PluginLimits::is_empty")] PluginLimits,
+);
 
 #[cfg(any(feature = "plugin", feature = "plugin-bytecheck"))]
 pub fn plugins(
@@ -53,6 +66,38 @@ pub fn plugins() -> impl Fold {
     noop()
 }
 
+/// Resolves the paths to the Wasm files of `plugins`, in the same way as
+/// the plugins are resolved when they are invoked.
+#[cfg(all(
+    any(feature = "plugin", feature = "plugin-bytecheck"),
+    not(target_arch = "wasm32")
+))]
+pub(crate) fn resolve_plugin_paths(
+    plugins: &[PluginConfig],
+) -> Result<Vec<std::path::PathBuf>, anyhow::Error> {
+    use std::path::PathBuf;
+
+    use swc_common::FileName;
+    use swc_ecma_loader::{resolve::Resolve, TargetEnv};
+
+    let resolver = CachingResolver::new(
+        40,
+        NodeModulesResolver::new(TargetEnv::Node, Default::default(), true),
+    );
+
+    plugins
+        .iter()
+        .map(
+            |p| match resolver.resolve(&FileName::Real(PathBuf::from(&p.0)), &p.0)? {
+                FileName::Real(path) => Ok(path),
+                resolved_path => {
+                    anyhow::bail!("Failed to resolve plugin path: {:?}", resolved_path)
+                }
+            },
+        )
+        .collect()
+}
+
 struct RustPlugins {
     plugins: Option<Vec<PluginConfig>>,
     metadata_context: std::sync::Arc<swc_common::plugin::metadata::TransformPluginMetadataContext>,
@@ -88,7 +133,8 @@ impl RustPlugins {
 
         use anyhow::Context;
         use swc_common::{plugin::serialized::PluginSerializedBytes, FileName};
-        use swc_ecma_loader::resolve::Resolve;
+        use swc_ecma_loader::{resolve::Resolve, TargetEnv};
+        use swc_plugin_runner::module_loader::{PluginModuleLoader, PLUGIN_MODULE_LOADER};
 
         // swc_plugin_macro will not inject proxy to the comments if comments is empty
         let should_enable_comments_proxy = self.comments.is_some();
@@ -109,6 +155,10 @@ impl RustPlugins {
                 // Note: This doesn't mean plugin won't perform any se/deserialization: it
                 // still have to construct from raw bytes internally to perform actual
                 // transform.
+                //
+                // All of the configured plugins run as a single pass, so the host
+                // deserializes the program only once after the last plugin, before
+                // native passes run.
                 if let Some(plugins) = &mut self.plugins {
                     for p in plugins.drain(..) {
                         let resolved_path = self
@@ -130,12 +180,30 @@ impl RustPlugins {
                                 &self.source_map,
                                 &self.metadata_context,
                                 Some(p.1),
+                                &p.3,
                             )?;
 
                         if !transform_plugin_executor.is_transform_schema_compatible()? {
                             anyhow::bail!("Cannot execute incompatible plugin {}", &p.0);
                         }
 
+                        // Plugin can resolve and read other modules only if the host allowed it.
+                        let module_loader = if p.2.is_empty() {
+                            None
+                        } else {
+                            Some(PluginModuleLoader::new(
+                                Box::new(CachingResolver::new(
+                                    40,
+                                    NodeModulesResolver::new(
+                                        TargetEnv::Node,
+                                        Default::default(),
+                                        true,
+                                    ),
+                                )),
+                                &p.2.iter().map(PathBuf::from).collect::<Vec<_>>(),
+                            ))
+                        };
+
                         let span = tracing::span!(
                             tracing::Level::INFO,
                             "execute_plugin_runner",
@@ -143,19 +211,27 @@ impl RustPlugins {
                         )
                         .entered();
 
-                        serialized_program = transform_plugin_executor
-                            .transform(
+                        let transform = || {
+                            transform_plugin_executor.transform(
                                 &serialized_program,
                                 self.unresolved_mark,
                                 should_enable_comments_proxy,
                             )
-                            .with_context(|| {
-                                format!(
-                                    "failed to invoke `{}` as js transform plugin at {}",
-                                    &p.0,
-                                    path.display()
-                                )
-                            })?;
+                        };
+
+                        serialized_program = match &module_loader {
+                            Some(module_loader) => {
+                                PLUGIN_MODULE_LOADER.set(module_loader, transform)
+                            }
+                            None => transform(),
+                        }
+                        .with_context(|| {
+                            format!(
+                                "failed to invoke `{}` as js transform plugin at {}",
+                                &p.0,
+                                path.display()
+                            )
+                        })?;
                         drop(span);
                     }
                 }
@@ -190,6 +266,8 @@ impl RustPlugins {
             || {
                 let mut serialized_program = PluginSerializedBytes::try_serialize(&n)?;
 
+                // Same as the native runtime, transformed bytes are passed to the next
+                // plugin as-is.
                 if let Some(plugins) = &mut self.plugins {
                     for p in plugins.drain(..) {
                         let mut transform_plugin_executor =
@@ -199,6 +277,7 @@ impl RustPlugins {
                                 &self.source_map,
                                 &self.metadata_context,
                                 Some(p.1),
+                                &p.3,
                             )?;
 
                         serialized_program = transform_plugin_executor
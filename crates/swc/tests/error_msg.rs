@@ -61,6 +61,7 @@ fn fixture(input: PathBuf) {
             HandlerOpts {
                 skip_filename: true,
                 color: ColorConfig::Never,
+                ..Default::default()
             },
             |handler| {
                 let c = Compiler::new(cm.clone());
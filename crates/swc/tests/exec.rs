@@ -261,6 +261,7 @@ fn get_expected_stdout(input: &Path) -> Result<String, Error> {
             HandlerOpts {
                 color: ColorConfig::Always,
                 skip_filename: true,
+                ..Default::default()
             },
             |handler| {
                 let fm = cm.load_file(input).context("failed to load file")?;
@@ -1066,3 +1066,38 @@ fn issue_6009() {
     })
     .unwrap()
 }
+
+#[cfg(feature = "concurrent")]
+#[test]
+fn process_js_files() {
+    let c = Compiler::new(Default::default());
+
+    let files = vec![
+        PathBuf::from("tests/projects/batch/ts/b.ts"),
+        PathBuf::from("tests/projects/batch/a.js"),
+        PathBuf::from("tests/projects/batch/broken.js"),
+        PathBuf::from("tests/projects/batch/ts/ignored.ts"),
+    ];
+    let outputs = c.process_js_files(
+        &files,
+        &Options {
+            swcrc: true,
+            ..Default::default()
+        },
+        Default::default(),
+    );
+
+    assert_eq!(
+        outputs.iter().map(|v| &v.path).collect::<Vec<_>>(),
+        files.iter().collect::<Vec<_>>()
+    );
+
+    let b = outputs[0].output.as_ref().unwrap().as_ref().unwrap();
+    assert!(b.code.contains("var b = 1"), "{}", b.code);
+
+    let a = outputs[1].output.as_ref().unwrap().as_ref().unwrap();
+    assert!(a.code.contains("var a = function"), "{}", a.code);
+
+    assert!(outputs[2].output.is_err());
+    assert!(outputs[3].output.as_ref().unwrap().is_none());
+}
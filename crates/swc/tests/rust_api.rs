@@ -1,10 +1,14 @@
+use std::{env, fs, path::Path, process, sync::Arc, thread};
+
+use filetime::FileTime;
 use swc::{
+    cache::TransformCache,
     config::{
         Config, InputSourceMap, IsModule, JscConfig, ModuleConfig, Options, SourceMapsConfig,
     },
-    Compiler,
+    Compiler, TransformOutput,
 };
-use swc_common::{comments::SingleThreadedComments, FileName};
+use swc_common::{comments::SingleThreadedComments, FileName, Mark};
 use swc_ecma_ast::*;
 use swc_ecma_parser::{EsConfig, Syntax, TsConfig};
 use swc_ecma_transforms::{modules::common_js, pass::noop};
@@ -340,3 +344,172 @@ fn shopify_4_reduce_more() {
     })
     .unwrap()
 }
+
+#[test]
+fn process_js_file_with_cache() {
+    let dir = env::temp_dir().join(format!("swc-transform-cache-{}", process::id()));
+    let cache = TransformCache::new(&dir, 1024 * 1024).unwrap();
+
+    testing::run_test2(false, |cm, handler| {
+        let c = Compiler::new(cm.clone());
+        let opts = Options::default();
+
+        let fm = cm.new_source_file(FileName::Anon, "const a = () => 1;".into());
+        let res = c
+            .process_js_file_with_cache(&cache, fm.clone(), &handler, &opts)
+            .unwrap();
+        assert!(res.code.contains("var a = function"), "{}", res.code);
+
+        // Overwrite the stored entry to check if it's used.
+        let entry = walkdir::WalkDir::new(&dir)
+            .into_iter()
+            .map(|entry| entry.unwrap())
+            .find(|entry| entry.file_type().is_file() && !entry.path().starts_with(dir.join("tmp")))
+            .unwrap();
+        fs::write(entry.path(), r#"{"code":"cached"}"#).unwrap();
+
+        let res = c
+            .process_js_file_with_cache(&cache, fm.clone(), &handler, &opts)
+            .unwrap();
+        assert_eq!(res.code, "cached");
+
+        // Marks are not a part of the key.
+        let opts_with_mark = Options {
+            top_level_mark: Some(Mark::new()),
+            ..opts.clone()
+        };
+        let res = c
+            .process_js_file_with_cache(&cache, fm, &handler, &opts_with_mark)
+            .unwrap();
+        assert_eq!(res.code, "cached");
+
+        let fm = cm.new_source_file(FileName::Anon, "const b = () => 1;".into());
+        let res = c
+            .process_js_file_with_cache(&cache, fm, &handler, &opts)
+            .unwrap();
+        assert!(res.code.contains("var b = function"), "{}", res.code);
+
+        Ok(())
+    })
+    .unwrap();
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+fn cache_output(code: &str) -> TransformOutput {
+    TransformOutput {
+        code: code.repeat(1000),
+        map: None,
+    }
+}
+
+/// Returns a valid key, which is a SHA-1 hash encoded as hex.
+fn cache_key(c: char) -> String {
+    c.to_string().repeat(40)
+}
+
+/// Sets the modification time of the entry for `key`, which is the time it's
+/// last used.
+fn set_cache_entry_mtime(dir: &Path, key: &str, unix_seconds: i64) {
+    let path = dir.join(&key[..2]).join(key);
+    filetime::set_file_mtime(path, FileTime::from_unix_time(unix_seconds, 0)).unwrap();
+}
+
+#[test]
+fn transform_cache_evicts_least_recently_used() {
+    let dir = env::temp_dir().join(format!("swc-transform-cache-lru-{}", process::id()));
+    // Large enough for two entries but not for three.
+    let cache = TransformCache::new(&dir, 2900).unwrap();
+    let (a, b, c) = (cache_key('a'), cache_key('b'), cache_key('c'));
+
+    cache.put(&a, &cache_output("a")).unwrap();
+    cache.put(&b, &cache_output("b")).unwrap();
+    set_cache_entry_mtime(&dir, &a, 1);
+    set_cache_entry_mtime(&dir, &b, 2);
+
+    // `a` is now used more recently than `b`.
+    assert!(cache.get(&a).is_some());
+
+    cache.put(&c, &cache_output("c")).unwrap();
+
+    assert!(cache.get(&b).is_none());
+    assert_eq!(cache.get(&a).unwrap().code, cache_output("a").code);
+    assert_eq!(cache.get(&c).unwrap().code, cache_output("c").code);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn transform_cache_rejects_invalid_keys() {
+    let dir = env::temp_dir().join(format!("swc-transform-cache-keys-{}", process::id()));
+    let cache = TransformCache::new(&dir, 1024 * 1024).unwrap();
+
+    let not_hex = cache_key('g');
+    for key in [
+        "",
+        "a",
+        "é",
+        "éééééééééééééééééééé",
+        not_hex.as_str(),
+        "../a",
+    ] {
+        assert!(cache.put(key, &cache_output("a")).is_err(), "{:?}", key);
+        assert!(cache.get(key).is_none(), "{:?}", key);
+    }
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn transform_cache_removes_stale_temporary_files() {
+    let dir = env::temp_dir().join(format!("swc-transform-cache-tmp-{}", process::id()));
+    fs::create_dir_all(dir.join("tmp")).unwrap();
+
+    // Left by a writer which crashed.
+    let stale = dir.join("tmp").join("stale");
+    fs::write(&stale, "a").unwrap();
+    filetime::set_file_mtime(&stale, FileTime::from_unix_time(1, 0)).unwrap();
+    // May be renamed into place by another process.
+    let fresh = dir.join("tmp").join("fresh");
+    fs::write(&fresh, "a").unwrap();
+
+    TransformCache::new(&dir, 1024 * 1024).unwrap();
+
+    assert!(!stale.exists());
+    assert!(fresh.exists());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn transform_cache_concurrent_writers() {
+    let dir = env::temp_dir().join(format!("swc-transform-cache-writers-{}", process::id()));
+    let dir = Arc::new(dir);
+
+    // Each writer has its own cache, like separate processes sharing a directory.
+    let writers = (0..2)
+        .map(|_| {
+            let dir = dir.clone();
+            thread::spawn(move || {
+                let cache = TransformCache::new(&*dir, 1024 * 1024).unwrap();
+                let key = cache_key('a');
+                for _ in 0..100 {
+                    cache.put(&key, &cache_output("a")).unwrap();
+                    assert_eq!(cache.get(&key).unwrap().code, cache_output("a").code);
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    for writer in writers {
+        writer.join().unwrap();
+    }
+
+    let cache = TransformCache::new(&*dir, 1024 * 1024).unwrap();
+    assert_eq!(
+        cache.get(&cache_key('a')).unwrap().code,
+        cache_output("a").code
+    );
+    assert_eq!(fs::read_dir(dir.join("tmp")).unwrap().count(), 0);
+
+    fs::remove_dir_all(&*dir).unwrap();
+}
@@ -314,7 +314,7 @@ fn matrix(input: &Path) -> Vec<TestUnitData> {
                 Self::Amd => ModuleConfig::Amd(Default::default()),
                 Self::SystemJs => ModuleConfig::SystemJs(Default::default()),
                 Self::Es6 => ModuleConfig::Es6,
-                Self::NodeNext => ModuleConfig::NodeNext,
+                Self::NodeNext => ModuleConfig::NodeNext(Default::default()),
             }
         }
     }
@@ -435,6 +435,7 @@ fn compile(output: &Path, test_unit_data: TestUnitData) {
                 swc::HandlerOpts {
                     color: ColorConfig::Never,
                     skip_filename: true,
+                    ..Default::default()
                 },
                 |handler| c.process_js_file(file, handler, &test_unit_data.opts),
             ) {
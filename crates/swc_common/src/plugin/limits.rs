@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Resource limits of a transform plugin. Limits are not enforced if not
+/// specified.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct PluginLimits {
+    /// Maximum size of the linear memory of the plugin, in bytes.
+    #[serde(default)]
+    pub memory: Option<u64>,
+
+    /// Maximum number of wasm instructions a single transform can execute.
+    #[serde(default)]
+    pub fuel: Option<u64>,
+
+    /// Wall-clock deadline of a single transform, in milliseconds.
+    #[serde(default)]
+    pub timeout: Option<u64>,
+}
+
+const WASM_PAGE_SIZE: u64 = 0x10000;
+
+impl PluginLimits {
+    pub fn is_empty(&self) -> bool {
+        *self == Default::default()
+    }
+
+    /// Memory limit in wasm pages.
+    pub fn memory_pages(&self) -> Option<u32> {
+        self.memory
+            .map(|bytes| (bytes / WASM_PAGE_SIZE).try_into().unwrap_or(u32::MAX))
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout.map(Duration::from_millis)
+    }
+}
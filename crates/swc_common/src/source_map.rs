@@ -17,9 +17,9 @@
 //! within the SourceMap, which upon request can be converted to line and column
 //! information, source code snippets, etc.
 use std::{
-    cmp, env, fs,
+    cmp, env, fmt, fs,
     hash::Hash,
-    io,
+    io, mem,
     path::{Path, PathBuf},
     sync::atomic::{AtomicUsize, Ordering::SeqCst},
 };
@@ -35,8 +35,13 @@ use crate::{
     errors::SourceMapper,
     rustc_data_structures::stable_hasher::StableHasher,
     sync::{Lock, LockGuard, Lrc, MappedLockGuard},
+    syntax_pos::DUMMY_RESERVE,
 };
 
+#[cfg(feature = "sourcemap")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sourcemap")))]
+pub mod compose;
+
 static CURRENT_DIR: Lazy<Option<PathBuf>> = Lazy::new(|| env::current_dir().ok());
 
 // _____________________________________________________________________________
@@ -98,10 +103,144 @@ impl StableSourceFileId {
 // SourceMap
 //
 
+/// A group of files of a [SourceMap], see [SourceMap::new_generation].
+#[derive(Copy, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub struct SourceMapGeneration(u32);
+
+/// The error returned by [SourceMap::try_lookup_source_file].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum SourceFileLookupError {
+    /// The position doesn't belong to any file.
+    Unknown(BytePos),
+    /// The position belongs to a file removed by
+    /// [SourceMap::remove_source_file] or [SourceMap::remove_generation].
+    Removed { pos: BytePos, filename: FileName },
+}
+
+impl fmt::Display for SourceFileLookupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SourceFileLookupError::Unknown(pos) => write!(
+                f,
+                "position {} does not resolve to a source location",
+                pos.to_usize()
+            ),
+            SourceFileLookupError::Removed { pos, filename } => write!(
+                f,
+                "position {} belongs to the removed source file {}",
+                pos.to_usize(),
+                filename
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SourceFileLookupError {}
+
+/// The positions of a removed file, `hi` is exclusive.
+struct RemovedSourceFile {
+    lo: BytePos,
+    hi: BytePos,
+    name: FileName,
+}
+
 #[derive(Default)]
 pub(super) struct SourceMapFiles {
     pub(super) source_files: Vec<Lrc<SourceFile>>,
+    /// The generation of each file of `source_files`.
+    generations: Vec<SourceMapGeneration>,
     stable_id_to_source_file: AHashMap<StableSourceFileId, Lrc<SourceFile>>,
+    current_generation: SourceMapGeneration,
+    /// Sorted by position, these positions are not reused until
+    /// [SourceMap::reclaim_positions] is called.
+    removed: Vec<RemovedSourceFile>,
+    /// Sorted and disjoint ranges of reusable positions, `hi` is exclusive.
+    free: Vec<(BytePos, BytePos)>,
+}
+
+impl SourceMapFiles {
+    fn lookup(&self, pos: BytePos) -> Result<Lrc<SourceFile>, SourceFileLookupError> {
+        let file = SourceMap::lookup_source_file_in(&self.source_files, pos);
+
+        if let Some(file) = &file {
+            if file.start_pos <= pos && pos <= file.end_pos {
+                return Ok(file.clone());
+            }
+        }
+
+        // Removed files are not in `source_files`, so their positions resolve to
+        // an adjacent file
+        let index = self.removed.partition_point(|removed| removed.lo <= pos);
+
+        if let Some(removed) = index.checked_sub(1).map(|index| &self.removed[index]) {
+            if pos < removed.hi {
+                return Err(SourceFileLookupError::Removed {
+                    pos,
+                    filename: removed.name.clone(),
+                });
+            }
+        }
+
+        match file {
+            Some(file) if file.start_pos <= pos => Ok(file),
+            _ => Err(SourceFileLookupError::Unknown(pos)),
+        }
+    }
+
+    /// Takes `len` positions from the reclaimed ones.
+    fn take_free_positions(&mut self, len: usize) -> Option<usize> {
+        let index = self
+            .free
+            .iter()
+            .position(|(lo, hi)| (*hi - *lo).to_usize() >= len)?;
+        let (lo, hi) = self.free[index];
+
+        if (hi - lo).to_usize() == len {
+            self.free.remove(index);
+        } else {
+            self.free[index].0 = lo + BytePos::from_usize(len);
+        }
+
+        Some(lo.to_usize())
+    }
+
+    fn insert(&mut self, source_file: Lrc<SourceFile>) {
+        let index = self
+            .source_files
+            .partition_point(|file| file.start_pos < source_file.start_pos);
+
+        self.source_files.insert(index, source_file.clone());
+        self.generations.insert(index, self.current_generation);
+        self.stable_id_to_source_file
+            .insert(StableSourceFileId::new(&source_file), source_file);
+    }
+
+    fn remove(&mut self, index: usize) {
+        let file = self.source_files.remove(index);
+
+        self.generations.remove(index);
+
+        // Another file with the same name may have replaced it
+        let stable_id = StableSourceFileId::new(&file);
+
+        if self
+            .stable_id_to_source_file
+            .get(&stable_id)
+            .map_or(false, |other| Lrc::ptr_eq(other, &file))
+        {
+            self.stable_id_to_source_file.remove(&stable_id);
+        }
+
+        let removed = RemovedSourceFile {
+            lo: file.start_pos,
+            // Includes the position between files, see `SourceMap::next_start_pos`
+            hi: file.end_pos + BytePos(1),
+            name: file.name.clone(),
+        };
+        let index = self.removed.partition_point(|other| other.lo < removed.lo);
+
+        self.removed.insert(index, removed);
+    }
 }
 
 /// The interner for spans.
@@ -123,6 +262,19 @@ pub(super) struct SourceMapFiles {
 /// If you are bundling modules, you should share this struct while parsing
 /// modules. Otherwise, you have to implement a code generator which accepts
 /// multiple [SourceMap].
+///
+/// ## Note for long-running processes
+///
+/// Positions are never reused by default, so a [SourceMap] shared by a
+/// long-running process, i.e. a watch mode, eventually runs out of positions.
+/// Files can be grouped with [SourceMap::new_generation] and removed with
+/// [SourceMap::remove_generation] or [SourceMap::remove_source_file] once
+/// they are not used anymore. Looking up a position of a removed file is an
+/// error, instead of resolving to another file.
+///
+/// The positions of removed files are reused only after
+/// [SourceMap::reclaim_positions] is called, which must be done when no span
+/// of the removed files is used anymore.
 pub struct SourceMap {
     pub(super) files: Lock<SourceMapFiles>,
     start_pos: AtomicUsize,
@@ -194,10 +346,108 @@ impl SourceMap {
             .cloned()
     }
 
-    fn next_start_pos(&self, len: usize) -> usize {
+    fn next_start_pos(&self, files: &mut SourceMapFiles, len: usize) -> usize {
         // Add one so there is some space between files. This lets us distinguish
         // positions in the source_map, even in the presence of zero-length files.
-        self.start_pos.fetch_add(len + 1, SeqCst)
+        if let Some(start_pos) = files.take_free_positions(len + 1) {
+            return start_pos;
+        }
+
+        let start_pos = self.start_pos.fetch_add(len + 1, SeqCst);
+
+        assert!(
+            start_pos + len < DUMMY_RESERVE as usize,
+            "the source map ran out of positions, remove unused files with \
+             `SourceMap::remove_generation` and call `SourceMap::reclaim_positions`"
+        );
+
+        start_pos
+    }
+
+    /// Starts a new generation, files created from now on belong to it until
+    /// the next call.
+    ///
+    /// All the files of a generation can be removed at once with
+    /// [SourceMap::remove_generation].
+    pub fn new_generation(&self) -> SourceMapGeneration {
+        let mut files = self.files.borrow_mut();
+
+        files.current_generation = SourceMapGeneration(files.current_generation.0 + 1);
+        files.current_generation
+    }
+
+    /// Removes `source_file` from this [SourceMap], and returns `false` if it
+    /// doesn't belong to it.
+    ///
+    /// Its positions are not reused until [SourceMap::reclaim_positions] is
+    /// called.
+    pub fn remove_source_file(&self, source_file: &SourceFile) -> bool {
+        let mut files = self.files.borrow_mut();
+        let index = match files
+            .source_files
+            .binary_search_by_key(&source_file.start_pos, |file| file.start_pos)
+        {
+            Ok(index) if std::ptr::eq(&*files.source_files[index], source_file) => index,
+            _ => return false,
+        };
+
+        files.remove(index);
+
+        true
+    }
+
+    /// Removes the files of `generation` from this [SourceMap], and returns
+    /// the number of removed files.
+    ///
+    /// Their positions are not reused until [SourceMap::reclaim_positions] is
+    /// called.
+    pub fn remove_generation(&self, generation: SourceMapGeneration) -> usize {
+        let mut files = self.files.borrow_mut();
+        let mut count = 0;
+
+        for index in (0..files.source_files.len()).rev() {
+            if files.generations[index] == generation {
+                files.remove(index);
+
+                count += 1;
+            }
+        }
+
+        count
+    }
+
+    /// Allows the positions of removed files to be reused by new files.
+    ///
+    /// Spans of removed files must not be used after calling this method, as
+    /// they may resolve to the new files.
+    pub fn reclaim_positions(&self) {
+        let mut files = self.files.borrow_mut();
+        let files = &mut *files;
+
+        files.free.extend(
+            mem::take(&mut files.removed)
+                .into_iter()
+                .map(|removed| (removed.lo, removed.hi)),
+        );
+        files.free.sort_unstable_by_key(|(lo, _)| *lo);
+        files.free.dedup_by(|(lo, hi), (_, previous_hi)| {
+            let is_adjacent = *lo == *previous_hi;
+
+            if is_adjacent {
+                *previous_hi = *hi;
+            }
+
+            is_adjacent
+        });
+
+        // Positions at the end are given back
+        if let Some((lo, hi)) = files.free.last().copied() {
+            if hi.to_usize() == self.start_pos.load(SeqCst) {
+                files.free.pop();
+
+                self.start_pos.store(lo.to_usize(), SeqCst);
+            }
+        }
     }
 
     /// Creates a new source_file.
@@ -222,7 +472,7 @@ impl SourceMap {
         // If we don't do this, lookup_char_pos and its family **may** panic.
         let mut files = self.files.borrow_mut();
 
-        let start_pos = self.next_start_pos(src.len());
+        let start_pos = self.next_start_pos(&mut files, src.len());
 
         let source_file = Lrc::new(SourceFile::new(
             filename,
@@ -232,12 +482,7 @@ impl SourceMap {
             Pos::from_usize(start_pos),
         ));
 
-        {
-            files.source_files.push(source_file.clone());
-            files
-                .stable_id_to_source_file
-                .insert(StableSourceFileId::new(&source_file), source_file.clone());
-        }
+        files.insert(source_file.clone());
 
         source_file
     }
@@ -549,8 +794,18 @@ impl SourceMap {
             return Err(Box::new(SpanSnippetError::DummyBytePos));
         }
 
-        let local_begin = self.lookup_byte_offset(sp.lo());
-        let local_end = self.lookup_byte_offset(sp.hi());
+        let lookup_byte_offset = |pos| match self.try_lookup_source_file(pos) {
+            Ok(sf) => Ok(SourceFileAndBytePos {
+                pos: pos - sf.start_pos,
+                sf,
+            }),
+            Err(SourceFileLookupError::Removed { filename, .. }) => {
+                Err(Box::new(SpanSnippetError::SourceFileRemoved { filename }))
+            }
+            Err(err) => panic!("{}", err),
+        };
+        let local_begin = lookup_byte_offset(sp.lo())?;
+        let local_end = lookup_byte_offset(sp.hi())?;
 
         if local_begin.sf.start_pos != local_end.sf.start_pos {
             Err(Box::new(SpanSnippetError::DistinctSources(
@@ -1077,20 +1332,20 @@ impl SourceMap {
     /// This is not a public api.
     #[doc(hidden)]
     pub fn lookup_source_file(&self, pos: BytePos) -> Lrc<SourceFile> {
-        let files = self.files.borrow();
-        let files = &files.source_files;
-        let fm = Self::lookup_source_file_in(files, pos);
-        match fm {
-            Some(fm) => fm,
-            None => {
-                panic!(
-                    "position {} does not resolve to a source location",
-                    pos.to_usize()
-                );
-            }
+        match self.try_lookup_source_file(pos) {
+            Ok(fm) => fm,
+            Err(err) => panic!("{}", err),
         }
     }
 
+    /// Returns the source file which contains `pos`.
+    pub fn try_lookup_source_file(
+        &self,
+        pos: BytePos,
+    ) -> Result<Lrc<SourceFile>, SourceFileLookupError> {
+        self.files.borrow().lookup(pos)
+    }
+
     pub fn count_lines(&self) -> usize {
         self.files().iter().fold(0, |a, f| a + f.count_lines())
     }
@@ -1702,6 +1957,86 @@ mod tests {
         assert!(sm.merge_spans(span1, span2).is_none());
     }
 
+    #[test]
+    fn remove_source_file() {
+        let sm = init_source_map();
+        let file = sm.lookup_source_file(BytePos(1));
+
+        assert!(sm.remove_source_file(&file));
+        assert!(!sm.remove_source_file(&file));
+        assert!(sm
+            .get_source_file(&PathBuf::from("blork.rs").into())
+            .is_none());
+
+        assert_eq!(
+            sm.try_lookup_source_file(BytePos(24)).unwrap_err(),
+            SourceFileLookupError::Removed {
+                pos: BytePos(24),
+                filename: PathBuf::from("blork.rs").into(),
+            }
+        );
+        assert_eq!(
+            *sm.span_to_snippet(Span::new(BytePos(1), BytePos(6), NO_EXPANSION))
+                .unwrap_err(),
+            SpanSnippetError::SourceFileRemoved {
+                filename: PathBuf::from("blork.rs").into(),
+            }
+        );
+
+        let srcfbp = sm.lookup_byte_offset(BytePos(25));
+        assert_eq!(srcfbp.sf.name, PathBuf::from("empty.rs").into());
+    }
+
+    #[test]
+    fn reclaim_positions() {
+        let sm = init_source_map();
+        let file = sm.lookup_source_file(BytePos(1));
+
+        sm.remove_source_file(&file);
+
+        // Positions are not reused before being reclaimed
+        let file = sm.new_source_file(PathBuf::from("new.rs").into(), "new".into());
+        assert_eq!(file.start_pos, BytePos(50));
+
+        sm.reclaim_positions();
+
+        let file = sm.new_source_file(PathBuf::from("new2.rs").into(), "new2".into());
+        assert_eq!(file.start_pos, BytePos(1));
+
+        let srcfbp = sm.lookup_byte_offset(BytePos(3));
+        assert_eq!(srcfbp.sf.name, PathBuf::from("new2.rs").into());
+        assert_eq!(srcfbp.pos, BytePos(2));
+
+        let srcfbp = sm.lookup_byte_offset(BytePos(26));
+        assert_eq!(srcfbp.sf.name, PathBuf::from("blork2.rs").into());
+        assert_eq!(srcfbp.pos, BytePos(0));
+    }
+
+    #[test]
+    fn remove_generation() {
+        let sm = init_source_map();
+
+        let generation = sm.new_generation();
+        let file = sm.new_source_file(PathBuf::from("a.rs").into(), "a".into());
+        sm.new_source_file(PathBuf::from("b.rs").into(), "b".into());
+        let next_generation = sm.new_generation();
+        sm.new_source_file(PathBuf::from("c.rs").into(), "c".into());
+
+        assert_eq!(sm.remove_generation(generation), 2);
+        assert_eq!(sm.files().len(), 4);
+        assert!(sm.try_lookup_source_file(file.start_pos).is_err());
+
+        // The positions at the end are given back
+        assert_eq!(sm.remove_generation(next_generation), 1);
+        sm.reclaim_positions();
+
+        let file = sm.new_source_file(PathBuf::from("d.rs").into(), "dd".into());
+        assert_eq!(file.start_pos, BytePos(50));
+
+        let file = sm.new_source_file(PathBuf::from("e.rs").into(), "e".into());
+        assert_eq!(file.start_pos, BytePos(53));
+    }
+
     #[test]
     fn calc_utf16_offset() {
         let input = "t¢e∆s💩t";
@@ -0,0 +1,135 @@
+//! Composition of source maps, for pipelines where each step emits a source
+//! map of its input, i.e. `typescript -> swc -> minifier -> bundler`.
+//!
+//! A composed map points from the output of the last step directly to the
+//! original sources.
+
+use sourcemap::{SourceMap, SourceMapBuilder, SourceMapIndex};
+
+use crate::collections::AHashSet;
+
+/// Parses a source map, flattening it if it's an index (sectioned) map.
+///
+/// Sections referring to a map by `url` are not supported.
+pub fn parse_source_map(slice: &[u8]) -> Result<SourceMap, sourcemap::Error> {
+    match SourceMap::from_slice(slice) {
+        Ok(map) => Ok(map),
+        Err(err) => SourceMapIndex::from_slice(slice)
+            .and_then(|index| index.flatten())
+            .map_err(|_| err),
+    }
+}
+
+/// Composes `map` with the maps of its sources.
+///
+/// `upstream` is called with each source of `map` and returns the map from
+/// which the source was generated, if any. Sources without an upstream map are
+/// kept as is, which allows composing the map of a bundle with the maps of the
+/// modules it's built from.
+///
+/// A mapping is traced through the upstream map using the closest mapping on
+/// the same line. Mappings which can't be traced are emitted without a source.
+/// The names of the upstream map take precedence, as those are the names of
+/// the original sources.
+pub fn compose_source_map<'a, F>(map: &'a SourceMap, mut upstream: F) -> SourceMap
+where
+    F: FnMut(&str) -> Option<&'a SourceMap>,
+{
+    let mut builder = SourceMapBuilder::new(map.get_file());
+    let mut with_contents = AHashSet::default();
+
+    for token in map.tokens() {
+        let (dst_line, dst_col) = (token.get_dst_line(), token.get_dst_col());
+
+        let traced = match token.get_source() {
+            Some(source) => match upstream(source) {
+                Some(up) => up
+                    .lookup_token(token.get_src_line(), token.get_src_col())
+                    .filter(|t| t.get_dst_line() == token.get_src_line())
+                    .map(|t| (up, t)),
+                None => Some((map, token)),
+            },
+            None => None,
+        };
+
+        let (orig_map, orig) = match traced {
+            Some((orig_map, orig)) if orig.get_source().is_some() => (orig_map, orig),
+            _ => {
+                builder.add_raw(dst_line, dst_col, 0, 0, None, None);
+                continue;
+            }
+        };
+
+        let src_id = builder.add_source(orig.get_source().unwrap());
+        let name = orig
+            .get_name()
+            .or_else(|| token.get_name())
+            .map(|name| builder.add_name(name));
+
+        builder.add_raw(
+            dst_line,
+            dst_col,
+            orig.get_src_line(),
+            orig.get_src_col(),
+            Some(src_id),
+            name,
+        );
+
+        if !with_contents.contains(&src_id) {
+            if let Some(contents) = orig_map.get_source_contents(orig.get_src_id()) {
+                builder.set_source_contents(src_id, Some(contents));
+                with_contents.insert(src_id);
+            }
+        }
+    }
+
+    builder.into_sourcemap()
+}
+
+/// Normalizes a relative path for comparison, i.e. `./lib/../a.js` is `a.js`.
+fn normalize_path(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let is_absolute = path.starts_with('/');
+    let mut segments: Vec<&str> = vec![];
+
+    for segment in path.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." if matches!(segments.last(), Some(last) if *last != "..") => {
+                segments.pop();
+            }
+            ".." if is_absolute => {}
+            _ => segments.push(segment),
+        }
+    }
+
+    let normalized = segments.join("/");
+
+    if is_absolute {
+        format!("/{}", normalized)
+    } else {
+        normalized
+    }
+}
+
+/// Composes the maps of a pipeline, in the order of the steps.
+///
+/// Each map is composed with the result of the previous steps. If the previous
+/// map has a `file`, only the source with the same name is traced through it,
+/// and the other sources are kept as is. Names are compared after normalizing
+/// the paths, i.e. `./a.js` is the same as `a.js`.
+///
+/// Returns [None] if `maps` is empty.
+pub fn compose_source_maps<I>(maps: I) -> Option<SourceMap>
+where
+    I: IntoIterator<Item = SourceMap>,
+{
+    maps.into_iter().reduce(|prev, map| {
+        let file = prev.get_file().map(normalize_path);
+
+        compose_source_map(&map, |source| match &file {
+            Some(file) if *file != normalize_path(source) => None,
+            _ => Some(&prev),
+        })
+    })
+}
@@ -76,7 +76,7 @@ pub struct Globals {
     marks: Mutex<Vec<MarkData>>,
 }
 
-const DUMMY_RESERVE: u32 = u32::MAX - 2_u32.pow(16);
+pub(crate) const DUMMY_RESERVE: u32 = u32::MAX - 2_u32.pow(16);
 
 impl Default for Globals {
     fn default() -> Self {
@@ -1269,7 +1269,13 @@ pub enum SpanSnippetError {
     IllFormedSpan(Span),
     DistinctSources(DistinctSources),
     MalformedForSourcemap(MalformedSourceMapPositions),
-    SourceNotAvailable { filename: FileName },
+    SourceNotAvailable {
+        filename: FileName,
+    },
+    /// The span belongs to a file removed from the [crate::SourceMap].
+    SourceFileRemoved {
+        filename: FileName,
+    },
 }
 
 #[derive(Clone, PartialEq, Eq, Debug)]
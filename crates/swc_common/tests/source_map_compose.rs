@@ -0,0 +1,128 @@
+#![cfg(feature = "sourcemap")]
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use sourcemap::SourceMap;
+use swc_common::source_map::compose::{compose_source_map, compose_source_maps, parse_source_map};
+
+fn fixture(dir: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixture")
+        .join("source_map_compose")
+        .join(dir)
+}
+
+fn read(path: &Path) -> SourceMap {
+    let bytes = fs::read(path).unwrap();
+
+    parse_source_map(&bytes).unwrap_or_else(|err| panic!("{}: {}", path.display(), err))
+}
+
+type Mapping = (u32, u32, Option<String>, u32, u32, Option<String>);
+
+/// Decoded mappings, sources and contents of `map`, which don't depend on the
+/// order of `sources` and `names`.
+fn dump(map: &SourceMap) -> (Vec<Mapping>, Vec<(String, Option<String>)>) {
+    let mappings = map
+        .tokens()
+        .map(|t| {
+            let source = t.get_source().map(String::from);
+            let (line, col) = match source {
+                Some(..) => (t.get_src_line(), t.get_src_col()),
+                None => (0, 0),
+            };
+
+            (
+                t.get_dst_line(),
+                t.get_dst_col(),
+                source,
+                line,
+                col,
+                t.get_name().map(String::from),
+            )
+        })
+        .collect();
+    let sources = map
+        .sources()
+        .enumerate()
+        .map(|(i, source)| {
+            (
+                source.to_string(),
+                map.get_source_contents(i as u32).map(String::from),
+            )
+        })
+        .collect();
+
+    (mappings, sources)
+}
+
+/// Compares `actual` with `output.map` in `dir`, and checks that `actual`
+/// survives a round trip through JSON.
+fn assert_output(dir: &Path, actual: &SourceMap) {
+    let expected = read(&dir.join("output.map"));
+
+    assert_eq!(dump(actual), dump(&expected));
+    assert_eq!(actual.get_file(), expected.get_file());
+
+    let mut json = vec![];
+    actual.to_writer(&mut json).unwrap();
+
+    assert_eq!(dump(&parse_source_map(&json).unwrap()), dump(actual));
+}
+
+fn chain(dir: &str) {
+    let dir = fixture(dir);
+    let mut maps = vec![];
+
+    for i in 1.. {
+        let path = dir.join(format!("{}.map", i));
+        if !path.exists() {
+            break;
+        }
+        maps.push(read(&path));
+    }
+
+    let composed = compose_source_maps(maps).unwrap();
+
+    assert_output(&dir, &composed);
+}
+
+#[test]
+fn chain_of_three() {
+    chain("chain");
+}
+
+#[test]
+fn chain_with_unnormalized_names() {
+    chain("chain-normalized");
+}
+
+#[test]
+fn index_map() {
+    chain("index");
+}
+
+#[test]
+fn bundle() {
+    let dir = fixture("bundle");
+    let bundle = read(&dir.join("bundle.map"));
+    let upstream = ["a.js", "b.js"].map(|name| (name, read(&dir.join(format!("{}.map", name)))));
+
+    let composed = compose_source_map(&bundle, |source| {
+        upstream
+            .iter()
+            .find(|(name, _)| *name == source)
+            .map(|(_, map)| map)
+    });
+
+    assert_output(&dir, &composed);
+}
+
+#[test]
+fn empty() {
+    assert!(compose_source_maps(vec![]).is_none());
+}
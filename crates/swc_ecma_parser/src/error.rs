@@ -4,8 +4,8 @@ use std::{borrow::Cow, fmt::Debug};
 
 use swc_atoms::JsWord;
 use swc_common::{
-    errors::{DiagnosticBuilder, Handler},
-    Span, Spanned,
+    errors::{Applicability, DiagnosticBuilder, DiagnosticId, Handler},
+    BytePos, Span, Spanned,
 };
 
 use crate::token::Token;
@@ -161,7 +161,10 @@ pub enum SyntaxError {
 
     AwaitForStmt,
 
-    AwaitInFunction,
+    AwaitInFunction {
+        /// Where `async` can be inserted to make the function async.
+        fn_start: Option<BytePos>,
+    },
 
     UnterminatedJSXContents,
     EmptyJSXAttr,
@@ -272,6 +275,7 @@ pub enum SyntaxError {
     TS4112,
     TSTypeAnnotationAfterAssign,
     TsNonNullAssertionNotAllowed(JsWord),
+    TsOnlySyntaxInFlow(&'static str),
 
     WithLabel {
         inner: Box<Error>,
@@ -431,7 +435,9 @@ impl SyntaxError {
                 "for await syntax is valid only for for-of statement".into()
             }
 
-            SyntaxError::AwaitInFunction => "await isn't allowed in non-async function".into(),
+            SyntaxError::AwaitInFunction { .. } => {
+                "await isn't allowed in non-async function".into()
+            }
 
             SyntaxError::UnterminatedJSXContents => "Unterminated JSX contents".into(),
             SyntaxError::EmptyJSXAttr => {
@@ -692,6 +698,9 @@ impl SyntaxError {
                 word
             )
             .into(),
+            SyntaxError::TsOnlySyntaxInFlow(syntax) => {
+                format!("{} are only supported in typescript, not in flow", syntax).into()
+            }
             SyntaxError::SetterParamRequired => "Setter should have exactly one parameter".into(),
             SyntaxError::UnexpectedTokenWithSuggestions {
                 candidate_list: token_list,
@@ -707,6 +716,199 @@ impl SyntaxError {
             SyntaxError::WithLabel { inner, .. } => inner.error.1.msg(),
         }
     }
+
+    /// A stable code of the error, which does not change with the message.
+    ///
+    /// Errors also reported by `tsc` use the code of `tsc`, i.e. `TS1005`, and
+    /// the others use a name in kebab case, i.e. `expected-semi-for-expr-stmt`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            SyntaxError::Eof => "unexpected-eof",
+            SyntaxError::DeclNotAllowed => "decl-not-allowed",
+            SyntaxError::PrivateNameInInterface => "private-name-in-interface",
+            SyntaxError::InvalidSuperCall => "invalid-super-call",
+            SyntaxError::InvalidSuper => "invalid-super",
+            SyntaxError::InvalidSuperPrivateName => "invalid-super-private-name",
+            SyntaxError::InvalidNewTarget => "invalid-new-target",
+            SyntaxError::InvalidImport => "invalid-import",
+            SyntaxError::ArrowNotAllowed => "arrow-not-allowed",
+            SyntaxError::ExportNotAllowed => "export-not-allowed",
+            SyntaxError::GetterSetterCannotBeReadonly => "getter-setter-cannot-be-readonly",
+            SyntaxError::GetterParam => "getter-param",
+            SyntaxError::SetterParam => "setter-param",
+            SyntaxError::TopLevelAwait => "top-level-await",
+            SyntaxError::TopLevelAwaitInScript => "top-level-await-in-script",
+            SyntaxError::LegacyDecimal => "legacy-decimal",
+            SyntaxError::LegacyOctal => "legacy-octal",
+            SyntaxError::InvalidIdentChar => "invalid-ident-char",
+            SyntaxError::ExpectedDigit { .. } => "expected-digit",
+            SyntaxError::SetterParamRequired => "setter-param-required",
+            SyntaxError::RestPatInSetter => "rest-pat-in-setter",
+            SyntaxError::UnterminatedBlockComment => "unterminated-block-comment",
+            SyntaxError::UnterminatedStrLit => "unterminated-str-lit",
+            SyntaxError::ExpectedUnicodeEscape => "expected-unicode-escape",
+            SyntaxError::EscapeInReservedWord { .. } => "escape-in-reserved-word",
+            SyntaxError::UnterminatedRegExp => "unterminated-regexp",
+            SyntaxError::UnterminatedTpl => "unterminated-tpl",
+            SyntaxError::IdentAfterNum => "ident-after-num",
+            SyntaxError::UnexpectedChar { .. } => "unexpected-char",
+            SyntaxError::InvalidStrEscape => "invalid-str-escape",
+            SyntaxError::InvalidUnicodeEscape => "invalid-unicode-escape",
+            SyntaxError::BadCharacterEscapeSequence { .. } => "bad-character-escape-sequence",
+            SyntaxError::NumLitTerminatedWithExp => "num-lit-terminated-with-exp",
+            SyntaxError::LegacyCommentInModule => "legacy-comment-in-module",
+            SyntaxError::InvalidIdentInStrict(..) => "invalid-ident-in-strict",
+            SyntaxError::InvalidIdentInAsync => "invalid-ident-in-async",
+            SyntaxError::EvalAndArgumentsInStrict => "eval-and-arguments-in-strict",
+            SyntaxError::ArgumentsInClassField => "arguments-in-class-field",
+            SyntaxError::IllegalLanguageModeDirective => "illegal-language-mode-directive",
+            SyntaxError::UnaryInExp { .. } => "unary-in-exp",
+            SyntaxError::Hash => "hash",
+            SyntaxError::LineBreakInThrow => "line-break-in-throw",
+            SyntaxError::LineBreakBeforeArrow => "line-break-before-arrow",
+            SyntaxError::Unexpected { .. } => "unexpected",
+            SyntaxError::UnexpectedTokenWithSuggestions { .. } => {
+                "unexpected-token-with-suggestions"
+            }
+            SyntaxError::ReservedWordInImport => "reserved-word-in-import",
+            SyntaxError::AssignProperty => "assign-property",
+            SyntaxError::Expected(..) => "expected",
+            SyntaxError::ExpectedSemiForExprStmt { .. } => "expected-semi-for-expr-stmt",
+            SyntaxError::AwaitStar => "await-star",
+            SyntaxError::ReservedWordInObjShorthandOrPat => "reserved-word-in-obj-shorthand-or-pat",
+            SyntaxError::NullishCoalescingWithLogicalOp => "nullish-coalescing-with-logical-op",
+            SyntaxError::MultipleDefault { .. } => "multiple-default",
+            SyntaxError::CommaAfterRestElement => "comma-after-rest-element",
+            SyntaxError::NonLastRestParam => "non-last-rest-param",
+            SyntaxError::SpreadInParenExpr => "spread-in-paren-expr",
+            SyntaxError::EmptyParenExpr => "empty-paren-expr",
+            SyntaxError::InvalidPat => "invalid-pat",
+            SyntaxError::InvalidExpr => "invalid-expr",
+            SyntaxError::NotSimpleAssign => "not-simple-assign",
+            SyntaxError::ExpectedIdent => "expected-ident",
+            SyntaxError::ExpectedSemi => "expected-semi",
+            SyntaxError::DuplicateLabel(..) => "duplicate-label",
+            SyntaxError::AsyncGenerator => "async-generator",
+            SyntaxError::NonTopLevelImportExport => "non-top-level-import-export",
+            SyntaxError::ImportExportInScript => "import-export-in-script",
+            SyntaxError::ImportMetaInScript => "import-meta-in-script",
+            SyntaxError::PatVarWithoutInit => "pat-var-without-init",
+            SyntaxError::WithInStrict => "with-in-strict",
+            SyntaxError::ReturnNotAllowed => "return-not-allowed",
+            SyntaxError::TooManyVarInForInHead => "too-many-var-in-for-in-head",
+            SyntaxError::VarInitializerInForInHead => "var-initializer-in-for-in-head",
+            SyntaxError::LabelledGeneratorOrAsync => "labelled-generator-or-async",
+            SyntaxError::LabelledFunctionInStrict => "labelled-function-in-strict",
+            SyntaxError::YieldParamInGen => "yield-param-in-gen",
+            SyntaxError::AwaitParamInAsync => "await-param-in-async",
+            SyntaxError::AwaitForStmt => "await-for-stmt",
+            SyntaxError::AwaitInFunction { .. } => "await-in-function",
+            SyntaxError::UnterminatedJSXContents => "unterminated-jsx-contents",
+            SyntaxError::EmptyJSXAttr => "empty-jsx-attr",
+            SyntaxError::InvalidJSXValue => "invalid-jsx-value",
+            SyntaxError::JSXExpectedClosingTagForLtGt => "jsx-expected-closing-tag-for-lt-gt",
+            SyntaxError::JSXExpectedClosingTag { .. } => "jsx-expected-closing-tag",
+            SyntaxError::InvalidLeadingDecorator => "invalid-leading-decorator",
+            SyntaxError::DecoratorOnExport => "decorator-on-export",
+            SyntaxError::TsRequiredAfterOptional => "ts-required-after-optional",
+            SyntaxError::TsInvalidParamPropPat => "ts-invalid-param-prop-pat",
+            SyntaxError::SpaceBetweenHashAndIdent => "space-between-hash-and-ident",
+            SyntaxError::AsyncConstructor => "async-constructor",
+            SyntaxError::PropertyNamedConstructor => "property-named-constructor",
+            SyntaxError::PrivateConstructor => "private-constructor",
+            SyntaxError::PrivateNameModifier(..) => "private-name-modifier",
+            SyntaxError::ConstructorAccessor => "constructor-accessor",
+            SyntaxError::ReadOnlyMethod => "readonly-method",
+            SyntaxError::GeneratorConstructor => "generator-constructor",
+            SyntaxError::DuplicateConstructor => "duplicate-constructor",
+            SyntaxError::TsBindingPatCannotBeOptional => "ts-binding-pat-cannot-be-optional",
+            SyntaxError::SuperCallOptional => "super-call-optional",
+            SyntaxError::OptChainCannotFollowConstructorCall => {
+                "opt-chain-cannot-follow-constructor-call"
+            }
+            SyntaxError::TrailingCommaInsideImport => "trailing-comma-inside-import",
+            SyntaxError::ExportDefaultWithOutFrom => "export-default-without-from",
+            SyntaxError::DotsWithoutIdentifier => "dots-without-identifier",
+            SyntaxError::NumericSeparatorIsAllowedOnlyBetweenTwoDigits => {
+                "numeric-separator-is-allowed-only-between-two-digits"
+            }
+            SyntaxError::ImportBindingIsString(..) => "import-binding-is-string",
+            SyntaxError::ExportBindingIsString => "export-binding-is-string",
+            SyntaxError::ConstDeclarationsRequireInitialization => {
+                "const-declarations-require-initialization"
+            }
+            SyntaxError::DuplicatedRegExpFlags(..) => "duplicated-regexp-flags",
+            SyntaxError::UnknownRegExpFlags => "unknown-regexp-flags",
+            SyntaxError::TS1003 => "TS1003",
+            SyntaxError::TS1005 => "TS1005",
+            SyntaxError::TS1009 => "TS1009",
+            SyntaxError::TS1014 => "TS1014",
+            SyntaxError::TS1015 => "TS1015",
+            SyntaxError::TS1029(..) => "TS1029",
+            SyntaxError::TS1030(..) => "TS1030",
+            SyntaxError::TS1031 => "TS1031",
+            SyntaxError::TS1038 => "TS1038",
+            SyntaxError::TS1042 => "TS1042",
+            SyntaxError::TS1047 => "TS1047",
+            SyntaxError::TS1048 => "TS1048",
+            SyntaxError::TS1056 => "TS1056",
+            SyntaxError::TS1085 => "TS1085",
+            SyntaxError::TS1089(..) => "TS1089",
+            SyntaxError::TS1092 => "TS1092",
+            SyntaxError::TS1096 => "TS1096",
+            SyntaxError::TS1098 => "TS1098",
+            SyntaxError::TS1100 => "TS1100",
+            SyntaxError::TS1102 => "TS1102",
+            SyntaxError::TS1105 => "TS1105",
+            SyntaxError::TS1106 => "TS1106",
+            SyntaxError::TS1107 => "TS1107",
+            SyntaxError::TS1109 => "TS1109",
+            SyntaxError::TS1110 => "TS1110",
+            SyntaxError::TS1114 => "TS1114",
+            SyntaxError::TS1115 => "TS1115",
+            SyntaxError::TS1116 => "TS1116",
+            SyntaxError::TS1123 => "TS1123",
+            SyntaxError::TS1141 => "TS1141",
+            SyntaxError::TS1162 => "TS1162",
+            SyntaxError::TS1164 => "TS1164",
+            SyntaxError::TS1171 => "TS1171",
+            SyntaxError::TS1172 => "TS1172",
+            SyntaxError::TS1173 => "TS1173",
+            SyntaxError::TS1174 => "TS1174",
+            SyntaxError::TS1175 => "TS1175",
+            SyntaxError::TS1183 => "TS1183",
+            SyntaxError::TS1184 => "TS1184",
+            SyntaxError::TS1185 => "TS1185",
+            SyntaxError::TS1093 => "TS1093",
+            SyntaxError::TS1196 => "TS1196",
+            SyntaxError::TS1242 => "TS1242",
+            SyntaxError::TS1243(..) => "TS1243",
+            SyntaxError::TS1244 => "TS1244",
+            SyntaxError::TS1245 => "TS1245",
+            SyntaxError::TS1267 => "TS1267",
+            SyntaxError::TS1273(..) => "TS1273",
+            SyntaxError::TS1274(..) => "TS1274",
+            SyntaxError::TS1383 => "TS1383",
+            SyntaxError::TS2206 => "TS2206",
+            SyntaxError::TS2207 => "TS2207",
+            SyntaxError::TS2369 => "TS2369",
+            SyntaxError::TS2371 => "TS2371",
+            SyntaxError::TS2406 => "TS2406",
+            SyntaxError::TS2410 => "TS2410",
+            SyntaxError::TS2414 => "TS2414",
+            SyntaxError::TS2427 => "TS2427",
+            SyntaxError::TS2452 => "TS2452",
+            SyntaxError::TS2483 => "TS2483",
+            SyntaxError::TS2491 => "TS2491",
+            SyntaxError::TS2499 => "TS2499",
+            SyntaxError::TS2703 => "TS2703",
+            SyntaxError::TS4112 => "TS4112",
+            SyntaxError::TSTypeAnnotationAfterAssign => "ts-type-annotation-after-assign",
+            SyntaxError::TsNonNullAssertionNotAllowed(..) => "ts-non-null-assertion-not-allowed",
+            SyntaxError::TsOnlySyntaxInFlow(..) => "ts-only-syntax-in-flow",
+            SyntaxError::WithLabel { inner, .. } => inner.error.1.code(),
+        }
+    }
 }
 
 impl Error {
@@ -724,7 +926,8 @@ impl Error {
         let kind = self.into_kind();
         let msg = kind.msg();
 
-        let mut db = handler.struct_span_err(span, &msg);
+        let mut db =
+            handler.struct_span_err_with_code(span, &msg, DiagnosticId::Error(kind.code().into()));
 
         match kind {
             SyntaxError::ExpectedSemiForExprStmt { expr } => {
@@ -732,6 +935,52 @@ impl Error {
                     expr,
                     "This is the expression part of an expression statement",
                 );
+                db.span_suggestion_with_applicability(
+                    expr.shrink_to_hi(),
+                    "insert a semicolon",
+                    ";".into(),
+                    Applicability::MachineApplicable,
+                );
+            }
+            SyntaxError::Expected(token, ref got)
+                if matches!(
+                    token,
+                    Token::Semi | Token::RParen | Token::RBracket | Token::RBrace
+                ) =>
+            {
+                // The span of the last token is used at the end of the file
+                let at = if got == "<eof>" {
+                    span.shrink_to_hi()
+                } else {
+                    span.shrink_to_lo()
+                };
+
+                // The missing token may belong somewhere before the unexpected one, e.g.
+                // `while (a b) {}`
+                db.span_suggestion_with_applicability(
+                    at,
+                    &format!("insert '{:?}'", token),
+                    format!("{:?}", token),
+                    Applicability::MaybeIncorrect,
+                );
+            }
+            SyntaxError::AwaitInFunction {
+                fn_start: Some(fn_start),
+            } => {
+                db.span_suggestion_with_applicability(
+                    Span::new(fn_start, fn_start, Default::default()),
+                    "make the function async",
+                    "async ".into(),
+                    Applicability::MachineApplicable,
+                );
+            }
+            SyntaxError::CommaAfterRestElement => {
+                db.span_suggestion_with_applicability(
+                    span,
+                    "remove the comma",
+                    String::new(),
+                    Applicability::MachineApplicable,
+                );
             }
             SyntaxError::MultipleDefault { previous } => {
                 db.span_label(previous, "previous default case is declared at here");
@@ -747,3 +996,191 @@ impl Error {
 fn size_of_error() {
     assert_eq!(std::mem::size_of::<Error>(), 8);
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use swc_common::{
+        errors::{Diagnostic, DiagnosticBuilder, Emitter},
+        FileName, SourceMap,
+    };
+
+    use super::*;
+    use crate::{Parser, StringInput, Syntax, TsConfig};
+
+    #[derive(Clone, Default)]
+    struct Collector(Arc<Mutex<Vec<Diagnostic>>>);
+
+    impl Emitter for Collector {
+        fn emit(&mut self, db: &DiagnosticBuilder) {
+            self.0.lock().unwrap().push((**db).clone());
+        }
+    }
+
+    fn diagnostics(syntax: Syntax, src: &str) -> Vec<(String, Option<(String, Applicability)>)> {
+        let cm = SourceMap::default();
+        let fm = cm.new_source_file(FileName::Anon, src.into());
+        let collector = Collector::default();
+        let handler = Handler::with_emitter(true, false, Box::new(collector.clone()));
+
+        let mut p = Parser::new(syntax, StringInput::from(&*fm), None);
+        let res = p.parse_module();
+
+        for e in p.take_errors() {
+            e.into_diagnostic(&handler).emit();
+        }
+        if let Err(e) = res {
+            e.into_diagnostic(&handler).emit();
+        }
+
+        let diagnostics = collector.0.lock().unwrap();
+
+        diagnostics
+            .iter()
+            .map(|d| {
+                let code = match d.code.clone() {
+                    Some(DiagnosticId::Error(code)) => code,
+                    code => panic!("unexpected code: {:?}", code),
+                };
+
+                // Applies the first suggestion
+                let fixed = d.suggestions.first().map(|suggestion| {
+                    let mut fixed = src.to_string();
+                    let mut parts = suggestion.substitutions[0].parts.clone();
+                    parts.sort_by_key(|part| part.span.lo);
+
+                    for part in parts.iter().rev() {
+                        let lo = (part.span.lo - fm.start_pos).0 as usize;
+                        let hi = (part.span.hi - fm.start_pos).0 as usize;
+
+                        fixed.replace_range(lo..hi, &part.snippet);
+                    }
+
+                    (fixed, suggestion.applicability)
+                });
+
+                (code, fixed)
+            })
+            .collect()
+    }
+
+    fn assert_suggested(src: &str, code: &str, expected: &str, applicability: Applicability) {
+        assert_eq!(
+            diagnostics(Default::default(), src),
+            vec![(
+                code.to_string(),
+                Some((expected.to_string(), applicability))
+            )]
+        );
+    }
+
+    fn assert_fixed(src: &str, code: &str, expected: &str) {
+        assert_suggested(src, code, expected, Applicability::MachineApplicable);
+        assert_eq!(diagnostics(Default::default(), expected), vec![]);
+    }
+
+    #[test]
+    fn await_in_function() {
+        assert_fixed(
+            "function foo() { await bar(); }",
+            "await-in-function",
+            "async function foo() { await bar(); }",
+        );
+        assert_fixed(
+            "export default function* () { await bar(); }",
+            "await-in-function",
+            "export default async function* () { await bar(); }",
+        );
+        assert_fixed(
+            "const foo = { bar() { await baz(); } };",
+            "await-in-function",
+            "const foo = { async bar() { await baz(); } };",
+        );
+        assert_fixed(
+            "const foo = (a) => { await a; };",
+            "await-in-function",
+            "const foo = async (a) => { await a; };",
+        );
+        assert_fixed(
+            "const foo = a => { await a; };",
+            "await-in-function",
+            "const foo = async a => { await a; };",
+        );
+    }
+
+    #[test]
+    fn await_in_nested_function() {
+        assert_fixed(
+            "async function foo() { [1].map(function (a) { await a; }); }",
+            "await-in-function",
+            "async function foo() { [1].map(async function (a) { await a; }); }",
+        );
+        assert_eq!(
+            diagnostics(
+                Default::default(),
+                "function foo() { class A { bar() { await baz(); } } }"
+            ),
+            vec![("await-in-function".to_string(), None)]
+        );
+    }
+
+    #[test]
+    fn expected_token() {
+        for (src, expected) in [
+            ("while (a{ b(); }", "while (a){ b(); }"),
+            ("do { b(); } while (a", "do { b(); } while (a)"),
+            ("while (a) { b();", "while (a) { b();}"),
+        ] {
+            assert_suggested(src, "expected", expected, Applicability::MaybeIncorrect);
+            assert_eq!(diagnostics(Default::default(), expected), vec![]);
+        }
+    }
+
+    #[test]
+    fn expected_token_may_be_incorrect() {
+        assert_suggested(
+            "while (a b) {}",
+            "expected",
+            "while (a )b) {}",
+            Applicability::MaybeIncorrect,
+        );
+    }
+
+    #[test]
+    fn expected_semi() {
+        assert_fixed("a b", "expected-semi-for-expr-stmt", "a; b");
+    }
+
+    #[test]
+    fn comma_after_rest_element() {
+        assert_fixed(
+            "const [a, ...b,] = c;",
+            "comma-after-rest-element",
+            "const [a, ...b] = c;",
+        );
+    }
+
+    #[test]
+    fn code_of_tsc() {
+        assert_eq!(
+            diagnostics(
+                Syntax::Typescript(TsConfig::default()),
+                "class A { constructor<T>() {} }"
+            ),
+            vec![("TS1092".to_string(), None)]
+        );
+    }
+
+    #[test]
+    fn code_of_label() {
+        let inner = Error::new(Default::default(), SyntaxError::TS1005);
+        let error = SyntaxError::WithLabel {
+            inner: Box::new(inner),
+            span: Default::default(),
+            note: "note",
+        };
+
+        assert_eq!(error.code(), "TS1005");
+    }
+}
@@ -0,0 +1,231 @@
+//! Incremental reparsing of modules, for editors which parse a file on every
+//! change.
+//!
+//! Top-level items before and after the edited region are reused with their
+//! spans shifted, and only the items in between are parsed again.
+
+use swc_common::{BytePos, SourceFile, Span, Spanned};
+use swc_ecma_ast::{Module, ModuleItem};
+use swc_ecma_visit::{VisitMut, VisitMutWith};
+
+use crate::{error::Error, lexer::Lexer, EsVersion, PResult, Parser, StringInput, Syntax};
+
+/// Replacement of the bytes `start..end` of the previous source by `text`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    pub start: usize,
+    pub end: usize,
+    pub text: String,
+}
+
+/// Applies `edits` to `src`.
+///
+/// All edits are relative to `src` and should not overlap.
+pub fn apply_edits(src: &str, edits: &[TextEdit]) -> String {
+    let mut edits = edits.iter().collect::<Vec<_>>();
+    edits.sort_by_key(|edit| edit.start);
+
+    let mut buf = String::with_capacity(src.len());
+    let mut last = 0;
+
+    for edit in edits {
+        debug_assert!(last <= edit.start && edit.start <= edit.end);
+
+        buf.push_str(&src[last..edit.start]);
+        buf.push_str(&edit.text);
+        last = edit.end;
+    }
+    buf.push_str(&src[last..]);
+
+    buf
+}
+
+/// Parses `fm`, which is `prev_fm` with `edits` applied, reusing the items of
+/// `prev`, which is the module parsed from `prev_fm`.
+///
+/// The result is the same as parsing `fm` with [crate::parse_file_as_module],
+/// except for comments which are not collected. `recovered_errors` only
+/// contains the errors of the items which are parsed again.
+///
+/// If `recovery` is true, the parser is in the recovery mode (see
+/// [Parser::set_recovery]).
+pub fn reparse_file_as_module(
+    prev_fm: &SourceFile,
+    prev: &Module,
+    edits: &[TextEdit],
+    fm: &SourceFile,
+    syntax: Syntax,
+    target: EsVersion,
+    recovery: bool,
+    recovered_errors: &mut Vec<Error>,
+) -> PResult<Module> {
+    let start = edits.iter().map(|edit| edit.start).min();
+    let end = edits.iter().map(|edit| edit.end).max();
+    let (start, end) = start
+        .zip(end)
+        .unwrap_or((prev_fm.src.len(), prev_fm.src.len()));
+    let added = edits.iter().map(|edit| edit.text.len()).sum::<usize>();
+    let removed = edits
+        .iter()
+        .map(|edit| edit.end - edit.start)
+        .sum::<usize>();
+
+    debug_assert_eq!(
+        prev_fm.src.len() + added - removed,
+        fm.src.len(),
+        "`fm` should be `prev_fm` with `edits` applied"
+    );
+
+    let edit_start = prev_fm.start_pos + BytePos(start as u32);
+    let edit_end = prev_fm.start_pos + BytePos(end as u32);
+
+    // An item is reused if the next item is also before the edits, because the
+    // end of an item depends on the tokens after it.
+    let before = prev
+        .body
+        .iter()
+        .take_while(|item| item.span().hi <= edit_start)
+        .count()
+        .saturating_sub(1);
+    let reparse_start = match before {
+        0 => prev_fm.start_pos,
+        _ => prev.body[before - 1].span().hi,
+    };
+
+    // Positions before and after the edits
+    let shift_before = Shift {
+        from: prev_fm.start_pos,
+        to: fm.start_pos,
+    };
+    let shift_after = Shift {
+        from: prev_fm.start_pos + BytePos(removed as u32),
+        to: fm.start_pos + BytePos(added as u32),
+    };
+
+    // Items after the edits are reused from the first one at which the parser
+    // stops, as the rest of the input is the same.
+    let after = prev
+        .body
+        .iter()
+        .position(|item| item.span().lo >= edit_end)
+        .unwrap_or(prev.body.len());
+    let mut reuse_from = None;
+
+    let input = StringInput::new(
+        &fm.src[(reparse_start - prev_fm.start_pos).0 as usize..],
+        shift_before.apply(reparse_start),
+        fm.end_pos,
+    );
+    let mut p = Parser::new_from(Lexer::new(syntax, target, input, None));
+    p.set_recovery(recovery);
+
+    let res = p.parse_module_until(|pos| {
+        reuse_from = prev.body[after..]
+            .binary_search_by_key(&pos, |item| shift_after.apply(item.span().lo))
+            .ok()
+            .map(|i| after + i);
+        reuse_from.is_some()
+    });
+    recovered_errors.append(&mut p.take_errors());
+    let module = res?;
+
+    let mut body = shift_items(&prev.body[..before], shift_before);
+    body.extend(module.body);
+    if let Some(i) = reuse_from {
+        body.extend(shift_items(&prev.body[i..], shift_after));
+    }
+
+    let lo = match before {
+        0 => module.span.lo,
+        _ => shift_before.apply(prev.span.lo),
+    };
+    let hi = match reuse_from {
+        Some(..) => shift_after.apply(prev.span.hi),
+        None => module.span.hi,
+    };
+
+    Ok(Module {
+        span: Span::new(lo, hi, Default::default()),
+        body,
+        shebang: match before {
+            0 => module.shebang,
+            _ => prev.shebang.clone(),
+        },
+    })
+}
+
+/// Same as [reparse_file_as_module], but also parses `fm` from scratch and
+/// panics if the results differ.
+///
+/// This is meant for tests.
+pub fn reparse_file_as_module_checked(
+    prev_fm: &SourceFile,
+    prev: &Module,
+    edits: &[TextEdit],
+    fm: &SourceFile,
+    syntax: Syntax,
+    target: EsVersion,
+    recovery: bool,
+    recovered_errors: &mut Vec<Error>,
+) -> PResult<Module> {
+    let res = reparse_file_as_module(
+        prev_fm,
+        prev,
+        edits,
+        fm,
+        syntax,
+        target,
+        recovery,
+        recovered_errors,
+    );
+
+    let mut p = Parser::new_from(Lexer::new(syntax, target, StringInput::from(fm), None));
+    p.set_recovery(recovery);
+    let expected = p.parse_module();
+
+    match (&res, &expected) {
+        (Ok(module), Ok(expected)) => {
+            for (item, expected) in module.body.iter().zip(&expected.body) {
+                assert_eq!(item, expected, "reparsed item differs");
+            }
+            assert_eq!(module, expected, "reparsed module differs");
+        }
+        (Err(..), Err(..)) => {}
+        _ => panic!(
+            "reparsed module differs:\nreparsed: {:?}\nexpected: {:?}",
+            res, expected
+        ),
+    }
+
+    res
+}
+
+/// Maps positions in the previous source to the new one.
+#[derive(Clone, Copy)]
+struct Shift {
+    from: BytePos,
+    to: BytePos,
+}
+
+impl Shift {
+    fn apply(self, pos: BytePos) -> BytePos {
+        pos - self.from + self.to
+    }
+}
+
+impl VisitMut for Shift {
+    fn visit_mut_span(&mut self, span: &mut Span) {
+        if !span.is_dummy() {
+            span.lo = self.apply(span.lo);
+            span.hi = self.apply(span.hi);
+        }
+    }
+}
+
+fn shift_items(items: &[ModuleItem], mut shift: Shift) -> Vec<ModuleItem> {
+    let mut items = items.to_vec();
+    if shift.from != shift.to {
+        items.visit_mut_with(&mut shift);
+    }
+    items
+}
@@ -517,7 +517,7 @@ impl<'a, I: Input> Lexer<'a, I> {
     fn make_legacy_octal(&mut self, start: BytePos, val: f64) -> LexResult<f64> {
         self.ensure_not_ident()?;
 
-        if self.syntax.typescript() && self.target >= EsVersion::Es5 {
+        if self.syntax.uses_ts_parser() && self.target >= EsVersion::Es5 {
             self.emit_error(start, SyntaxError::TS1085);
         }
 
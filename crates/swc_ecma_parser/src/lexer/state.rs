@@ -199,8 +199,11 @@ impl<'a, I: Input> Iterator for Lexer<'a, I> {
         let mut start = self.cur_pos();
 
         let res = (|| -> Result<Option<_>, _> {
-            if let Some(start) = self.state.next_regexp {
-                return Ok(Some(self.read_regexp(start)?));
+            // Cleared here, as the parser can't clear it if the regex is invalid
+            if let Some(regexp_start) = self.state.next_regexp.take() {
+                // The regex starts at the `/` token read before
+                start = regexp_start;
+                return Ok(Some(self.read_regexp(regexp_start)?));
             }
 
             if self.state.is_first {
@@ -325,7 +328,7 @@ impl<'a, I: Input> Iterator for Lexer<'a, I> {
                 return self.read_tmpl_token(start_pos_of_tpl).map(Some);
             }
 
-            if self.syntax.typescript() && self.ctx.in_type {
+            if self.syntax.uses_ts_parser() && self.ctx.in_type {
                 if c == '<' {
                     self.input.bump();
                     return Ok(Some(tok!('<')));
@@ -30,10 +30,35 @@
 //! }
 //! ```
 //!
+//! Other errors are fatal by default. Editors and other tools which need an AST
+//! of a file being edited can enable the recovery mode with
+//! [Parser::set_recovery], which replaces statements and class members that
+//! can't be parsed with [swc_ecma_ast::Invalid] nodes and collects all errors.
+//!
+//! ```
+//! use swc_ecma_parser::{Parser, StringInput, Syntax};
+//! use swc_common::BytePos;
+//!
+//! let mut parser = Parser::new(
+//!     Syntax::Es(Default::default()),
+//!     StringInput::new("foo(;\nbar();", BytePos(0), BytePos(12)),
+//!     None,
+//! );
+//! parser.set_recovery(true);
+//!
+//! let module = parser.parse_module().unwrap();
+//! assert_eq!(module.body.len(), 2);
+//! assert_eq!(parser.take_errors().len(), 1);
+//! ```
+//!
 //! # Example (lexer)
 //!
 //! See `lexer.rs` in examples directory.
 //!
+//! Formatters and other tools which need the whitespaces and comments between
+//! tokens can use the functions in [lossless], which return all tokens consumed
+//! by the parser with their trivia.
+//!
 //! # Example (parser)
 //!
 //! ```
@@ -90,12 +115,17 @@
 //!
 //! ### `typescript`
 //!
-//! Enables typescript parser.
+//! Enables typescript parser, which also parses flow.
 //!
 //! ### `verify`
 //!
 //! Verify more errors, using `swc_ecma_visit`.
 //!
+//! ### `incremental`
+//!
+//! Enables reparsing modules after text edits, see
+//! `swc_ecma_parser::incremental`.
+//!
 //! ## Known issues
 //!
 //! ### Null character after `\`
@@ -128,7 +158,7 @@
 use error::Error;
 use lexer::Lexer;
 use serde::{Deserialize, Serialize};
-use swc_common::{comments::Comments, input::SourceFileInput, SourceFile};
+use swc_common::{comments::Comments, input::SourceFileInput, BytePos, SourceFile};
 use swc_ecma_ast::*;
 
 pub use self::{
@@ -141,7 +171,10 @@ pub type JscTarget = EsVersion;
 #[macro_use]
 mod macros;
 pub mod error;
+#[cfg(feature = "incremental")]
+pub mod incremental;
 pub mod lexer;
+pub mod lossless;
 mod parser;
 pub mod token;
 
@@ -156,6 +189,13 @@ pub enum Syntax {
     #[cfg_attr(docsrs, doc(cfg(feature = "typescript")))]
     #[serde(rename = "typescript")]
     Typescript(TsConfig),
+    /// Flow type annotations are parsed by the typescript parser, into the
+    /// closest typescript nodes. This variant requires the cargo feature
+    /// `typescript` to be enabled.
+    #[cfg(feature = "typescript")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "typescript")))]
+    #[serde(rename = "flow")]
+    Flow(FlowConfig),
 }
 
 impl Default for Syntax {
@@ -171,6 +211,7 @@ impl Syntax {
                 import_assertions, ..
             }) => import_assertions,
             Syntax::Typescript(_) => true,
+            Syntax::Flow(_) => false,
         }
     }
 
@@ -178,7 +219,9 @@ impl Syntax {
     pub fn jsx(self) -> bool {
         matches!(
             self,
-            Syntax::Es(EsConfig { jsx: true, .. }) | Syntax::Typescript(TsConfig { tsx: true, .. })
+            Syntax::Es(EsConfig { jsx: true, .. })
+                | Syntax::Typescript(TsConfig { tsx: true, .. })
+                | Syntax::Flow(FlowConfig { jsx: true, .. })
         )
     }
 
@@ -221,6 +264,32 @@ impl Syntax {
         matches!(self, Syntax::Typescript(..))
     }
 
+    /// Should we use the typescript parser? This is true for flow, which is
+    /// parsed by the typescript parser.
+    #[cfg(not(feature = "typescript"))]
+    pub(crate) const fn uses_ts_parser(self) -> bool {
+        false
+    }
+
+    /// Should we use the typescript parser? This is true for flow, which is
+    /// parsed by the typescript parser.
+    #[cfg(feature = "typescript")]
+    pub(crate) const fn uses_ts_parser(self) -> bool {
+        matches!(self, Syntax::Typescript(..) | Syntax::Flow(..))
+    }
+
+    /// Should we parse flow?
+    #[cfg(not(feature = "typescript"))]
+    pub const fn flow(self) -> bool {
+        false
+    }
+
+    /// Should we parse flow?
+    #[cfg(feature = "typescript")]
+    pub const fn flow(self) -> bool {
+        matches!(self, Syntax::Flow(..))
+    }
+
     pub fn export_default_from(self) -> bool {
         matches!(
             self,
@@ -245,6 +314,7 @@ impl Syntax {
                 ..
             }) => allow_super_outside_method,
             Syntax::Typescript(_) => true,
+            Syntax::Flow(_) => false,
         }
     }
 
@@ -254,14 +324,14 @@ impl Syntax {
                 allow_return_outside_function,
                 ..
             }) => allow_return_outside_function,
-            Syntax::Typescript(_) => false,
+            Syntax::Typescript(_) | Syntax::Flow(_) => false,
         }
     }
 
     pub(crate) fn early_errors(self) -> bool {
         match self {
             Syntax::Typescript(t) => !t.no_early_errors,
-            Syntax::Es(..) => true,
+            Syntax::Es(..) | Syntax::Flow(..) => true,
         }
     }
 }
@@ -283,6 +353,13 @@ pub struct TsConfig {
     pub no_early_errors: bool,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FlowConfig {
+    #[serde(default)]
+    pub jsx: bool,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct EsConfig {
@@ -356,6 +433,9 @@ pub struct Context {
     in_class_field: bool,
 
     in_function: bool,
+    /// Where `async` can be inserted to make the current function async, i.e.
+    /// the `function` keyword or the parameters of an arrow function.
+    fn_start: Option<BytePos>,
 
     /// This indicates current scope or the scope out of arrow function is
     /// function declaration or function expression or not.
@@ -375,6 +455,10 @@ pub struct Context {
     ignore_else_clause: bool,
 
     disallow_conditional_types: bool,
+
+    /// Disallows flow function types without parentheses, like `string =>
+    /// void`, which are ambiguous in return types of arrow functions.
+    no_anon_fn_type: bool,
 }
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -0,0 +1,235 @@
+//! Lossless token streams, for formatters and codemods which need every byte
+//! of the source.
+//!
+//! The tokens are the ones consumed by the parser, so regular expressions,
+//! templates and jsx texts are lexed the same way as in the AST. Whitespaces,
+//! line breaks and comments between them are stored as [Trivia] attached to
+//! the tokens.
+
+use swc_common::{input::SourceFileInput, BytePos, SourceFile, Span};
+use swc_ecma_ast::{Module, Program, Script};
+
+use crate::{
+    error::Error,
+    lexer::{util::CharExt, Lexer},
+    token::TokenAndSpan,
+    Capturing, EsVersion, PResult, Parser, Syntax,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TriviaKind {
+    /// Spaces, tabs and other non-line-terminating whitespaces.
+    Whitespace,
+    /// `\n`, `\r\n`, `\r`, U+2028 or U+2029.
+    LineBreak,
+    /// `// ...`, or another comment which ends at a line break like `<!--`.
+    LineComment,
+    /// `/* ... */`
+    BlockComment,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Trivia {
+    pub kind: TriviaKind,
+    pub span: Span,
+}
+
+impl Trivia {
+    pub fn text<'a>(&self, fm: &'a SourceFile) -> &'a str {
+        text(fm, self.span)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenWithTrivia {
+    /// Trivia between the previous token, including the line break after it,
+    /// and this token.
+    pub leading: Vec<Trivia>,
+    pub token: TokenAndSpan,
+    /// Trivia after this token, up to the next line break.
+    pub trailing: Vec<Trivia>,
+}
+
+impl TokenWithTrivia {
+    /// Span of the token, including its trivia.
+    pub fn full_span(&self) -> Span {
+        let lo = self
+            .leading
+            .first()
+            .map_or(self.token.span.lo, |t| t.span.lo);
+        let hi = self
+            .trailing
+            .last()
+            .map_or(self.token.span.hi, |t| t.span.hi);
+
+        self.token.span.with_lo(lo).with_hi(hi)
+    }
+
+    pub fn text<'a>(&self, fm: &'a SourceFile) -> &'a str {
+        text(fm, self.token.span)
+    }
+}
+
+/// All tokens of a file, which cover it without gaps.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LosslessTokens {
+    pub tokens: Vec<TokenWithTrivia>,
+    /// Trivia after the last token.
+    pub end: Vec<Trivia>,
+}
+
+impl LosslessTokens {
+    /// Tokens of an AST node with the span `span`.
+    pub fn tokens_of(&self, span: Span) -> &[TokenWithTrivia] {
+        let start = self.tokens.partition_point(|t| t.token.span.lo < span.lo);
+        let end = start + self.tokens[start..].partition_point(|t| t.token.span.hi <= span.hi);
+
+        &self.tokens[start..end]
+    }
+
+    /// Reproduces the source of `fm`, which these tokens are lexed from.
+    pub fn to_source(&self, fm: &SourceFile) -> String {
+        let mut buf = String::with_capacity(fm.src.len());
+
+        for token in &self.tokens {
+            buf.push_str(text(fm, token.full_span()));
+        }
+        for trivia in &self.end {
+            buf.push_str(trivia.text(fm));
+        }
+
+        buf
+    }
+}
+
+/// Same as [crate::with_file_parser], but also returns the tokens consumed by
+/// the parser, with the trivia between them.
+///
+/// `op` should parse the whole file, as the rest of it is not tokenized.
+pub fn with_file_parser<T>(
+    fm: &SourceFile,
+    syntax: Syntax,
+    target: EsVersion,
+    recovered_errors: &mut Vec<Error>,
+    op: impl for<'aa> FnOnce(&mut Parser<Capturing<Lexer<SourceFileInput<'aa>>>>) -> PResult<T>,
+) -> PResult<(T, LosslessTokens)> {
+    let lexer = Lexer::new(syntax, target, SourceFileInput::from(fm), None);
+    let mut tokens = Capturing::new(lexer);
+    let mut p = Parser::new_from(tokens.clone());
+    let ret = op(&mut p);
+
+    recovered_errors.append(&mut p.take_errors());
+
+    let ret = ret?;
+    Ok((ret, attach_trivia(fm, Capturing::take(&mut tokens))))
+}
+
+macro_rules! expose {
+    (
+        $name:ident,
+        $T:ty,
+        $($t:tt)*
+    ) => {
+        /// Same as the function with the same name in the crate root, but also
+        /// returns the tokens of the file.
+        pub fn $name(
+            fm: &SourceFile,
+            syntax: Syntax,
+            target: EsVersion,
+            recovered_errors: &mut Vec<Error>,
+        ) -> PResult<($T, LosslessTokens)> {
+            with_file_parser(fm, syntax, target, recovered_errors, $($t)*)
+        }
+    };
+}
+
+expose!(parse_file_as_module, Module, |p| { p.parse_module() });
+expose!(parse_file_as_script, Script, |p| { p.parse_script() });
+expose!(parse_file_as_program, Program, |p| { p.parse_program() });
+
+fn attach_trivia(fm: &SourceFile, tokens: Vec<TokenAndSpan>) -> LosslessTokens {
+    let mut result: Vec<TokenWithTrivia> = Vec::with_capacity(tokens.len());
+    let mut last = fm.start_pos;
+
+    for token in tokens {
+        let mut leading = lex_trivia(fm, last, token.span.lo);
+        if let Some(prev) = result.last_mut() {
+            prev.trailing = take_trailing(&mut leading);
+        }
+
+        last = token.span.hi;
+        result.push(TokenWithTrivia {
+            leading,
+            token,
+            trailing: vec![],
+        });
+    }
+
+    let mut end = lex_trivia(fm, last, fm.end_pos);
+    if let Some(prev) = result.last_mut() {
+        prev.trailing = take_trailing(&mut end);
+    }
+
+    LosslessTokens {
+        tokens: result,
+        end,
+    }
+}
+
+/// Removes the trivia before the first line break from `trivia`.
+fn take_trailing(trivia: &mut Vec<Trivia>) -> Vec<Trivia> {
+    let len = trivia
+        .iter()
+        .take_while(|t| t.kind != TriviaKind::LineBreak)
+        .count();
+
+    trivia.drain(..len).collect()
+}
+
+/// Splits `lo..hi`, which only contains trivia, into pieces.
+fn lex_trivia(fm: &SourceFile, lo: BytePos, hi: BytePos) -> Vec<Trivia> {
+    let src = text(fm, Span::new(lo, hi, Default::default()));
+    let mut trivia = vec![];
+    let mut pos = 0;
+
+    while let Some(c) = src[pos..].chars().next() {
+        let rest = &src[pos..];
+        let (kind, len) = if c.is_line_terminator() {
+            let len = if rest.starts_with("\r\n") {
+                2
+            } else {
+                c.len_utf8()
+            };
+            (TriviaKind::LineBreak, len)
+        } else if c.is_whitespace() || c == '\u{feff}' {
+            let len = rest
+                .find(|c: char| c.is_line_terminator() || !(c.is_whitespace() || c == '\u{feff}'))
+                .unwrap_or(rest.len());
+            (TriviaKind::Whitespace, len)
+        } else if rest.starts_with("/*") {
+            let len = rest.find("*/").map_or(rest.len(), |i| i + 2);
+            (TriviaKind::BlockComment, len)
+        } else {
+            let len = rest
+                .find(|c: char| c.is_line_terminator())
+                .unwrap_or(rest.len());
+            (TriviaKind::LineComment, len)
+        };
+
+        let start = lo + BytePos(pos as u32);
+        pos += len;
+        trivia.push(Trivia {
+            kind,
+            span: Span::new(start, lo + BytePos(pos as u32), Default::default()),
+        });
+    }
+
+    trivia
+}
+
+fn text(fm: &SourceFile, span: Span) -> &str {
+    let lo = (span.lo - fm.start_pos).0 as usize;
+    let hi = (span.hi - fm.start_pos).0 as usize;
+
+    &fm.src[lo..hi]
+}
@@ -105,7 +105,7 @@ macro_rules! tok {
         crate::token::Token::AssignOp(crate::token::AssignOpToken::DivAssign)
     };
     ('%') => {
-        crate::token::Token::BinOp(Mod)
+        crate::token::Token::BinOp(crate::token::BinOpToken::Mod)
     };
     ('~') => {
         crate::token::Token::Tilde
@@ -338,6 +338,9 @@ macro_rules! tok {
     ("namespace") => {
         crate::token::Token::Word(crate::token::Word::Ident(swc_atoms::js_word!("namespace")))
     };
+    ("module") => {
+        crate::token::Token::Word(crate::token::Word::Ident(swc_atoms::js_word!("module")))
+    };
     ("abstract") => {
         crate::token::Token::Word(crate::token::Word::Ident(swc_atoms::js_word!("abstract")))
     };
@@ -2,7 +2,7 @@ use either::Either;
 use swc_atoms::js_word;
 use swc_common::{Spanned, SyntaxContext};
 
-use super::*;
+use super::{recovery::Boundary, *};
 use crate::{error::SyntaxError, lexer::TokenContext, parser::stmt::IsDirective, Tokens};
 
 /// Parser for function expression and function declaration.
@@ -86,13 +86,13 @@ impl<I: Tokens> Parser<I> {
             expect!(p, "class");
 
             let ident = p.parse_maybe_opt_binding_ident(is_ident_required)?;
-            if p.input.syntax().typescript() {
+            if p.input.syntax().uses_ts_parser() {
                 if let Some(span) = ident.invalid_class_name() {
                     p.emit_err(span, SyntaxError::TS2414);
                 }
             }
 
-            let type_params = if p.input.syntax().typescript() {
+            let type_params = if p.input.syntax().uses_ts_parser() {
                 p.try_parse_ts_type_params(true)?
             } else {
                 None
@@ -101,7 +101,7 @@ impl<I: Tokens> Parser<I> {
             let (mut super_class, mut super_type_params) = if eat!(p, "extends") {
                 let (super_class, super_type_params) = p.parse_super_class()?;
 
-                if p.syntax().typescript() && eat!(p, ',') {
+                if p.syntax().uses_ts_parser() && eat!(p, ',') {
                     let exprs = p.parse_ts_heritage_clause()?;
 
                     for e in &exprs {
@@ -121,7 +121,7 @@ impl<I: Tokens> Parser<I> {
                 p.parse_super_class()?;
             };
 
-            let implements = if p.input.syntax().typescript() && eat!(p, "implements") {
+            let implements = if p.input.syntax().uses_ts_parser() && eat!(p, "implements") {
                 p.parse_ts_heritage_clause()?
             } else {
                 vec![]
@@ -129,7 +129,7 @@ impl<I: Tokens> Parser<I> {
 
             {
                 // Handle TS1175
-                if p.input.syntax().typescript() && eat!(p, "implements") {
+                if p.input.syntax().uses_ts_parser() && eat!(p, "implements") {
                     p.emit_err(p.input.prev_span(), SyntaxError::TS1175);
 
                     p.parse_ts_heritage_clause()?;
@@ -137,7 +137,7 @@ impl<I: Tokens> Parser<I> {
             }
 
             // Handle TS1173
-            if p.input.syntax().typescript() && eat!(p, "extends") {
+            if p.input.syntax().uses_ts_parser() && eat!(p, "extends") {
                 p.emit_err(p.input.prev_span(), SyntaxError::TS1173);
 
                 let (sc, type_params) = p.parse_super_class()?;
@@ -203,6 +203,7 @@ impl<I: Tokens> Parser<I> {
             .parse_class_inner(start, class_start, decorators, T::IS_IDENT_REQUIRED)?;
 
         if is_abstract {
+            self.emit_err_if_flow(span!(self, start), "Abstract classes");
             class.is_abstract = true
         } else {
             for member in class.body.iter() {
@@ -239,7 +240,7 @@ impl<I: Tokens> Parser<I> {
                 // because in some cases "super class" returned by `parse_lhs_expr`
                 // may not include `TsExprWithTypeArgs`
                 // but it's a super class with type params, for example, in JSX.
-                if self.syntax().typescript() && is!(self, '<') {
+                if self.syntax().uses_ts_parser() && is!(self, '<') {
                     Ok((super_class, self.parse_ts_type_args().map(Some)?))
                 } else {
                     Ok((super_class, None))
@@ -308,7 +309,7 @@ impl<I: Tokens> Parser<I> {
     }
 
     fn parse_maybe_decorator_args(&mut self, expr: Box<Expr>) -> PResult<Box<Expr>> {
-        let type_args = if self.input.syntax().typescript() && is!(self, '<') {
+        let type_args = if self.input.syntax().uses_ts_parser() && is!(self, '<') {
             Some(self.parse_ts_type_args()?)
         } else {
             None
@@ -338,11 +339,24 @@ impl<I: Tokens> Parser<I> {
                 }));
                 continue;
             }
+            let start = cur_pos!(self);
+
             let mut p = self.with_ctx(Context {
                 allow_direct_super: true,
                 ..self.ctx()
             });
-            let elem = p.parse_class_member()?;
+            let elem = match p.parse_class_member() {
+                Ok(elem) => elem,
+                Err(err) if p.should_recover() => {
+                    // Restores the context
+                    drop(p);
+
+                    let span = self.recover_from(err, start, Boundary::ClassMember, true);
+                    elems.push(ClassMember::Empty(EmptyStmt { span }));
+                    continue;
+                }
+                Err(err) => return Err(err),
+            };
 
             if !p.ctx().in_declare {
                 if let ClassMember::Constructor(Constructor {
@@ -381,14 +395,17 @@ impl<I: Tokens> Parser<I> {
 
         let start = cur_pos!(self);
         let decorators = self.parse_decorators(false)?;
-        let declare = self.syntax().typescript() && eat!(self, "declare");
-        let accessibility = if self.input.syntax().typescript() {
+        let declare = self.syntax().uses_ts_parser() && eat!(self, "declare");
+        let accessibility = if self.input.syntax().uses_ts_parser() {
             self.parse_access_modifier()?
         } else {
             None
         };
+        if accessibility.is_some() {
+            self.emit_err_if_flow(self.input.prev_span(), "Accessibility modifiers");
+        }
         // Allow `private declare`.
-        let declare = declare || self.syntax().typescript() && eat!(self, "declare");
+        let declare = declare || self.syntax().uses_ts_parser() && eat!(self, "declare");
 
         let declare_token = if declare {
             // Handle declare(){}
@@ -397,7 +414,7 @@ impl<I: Tokens> Parser<I> {
                     js_word!("declare"),
                     span!(self, start),
                 )));
-                let is_optional = self.input.syntax().typescript() && eat!(self, '?');
+                let is_optional = self.input.syntax().uses_ts_parser() && eat!(self, '?');
                 return self.make_method(
                     |p| p.parse_unique_formal_params(),
                     MakeMethodArgs {
@@ -415,7 +432,7 @@ impl<I: Tokens> Parser<I> {
                     },
                 );
             } else if self.is_class_property(/* asi */ true)
-                || (self.syntax().typescript() && is!(self, '?'))
+                || (self.syntax().uses_ts_parser() && is!(self, '?'))
             {
                 // Property named `declare`
 
@@ -423,7 +440,7 @@ impl<I: Tokens> Parser<I> {
                     js_word!("declare"),
                     span!(self, start),
                 )));
-                let is_optional = self.input.syntax().typescript() && eat!(self, '?');
+                let is_optional = self.input.syntax().uses_ts_parser() && eat!(self, '?');
                 return self.make_property(
                     start,
                     decorators,
@@ -459,7 +476,7 @@ impl<I: Tokens> Parser<I> {
                     js_word!("static"),
                     static_token,
                 )));
-                let is_optional = self.input.syntax().typescript() && eat!(self, '?');
+                let is_optional = self.input.syntax().uses_ts_parser() && eat!(self, '?');
                 return self.make_method(
                     |p| p.parse_unique_formal_params(),
                     MakeMethodArgs {
@@ -477,7 +494,7 @@ impl<I: Tokens> Parser<I> {
                     },
                 );
             } else if self.is_class_property(/* asi */ true)
-                || (self.syntax().typescript() && is!(self, '?'))
+                || (self.syntax().uses_ts_parser() && is!(self, '?'))
             {
                 // Property named `static`
 
@@ -490,7 +507,7 @@ impl<I: Tokens> Parser<I> {
                         js_word!("static"),
                         static_token,
                     )));
-                    let is_optional = self.input.syntax().typescript() && eat!(self, '?');
+                    let is_optional = self.input.syntax().uses_ts_parser() && eat!(self, '?');
                     return self.make_property(
                         start,
                         decorators,
@@ -562,6 +579,7 @@ impl<I: Tokens> Parser<I> {
                             SyntaxError::TS1029(js_word!("abstract"), js_word!("override")),
                         );
                     } else {
+                        self.emit_err_if_flow(self.input.prev_span(), "Abstract members");
                         is_abstract = true;
                     }
                 }
@@ -609,6 +627,14 @@ impl<I: Tokens> Parser<I> {
             }
         }
 
+        // Variance of flow class properties, `+x` is read-only.
+        if self.input.syntax().flow() && is_one_of!(self, '+', '-') {
+            if is!(self, '+') {
+                readonly = Some(self.input.cur_span());
+            }
+            bump!(self);
+        }
+
         if is_static && is!(self, '{') {
             if let Some(span) = declare_token {
                 self.emit_err(span, SyntaxError::TS1184);
@@ -630,7 +656,7 @@ impl<I: Tokens> Parser<I> {
             return self.parse_static_block(start);
         }
 
-        if self.input.syntax().typescript()
+        if self.input.syntax().uses_ts_parser()
             && !is_abstract
             && !is_override
             && accessibility.is_none()
@@ -678,7 +704,7 @@ impl<I: Tokens> Parser<I> {
         } else {
             self.parse_class_prop_name()?
         };
-        let is_optional = self.input.syntax().typescript() && eat!(self, '?');
+        let is_optional = self.input.syntax().uses_ts_parser() && eat!(self, '?');
 
         if let Either::Right(PropName::Ident(i)) = &mut key {
             i.optional = is_optional;
@@ -699,14 +725,14 @@ impl<I: Tokens> Parser<I> {
             let is_constructor = is_constructor(&key);
 
             if is_constructor {
-                if self.syntax().typescript() && is_override {
+                if self.syntax().uses_ts_parser() && is_override {
                     self.emit_err(
                         span!(self, start),
                         SyntaxError::TS1089(js_word!("override")),
                     );
                 }
 
-                if self.syntax().typescript() && is!(self, '<') {
+                if self.syntax().uses_ts_parser() && is!(self, '<') {
                     let start = cur_pos!(self);
                     if peeked_is!(self, '>') {
                         assert_and_bump!(self, '<');
@@ -730,15 +756,20 @@ impl<I: Tokens> Parser<I> {
                 let params = self.parse_constructor_params()?;
                 expect!(self, ')');
 
-                if self.syntax().typescript() && is!(self, ':') {
+                if self.syntax().uses_ts_parser() && is!(self, ':') {
                     let start = cur_pos!(self);
                     let type_ann = self.parse_ts_type_ann(true, start)?;
 
                     self.emit_err(type_ann.type_ann.span(), SyntaxError::TS1093);
                 }
 
-                let body: Option<_> =
-                    self.parse_fn_body(false, false, false, params.is_simple_parameter_list())?;
+                let body: Option<_> = self.parse_fn_body(
+                    false,
+                    false,
+                    false,
+                    params.is_simple_parameter_list(),
+                    None,
+                )?;
 
                 if body.is_none() {
                     for param in params.iter() {
@@ -748,7 +779,7 @@ impl<I: Tokens> Parser<I> {
                     }
                 }
 
-                if self.syntax().typescript() && body.is_none() {
+                if self.syntax().uses_ts_parser() && body.is_none() {
                     // Declare constructors cannot have assignment pattern in parameters
                     for p in &params {
                         // TODO: Search deeply for assignment pattern using a Visitor
@@ -865,7 +896,8 @@ impl<I: Tokens> Parser<I> {
             }
 
             // handle async foo(){}
-            let is_optional = is_optional || self.input.syntax().typescript() && eat!(self, '?');
+            let is_optional =
+                is_optional || self.input.syntax().uses_ts_parser() && eat!(self, '?');
             return self.make_method(
                 |p| p.parse_unique_formal_params(),
                 MakeMethodArgs {
@@ -990,7 +1022,7 @@ impl<I: Tokens> Parser<I> {
                 )
             }
         }
-        let definite = self.input.syntax().typescript() && !is_optional && eat!(self, '!');
+        let definite = self.input.syntax().uses_ts_parser() && !is_optional && eat!(self, '!');
 
         let type_ann = self.try_parse_ts_type_ann()?;
 
@@ -1054,12 +1086,12 @@ impl<I: Tokens> Parser<I> {
 
     fn is_class_method(&mut self) -> bool {
         is!(self, '(')
-            || (self.input.syntax().typescript() && is!(self, '<'))
-            || (self.input.syntax().typescript() && is!(self, JSXTagStart))
+            || (self.input.syntax().uses_ts_parser() && is!(self, '<'))
+            || (self.input.syntax().uses_ts_parser() && is!(self, JSXTagStart))
     }
 
     fn is_class_property(&mut self, asi: bool) -> bool {
-        (self.input.syntax().typescript() && is_one_of!(self, '!', ':'))
+        (self.input.syntax().uses_ts_parser() && is_one_of!(self, '!', ':'))
             || is_one_of!(self, '=', '}')
             || if asi {
                 is!(self, ';')
@@ -1115,6 +1147,7 @@ impl<I: Tokens> Parser<I> {
                 |p| p.parse_formal_params(),
                 is_async,
                 is_generator,
+                Some(start),
             )?;
 
             if is_fn_expr && f.body.is_none() {
@@ -1159,6 +1192,8 @@ impl<I: Tokens> Parser<I> {
     }
 
     /// `parse_args` closure should not eat '(' or ')'.
+    ///
+    /// `fn_start` is passed to [Parser::parse_fn_body].
     pub(super) fn parse_fn_args_body<F>(
         &mut self,
         decorators: Vec<Decorator>,
@@ -1166,6 +1201,7 @@ impl<I: Tokens> Parser<I> {
         parse_args: F,
         is_async: bool,
         is_generator: bool,
+        fn_start: Option<BytePos>,
     ) -> PResult<Box<Function>>
     where
         F: FnOnce(&mut Self) -> PResult<Vec<Param>>,
@@ -1179,7 +1215,7 @@ impl<I: Tokens> Parser<I> {
         };
 
         self.with_ctx(ctx).parse_with(|p| {
-            let type_params = if p.syntax().typescript() {
+            let type_params = if p.syntax().uses_ts_parser() {
                 p.in_type().parse_with(|p| {
                     trace_cur!(p, parse_fn_args_body__type_params);
 
@@ -1220,7 +1256,7 @@ impl<I: Tokens> Parser<I> {
             expect!(p, ')');
 
             // typescript extension
-            let return_type = if p.syntax().typescript() && is!(p, ':') {
+            let return_type = if p.syntax().uses_ts_parser() && is!(p, ':') {
                 p.parse_ts_type_or_type_predicate_ann(&tok!(':'))
                     .map(Some)?
             } else {
@@ -1232,9 +1268,10 @@ impl<I: Tokens> Parser<I> {
                 is_generator,
                 false,
                 params.is_simple_parameter_list(),
+                fn_start,
             )?;
 
-            if p.syntax().typescript() && body.is_none() {
+            if p.syntax().uses_ts_parser() && body.is_none() {
                 // Declare functions cannot have assignment pattern in parameters
                 for param in &params {
                     // TODO: Search deeply for assignment pattern using a Visitor
@@ -1275,17 +1312,20 @@ impl<I: Tokens> Parser<I> {
         }
     }
 
+    /// `fn_start` is where `async` can be inserted to make the function async,
+    /// if there's such a position.
     pub(super) fn parse_fn_body<T>(
         &mut self,
         is_async: bool,
         is_generator: bool,
         is_arrow_function: bool,
         is_simple_parameter_list: bool,
+        fn_start: Option<BytePos>,
     ) -> PResult<T>
     where
         Self: FnBodyParser<T>,
     {
-        if self.ctx().in_declare && self.syntax().typescript() && is!(self, '{') {
+        if self.ctx().in_declare && self.syntax().uses_ts_parser() && is!(self, '{') {
             //            self.emit_err(
             //                self.ctx().span_of_fn_name.expect("we are not in function"),
             //                SyntaxError::TS1183,
@@ -1302,12 +1342,14 @@ impl<I: Tokens> Parser<I> {
                 true
             },
             in_function: true,
+            fn_start,
             is_break_allowed: false,
             is_continue_allowed: false,
             ..self.ctx()
         };
         let state = State {
             labels: vec![],
+            recovery: self.state.recovery,
             ..Default::default()
         };
         self.with_ctx(ctx)
@@ -1346,12 +1388,13 @@ impl<I: Tokens> Parser<I> {
                 ..self.ctx()
             })
             .parse_with(|p| {
-                p.parse_fn_args_body(decorators, start, parse_args, is_async, is_generator)
+                p.parse_fn_args_body(decorators, start, parse_args, is_async, is_generator, None)
             })?;
 
         match kind {
             MethodKind::Getter | MethodKind::Setter
-                if self.input.syntax().typescript() && self.input.target() == EsVersion::Es3 =>
+                if self.input.syntax().uses_ts_parser()
+                    && self.input.target() == EsVersion::Es3 =>
             {
                 self.emit_err(key.span(), SyntaxError::TS1056);
             }
@@ -1566,7 +1609,7 @@ impl<I: Tokens> FnBodyParser<Option<BlockStmt>> for Parser<I> {
         is_simple_parameter_list: bool,
     ) -> PResult<Option<BlockStmt>> {
         // allow omitting body and allow placing `{` on next line
-        if self.input.syntax().typescript() && !is!(self, '{') && eat!(self, ';') {
+        if self.input.syntax().uses_ts_parser() && !is!(self, '{') && eat!(self, ';') {
             return Ok(None);
         }
         let block = self.include_in_expr(true).parse_block(true);
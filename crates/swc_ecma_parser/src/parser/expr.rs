@@ -41,7 +41,7 @@ impl<I: Tokens> Parser<I> {
     pub(super) fn parse_assignment_expr(&mut self) -> PResult<Box<Expr>> {
         trace_cur!(self, parse_assignment_expr);
 
-        if self.input.syntax().typescript() && self.input.syntax().jsx() {
+        if self.input.syntax().uses_ts_parser() && self.input.syntax().jsx() {
             // Note: When the JSX plugin is on, type assertions (`<T> x`) aren't valid
             // syntax.
 
@@ -83,7 +83,7 @@ impl<I: Tokens> Parser<I> {
     fn parse_assignment_expr_base(&mut self) -> PResult<Box<Expr>> {
         trace_cur!(self, parse_assignment_expr_base);
 
-        if self.input.syntax().typescript()
+        if self.input.syntax().uses_ts_parser()
             && (is_one_of!(self, '<', JSXTagStart))
             && (peeked_is!(self, IdentName) || peeked_is!(self, JSXName))
         {
@@ -163,13 +163,13 @@ impl<I: Tokens> Parser<I> {
                     // It is an early Reference Error if IsValidSimpleAssignmentTarget of
                     // LeftHandSideExpression is false.
                     if !cond.is_valid_simple_assignment_target(self.ctx().strict) {
-                        if self.input.syntax().typescript() {
+                        if self.input.syntax().uses_ts_parser() {
                             self.emit_err(cond.span(), SyntaxError::TS2406);
                         } else {
                             self.emit_err(cond.span(), SyntaxError::NotSimpleAssign)
                         }
                     }
-                    if self.input.syntax().typescript()
+                    if self.input.syntax().uses_ts_parser()
                         && cond
                             .as_ident()
                             .map(|i| i.is_reserved_in_strict_bind())
@@ -281,7 +281,8 @@ impl<I: Tokens> Parser<I> {
                         return self.parse_async_fn_expr();
                     }
 
-                    if can_be_arrow && self.input.syntax().typescript() && peeked_is!(self, '<') {
+                    if can_be_arrow && self.input.syntax().uses_ts_parser() && peeked_is!(self, '<')
+                    {
                         // try parsing `async<T>() => {}`
                         if let Some(res) = self.try_parse_ts(|p| {
                             let start = cur_pos!(p);
@@ -400,7 +401,7 @@ impl<I: Tokens> Parser<I> {
         }
 
         if is!(self, "let")
-            || (self.input.syntax().typescript() && is_one_of!(self, IdentRef, "await"))
+            || (self.input.syntax().uses_ts_parser() && is_one_of!(self, IdentRef, "await"))
             || is!(self, IdentRef)
         {
             let ctx = self.ctx();
@@ -437,7 +438,7 @@ impl<I: Tokens> Parser<I> {
                 }
 
                 let ident = self.parse_binding_ident()?;
-                if self.input.syntax().typescript()
+                if self.input.syntax().uses_ts_parser()
                     && ident.id.sym == js_word!("as")
                     && !is!(self, "=>")
                 {
@@ -455,7 +456,7 @@ impl<I: Tokens> Parser<I> {
                 let params = vec![arg];
                 expect!(self, "=>");
                 let body =
-                    self.parse_fn_body(true, false, true, params.is_simple_parameter_list())?;
+                    self.parse_fn_body(true, false, true, params.is_simple_parameter_list(), None)?;
 
                 return Ok(Box::new(Expr::Arrow(ArrowExpr {
                     span: span!(self, start),
@@ -470,9 +471,15 @@ impl<I: Tokens> Parser<I> {
                 if self.ctx().strict && id.is_reserved_in_strict_bind() {
                     self.emit_strict_mode_err(id.span, SyntaxError::EvalAndArgumentsInStrict)
                 }
+                let fn_start = id.span.lo;
                 let params = vec![id.into()];
-                let body =
-                    self.parse_fn_body(false, false, true, params.is_simple_parameter_list())?;
+                let body = self.parse_fn_body(
+                    false,
+                    false,
+                    true,
+                    params.is_simple_parameter_list(),
+                    Some(fn_start),
+                )?;
 
                 return Ok(Box::new(Expr::Arrow(ArrowExpr {
                     span: span!(self, start),
@@ -616,7 +623,7 @@ impl<I: Tokens> Parser<I> {
                 }
             }
 
-            let type_args = if self.input.syntax().typescript() && is!(self, '<') {
+            let type_args = if self.input.syntax().uses_ts_parser() && is!(self, '<') {
                 self.try_parse_ts(|p| {
                     let args = p.parse_ts_type_args()?;
                     if !is!(p, '(') {
@@ -670,7 +677,7 @@ impl<I: Tokens> Parser<I> {
         let obj = self.parse_primary_expr()?;
         return_if_arrow!(self, obj);
 
-        let type_args = if self.syntax().typescript() && is!(self, '<') {
+        let type_args = if self.syntax().uses_ts_parser() && is!(self, '<') {
             self.try_parse_ts_type_args()
         } else {
             None
@@ -802,11 +809,16 @@ impl<I: Tokens> Parser<I> {
 
         let will_expect_colon_for_cond = self.ctx().will_expect_colon_for_cond;
         // This is slow path. We handle arrow in conditional expression.
-        if self.syntax().typescript() && self.ctx().in_cond_expr && is!(self, ':') {
+        if self.syntax().uses_ts_parser() && self.ctx().in_cond_expr && is!(self, ':') {
             // TODO: Remove clone
             let items_ref = &paren_items;
             if let Some(expr) = self.try_parse_ts(|p| {
-                let return_type = p.parse_ts_type_or_type_predicate_ann(&tok!(':'))?;
+                let return_type = p
+                    .with_ctx(Context {
+                        no_anon_fn_type: true,
+                        ..p.ctx()
+                    })
+                    .parse_ts_type_or_type_predicate_ann(&tok!(':'))?;
 
                 expect!(p, "=>");
 
@@ -820,6 +832,7 @@ impl<I: Tokens> Parser<I> {
                     false,
                     true,
                     params.is_simple_parameter_list(),
+                    None,
                 )?;
 
                 if will_expect_colon_for_cond && !is!(p, ':') {
@@ -842,11 +855,16 @@ impl<I: Tokens> Parser<I> {
         }
 
         let return_type = if !self.ctx().will_expect_colon_for_cond
-            && self.input.syntax().typescript()
+            && self.input.syntax().uses_ts_parser()
             && is!(self, ':')
         {
             self.try_parse_ts(|p| {
-                let return_type = p.parse_ts_type_or_type_predicate_ann(&tok!(':'))?;
+                let return_type = p
+                    .with_ctx(Context {
+                        no_anon_fn_type: true,
+                        ..p.ctx()
+                    })
+                    .parse_ts_type_or_type_predicate_ann(&tok!(':'))?;
 
                 if !is!(p, "=>") {
                     unexpected!(p, "fail")
@@ -878,11 +896,18 @@ impl<I: Tokens> Parser<I> {
                 .into_iter()
                 .collect();
 
+            // In typescript, type parameters of the arrow function precede `expr_start`
+            let fn_start = if async_span.is_none() && !self.input.syntax().uses_ts_parser() {
+                Some(expr_start)
+            } else {
+                None
+            };
             let body: BlockStmtOrExpr = self.parse_fn_body(
                 async_span.is_some(),
                 false,
                 true,
                 params.is_simple_parameter_list(),
+                fn_start,
             )?;
             let arrow_expr = ArrowExpr {
                 span: span!(self, expr_start),
@@ -1123,10 +1148,11 @@ impl<I: Tokens> Parser<I> {
     ) -> PResult<(Box<Expr>, bool)> {
         let _ = cur!(self, false);
 
-        if self.input.syntax().typescript() {
+        if self.input.syntax().uses_ts_parser() {
             if !self.input.had_line_break_before_cur() && is!(self, '!') {
                 self.input.set_expr_allowed(false);
                 assert_and_bump!(self, '!');
+                self.emit_err_if_flow(self.input.prev_span(), "Non-null assertions");
 
                 let expr = match obj {
                     Callee::Super(..) => {
@@ -1220,7 +1246,7 @@ impl<I: Tokens> Parser<I> {
             }
         }
 
-        let type_args = if self.syntax().typescript() && is!(self, '<') {
+        let type_args = if self.syntax().uses_ts_parser() && is!(self, '<') {
             self.try_parse_ts_type_args()
         } else {
             None
@@ -1257,7 +1283,7 @@ impl<I: Tokens> Parser<I> {
                 expr: prop,
             };
 
-            let type_args = if self.syntax().typescript() && is!(self, '<') {
+            let type_args = if self.syntax().uses_ts_parser() && is!(self, '<') {
                 self.try_parse_ts_type_args()
             } else {
                 None
@@ -1321,11 +1347,11 @@ impl<I: Tokens> Parser<I> {
 
         if (question_dot_token.is_some()
             && is!(self, '.')
-            && (peeked_is!(self, '(') || (self.syntax().typescript() && peeked_is!(self, '<')))
+            && (peeked_is!(self, '(') || (self.syntax().uses_ts_parser() && peeked_is!(self, '<')))
             && eat!(self, '.'))
             || (!no_call && (is!(self, '(')))
         {
-            let type_args = if self.syntax().typescript() && is!(self, '<') {
+            let type_args = if self.syntax().uses_ts_parser() && is!(self, '<') {
                 self.parse_ts_type_args().map(Some)?
             } else {
                 None
@@ -1376,7 +1402,7 @@ impl<I: Tokens> Parser<I> {
             debug_assert_eq!(obj.span_lo(), span.lo());
             debug_assert_eq!(prop.span_hi(), span.hi());
 
-            let type_args = if self.syntax().typescript() && is!(self, '<') {
+            let type_args = if self.syntax().uses_ts_parser() && is!(self, '<') {
                 self.try_parse_ts_type_args()
             } else {
                 None
@@ -1551,7 +1577,7 @@ impl<I: Tokens> Parser<I> {
         let callee = self.parse_new_expr()?;
         return_if_arrow!(self, callee);
 
-        let type_args = if self.input.syntax().typescript() && is!(self, '<') {
+        let type_args = if self.input.syntax().uses_ts_parser() && is!(self, '<') {
             self.try_parse_ts(|p| {
                 let type_args = p.parse_ts_type_args()?;
                 if is!(p, '(') {
@@ -1654,7 +1680,7 @@ impl<I: Tokens> Parser<I> {
             let pat_start = cur_pos!(self);
 
             let mut arg = {
-                if self.input.syntax().typescript()
+                if self.input.syntax().uses_ts_parser()
                     && (is!(self, IdentRef) || (is!(self, "...") && peeked_is!(self, IdentRef)))
                 {
                     let spread = if eat!(self, "...") {
@@ -1683,7 +1709,7 @@ impl<I: Tokens> Parser<I> {
                 }
             };
 
-            let optional = if self.input.syntax().typescript() {
+            let optional = if self.input.syntax().uses_ts_parser() {
                 if is!(self, '?') {
                     if peeked_is!(self, ',')
                         || peeked_is!(self, ':')
@@ -1746,10 +1772,29 @@ impl<I: Tokens> Parser<I> {
                 false
             };
 
-            if optional || (self.input.syntax().typescript() && is!(self, ':')) {
+            // A type cast of flow, like `(a: T)`
+            if self.input.syntax().flow()
+                && !optional
+                && arg.spread.is_none()
+                && is!(self, ':')
+                && self.is_flow_type_cast()
+            {
+                let type_ann_start = cur_pos!(self);
+                let type_ann = self.parse_ts_type_ann(/* eat_colon */ true, type_ann_start)?;
+                arg = ExprOrSpread {
+                    spread: None,
+                    expr: Box::new(Expr::TsAs(TsAsExpr {
+                        span: span!(self, pat_start),
+                        expr: arg.expr,
+                        type_ann: type_ann.type_ann,
+                    })),
+                };
+            }
+
+            if optional || (self.input.syntax().uses_ts_parser() && is!(self, ':')) {
                 // TODO: `async(...args?: any[]) : any => {}`
                 //
-                // if self.input.syntax().typescript() && optional && arg.spread.is_some() {
+                // if self.input.syntax().uses_ts_parser() && optional && arg.spread.is_some() {
                 //     self.emit_err(self.input.prev_span(), SyntaxError::TS1047)
                 // }
 
@@ -1852,8 +1897,13 @@ impl<I: Tokens> Parser<I> {
                     .into_iter()
                     .collect();
 
-                let body: BlockStmtOrExpr =
-                    self.parse_fn_body(false, false, true, params.is_simple_parameter_list())?;
+                let body: BlockStmtOrExpr = self.parse_fn_body(
+                    false,
+                    false,
+                    true,
+                    params.is_simple_parameter_list(),
+                    None,
+                )?;
                 let span = span!(self, start);
 
                 items.push(PatOrExprOrSpread::ExprOrSpread(ExprOrSpread {
@@ -2026,7 +2076,7 @@ impl<I: Tokens> Parser<I> {
         }
 
         // We follow behavior of tsc
-        if self.input.syntax().typescript() && self.syntax().early_errors() {
+        if self.input.syntax().uses_ts_parser() && self.syntax().early_errors() {
             let is_eval_or_arguments = match expr {
                 Expr::Ident(i) => i.is_reserved_in_strict_bind(),
                 _ => false,
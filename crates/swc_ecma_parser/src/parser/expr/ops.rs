@@ -88,7 +88,7 @@ impl<I: Tokens> Parser<I> {
     ) -> PResult<(Box<Expr>, Option<u8>)> {
         const PREC_OF_IN: u8 = 7;
 
-        if self.input.syntax().typescript()
+        if self.input.syntax().uses_ts_parser()
             && PREC_OF_IN > min_prec
             && !self.input.had_line_break_before_cur()
             && is!(self, "as")
@@ -114,7 +114,7 @@ impl<I: Tokens> Parser<I> {
 
             return self.parse_bin_op_recursively_inner(node, min_prec);
         }
-        if self.input.syntax().typescript()
+        if self.input.syntax().uses_ts_parser()
             && !self.input.had_line_break_before_cur()
             && is!(self, "satisfies")
         {
@@ -245,7 +245,7 @@ impl<I: Tokens> Parser<I> {
         trace_cur!(self, parse_unary_expr);
         let start = cur_pos!(self);
 
-        if !self.input.syntax().jsx() && self.input.syntax().typescript() && eat!(self, '<') {
+        if !self.input.syntax().jsx() && self.input.syntax().uses_ts_parser() && eat!(self, '<') {
             if eat!(self, "const") {
                 expect!(self, '>');
                 let expr = self.parse_unary_expr()?;
@@ -310,7 +310,7 @@ impl<I: Tokens> Parser<I> {
                 }
             }
 
-            if self.input.syntax().typescript() && op == op!("delete") {
+            if self.input.syntax().uses_ts_parser() && op == op!("delete") {
                 match arg.unwrap_parens() {
                     Expr::Member(..)
                     | Expr::OptChain(OptChainExpr {
@@ -384,7 +384,12 @@ impl<I: Tokens> Parser<I> {
         }
 
         if ctx.in_function && !ctx.in_async {
-            self.emit_err(self.input.cur_span(), SyntaxError::AwaitInFunction);
+            self.emit_err(
+                self.input.cur_span(),
+                SyntaxError::AwaitInFunction {
+                    fn_start: ctx.fn_start,
+                },
+            );
         }
 
         if ctx.in_parameters && !ctx.in_function {
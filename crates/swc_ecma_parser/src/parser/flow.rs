@@ -0,0 +1,434 @@
+//! Flow syntax, which is parsed into the closest typescript nodes.
+//!
+//! Only the parts of flow which typescript does not have are here. Everything
+//! else is handled by the typescript parser with small hooks.
+
+use swc_atoms::js_word;
+use swc_common::Spanned;
+
+use super::*;
+
+impl<I: Tokens> Parser<I> {
+    /// Reports `syntax` at `span` if we are parsing flow.
+    ///
+    /// Flow is parsed by the typescript parser, so this is used to reject the
+    /// parts of typescript which flow does not have, like enums.
+    pub(super) fn emit_err_if_flow(&mut self, span: Span, syntax: &'static str) {
+        if self.input.syntax().flow() {
+            self.emit_err(span, SyntaxError::TsOnlySyntaxInFlow(syntax));
+        }
+    }
+
+    /// `?T`, parsed as `T | null | undefined`.
+    pub(super) fn parse_flow_maybe_type(&mut self) -> PResult<Box<TsType>> {
+        debug_assert!(self.input.syntax().flow());
+
+        let start = cur_pos!(self);
+        assert_and_bump!(self, '?');
+        let question_span = self.input.prev_span();
+
+        let ty = self.parse_flow_prefix_type()?;
+        let keyword = |kind| {
+            Box::new(TsType::TsKeywordType(TsKeywordType {
+                span: question_span,
+                kind,
+            }))
+        };
+
+        Ok(Box::new(TsType::TsUnionOrIntersectionType(
+            TsUnionOrIntersectionType::TsUnionType(TsUnionType {
+                span: span!(self, start),
+                types: vec![
+                    ty,
+                    keyword(TsKeywordTypeKind::TsNullKeyword),
+                    keyword(TsKeywordTypeKind::TsUndefinedKeyword),
+                ],
+            }),
+        )))
+    }
+
+    /// `flowParsePrefixType`
+    fn parse_flow_prefix_type(&mut self) -> PResult<Box<TsType>> {
+        if is!(self, '?') {
+            return self.parse_flow_maybe_type();
+        }
+
+        self.parse_ts_type_operator_or_higher_inner()
+    }
+
+    /// Parses a function type without parentheses like `string => void` if
+    /// `ty` is followed by `=>`.
+    ///
+    /// `flowParseAnonFunctionWithoutParens`
+    pub(super) fn parse_flow_anon_fn_type(&mut self, ty: Box<TsType>) -> PResult<Box<TsType>> {
+        debug_assert!(self.input.syntax().flow());
+
+        if self.ctx().no_anon_fn_type || !is!(self, "=>") {
+            return Ok(ty);
+        }
+
+        let start = ty.span_lo();
+        let ret_start = cur_pos!(self);
+        bump!(self);
+        let ret = self.parse_ts_type()?;
+
+        Ok(Box::new(TsType::TsFnOrConstructorType(
+            TsFnOrConstructorType::TsFnType(TsFnType {
+                span: span!(self, start),
+                params: vec![unnamed_param(ty)],
+                type_params: None,
+                type_ann: Box::new(TsTypeAnn {
+                    span: span!(self, ret_start),
+                    type_ann: ret,
+                }),
+            }),
+        )))
+    }
+
+    /// Tries to parse a function type whose parameters may not have names,
+    /// like `(string, b?: number) => void`.
+    pub(super) fn try_parse_flow_fn_type(&mut self) -> Option<Box<TsType>> {
+        debug_assert!(self.input.syntax().flow());
+
+        self.try_parse_ts(|p| p.parse_flow_fn_type().map(Some))
+    }
+
+    /// `flowParseFunctionTypeParams`
+    fn parse_flow_fn_type(&mut self) -> PResult<Box<TsType>> {
+        let start = cur_pos!(self);
+        let type_params = self.try_parse_ts_type_params(false)?;
+
+        expect!(self, '(');
+        let params = self
+            .with_ctx(Context {
+                no_anon_fn_type: false,
+                ..self.ctx()
+            })
+            .parse_with(|p| {
+                let mut params = vec![];
+                while !is!(p, ')') {
+                    params.push(p.parse_flow_fn_type_param()?);
+                    if !eat!(p, ',') {
+                        break;
+                    }
+                }
+                Ok(params)
+            })?;
+        expect!(self, ')');
+
+        let ret_start = cur_pos!(self);
+        expect!(self, "=>");
+        let ret = self.parse_ts_type()?;
+
+        Ok(Box::new(TsType::TsFnOrConstructorType(
+            TsFnOrConstructorType::TsFnType(TsFnType {
+                span: span!(self, start),
+                params,
+                type_params,
+                type_ann: Box::new(TsTypeAnn {
+                    span: span!(self, ret_start),
+                    type_ann: ret,
+                }),
+            }),
+        )))
+    }
+
+    fn parse_flow_fn_type_param(&mut self) -> PResult<TsFnParam> {
+        let start = cur_pos!(self);
+        if !eat!(self, "...") {
+            return self.parse_flow_fn_type_param_ident().map(TsFnParam::Ident);
+        }
+        let dot3_token = self.input.prev_span();
+
+        let BindingIdent { id, type_ann } = self.parse_flow_fn_type_param_ident()?;
+        Ok(TsFnParam::Rest(RestPat {
+            span: span!(self, start),
+            dot3_token,
+            arg: Box::new(Pat::Ident(id.into())),
+            type_ann,
+        }))
+    }
+
+    /// `a: T`, `a?: T` or `T`.
+    fn parse_flow_fn_type_param_ident(&mut self) -> PResult<BindingIdent> {
+        if is!(self, IdentName) && (peeked_is!(self, ':') || peeked_is!(self, '?')) {
+            let mut id = self.parse_ident_name()?;
+            id.optional = eat!(self, '?');
+            let type_ann = self.try_parse_ts_type_ann()?;
+
+            return Ok(BindingIdent { id, type_ann });
+        }
+
+        match unnamed_param(self.parse_ts_type()?) {
+            TsFnParam::Ident(i) => Ok(i),
+            _ => unreachable!(),
+        }
+    }
+
+    /// `{ ... }` and `{| ... |}`, which may contain spreads.
+    ///
+    /// `flowParseObjectType`
+    pub(super) fn parse_flow_object_type(&mut self) -> PResult<Box<TsType>> {
+        debug_assert!(self.input.syntax().flow());
+
+        let start = cur_pos!(self);
+        let (members, mut types) = self.parse_flow_object_type_members()?;
+        let lit = Box::new(TsType::TsTypeLit(TsTypeLit {
+            span: span!(self, start),
+            members,
+        }));
+        if types.is_empty() {
+            return Ok(lit);
+        }
+
+        // `{ ...A, b: B }` is `A & { b: B }`
+        types.push(lit);
+        Ok(Box::new(TsType::TsUnionOrIntersectionType(
+            TsUnionOrIntersectionType::TsIntersectionType(TsIntersectionType {
+                span: span!(self, start),
+                types,
+            }),
+        )))
+    }
+
+    /// Returns the members of an object type and the types spread into it.
+    pub(super) fn parse_flow_object_type_members(
+        &mut self,
+    ) -> PResult<(Vec<TsTypeElement>, Vec<Box<TsType>>)> {
+        debug_assert!(self.input.syntax().flow());
+
+        self.with_ctx(Context {
+            no_anon_fn_type: false,
+            ..self.ctx()
+        })
+        .parse_with(|p| {
+            expect!(p, '{');
+            // `{||}`
+            if eat!(p, "||") {
+                expect!(p, '}');
+                return Ok((vec![], vec![]));
+            }
+            let exact = eat!(p, '|');
+
+            let mut members = vec![];
+            let mut spreads = vec![];
+            while !eof!(p) && !is!(p, '}') && !(exact && is!(p, '|')) {
+                let start = cur_pos!(p);
+
+                if eat!(p, "...") {
+                    // A lone `...` makes the object inexact.
+                    if !is_one_of!(p, ',', ';', '}') && !(exact && is!(p, '|')) {
+                        spreads.push(p.parse_ts_type()?);
+                    }
+                    p.parse_ts_type_member_semicolon()?;
+                    continue;
+                }
+
+                // Variance, `+a` is read-only.
+                let readonly = is!(p, '+');
+                if is_one_of!(p, '+', '-') {
+                    bump!(p);
+                }
+
+                if is!(p, '[') && !p.ts_look_ahead(|p| p.is_ts_unambiguously_index_signature())? {
+                    members.push(p.parse_flow_indexer(start, readonly)?.into());
+                    continue;
+                }
+
+                let mut member = p.parse_ts_type_member()?;
+                if readonly {
+                    match &mut member {
+                        TsTypeElement::TsPropertySignature(m) => m.readonly = true,
+                        TsTypeElement::TsIndexSignature(m) => m.readonly = true,
+                        _ => {}
+                    }
+                }
+                members.push(member);
+            }
+
+            if exact {
+                expect!(p, '|');
+            }
+            expect!(p, '}');
+
+            Ok((members, spreads))
+        })
+    }
+
+    /// Whether the current token is `|` of `|}`, which is not an union.
+    pub(super) fn is_flow_exact_object_end(&mut self) -> PResult<bool> {
+        Ok(self.input.syntax().flow() && is!(self, '|') && peeked_is!(self, '}'))
+    }
+
+    /// `[K]: V`, an indexer without a name.
+    fn parse_flow_indexer(&mut self, start: BytePos, readonly: bool) -> PResult<TsIndexSignature> {
+        expect!(self, '[');
+        let key = self.parse_ts_type()?;
+        expect!(self, ']');
+        let type_ann = self.try_parse_ts_type_ann()?;
+        self.parse_ts_type_member_semicolon()?;
+
+        Ok(TsIndexSignature {
+            span: span!(self, start),
+            params: vec![unnamed_param(key)],
+            type_ann,
+            readonly,
+            is_static: false,
+        })
+    }
+
+    /// Skips `%checks` or `%checks(expr)` after a return type.
+    pub(super) fn eat_flow_predicate(&mut self) -> PResult<()> {
+        if !self.input.syntax().flow()
+            || !is!(self, '%')
+            || !matches!(peek!(self), Ok(Token::Word(Word::Ident(w))) if &**w == "checks")
+        {
+            return Ok(());
+        }
+        bump!(self);
+        let _ = cur!(self, true);
+        bump!(self);
+
+        if eat!(self, '(') {
+            self.with_ctx(Context {
+                in_type: false,
+                ..self.ctx()
+            })
+            .parse_expr()?;
+            expect!(self, ')');
+        }
+
+        Ok(())
+    }
+
+    /// `opaque type T: Super = U`, after `opaque`.
+    ///
+    /// The supertype is used as the type if there's no `= U`, which is only
+    /// allowed in `declare opaque type`.
+    pub(super) fn parse_flow_opaque_type_alias(
+        &mut self,
+        start: BytePos,
+    ) -> PResult<Box<TsTypeAliasDecl>> {
+        debug_assert!(self.input.syntax().flow());
+
+        expect!(self, "type");
+        let id = self.parse_ident_name()?;
+        let type_params = self.try_parse_ts_type_params(true)?;
+        let supertype = self.eat_then_parse_ts_type(&tok!(':'))?;
+
+        let type_ann = match self.eat_then_parse_ts_type(&tok!('='))? {
+            Some(ty) => ty,
+            None if self.ctx().in_declare => supertype.unwrap_or_else(|| {
+                Box::new(TsType::TsKeywordType(TsKeywordType {
+                    span: id.span,
+                    kind: TsKeywordTypeKind::TsUnknownKeyword,
+                }))
+            }),
+            None => unexpected!(self, "="),
+        };
+        expect!(self, ';');
+
+        Ok(Box::new(TsTypeAliasDecl {
+            span: span!(self, start),
+            declare: false,
+            id,
+            type_params,
+            type_ann,
+        }))
+    }
+
+    /// `declare export ...` and `declare module.exports: T`, after `declare`.
+    ///
+    /// `declare export default T` and `declare module.exports: T` are parsed
+    /// as declared type aliases named `default` and `exports`.
+    pub(super) fn try_parse_flow_declare(&mut self, start: BytePos) -> PResult<Option<Decl>> {
+        debug_assert!(self.input.syntax().flow());
+
+        if is!(self, "module") && peeked_is!(self, '.') {
+            bump!(self);
+            let _ = cur!(self, true);
+            bump!(self);
+            let id = self.parse_ident_name()?;
+            if &*id.sym != "exports" {
+                syntax_error!(
+                    self,
+                    id.span,
+                    SyntaxError::Unexpected {
+                        got: id.sym.to_string(),
+                        expected: "exports"
+                    }
+                )
+            }
+            let type_ann = self.expect_then_parse_ts_type(&tok!(':'), ":")?;
+            expect!(self, ';');
+
+            return Ok(Some(self.flow_declared_type(start, id, type_ann)));
+        }
+
+        if !eat!(self, "export") {
+            return Ok(None);
+        }
+        if !eat!(self, "default") || is_one_of!(self, "function", "class") {
+            return self.try_parse_ts_declare(start, vec![]);
+        }
+
+        let id = Ident::new(js_word!("default"), self.input.prev_span());
+        let type_ann = self.in_type().parse_with(|p| p.parse_ts_type())?;
+        expect!(self, ';');
+
+        Ok(Some(self.flow_declared_type(start, id, type_ann)))
+    }
+
+    fn flow_declared_type(&mut self, start: BytePos, id: Ident, type_ann: Box<TsType>) -> Decl {
+        Decl::TsTypeAlias(Box::new(TsTypeAliasDecl {
+            span: span!(self, start),
+            declare: true,
+            id,
+            type_params: None,
+            type_ann,
+        }))
+    }
+
+    /// Whether the `:` after an expression in parentheses starts a type cast
+    /// like `(a: T)`, instead of the type of an arrow function parameter.
+    pub(super) fn is_flow_type_cast(&mut self) -> bool {
+        debug_assert!(self.input.syntax().flow());
+
+        let res = self.ts_look_ahead(|p| {
+            let start = cur_pos!(p);
+            p.parse_ts_type_ann(/* eat_colon */ true, start)?;
+
+            if !eat!(p, ')') || is!(p, "=>") {
+                return Ok(false);
+            }
+            if !is!(p, ':') {
+                return Ok(true);
+            }
+
+            // `(a: T): U => a`
+            let start = cur_pos!(p);
+            let is_return_type = p
+                .with_ctx(Context {
+                    no_anon_fn_type: true,
+                    ..p.ctx()
+                })
+                .parse_ts_type_ann(/* eat_colon */ true, start)
+                .is_ok()
+                && is!(p, "=>");
+            Ok(!is_return_type)
+        });
+
+        matches!(res, Ok(true))
+    }
+}
+
+/// A parameter of a function type or an indexer, which does not have a name in
+/// flow.
+fn unnamed_param(ty: Box<TsType>) -> TsFnParam {
+    let span = ty.span();
+
+    TsFnParam::Ident(BindingIdent {
+        id: Ident::new(js_word!(""), span),
+        type_ann: Some(Box::new(TsTypeAnn { span, type_ann: ty })),
+    })
+}
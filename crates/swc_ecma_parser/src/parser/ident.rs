@@ -150,7 +150,7 @@ impl<I: Tokens> Parser<I> {
                 Word::Keyword(Keyword::Await) if p.ctx().module | p.ctx().in_async => {
                     syntax_error!(p, p.input.prev_span(), SyntaxError::InvalidIdentInAsync)
                 }
-                Word::Keyword(Keyword::This) if p.input.syntax().typescript() => {
+                Word::Keyword(Keyword::This) if p.input.syntax().uses_ts_parser() => {
                     Ok(js_word!("this"))
                 }
                 Word::Keyword(Keyword::Let) => Ok(js_word!("let")),
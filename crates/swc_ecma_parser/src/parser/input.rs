@@ -185,7 +185,12 @@ impl<I: Tokens> Iterator for Capturing<I> {
 
                 // remove tokens that could change due to backtracing
                 while let Some(last) = v.last() {
-                    if last.span.lo >= ts.span.lo {
+                    // An empty token (like an empty template element) is not
+                    // replaced by the next token at the same position
+                    let is_empty_before = last.span.lo == last.span.hi && ts.span.lo != ts.span.hi;
+
+                    if last.span.lo > ts.span.lo || (last.span.lo == ts.span.lo && !is_empty_before)
+                    {
                         v.pop();
                     } else {
                         break;
@@ -209,7 +209,7 @@ impl<I: Tokens> Parser<I> {
     ) -> PResult<JSXOpeningElement> {
         debug_assert!(self.input.syntax().jsx());
 
-        let type_args = if self.input.syntax().typescript() && is!(self, '<') {
+        let type_args = if self.input.syntax().uses_ts_parser() && is!(self, '<') {
             self.try_parse_ts(|p| p.parse_ts_type_args().map(Some))
         } else {
             None
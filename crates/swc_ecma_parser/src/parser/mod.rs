@@ -26,11 +26,13 @@ use crate::error::Error;
 mod macros;
 mod class_and_fn;
 mod expr;
+mod flow;
 mod ident;
 pub mod input;
 mod jsx;
 mod object;
 mod pat;
+mod recovery;
 mod stmt;
 #[cfg(test)]
 mod tests;
@@ -56,6 +58,9 @@ struct State {
     found_module_item: bool,
     /// Start position of an AST node and the span of its trailing comma.
     trailing_commas: AHashMap<BytePos, Span>,
+
+    /// See [Parser::set_recovery].
+    recovery: bool,
 }
 
 impl<'a, I: Input> Parser<Lexer<'a, I>> {
@@ -82,6 +87,19 @@ impl<I: Tokens> Parser<I> {
         }
     }
 
+    /// Enables the error recovery mode, which is meant for editors and other
+    /// tools working on files with syntax errors.
+    ///
+    /// In this mode, a statement or a class member which can't be parsed is
+    /// skipped up to the next statement or member boundary, and replaced by an
+    /// [Invalid] expression statement or an empty class member spanning the
+    /// skipped tokens. The errors are collected and can be taken with
+    /// [Parser::take_errors], while methods like [Parser::parse_module] return
+    /// the partial AST.
+    pub fn set_recovery(&mut self, recovery: bool) {
+        self.state.recovery = recovery;
+    }
+
     pub fn take_errors(&mut self) -> Vec<Error> {
         self.input().take_errors()
     }
@@ -113,7 +131,7 @@ impl<I: Tokens> Parser<I> {
     pub fn parse_typescript_module(&mut self) -> PResult<Module> {
         trace_cur!(self, parse_typescript_module);
 
-        debug_assert!(self.syntax().typescript());
+        debug_assert!(self.syntax().uses_ts_parser());
 
         //TODO: parse() -> PResult<Program>
         let ctx = Context {
@@ -205,6 +223,39 @@ impl<I: Tokens> Parser<I> {
         })
     }
 
+    /// Parses module items until the end of the input, or until `stop` returns
+    /// true for the start of the next item.
+    #[cfg(feature = "incremental")]
+    pub(crate) fn parse_module_until(
+        &mut self,
+        mut stop: impl FnMut(BytePos) -> bool,
+    ) -> PResult<Module> {
+        let ctx = Context {
+            module: true,
+            can_be_module: true,
+            strict: true,
+            ..self.ctx()
+        };
+        self.set_ctx(ctx);
+
+        let start = cur_pos!(self);
+        let shebang = self.parse_shebang()?;
+
+        let mut body = vec![];
+        while !eof!(self) && !stop(cur_pos!(self)) {
+            body.push(self.parse_stmt_like_or_recover(true, false)?);
+        }
+
+        // The parser may stop before the first item
+        let end = start.max(last_pos!(self));
+
+        Ok(Module {
+            span: Span::new(start, end, Default::default()),
+            body,
+            shebang,
+        })
+    }
+
     fn parse_shebang(&mut self) -> PResult<Option<Atom>> {
         match cur!(self, false) {
             Ok(&Token::Shebang(..)) => match bump!(self) {
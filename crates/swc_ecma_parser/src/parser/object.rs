@@ -87,7 +87,7 @@ impl<I: Tokens> Parser<I> {
 
                     let mut expr = p.include_in_expr(true).parse_assignment_expr()?;
 
-                    if p.syntax().typescript() && is!(p, ',') {
+                    if p.syntax().uses_ts_parser() && is!(p, ',') {
                         let mut exprs = vec![expr];
 
                         while eat!(p, ',') {
@@ -169,6 +169,7 @@ impl<I: Tokens> ParseObject<Box<Expr>> for Parser<I> {
                     |p| p.parse_unique_formal_params(),
                     false,
                     true,
+                    Some(start),
                 )
                 .map(|function| {
                     PropOrSpread::Prop(Box::new(Prop::Method(MethodProp {
@@ -183,9 +184,9 @@ impl<I: Tokens> ParseObject<Box<Expr>> for Parser<I> {
 
         let key = self.parse_prop_name()?;
 
-        if self.input.syntax().typescript()
+        if self.input.syntax().uses_ts_parser()
             && !is_one_of!(self, '(', '[', ':', ',', '?', '=', '*', IdentName, Str, Num)
-            && !(self.input.syntax().typescript() && is!(self, '<'))
+            && !(self.input.syntax().uses_ts_parser() && is!(self, '<'))
             && !(is!(self, '}') && matches!(key, PropName::Ident(..)))
         {
             trace_cur!(self, parse_object_prop_error);
@@ -212,7 +213,7 @@ impl<I: Tokens> ParseObject<Box<Expr>> for Parser<I> {
         }
 
         // Handle `a(){}` (and async(){} / get(){} / set(){})
-        if (self.input.syntax().typescript() && is!(self, '<')) || is!(self, '(') {
+        if (self.input.syntax().uses_ts_parser() && is!(self, '<')) || is!(self, '(') {
             return self
                 .with_ctx(Context {
                     allow_direct_super: true,
@@ -226,6 +227,7 @@ impl<I: Tokens> ParseObject<Box<Expr>> for Parser<I> {
                     |p| p.parse_unique_formal_params(),
                     false,
                     false,
+                    Some(start),
                 )
                 .map(|function| Box::new(Prop::Method(MethodProp { key, function })))
                 .map(PropOrSpread::Prop);
@@ -298,13 +300,14 @@ impl<I: Tokens> ParseObject<Box<Expr>> for Parser<I> {
                                 },
                                 false,
                                 false,
+                                None,
                             )
                             .map(|v| *v)
                             .map(
                                 |Function {
                                      body, return_type, ..
                                  }| {
-                                    if parser.input.syntax().typescript()
+                                    if parser.input.syntax().uses_ts_parser()
                                         && parser.input.target() == EsVersion::Es3
                                     {
                                         parser.emit_err(key_span, SyntaxError::TS1056);
@@ -340,7 +343,7 @@ impl<I: Tokens> ParseObject<Box<Expr>> for Parser<I> {
                                             }
                                         }
 
-                                        if p.input.syntax().typescript()
+                                        if p.input.syntax().uses_ts_parser()
                                             && p.input.target() == EsVersion::Es3
                                         {
                                             p.emit_err(key_span, SyntaxError::TS1056);
@@ -350,6 +353,7 @@ impl<I: Tokens> ParseObject<Box<Expr>> for Parser<I> {
                                     },
                                     false,
                                     false,
+                                    None,
                                 )
                                 .map(|v| *v)
                                 .map(|Function { params, body, .. }| {
@@ -374,6 +378,7 @@ impl<I: Tokens> ParseObject<Box<Expr>> for Parser<I> {
                                 |p| p.parse_unique_formal_params(),
                                 true,
                                 is_generator,
+                                None,
                             )
                             .map(|function| {
                                 PropOrSpread::Prop(Box::new(Prop::Method(MethodProp {
@@ -386,7 +391,7 @@ impl<I: Tokens> ParseObject<Box<Expr>> for Parser<I> {
                 })
             }
             _ => {
-                if self.input.syntax().typescript() {
+                if self.input.syntax().uses_ts_parser() {
                     unexpected!(
                         self,
                         "... , *,  (, [, :, , ?, =, an identifier, public, protected, private, \
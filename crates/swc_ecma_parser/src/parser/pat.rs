@@ -18,7 +18,7 @@ impl<I: Tokens> Parser<I> {
     pub(super) fn parse_opt_binding_ident(&mut self) -> PResult<Option<BindingIdent>> {
         trace_cur!(self, parse_opt_binding_ident);
 
-        if is!(self, BindingIdent) || (self.input.syntax().typescript() && is!(self, "this")) {
+        if is!(self, BindingIdent) || (self.input.syntax().uses_ts_parser() && is!(self, "this")) {
             self.parse_binding_ident().map(Some)
         } else {
             Ok(None)
@@ -150,7 +150,7 @@ impl<I: Tokens> Parser<I> {
     }
 
     pub(super) fn eat_any_ts_modifier(&mut self) -> PResult<bool> {
-        let has_modifier = self.syntax().typescript()
+        let has_modifier = self.syntax().uses_ts_parser()
             && matches!(
                 *cur!(self, false)?,
                 Word(Word::Ident(
@@ -180,7 +180,7 @@ impl<I: Tokens> Parser<I> {
         let mut pat = self.parse_binding_element()?;
         let mut opt = false;
 
-        if self.input.syntax().typescript() {
+        if self.input.syntax().uses_ts_parser() {
             if eat!(self, '?') {
                 match pat {
                     Pat::Ident(BindingIdent {
@@ -296,7 +296,7 @@ impl<I: Tokens> Parser<I> {
                 let dot3_token = span!(self, pat_start);
 
                 let pat = self.parse_binding_pat_or_ident()?;
-                let type_ann = if self.input.syntax().typescript() && is!(self, ':') {
+                let type_ann = if self.input.syntax().uses_ts_parser() && is!(self, ':') {
                     let cur_pos = cur_pos!(self);
                     Some(self.parse_ts_type_ann(/* eat_colon */ true, cur_pos)?)
                 } else {
@@ -335,7 +335,7 @@ impl<I: Tokens> Parser<I> {
         param_start: BytePos,
         decorators: Vec<Decorator>,
     ) -> PResult<ParamOrTsParamProp> {
-        let (accessibility, is_override, readonly) = if self.input.syntax().typescript() {
+        let (accessibility, is_override, readonly) = if self.input.syntax().uses_ts_parser() {
             let accessibility = self.parse_access_modifier()?;
             (
                 accessibility,
@@ -358,6 +358,7 @@ impl<I: Tokens> Parser<I> {
                 Pat::Assign(a) => TsParamPropParam::Assign(a),
                 node => syntax_error!(self, node.span(), SyntaxError::TsInvalidParamPropPat),
             };
+            self.emit_err_if_flow(span!(self, param_start), "Parameter properties");
             Ok(ParamOrTsParamProp::TsParamProp(TsParamProp {
                 span: span!(self, param_start),
                 accessibility,
@@ -421,7 +422,7 @@ impl<I: Tokens> Parser<I> {
                     .into();
                 }
 
-                let type_ann = if self.input.syntax().typescript() && is!(self, ':') {
+                let type_ann = if self.input.syntax().uses_ts_parser() && is!(self, ':') {
                     let cur_pos = cur_pos!(self);
                     let ty = self.parse_ts_type_ann(/* eat_colon */ true, cur_pos)?;
                     Some(ty)
@@ -437,7 +438,7 @@ impl<I: Tokens> Parser<I> {
                     type_ann,
                 });
 
-                if self.syntax().typescript() && eat!(self, '?') {
+                if self.syntax().uses_ts_parser() && eat!(self, '?') {
                     self.emit_err(self.input.prev_span(), SyntaxError::TS1047);
                     //
                 }
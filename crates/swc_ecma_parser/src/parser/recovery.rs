@@ -0,0 +1,106 @@
+//! Error recovery, see [Parser::set_recovery].
+
+use std::cmp;
+
+use super::*;
+
+/// The kind of nodes separated by a synchronization point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Boundary {
+    /// A `;`, or a keyword or an identifier on a new line.
+    Stmt,
+    /// A `;`, or any token on a new line.
+    ClassMember,
+}
+
+impl<I: Tokens> Parser<I> {
+    /// Returns true if an error should be recovered from instead of being
+    /// returned.
+    ///
+    /// Speculative parsing (i.e. in typescript) ignores errors, and relies on
+    /// them to backtrack.
+    pub(super) fn should_recover(&self) -> bool {
+        self.state.recovery && !self.ctx().ignore_error
+    }
+
+    /// Records `err` and skips tokens up to the next `boundary`, and returns
+    /// the span of the skipped tokens starting at `start`.
+    ///
+    /// A `;` is skipped, while an unmatched `}` is not if the nodes are in a
+    /// block, because it closes the block.
+    pub(super) fn recover_from(
+        &mut self,
+        err: Error,
+        start: BytePos,
+        boundary: Boundary,
+        in_block: bool,
+    ) -> Span {
+        self.input_ref().add_error(err);
+
+        let mut depth = 0usize;
+        // A token on a new line is a boundary if the failed node consumed some tokens
+        let mut moved = self.input.last_pos() > start;
+
+        while let Some(token) = self.input.cur().cloned() {
+            match token {
+                Token::Error(..) => match self.input.bump() {
+                    Token::Error(err) => {
+                        self.input_ref().add_error(err);
+                        moved = true;
+                        continue;
+                    }
+                    _ => unreachable!(),
+                },
+                tok!('{') | tok!('(') | tok!('[') | tok!("${") => depth += 1,
+                tok!('}') | tok!(')') | tok!(']') if depth > 0 => depth -= 1,
+                tok!('}') if in_block => break,
+                Token::Semi if depth == 0 => {
+                    self.input.bump();
+                    break;
+                }
+                _ if depth == 0
+                    && moved
+                    && self.input.had_line_break_before_cur()
+                    && (boundary == Boundary::ClassMember || is_stmt_start(&token)) =>
+                {
+                    break
+                }
+                _ => {}
+            }
+
+            self.input.bump();
+            moved = true;
+        }
+
+        Span::new(
+            start,
+            cmp::max(start, self.input.last_pos()),
+            Default::default(),
+        )
+    }
+}
+
+fn is_stmt_start(token: &Token) -> bool {
+    matches!(
+        token,
+        Token::Word(Word::Ident(..))
+            | tok!("break")
+            | tok!("class")
+            | tok!("const")
+            | tok!("continue")
+            | tok!("debugger")
+            | tok!("do")
+            | tok!("export")
+            | tok!("for")
+            | tok!("function")
+            | tok!("if")
+            | tok!("import")
+            | tok!("let")
+            | tok!("return")
+            | tok!("switch")
+            | tok!("throw")
+            | tok!("try")
+            | tok!("var")
+            | tok!("while")
+    )
+}
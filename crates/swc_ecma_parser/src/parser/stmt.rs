@@ -2,7 +2,7 @@ use swc_atoms::js_word;
 use swc_common::Spanned;
 use typed_arena::Arena;
 
-use super::{pat::PatType, *};
+use super::{pat::PatType, recovery::Boundary, *};
 use crate::error::SyntaxError;
 
 mod module_item;
@@ -40,7 +40,7 @@ impl<'a, I: Tokens> Parser<I> {
                 c != end
             }
         } {
-            let stmt = self.parse_stmt_like(true, top_level)?;
+            let stmt = self.parse_stmt_like_or_recover(top_level, end.is_some())?;
             if allow_directives {
                 allow_directives = false;
                 if stmt.is_use_strict() {
@@ -72,6 +72,40 @@ impl<'a, I: Tokens> Parser<I> {
         Ok(stmts.into_vec())
     }
 
+    /// Parses a statement, declaration or module item, and replaces it with an
+    /// [Invalid] expression statement if it can't be parsed in the recovery
+    /// mode.
+    pub(super) fn parse_stmt_like_or_recover<Type>(
+        &mut self,
+        top_level: bool,
+        in_block: bool,
+    ) -> PResult<Type>
+    where
+        Self: StmtLikeParser<'a, Type>,
+        Type: IsDirective + From<Stmt>,
+    {
+        let start = cur_pos!(self);
+        let ctx = self.ctx();
+        let labels = self.state.labels.len();
+
+        match self.parse_stmt_like(true, top_level) {
+            Err(err) if self.should_recover() => {
+                // Contexts and labels of the failed statement are not restored by its parser
+                self.set_ctx(ctx);
+                self.state.labels.truncate(labels);
+
+                let span = self.recover_from(err, start, Boundary::Stmt, in_block);
+
+                Ok(Stmt::Expr(ExprStmt {
+                    span,
+                    expr: Box::new(Expr::Invalid(Invalid { span })),
+                })
+                .into())
+            }
+            res => res,
+        }
+    }
+
     /// Parse a statement but not a declaration.
     pub fn parse_stmt(&mut self, top_level: bool) -> PResult<Stmt> {
         trace_cur!(self, parse_stmt);
@@ -137,7 +171,7 @@ impl<'a, I: Tokens> Parser<I> {
             return Ok(Stmt::Expr(ExprStmt { span, expr }));
         }
 
-        let is_typescript = self.input.syntax().typescript();
+        let is_typescript = self.input.syntax().uses_ts_parser();
 
         if is_typescript && is!(self, "const") && peeked_is!(self, "enum") {
             assert_and_bump!(self, "const");
@@ -374,7 +408,7 @@ impl<'a, I: Tokens> Parser<I> {
                 }));
             }
 
-            if self.input.syntax().typescript() {
+            if self.input.syntax().uses_ts_parser() {
                 if let Some(decl) = self.parse_ts_expr_stmt(decorators, ident.clone())? {
                     return Ok(Stmt::Decl(decl));
                 }
@@ -390,7 +424,7 @@ impl<'a, I: Tokens> Parser<I> {
             }
         }
 
-        if self.syntax().typescript() {
+        if self.syntax().uses_ts_parser() {
             if let Expr::Ident(ref i) = *expr {
                 match i.sym {
                     js_word!("public") | js_word!("static") | js_word!("abstract") => {
@@ -722,7 +756,7 @@ impl<'a, I: Tokens> Parser<I> {
 
             let type_ann_start = cur_pos!(self);
 
-            if self.syntax().typescript() && eat!(self, ':') {
+            if self.syntax().uses_ts_parser() && eat!(self, ':') {
                 let ctx = Context {
                     in_type: true,
                     ..self.ctx()
@@ -764,7 +798,7 @@ impl<'a, I: Tokens> Parser<I> {
         let var_span = span!(self, start);
         let should_include_in = kind != VarDeclKind::Var || !for_loop;
 
-        if self.syntax().typescript() && for_loop {
+        if self.syntax().uses_ts_parser() && for_loop {
             let res = if is_one_of!(self, "in", "of") {
                 self.ts_look_ahead(|p| {
                     //
@@ -860,7 +894,7 @@ impl<'a, I: Tokens> Parser<I> {
 
         let mut name = self.parse_binding_pat_or_ident()?;
 
-        let definite = if self.input.syntax().typescript() {
+        let definite = if self.input.syntax().uses_ts_parser() {
             match name {
                 Pat::Ident(..) => eat!(self, '!'),
                 _ => false,
@@ -870,7 +904,7 @@ impl<'a, I: Tokens> Parser<I> {
         };
 
         // Typescript extension
-        if self.input.syntax().typescript() && is!(self, ':') {
+        if self.input.syntax().uses_ts_parser() && is!(self, ':') {
             let type_annotation = self.try_parse_ts_type_ann()?;
             match name {
                 Pat::Array(ArrayPat {
@@ -980,7 +1014,7 @@ impl<'a, I: Tokens> Parser<I> {
     }
 
     fn parse_with_stmt(&mut self) -> PResult<Stmt> {
-        if self.syntax().typescript() {
+        if self.syntax().uses_ts_parser() {
             let span = self.input.cur_span();
             self.emit_err(span, SyntaxError::TS2410);
         }
@@ -1148,7 +1182,7 @@ impl<'a, I: Tokens> Parser<I> {
                         );
                     }
 
-                    if self.syntax().typescript() {
+                    if self.syntax().uses_ts_parser() {
                         let type_ann = match decl.decls[0].name {
                             Pat::Ident(ref v) => Some(&v.type_ann),
                             Pat::Array(ref v) => Some(&v.type_ann),
@@ -1186,7 +1220,7 @@ impl<'a, I: Tokens> Parser<I> {
             let pat = self.reparse_expr_as_pat(PatType::AssignPat, init)?;
 
             // for ({} in foo) is invalid
-            if self.input.syntax().typescript() && is_in {
+            if self.input.syntax().uses_ts_parser() && is_in {
                 match pat {
                     Pat::Ident(..) => {}
                     Pat::Expr(..) => {}
@@ -42,7 +42,7 @@ impl<I: Tokens> Parser<I> {
 
         expect!(self, "import");
 
-        if self.input.syntax().typescript() && is!(self, IdentRef) && peeked_is!(self, '=') {
+        if self.input.syntax().uses_ts_parser() && is!(self, IdentRef) && peeked_is!(self, '=') {
             return self
                 .parse_ts_import_equals_decl(
                     start, /* is_export */ false, /* is_type_only */ false,
@@ -85,12 +85,13 @@ impl<I: Tokens> Parser<I> {
             .map(ModuleItem::from);
         }
 
-        let type_only = self.input.syntax().typescript()
-            && is!(self, "type")
+        // `import typeof x from 'mod'` of flow is also type-only.
+        let type_only = self.input.syntax().uses_ts_parser()
+            && (is!(self, "type") || (self.input.syntax().flow() && is!(self, "typeof")))
             && (peeked_is!(self, '{') || !peeked_is!(self, "from") && !peeked_is!(self, ','));
 
         if type_only {
-            assert_and_bump!(self, "type");
+            bump!(self);
 
             if is!(self, IdentRef) && peeked_is!(self, '=') {
                 return self
@@ -193,8 +194,9 @@ impl<I: Tokens> Parser<I> {
                 // `import { type as } from 'mod'`
                 // `import { type as as } from 'mod'`
                 // `import { type as as as } from 'mod'`
-                if self.syntax().typescript()
-                    && orig_name.sym == js_word!("type")
+                if self.syntax().uses_ts_parser()
+                    && (orig_name.sym == js_word!("type")
+                        || (self.syntax().flow() && orig_name.sym == js_word!("typeof")))
                     && is!(self, IdentName)
                 {
                     let possibly_orig_name = self.parse_ident_name()?;
@@ -344,7 +346,7 @@ impl<I: Tokens> Parser<I> {
         let after_export_start = cur_pos!(self);
 
         // "export declare" is equivalent to just "export".
-        let declare = self.input.syntax().typescript() && eat!(self, "declare");
+        let declare = self.input.syntax().uses_ts_parser() && eat!(self, "declare");
 
         if declare {
             // TODO: Remove
@@ -356,7 +358,7 @@ impl<I: Tokens> Parser<I> {
             }
         }
 
-        if self.input.syntax().typescript() && is!(self, IdentName) {
+        if self.input.syntax().uses_ts_parser() && is!(self, IdentName) {
             let sym = match *cur!(self, true)? {
                 Token::Word(ref w) => w.clone().into(),
                 _ => unreachable!(),
@@ -370,7 +372,7 @@ impl<I: Tokens> Parser<I> {
             }
         }
 
-        if self.input.syntax().typescript() {
+        if self.input.syntax().uses_ts_parser() {
             if eat!(self, "import") {
                 let is_type_only = is!(self, "type") && peeked_is!(self, IdentRef);
 
@@ -411,13 +413,13 @@ impl<I: Tokens> Parser<I> {
 
         let ns_export_specifier_start = cur_pos!(self);
 
-        let type_only = self.input.syntax().typescript() && eat!(self, "type");
+        let type_only = self.input.syntax().uses_ts_parser() && eat!(self, "type");
 
         // Some("default") if default is exported from 'src'
         let mut export_default = None;
 
         if !type_only && eat!(self, "default") {
-            if self.input.syntax().typescript() {
+            if self.input.syntax().uses_ts_parser() {
                 if is!(self, "abstract")
                     && peeked_is!(self, "class")
                     && !self.input.has_linebreak_between_cur_and_peeked()
@@ -489,7 +491,7 @@ impl<I: Tokens> Parser<I> {
         } else if !type_only && is!(self, "function") {
             self.parse_fn_decl(decorators)?
         } else if !type_only
-            && self.input.syntax().typescript()
+            && self.input.syntax().uses_ts_parser()
             && is!(self, "const")
             && peeked_is!(self, "enum")
         {
@@ -544,7 +546,7 @@ impl<I: Tokens> Parser<I> {
                 }
             };
 
-            if self.input.syntax().typescript() && type_only && !is!(self, '{') {
+            if self.input.syntax().uses_ts_parser() && type_only && !is!(self, '{') {
                 self.emit_err(span!(self, start), SyntaxError::TS1383)
             }
 
@@ -680,7 +682,7 @@ impl<I: Tokens> Parser<I> {
                 // `export { type as }`
                 // `export { type as as }`
                 // `export { type as as as }`
-                if self.syntax().typescript()
+                if self.syntax().uses_ts_parser()
                     && orig_ident.sym == js_word!("type")
                     && is!(self, IdentName)
                 {
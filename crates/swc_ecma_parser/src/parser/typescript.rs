@@ -10,7 +10,7 @@ use crate::{lexer::TokenContexts, parser::class_and_fn::IsSimpleParameterList, t
 impl<I: Tokens> Parser<I> {
     /// `tsNextTokenCanFollowModifier`
     fn ts_next_token_can_follow_modifier(&mut self) -> PResult<bool> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         // Note: TypeScript's implementation is much more complicated because
         // more things are considered modifiers there.
@@ -30,7 +30,7 @@ impl<I: Tokens> Parser<I> {
         allowed_modifiers: &[&'static str],
         stop_on_start_of_class_static_blocks: bool,
     ) -> PResult<Option<&'static str>> {
-        if !self.input.syntax().typescript() {
+        if !self.input.syntax().uses_ts_parser() {
             return Ok(None);
         }
 
@@ -61,7 +61,7 @@ impl<I: Tokens> Parser<I> {
     /// `tsIsListTerminator`
 
     fn is_ts_list_terminator(&mut self, kind: ParsingContext) -> PResult<bool> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         Ok(match kind {
             ParsingContext::EnumMembers | ParsingContext::TypeMembers => is!(self, '}'),
@@ -78,7 +78,7 @@ impl<I: Tokens> Parser<I> {
     where
         F: FnMut(&mut Self) -> PResult<T>,
     {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         let mut buf = vec![];
         while !self.is_ts_list_terminator(kind)? {
@@ -114,7 +114,7 @@ impl<I: Tokens> Parser<I> {
     where
         F: FnMut(&mut Self) -> PResult<(BytePos, T)>,
     {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         let mut buf = vec![];
 
@@ -161,7 +161,7 @@ impl<I: Tokens> Parser<I> {
     where
         F: FnMut(&mut Self) -> PResult<T>,
     {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         if !skip_first_token {
             if bracket {
@@ -184,7 +184,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseEntityName`
     fn parse_ts_entity_name(&mut self, allow_reserved_words: bool) -> PResult<TsEntityName> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         let init = self.parse_ident_name()?;
         if let Ident {
@@ -222,7 +222,7 @@ impl<I: Tokens> Parser<I> {
     /// `tsParseTypeReference`
     fn parse_ts_type_ref(&mut self) -> PResult<TsTypeRef> {
         trace_cur!(self, parse_ts_type_ref);
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         let start = cur_pos!(self);
 
@@ -254,7 +254,7 @@ impl<I: Tokens> Parser<I> {
         has_asserts_keyword: bool,
         lhs: TsThisType,
     ) -> PResult<TsTypePredicate> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         let param_name = TsThisTypeOrIdent::TsThisType(lhs);
         let type_ann = if eat!(self, "is") {
@@ -277,7 +277,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseThisTypeNode`
     fn parse_ts_this_type_node(&mut self) -> PResult<TsThisType> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         expect!(self, "this");
 
@@ -341,7 +341,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseTypeQuery`
     fn parse_ts_type_query(&mut self) -> PResult<TsTypeQuery> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         let start = cur_pos!(self);
         expect!(self, "typeof");
@@ -370,13 +370,18 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseTypeParameter`
     fn parse_ts_type_param(&mut self, allow_modifier: bool) -> PResult<TsTypeParam> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         let mut is_in = false;
         let mut is_out = false;
 
         let start = cur_pos!(self);
 
+        // Variance of flow type parameters
+        if self.input.syntax().flow() && is_one_of!(self, '+', '-') {
+            bump!(self);
+        }
+
         while let Some(modifer) = self.parse_ts_modifier(
             &[
                 "public",
@@ -420,7 +425,11 @@ impl<I: Tokens> Parser<I> {
         }
 
         let name = self.in_type().parse_ident_name()?;
-        let constraint = self.eat_then_parse_ts_type(&tok!("extends"))?;
+        let constraint = match self.eat_then_parse_ts_type(&tok!("extends"))? {
+            // `<T: Bound>` of flow
+            None if self.input.syntax().flow() => self.eat_then_parse_ts_type(&tok!(':'))?,
+            constraint => constraint,
+        };
         let default = self.eat_then_parse_ts_type(&tok!('='))?;
 
         Ok(TsTypeParam {
@@ -468,7 +477,7 @@ impl<I: Tokens> Parser<I> {
         &mut self,
         return_token: &'static Token,
     ) -> PResult<Box<TsTypeAnn>> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         self.in_type().parse_with(|p| {
             let return_token_start = cur_pos!(p);
@@ -490,11 +499,13 @@ impl<I: Tokens> Parser<I> {
                 && !p.input.has_linebreak_between_cur_and_peeked();
             let is_type_predicate = has_type_pred_asserts || has_type_pred_is;
             if !is_type_predicate {
-                return p.parse_ts_type_ann(
+                let type_ann = p.parse_ts_type_ann(
                     // eat_colon
                     false,
                     return_token_start,
-                );
+                )?;
+                p.eat_flow_predicate()?;
+                return Ok(type_ann);
             }
 
             let type_pred_var = p.parse_ident_name()?;
@@ -528,7 +539,7 @@ impl<I: Tokens> Parser<I> {
     where
         F: FnOnce(&mut Self) -> PResult<Option<bool>>,
     {
-        if !self.input.syntax().typescript() {
+        if !self.input.syntax().uses_ts_parser() {
             return Ok(false);
         }
         let prev_ignore_error = self.input.get_ctx().ignore_error;
@@ -555,7 +566,7 @@ impl<I: Tokens> Parser<I> {
     }
 
     pub(super) fn try_parse_ts_type_args(&mut self) -> Option<Box<TsTypeParamInstantiation>> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         self.try_parse_ts(|p| {
             let type_args = p.parse_ts_type_args()?;
@@ -583,7 +594,7 @@ impl<I: Tokens> Parser<I> {
     where
         F: FnOnce(&mut Self) -> PResult<Option<T>>,
     {
-        if !self.input.syntax().typescript() {
+        if !self.input.syntax().uses_ts_parser() {
             return None;
         }
         let _tracing = debug_tracing!(self, "try_parse_ts");
@@ -631,7 +642,7 @@ impl<I: Tokens> Parser<I> {
     ) -> PResult<Box<TsTypeAnn>> {
         trace_cur!(self, parse_ts_type_ann);
 
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         self.in_type().parse_with(|p| {
             if eat_colon {
@@ -650,7 +661,7 @@ impl<I: Tokens> Parser<I> {
     }
 
     /// `tsEatThenParseType`
-    fn eat_then_parse_ts_type(
+    pub(super) fn eat_then_parse_ts_type(
         &mut self,
         token_to_eat: &'static Token,
     ) -> PResult<Option<Box<TsType>>> {
@@ -668,12 +679,12 @@ impl<I: Tokens> Parser<I> {
     }
 
     /// `tsExpectThenParseType`
-    fn expect_then_parse_ts_type(
+    pub(super) fn expect_then_parse_ts_type(
         &mut self,
         token: &'static Token,
         token_str: &'static str,
     ) -> PResult<Box<TsType>> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         self.in_type().parse_with(|p| {
             if !p.input.eat(token) {
@@ -694,7 +705,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsNextThenParseType`
     pub(super) fn next_then_parse_ts_type(&mut self) -> PResult<Box<TsType>> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         self.in_type().parse_with(|p| {
             bump!(p);
@@ -705,7 +716,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseEnumMember`
     fn parse_ts_enum_member(&mut self) -> PResult<TsEnumMember> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         let start = cur_pos!(self);
         // Computed property names are grammar errors in an enum, so accept just string
@@ -776,7 +787,7 @@ impl<I: Tokens> Parser<I> {
         start: BytePos,
         is_const: bool,
     ) -> PResult<Box<TsEnumDecl>> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         let id = self.parse_ident_name()?;
         expect!(self, '{');
@@ -784,6 +795,8 @@ impl<I: Tokens> Parser<I> {
             .parse_ts_delimited_list(ParsingContext::EnumMembers, |p| p.parse_ts_enum_member())?;
         expect!(self, '}');
 
+        self.emit_err_if_flow(span!(self, start), "Enums");
+
         Ok(Box::new(TsEnumDecl {
             span: span!(self, start),
             declare: false,
@@ -797,7 +810,7 @@ impl<I: Tokens> Parser<I> {
     fn parse_ts_module_block(&mut self) -> PResult<TsModuleBlock> {
         trace_cur!(self, parse_ts_module_block);
 
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         let start = cur_pos!(self);
         expect!(self, '{');
@@ -817,7 +830,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseModuleOrNamespaceDeclaration`
     fn parse_ts_module_or_ns_decl(&mut self, start: BytePos) -> PResult<Box<TsModuleDecl>> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         let id = self.parse_ident_name()?;
         let body: TsNamespaceBody = if eat!(self, '.') {
@@ -852,7 +865,7 @@ impl<I: Tokens> Parser<I> {
         &mut self,
         start: BytePos,
     ) -> PResult<Box<TsModuleDecl>> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         let (global, id) = if is!(self, "global") {
             let id = self.parse_ident_name()?;
@@ -884,7 +897,7 @@ impl<I: Tokens> Parser<I> {
     }
 
     pub fn parse_type(&mut self) -> PResult<Box<TsType>> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         self.in_type().parse_ts_type()
     }
@@ -895,7 +908,7 @@ impl<I: Tokens> Parser<I> {
     pub(super) fn parse_ts_type(&mut self) -> PResult<Box<TsType>> {
         trace_cur!(self, parse_ts_type);
 
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         // Need to set `state.inType` so that we don't parse JSX in a type context.
         debug_assert!(self.ctx().in_type);
@@ -943,7 +956,13 @@ impl<I: Tokens> Parser<I> {
     fn parse_ts_non_conditional_type(&mut self) -> PResult<Box<TsType>> {
         trace_cur!(self, parse_ts_non_conditional_type);
 
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
+
+        if self.input.syntax().flow() && !self.ctx().no_anon_fn_type && is_one_of!(self, '(', '<') {
+            if let Some(ty) = self.try_parse_flow_fn_type() {
+                return Ok(ty);
+            }
+        }
 
         if self.is_ts_start_of_fn_type()? {
             return self
@@ -963,7 +982,7 @@ impl<I: Tokens> Parser<I> {
     }
 
     fn is_ts_start_of_fn_type(&mut self) -> PResult<bool> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         if is!(self, '<') {
             return Ok(true);
@@ -974,13 +993,14 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseTypeAssertion`
     pub(super) fn parse_ts_type_assertion(&mut self, start: BytePos) -> PResult<TsTypeAssertion> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         // Not actually necessary to set state.inType because we never reach here if JSX
         // plugin is enabled, but need `tsInType` to satisfy the assertion in
         // `tsParseType`.
         let type_ann = self.in_type().parse_with(|p| p.parse_ts_type())?;
         expect!(self, '>');
+        self.emit_err_if_flow(span!(self, start), "Type assertions");
         let expr = self.parse_unary_expr()?;
         Ok(TsTypeAssertion {
             span: span!(self, start),
@@ -991,7 +1011,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseHeritageClause`
     pub(super) fn parse_ts_heritage_clause(&mut self) -> PResult<Vec<TsExprWithTypeArgs>> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         self.parse_ts_delimited_list(ParsingContext::HeritageClauseElement, |p| {
             p.parse_ts_heritage_clause_element()
@@ -999,7 +1019,7 @@ impl<I: Tokens> Parser<I> {
     }
 
     fn parse_ts_heritage_clause_element(&mut self) -> PResult<TsExprWithTypeArgs> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         let start = cur_pos!(self);
         // Note: TS uses parseLeftHandSideExpressionOrHigher,
@@ -1041,7 +1061,7 @@ impl<I: Tokens> Parser<I> {
         &mut self,
         start: BytePos,
     ) -> PResult<Box<TsInterfaceDecl>> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         let id = self.parse_ident_name()?;
         match id.sym {
@@ -1101,7 +1121,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseTypeAliasDeclaration`
     fn parse_ts_type_alias_decl(&mut self, start: BytePos) -> PResult<Box<TsTypeAliasDecl>> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         let id = self.parse_ident_name()?;
         let type_params = self.try_parse_ts_type_params(true)?;
@@ -1123,7 +1143,7 @@ impl<I: Tokens> Parser<I> {
         is_export: bool,
         is_type_only: bool,
     ) -> PResult<Box<TsImportEqualsDecl>> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         let id = self.parse_ident_name()?;
         expect!(self, '=');
@@ -1142,14 +1162,14 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsIsExternalModuleReference`
     fn is_ts_external_module_ref(&mut self) -> PResult<bool> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         Ok(is!(self, "require") && peeked_is!(self, '('))
     }
 
     /// `tsParseModuleReference`
     fn parse_ts_module_ref(&mut self) -> PResult<TsModuleRef> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         if self.is_ts_external_module_ref()? {
             self.parse_ts_external_module_ref().map(From::from)
@@ -1161,7 +1181,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseExternalModuleReference`
     fn parse_ts_external_module_ref(&mut self) -> PResult<TsExternalModuleRef> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         let start = cur_pos!(self);
         expect!(self, "require");
@@ -1185,7 +1205,7 @@ impl<I: Tokens> Parser<I> {
     where
         F: FnOnce(&mut Self) -> PResult<T>,
     {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         let mut cloned = self.clone();
         let ctx = Context {
@@ -1198,7 +1218,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsIsUnambiguouslyStartOfFunctionType`
     fn is_ts_unambiguously_start_of_fn_type(&mut self) -> PResult<bool> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         assert_and_bump!(self, '(');
         if is_one_of!(self, ')', "...") {
@@ -1224,7 +1244,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsSkipParameterStart`
     fn skip_ts_parameter_start(&mut self) -> PResult<bool> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         let _ = self.eat_any_ts_modifier()?;
 
@@ -1241,8 +1261,13 @@ impl<I: Tokens> Parser<I> {
     }
 
     /// `tsParseTypeMemberSemicolon`
-    fn parse_ts_type_member_semicolon(&mut self) -> PResult<()> {
-        debug_assert!(self.input.syntax().typescript());
+    pub(super) fn parse_ts_type_member_semicolon(&mut self) -> PResult<()> {
+        debug_assert!(self.input.syntax().uses_ts_parser());
+
+        // The last member of an exact object type of flow, like `{| a: T |}`
+        if self.input.syntax().flow() && is!(self, '|') {
+            return Ok(());
+        }
 
         if !eat!(self, ',') {
             expect!(self, ';');
@@ -1256,7 +1281,7 @@ impl<I: Tokens> Parser<I> {
         &mut self,
         kind: SignatureParsingMode,
     ) -> PResult<Either<TsCallSignatureDecl, TsConstructSignatureDecl>> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         let start = cur_pos!(self);
 
@@ -1298,8 +1323,8 @@ impl<I: Tokens> Parser<I> {
     }
 
     /// `tsIsUnambiguouslyIndexSignature`
-    fn is_ts_unambiguously_index_signature(&mut self) -> PResult<bool> {
-        debug_assert!(self.input.syntax().typescript());
+    pub(super) fn is_ts_unambiguously_index_signature(&mut self) -> PResult<bool> {
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         // Note: babel's comment is wrong
         assert_and_bump!(self, '['); // Skip '['
@@ -1395,7 +1420,7 @@ impl<I: Tokens> Parser<I> {
         start: BytePos,
         readonly: bool,
     ) -> PResult<Either<TsPropertySignature, TsMethodSignature>> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         let (computed, key) = self.parse_ts_property_name()?;
 
@@ -1443,8 +1468,8 @@ impl<I: Tokens> Parser<I> {
     }
 
     /// `tsParseTypeMember`
-    fn parse_ts_type_member(&mut self) -> PResult<TsTypeElement> {
-        debug_assert!(self.input.syntax().typescript());
+    pub(super) fn parse_ts_type_member(&mut self) -> PResult<TsTypeElement> {
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         fn into_type_elem(
             e: Either<TsCallSignatureDecl, TsConstructSignatureDecl>,
@@ -1536,7 +1561,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsIsStartOfConstructSignature`
     fn is_ts_start_of_construct_signature(&mut self) -> PResult<bool> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         bump!(self);
 
@@ -1545,7 +1570,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseTypeLiteral`
     fn parse_ts_type_lit(&mut self) -> PResult<TsTypeLit> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         let start = cur_pos!(self);
         let members = self.parse_ts_object_type_members()?;
@@ -1557,7 +1582,22 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseObjectTypeMembers`
     fn parse_ts_object_type_members(&mut self) -> PResult<Vec<TsTypeElement>> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
+
+        if self.input.syntax().flow() {
+            let (members, spreads) = self.parse_flow_object_type_members()?;
+            if let Some(spread) = spreads.first() {
+                syntax_error!(
+                    self,
+                    spread.span(),
+                    SyntaxError::Unexpected {
+                        got: "spread".into(),
+                        expected: "a type member"
+                    }
+                )
+            }
+            return Ok(members);
+        }
 
         expect!(self, '{');
         let members =
@@ -1568,7 +1608,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsIsStartOfMappedType`
     fn is_ts_start_of_mapped_type(&mut self) -> PResult<bool> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         bump!(self);
         if eat!(self, '+') || eat!(self, '-') {
@@ -1591,7 +1631,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseMappedTypeParameter`
     fn parse_ts_mapped_type_param(&mut self) -> PResult<TsTypeParam> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         let start = cur_pos!(self);
         let name = self.parse_ident_name()?;
@@ -1609,7 +1649,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseMappedType`
     fn parse_ts_mapped_type(&mut self) -> PResult<TsMappedType> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         let start = cur_pos!(self);
         expect!(self, '{');
@@ -1664,7 +1704,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseTupleType`
     fn parse_ts_tuple_type(&mut self) -> PResult<TsTupleType> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         let start = cur_pos!(self);
         let elems = self.parse_ts_bracketed_list(
@@ -1739,7 +1779,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseTupleElementType`
     fn parse_ts_tuple_element_type(&mut self) -> PResult<TsTupleElement> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         // parses `...TsType[]`
         let start = cur_pos!(self);
@@ -1781,11 +1821,16 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseParenthesizedType`
     fn parse_ts_parenthesized_type(&mut self) -> PResult<TsParenthesizedType> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         let start = cur_pos!(self);
         expect!(self, '(');
-        let type_ann = self.parse_ts_type()?;
+        let type_ann = self
+            .with_ctx(Context {
+                no_anon_fn_type: false,
+                ..self.ctx()
+            })
+            .parse_ts_type()?;
         expect!(self, ')');
         Ok(TsParenthesizedType {
             span: span!(self, start),
@@ -1800,7 +1845,7 @@ impl<I: Tokens> Parser<I> {
     ) -> PResult<TsFnOrConstructorType> {
         trace_cur!(self, parse_ts_fn_or_constructor_type);
 
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         let start = cur_pos!(self);
         let is_abstract = if !is_fn_type {
@@ -1839,7 +1884,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseLiteralTypeNode`
     fn parse_ts_lit_type_node(&mut self) -> PResult<TsLitType> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         let start = cur_pos!(self);
 
@@ -1865,7 +1910,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseTemplateLiteralType`
     fn parse_ts_tpl_lit_type(&mut self) -> PResult<TsTplLitType> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         let start = cur_pos!(self);
 
@@ -1915,7 +1960,7 @@ impl<I: Tokens> Parser<I> {
             return Ok(Default::default());
         }
 
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         let params = self.parse_formal_params()?;
         let mut list = vec![];
@@ -1999,10 +2044,23 @@ impl<I: Tokens> Parser<I> {
             unreachable!()
         }
         trace_cur!(self, parse_ts_non_array_type);
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         let start = cur_pos!(self);
 
+        if self.input.syntax().flow() {
+            if is!(self, '{') {
+                return self.parse_flow_object_type();
+            }
+            if eat!(self, '*') {
+                // The existential type of flow
+                return Ok(Box::new(TsType::TsKeywordType(TsKeywordType {
+                    span: span!(self, start),
+                    kind: TsKeywordTypeKind::TsAnyKeyword,
+                })));
+            }
+        }
+
         match *cur!(self, true)? {
             Token::Word(Word::Ident(..))
             | tok!("void")
@@ -2182,7 +2240,7 @@ impl<I: Tokens> Parser<I> {
     /// `tsParseArrayTypeOrHigher`
     fn parse_ts_array_type_or_higher(&mut self, readonly: bool) -> PResult<Box<TsType>> {
         trace_cur!(self, parse_ts_array_type_or_higher);
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         let mut ty = self.parse_ts_non_array_type()?;
 
@@ -2209,7 +2267,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseTypeOperator`
     fn parse_ts_type_operator(&mut self, op: TsTypeOperatorOp) -> PResult<TsTypeOperator> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         let start = cur_pos!(self);
         match op {
@@ -2228,7 +2286,7 @@ impl<I: Tokens> Parser<I> {
 
     /// `tsParseInferType`
     fn parse_ts_infer_type(&mut self) -> PResult<TsInferType> {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         let start = cur_pos!(self);
         expect!(self, "infer");
@@ -2259,8 +2317,21 @@ impl<I: Tokens> Parser<I> {
     /// `tsParseTypeOperatorOrHigher`
     fn parse_ts_type_operator_or_higher(&mut self) -> PResult<Box<TsType>> {
         trace_cur!(self, parse_ts_type_operator_or_higher);
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
+
+        if self.input.syntax().flow() {
+            let ty = if is!(self, '?') {
+                self.parse_flow_maybe_type()?
+            } else {
+                self.parse_ts_type_operator_or_higher_inner()?
+            };
+            return self.parse_flow_anon_fn_type(ty);
+        }
+
+        self.parse_ts_type_operator_or_higher_inner()
+    }
 
+    pub(super) fn parse_ts_type_operator_or_higher_inner(&mut self) -> PResult<Box<TsType>> {
         let operator = if is!(self, "keyof") {
             Some(TsTypeOperatorOp::KeyOf)
         } else if is!(self, "unique") {
@@ -2355,7 +2426,7 @@ impl<I: Tokens> Parser<I> {
         start: BytePos,
         decorators: Vec<Decorator>,
     ) -> PResult<Option<Decl>> {
-        if !self.syntax().typescript() {
+        if !self.syntax().uses_ts_parser() {
             return Ok(None);
         }
 
@@ -2376,6 +2447,12 @@ impl<I: Tokens> Parser<I> {
         };
 
         self.with_ctx(ctx).parse_with(|p| {
+            if p.input.syntax().flow() {
+                if let Some(decl) = p.try_parse_flow_declare(start)? {
+                    return Ok(Some(decl));
+                }
+            }
+
             if is!(p, "function") {
                 return p
                     .parse_fn_decl(decorators)
@@ -2552,10 +2629,12 @@ impl<I: Tokens> Parser<I> {
                         .map(From::from)
                         .map(Some);
                 } else if next || is!(self, IdentRef) {
-                    return self
-                        .parse_ts_module_or_ns_decl(start)
-                        .map(From::from)
-                        .map(Some);
+                    let decl = self.parse_ts_module_or_ns_decl(start)?;
+                    // `declare module M {}` is a flow declaration.
+                    if !self.ctx().in_declare {
+                        self.emit_err_if_flow(decl.span, "Modules");
+                    }
+                    return Ok(Some(decl.into()));
                 }
             }
 
@@ -2564,10 +2643,9 @@ impl<I: Tokens> Parser<I> {
                     if next {
                         bump!(self);
                     }
-                    return self
-                        .parse_ts_module_or_ns_decl(start)
-                        .map(From::from)
-                        .map(Some);
+                    let decl = self.parse_ts_module_or_ns_decl(start)?;
+                    self.emit_err_if_flow(decl.span, "Namespaces");
+                    return Ok(Some(decl.into()));
                 }
             }
 
@@ -2583,6 +2661,18 @@ impl<I: Tokens> Parser<I> {
                 }
             }
 
+            _ if self.input.syntax().flow() && &*value == "opaque" => {
+                if next || is!(self, "type") {
+                    if next {
+                        bump!(self);
+                    }
+                    return self
+                        .parse_flow_opaque_type_alias(start)
+                        .map(From::from)
+                        .map(Some);
+                }
+            }
+
             _ => {}
         }
 
@@ -2609,7 +2699,12 @@ impl<I: Tokens> Parser<I> {
                     .map(|p| p.pat)
                     .collect();
                 expect!(p, ')');
-                let return_type = p.try_parse_ts_type_or_type_predicate_ann()?;
+                let return_type = p
+                    .with_ctx(Context {
+                        no_anon_fn_type: true,
+                        ..p.ctx()
+                    })
+                    .try_parse_ts_type_or_type_predicate_ann()?;
                 expect!(p, "=>");
 
                 Ok(Some((type_params, params, return_type)))
@@ -2631,7 +2726,8 @@ impl<I: Tokens> Parser<I> {
         self.with_ctx(ctx).parse_with(|p| {
             let is_generator = false;
             let is_async = true;
-            let body = p.parse_fn_body(true, false, true, params.is_simple_parameter_list())?;
+            let body =
+                p.parse_fn_body(true, false, true, params.is_simple_parameter_list(), None)?;
             Ok(Some(ArrowExpr {
                 span: span!(p, start),
                 body,
@@ -2647,7 +2743,7 @@ impl<I: Tokens> Parser<I> {
     /// `tsParseTypeArguments`
     pub fn parse_ts_type_args(&mut self) -> PResult<Box<TsTypeParamInstantiation>> {
         trace_cur!(self, parse_ts_type_args);
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         let start = cur_pos!(self);
         let params = self.in_type().parse_with(|p| {
@@ -2677,7 +2773,7 @@ impl<I: Tokens> Parser<I> {
     fn parse_ts_intersection_type_or_higher(&mut self) -> PResult<Box<TsType>> {
         trace_cur!(self, parse_ts_intersection_type_or_higher);
 
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         self.parse_ts_union_or_intersection_type(
             UnionOrIntersection::Intersection,
@@ -2689,7 +2785,7 @@ impl<I: Tokens> Parser<I> {
     /// `tsParseUnionTypeOrHigher`
     fn parse_ts_union_type_or_higher(&mut self) -> PResult<Box<TsType>> {
         trace_cur!(self, parse_ts_union_type_or_higher);
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         self.parse_ts_union_or_intersection_type(
             UnionOrIntersection::Union,
@@ -2710,7 +2806,7 @@ impl<I: Tokens> Parser<I> {
     {
         trace_cur!(self, parse_ts_union_or_intersection_type);
 
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         let start = cur_pos!(self); // include the leading operator in the start
         self.input.eat(operator);
@@ -2719,10 +2815,10 @@ impl<I: Tokens> Parser<I> {
         let ty = parse_constituent_type(self)?;
         trace_cur!(self, parse_ts_union_or_intersection_type__after_first);
 
-        if self.input.is(operator) {
+        if self.input.is(operator) && !self.is_flow_exact_object_end()? {
             let mut types = vec![ty];
 
-            while self.input.eat(operator) {
+            while !self.is_flow_exact_object_end()? && self.input.eat(operator) {
                 trace_cur!(self, parse_ts_union_or_intersection_type__constituent);
 
                 types.push(parse_constituent_type(self)?);
@@ -2752,7 +2848,7 @@ impl<I: Tokens> Parser<I> {
     where
         F: FnOnce(&mut Self) -> PResult<T>,
     {
-        debug_assert!(self.input.syntax().typescript());
+        debug_assert!(self.input.syntax().uses_ts_parser());
 
         trace_cur!(self, ts_in_no_context__before);
 
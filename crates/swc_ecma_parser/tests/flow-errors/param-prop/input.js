@@ -0,0 +1,3 @@
+class A {
+  constructor(readonly x: number) {}
+}
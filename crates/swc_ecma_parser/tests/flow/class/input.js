@@ -0,0 +1,13 @@
+class A<+T, -U> extends B<T> implements I, J {
+  +covariant: T;
+  -contravariant: U;
+  static +s: number = 1;
+  prop: ?string;
+  method<V>(x: V): this {
+    return this;
+  }
+}
+interface I {
+  -x: number;
+  m(): void;
+}
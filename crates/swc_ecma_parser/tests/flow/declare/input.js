@@ -0,0 +1,18 @@
+declare var x: number;
+declare function f(x: string): number;
+declare class C<T> extends D<T> {
+  static make(): C<T>;
+  prop: T;
+}
+declare module 'mod' {
+  declare module.exports: { a: number };
+}
+declare type T = string;
+declare opaque type O;
+declare opaque type P: string;
+declare interface I { +x: number }
+declare export function g(): void;
+declare export var y: string;
+declare export default T;
+declare export default class E {}
+declare module.exports: () => void;
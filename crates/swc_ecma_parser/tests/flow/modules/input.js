@@ -0,0 +1,11 @@
+import type { A, B as C } from './types';
+import typeof D from './d';
+import typeof * as E from './e';
+import { type F, typeof G, H } from './f';
+
+export type T = A | C;
+export opaque type Token = string;
+export opaque type Id: number = number;
+opaque type Secret = string;
+export type { A };
+export { H };
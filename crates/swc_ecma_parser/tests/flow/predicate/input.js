@@ -0,0 +1,4 @@
+function isString(x: mixed): boolean %checks {
+  return typeof x === 'string';
+}
+declare function isNumber(x: mixed): boolean %checks(typeof x === 'number');
@@ -0,0 +1,5 @@
+const a = (x: any);
+const b = ((x: any): string);
+foo((bar: Baz), (qux: ?Quux));
+const c = (x: number) => x;
+const d = (x: number): number => x;
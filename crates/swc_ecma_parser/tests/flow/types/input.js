@@ -0,0 +1,16 @@
+// @flow
+type Maybe = ?string;
+type Callback = (Error | null, data?: string, ...rest: Array<mixed>) => void;
+type Unary = string => number;
+type Curried = number => string => boolean;
+type Generic = <T: Object, +U>(T, U) => T;
+type Exact = {| +a: number, -b: ?string, c?: {||} |};
+type Inexact = { a: number, ... };
+type Spread = { ...Exact, d: Date };
+type Indexer = { [string]: number, [key: number]: string };
+type Method = { m(x: number): string, (): void };
+type Existential = Array<*>;
+
+function f<T>(x: ?T, cb: (T) => void = () => {}): Promise<T[]> {}
+const g = (x: number, y?: string): ?number => x;
+const h = async (x: number): Promise<void> => {};
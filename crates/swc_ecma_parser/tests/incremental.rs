@@ -0,0 +1,205 @@
+use swc_common::{sync::Lrc, FileName, SourceFile, SourceMap};
+use swc_ecma_ast::*;
+use swc_ecma_parser::{
+    incremental::{apply_edits, reparse_file_as_module, reparse_file_as_module_checked, TextEdit},
+    parse_file_as_module, Syntax, TsConfig,
+};
+
+const SRC: &str = r#"import { a } from "a";
+
+const b = a + 1
+let c = `${b}`
+
+/** Docs */
+export function d(e) {
+    if (e) {
+        return /re/g.test(e);
+    }
+    return b / 2;
+}
+
+class F {
+    g = 1;
+    h() {}
+}
+
+export default new F();
+"#;
+
+fn edit(start: usize, end: usize, text: &str) -> TextEdit {
+    TextEdit {
+        start,
+        end,
+        text: text.into(),
+    }
+}
+
+struct Session {
+    cm: Lrc<SourceMap>,
+    syntax: Syntax,
+    recovery: bool,
+}
+
+impl Session {
+    fn new(syntax: Syntax, recovery: bool) -> Self {
+        Session {
+            cm: Default::default(),
+            syntax,
+            recovery,
+        }
+    }
+
+    fn file(&self, src: String) -> Lrc<SourceFile> {
+        self.cm.new_source_file(FileName::Anon, src)
+    }
+
+    fn parse(&self, fm: &SourceFile) -> Module {
+        let mut errors = vec![];
+        parse_file_as_module(fm, self.syntax, EsVersion::latest(), None, &mut errors)
+            .expect("failed to parse the previous source")
+    }
+
+    /// Applies `edits` to `src`, and checks the result against a full parse.
+    fn check(&self, src: &str, edits: &[TextEdit]) -> Option<Module> {
+        let prev_fm = self.file(src.into());
+        let prev = self.parse(&prev_fm);
+        let fm = self.file(apply_edits(src, edits));
+
+        reparse_file_as_module_checked(
+            &prev_fm,
+            &prev,
+            edits,
+            &fm,
+            self.syntax,
+            EsVersion::latest(),
+            self.recovery,
+            &mut vec![],
+        )
+        .ok()
+    }
+}
+
+#[test]
+fn apply() {
+    assert_eq!(
+        apply_edits(
+            "abcdef",
+            &[edit(4, 5, "E"), edit(0, 1, ""), edit(2, 2, "__")]
+        ),
+        "b__cdEf"
+    );
+}
+
+#[test]
+fn insert_at_every_position() {
+    let s = Session::new(Default::default(), false);
+
+    for text in ["x", ";", "\n", "(", "}", "`", "+ 1\n", "/* c */"] {
+        for pos in (0..=SRC.len()).filter(|&pos| SRC.is_char_boundary(pos)) {
+            s.check(SRC, &[edit(pos, pos, text)]);
+        }
+    }
+}
+
+#[test]
+fn remove_at_every_position() {
+    let s = Session::new(Default::default(), false);
+
+    for len in [1, 2, 7] {
+        for pos in 0..=SRC.len() - len {
+            s.check(SRC, &[edit(pos, pos + len, "")]);
+        }
+    }
+}
+
+#[test]
+fn multiple_edits() {
+    let s = Session::new(Default::default(), false);
+
+    let b = SRC.find("b =").unwrap();
+    let h = SRC.find("h()").unwrap();
+
+    s.check(SRC, &[edit(b, b + 1, "z"), edit(h, h, "i() {}\n    ")])
+        .unwrap();
+    s.check(
+        SRC,
+        &[
+            edit(0, 0, "#!/usr/bin/env node\n"),
+            edit(SRC.len(), SRC.len(), "export const j = 1;\n"),
+        ],
+    )
+    .unwrap();
+    s.check(SRC, &[]).unwrap();
+}
+
+#[test]
+fn recovery() {
+    let s = Session::new(Default::default(), true);
+
+    for text in ["x", "(", "}", "{", "`", "const = ;"] {
+        for pos in (0..=SRC.len()).step_by(3) {
+            s.check(SRC, &[edit(pos, pos, text)]).unwrap();
+        }
+    }
+}
+
+#[test]
+fn typescript() {
+    let s = Session::new(
+        Syntax::Typescript(TsConfig {
+            decorators: true,
+            ..Default::default()
+        }),
+        false,
+    );
+    let src = "interface A {\n    b: string;\n}\n\nconst c = <T>(d: T) => d;\n\ntype E = A | \
+               null;\n\n@f\nclass G {}\n";
+
+    for text in ["x", "<", ";", "\n"] {
+        for pos in 0..=src.len() {
+            s.check(src, &[edit(pos, pos, text)]);
+        }
+    }
+}
+
+/// The items before and after the edits are not parsed again.
+#[test]
+fn reuses_items() {
+    let s = Session::new(Default::default(), false);
+    let src = "let a = 1;\nlet b = 2;\nlet c = 3;\nlet d = 4;\n";
+    let prev_fm = s.file(src.into());
+    let mut prev = s.parse(&prev_fm);
+
+    // Items which are reused keep the marker
+    let marker = |item: &mut ModuleItem| {
+        if let ModuleItem::Stmt(Stmt::Decl(Decl::Var(var))) = item {
+            var.declare = true;
+        }
+    };
+    prev.body.iter_mut().for_each(marker);
+
+    let edits = [edit(30, 31, "30")];
+    let fm = s.file(apply_edits(src, &edits));
+    let module = reparse_file_as_module(
+        &prev_fm,
+        &prev,
+        &edits,
+        &fm,
+        s.syntax,
+        EsVersion::latest(),
+        false,
+        &mut vec![],
+    )
+    .unwrap();
+
+    let reused = module
+        .body
+        .iter()
+        .map(|item| match item {
+            ModuleItem::Stmt(Stmt::Decl(Decl::Var(var))) => var.declare,
+            _ => unreachable!(),
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(reused, [true, false, false, true]);
+}
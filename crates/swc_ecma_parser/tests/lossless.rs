@@ -0,0 +1,182 @@
+use std::path::PathBuf;
+
+use swc_common::{sync::Lrc, FileName, SourceFile, SourceMap, Span, Spanned};
+use swc_ecma_ast::*;
+use swc_ecma_parser::{
+    lossless::{self, LosslessTokens, TriviaKind},
+    parse_file_as_module,
+    token::Token,
+    EsConfig, FlowConfig, Syntax, TsConfig,
+};
+
+/// Checks that `tokens` cover `fm` without gaps.
+fn check(fm: &SourceFile, tokens: &LosslessTokens) {
+    let mut last = fm.start_pos;
+    let mut expect = |span: Span| {
+        assert_eq!(span.lo, last, "gap or overlap at {:?}", span);
+        assert!(span.lo <= span.hi);
+        last = span.hi;
+    };
+
+    for token in &tokens.tokens {
+        token.leading.iter().for_each(|t| expect(t.span));
+        expect(token.token.span);
+        token.trailing.iter().for_each(|t| expect(t.span));
+
+        assert!(token
+            .trailing
+            .iter()
+            .all(|t| t.kind != TriviaKind::LineBreak));
+    }
+    tokens.end.iter().for_each(|t| expect(t.span));
+
+    assert_eq!(last, fm.end_pos);
+    assert_eq!(tokens.to_source(fm), *fm.src);
+}
+
+fn round_trip(file: PathBuf, syntax: Syntax) {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.load_file(&file).expect("failed to load file");
+
+    let (module, tokens) =
+        match lossless::parse_file_as_module(&fm, syntax, EsVersion::latest(), &mut vec![]) {
+            Ok(v) => v,
+            // Not a test for the parser
+            Err(..) => return,
+        };
+    check(&fm, &tokens);
+
+    // Capturing tokens does not change the AST
+    let expected = parse_file_as_module(&fm, syntax, EsVersion::latest(), None, &mut vec![]);
+    assert_eq!(Ok(module), expected.map_err(drop));
+}
+
+#[testing::fixture("tests/comments/**/*.js")]
+#[testing::fixture("tests/jsx/basic/**/*.js")]
+#[testing::fixture("tests/span/**/*.js")]
+fn js(file: PathBuf) {
+    round_trip(
+        file,
+        Syntax::Es(EsConfig {
+            jsx: true,
+            decorators: true,
+            ..Default::default()
+        }),
+    );
+}
+
+#[testing::fixture("tests/typescript/**/*.ts")]
+#[testing::fixture("tests/typescript/**/*.tsx")]
+#[testing::fixture("tests/tsc/**/*.ts")]
+fn typescript(file: PathBuf) {
+    let name = file.to_string_lossy().to_string();
+
+    round_trip(
+        file,
+        Syntax::Typescript(TsConfig {
+            dts: name.ends_with(".d.ts"),
+            tsx: name.ends_with(".tsx"),
+            decorators: true,
+            no_early_errors: true,
+        }),
+    );
+}
+
+#[testing::fixture("tests/flow/**/*.js")]
+fn flow(file: PathBuf) {
+    round_trip(file, Syntax::Flow(FlowConfig { jsx: true }));
+}
+
+fn parse(src: &str) -> (Lrc<SourceFile>, Module, LosslessTokens) {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(FileName::Anon, src.into());
+
+    let (module, tokens) =
+        lossless::parse_file_as_module(&fm, Default::default(), EsVersion::latest(), &mut vec![])
+            .unwrap();
+    check(&fm, &tokens);
+
+    (fm, module, tokens)
+}
+
+#[test]
+fn trivia() {
+    let (fm, _, tokens) =
+        parse("#!/usr/bin/env node\n/** a */\nlet a = 1; // b\r\n\n\tc /* d */ ;\n");
+
+    let texts = tokens
+        .tokens
+        .iter()
+        .map(|token| {
+            let trivia = |trivia: &[lossless::Trivia]| {
+                trivia
+                    .iter()
+                    .map(|t| (t.kind, t.text(&fm)))
+                    .collect::<Vec<_>>()
+            };
+
+            (
+                trivia(&token.leading),
+                token.text(&fm),
+                trivia(&token.trailing),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    use TriviaKind::*;
+    assert_eq!(
+        texts,
+        vec![
+            (vec![], "#!/usr/bin/env node", vec![]),
+            (
+                vec![
+                    (LineBreak, "\n"),
+                    (BlockComment, "/** a */"),
+                    (LineBreak, "\n")
+                ],
+                "let",
+                vec![(Whitespace, " ")]
+            ),
+            (vec![], "a", vec![(Whitespace, " ")]),
+            (vec![], "=", vec![(Whitespace, " ")]),
+            (vec![], "1", vec![]),
+            (vec![], ";", vec![(Whitespace, " "), (LineComment, "// b")]),
+            (
+                vec![(LineBreak, "\r\n"), (LineBreak, "\n"), (Whitespace, "\t")],
+                "c",
+                vec![
+                    (Whitespace, " "),
+                    (BlockComment, "/* d */"),
+                    (Whitespace, " ")
+                ]
+            ),
+            (vec![], ";", vec![]),
+        ]
+    );
+    assert_eq!(
+        tokens.end.iter().map(|t| t.kind).collect::<Vec<_>>(),
+        [LineBreak]
+    );
+}
+
+#[test]
+fn tokens_of() {
+    let (fm, module, tokens) = parse("let a = /re/g;\nfoo(a, `${a}`); // c\n");
+
+    let texts = tokens
+        .tokens_of(module.body[1].span())
+        .iter()
+        .map(|token| token.text(&fm))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        texts,
+        ["foo", "(", "a", ",", "`", "", "${", "a", "}", "", "`", ")", ";"]
+    );
+
+    let regex = tokens
+        .tokens
+        .iter()
+        .find(|token| matches!(token.token.token, Token::Regex(..)))
+        .unwrap();
+    assert_eq!(regex.text(&fm), "/re/g");
+}
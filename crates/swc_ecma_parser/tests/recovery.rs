@@ -0,0 +1,72 @@
+use std::{fs::read_to_string, path::PathBuf};
+
+use swc_common::comments::SingleThreadedComments;
+use swc_ecma_ast::*;
+use swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax};
+use testing::{run_test, StdErr};
+
+#[testing::fixture("tests/recovery/**/input.js")]
+#[testing::fixture("tests/recovery/**/input.ts")]
+fn recovery(entry: PathBuf) {
+    let input = read_to_string(&entry).unwrap();
+
+    eprintln!(
+        "\n\n========== Running recovery test \nSource:\n{}\n",
+        input
+    );
+
+    let syntax = if entry.extension().unwrap() == "ts" {
+        Syntax::Typescript(Default::default())
+    } else {
+        Syntax::Es(Default::default())
+    };
+
+    let err = run_test(false, |cm, handler| {
+        if false {
+            // Type annotation
+            return Ok(());
+        }
+
+        let fm = cm.load_file(&entry).unwrap();
+        let comments = SingleThreadedComments::default();
+        let lexer = Lexer::new(
+            syntax,
+            Default::default(),
+            StringInput::from(&*fm),
+            Some(&comments),
+        );
+        let mut p = Parser::new_from(lexer);
+        p.set_recovery(true);
+
+        let module: Module = p
+            .parse_module()
+            .unwrap_or_else(|err| panic!("failed to recover: {:?}", err));
+
+        let json =
+            serde_json::to_string_pretty(&module).expect("failed to serialize module as json");
+        if StdErr::from(json)
+            .compare_to_file(format!("{}.json", entry.display()))
+            .is_err()
+        {
+            panic!()
+        }
+
+        let errors = p.take_errors();
+        if errors.is_empty() {
+            panic!("should emit errors, but parsed without errors")
+        }
+        for e in errors {
+            e.into_diagnostic(handler).emit();
+        }
+
+        Err(())
+    })
+    .expect_err("should fail, but parsed as");
+
+    if err
+        .compare_to_file(format!("{}.swc-stderr", entry.display()))
+        .is_err()
+    {
+        panic!()
+    }
+}
@@ -0,0 +1,8 @@
+class A {
+    foo() {}
+    bar( {}
+    baz = 1;
+    #qux() {
+        return 1;
+    }
+}
@@ -0,0 +1,6 @@
+import { a } from "a";
+export function f(x) {
+    x.
+}
+export const y = a +;
+export default f;
@@ -0,0 +1,7 @@
+if (a) {
+    while (b {
+        c();
+    }
+    d();
+}
+e();
@@ -0,0 +1,3 @@
+let a = /abc
+foo();
+let b = 1;
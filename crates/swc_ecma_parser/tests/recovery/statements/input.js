@@ -0,0 +1,7 @@
+let a = 1;
+foo(;
+function f() {
+    let = ;
+    return a;
+}
+const b = 2;
@@ -0,0 +1,7 @@
+interface I {
+    a: string;
+}
+const x: = 1;
+function g<T>(a: T): T {
+    return a;
+}
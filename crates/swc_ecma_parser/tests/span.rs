@@ -1,8 +1,12 @@
 use std::path::PathBuf;
 
-use swc_common::{comments::SingleThreadedComments, errors::Handler, Spanned};
+use swc_common::{comments::SingleThreadedComments, errors::Handler, FileName, Span, Spanned};
 use swc_ecma_ast::*;
-use swc_ecma_parser::{lexer::Lexer, EsConfig, Parser, StringInput, Syntax, TsConfig};
+use swc_ecma_parser::{
+    lexer::Lexer,
+    token::{BinOpToken, Token},
+    Capturing, EsConfig, Parser, StringInput, Syntax, TsConfig,
+};
 use swc_ecma_visit::{Visit, VisitWith};
 
 #[testing::fixture("tests/span/**/*.js")]
@@ -58,6 +62,77 @@ fn span(entry: PathBuf) {
     content.compare_to_file(&ref_file).unwrap();
 }
 
+/// The parser reads `/` before it knows it starts a regex, and the regex
+/// token replaces it.
+#[test]
+fn regex_token_span() {
+    ::testing::run_test(false, |cm, _| {
+        let fm = cm.new_source_file(
+            FileName::Anon,
+            "use(/a/g); x = /=b/; y = a / /c/ / d; `${/d/}`; () => /e/i;".into(),
+        );
+
+        let lexer = Lexer::new(
+            Syntax::Es(Default::default()),
+            Default::default(),
+            StringInput::from(&*fm),
+            None,
+        );
+        let mut parser = Parser::new_from(Capturing::new(lexer));
+        let module = parser.parse_module().unwrap();
+        let tokens = parser.input().take();
+
+        let source = |span: Span| {
+            &fm.src[(span.lo - fm.start_pos).0 as usize..(span.hi - fm.start_pos).0 as usize]
+        };
+
+        let regex_tokens = tokens
+            .iter()
+            .filter(|token| matches!(token.token, Token::Regex(..)))
+            .map(|token| source(token.span))
+            .collect::<Vec<_>>();
+
+        assert_eq!(regex_tokens, ["/a/g", "/=b/", "/c/", "/d/", "/e/i"]);
+
+        // The division operators are kept
+        assert_eq!(
+            tokens
+                .iter()
+                .filter(|token| token.token == Token::BinOp(BinOpToken::Div))
+                .count(),
+            2
+        );
+
+        for pair in tokens.windows(2) {
+            assert!(pair[0].span.hi <= pair[1].span.lo, "{:?}", pair);
+        }
+
+        // Same as the spans of the literals
+        struct RegexSpans(Vec<Span>);
+
+        impl Visit for RegexSpans {
+            fn visit_regex(&mut self, n: &Regex) {
+                self.0.push(n.span);
+            }
+        }
+
+        let mut spans = RegexSpans(vec![]);
+        module.visit_with(&mut spans);
+
+        assert_eq!(
+            spans.0,
+            tokens
+                .iter()
+                .filter(|token| matches!(token.token, Token::Regex(..)))
+                .map(|token| token.span)
+                .collect::<Vec<_>>()
+        );
+
+        Ok(())
+    })
+    .unwrap();
+}
+
 struct Shower<'a> {
     handler: &'a Handler,
 }
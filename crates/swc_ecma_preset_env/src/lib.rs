@@ -4,9 +4,13 @@
 
 use std::path::PathBuf;
 
-use preset_env_base::query::{targets_to_versions, Query};
-pub use preset_env_base::{query::Targets, version::Version, BrowserData, Versions};
-use serde::Deserialize;
+use preset_env_base::query::Query;
+pub use preset_env_base::{
+    query::{targets_to_versions, Targets},
+    version::Version,
+    BrowserData, Versions,
+};
+use serde::{Deserialize, Serialize};
 use swc_atoms::{js_word, JsWord};
 use swc_common::{chain, collections::AHashSet, comments::Comments, FromVariant, Mark, DUMMY_SP};
 use swc_ecma_ast::*;
@@ -459,7 +463,7 @@ impl Fold for Polyfills {
     }
 }
 
-#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub enum Mode {
     #[serde(rename = "usage")]
     Usage,
@@ -467,7 +471,7 @@ pub enum Mode {
     Entry,
 }
 
-#[derive(Debug, Clone, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Config {
     #[serde(default)]
@@ -527,7 +531,7 @@ fn default_path() -> PathBuf {
     }
 }
 
-#[derive(Debug, Clone, Deserialize, FromVariant)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromVariant)]
 #[serde(untagged)]
 pub enum FeatureOrModule {
     Feature(Feature),
@@ -4448,6 +4448,7 @@ test_exec!(
         Config {
             legacy: true,
             emit_metadata: true,
+            strict_null_checks: false,
             use_define_for_class_fields: false,
         }
     ),
@@ -4481,6 +4482,7 @@ fn fixture_exec(input: PathBuf) {
                 decorators(Config {
                     legacy: true,
                     emit_metadata: true,
+                    strict_null_checks: false,
                     use_define_for_class_fields: false,
                 }),
                 strip(top_level_mark),
@@ -4505,6 +4507,7 @@ fn legacy_only(input: PathBuf) {
                 decorators(Config {
                     legacy: true,
                     emit_metadata: false,
+                    strict_null_checks: false,
                     use_define_for_class_fields: false,
                 })
             )
@@ -4530,6 +4533,33 @@ fn legacy_metadata(input: PathBuf) {
                 decorators(Config {
                     legacy: true,
                     emit_metadata: true,
+                    strict_null_checks: false,
+                    use_define_for_class_fields: false,
+                })
+            )
+        },
+        &input,
+        &output,
+        Default::default(),
+    );
+}
+
+#[testing::fixture("tests/fixture/legacy-metadata-strict-null-checks/**/input.ts")]
+fn legacy_metadata_strict_null_checks(input: PathBuf) {
+    let output = input.with_file_name("output.ts");
+
+    test_fixture(
+        ts(),
+        &|_| {
+            let unresolved_mark = Mark::new();
+            let top_level_mark = Mark::new();
+
+            chain!(
+                resolver(unresolved_mark, top_level_mark, true),
+                decorators(Config {
+                    legacy: true,
+                    emit_metadata: true,
+                    strict_null_checks: true,
                     use_define_for_class_fields: false,
                 })
             )
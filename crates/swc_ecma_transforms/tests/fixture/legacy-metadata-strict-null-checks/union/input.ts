@@ -0,0 +1,25 @@
+class Foo {}
+
+@Injectable()
+class Sample {
+    constructor(
+        p0: string | null,
+        p1: string | undefined,
+        p2: null | undefined,
+        p3: string | never,
+        p4: string & never,
+        p5: string | unknown,
+        p6: string & unknown,
+        p7: string | any,
+        p8: Foo | Foo,
+        p9: Foo | Bar,
+        p10: number | 1 | 2,
+        p11: never | never,
+        p12: `prefix-${string}`,
+        p13: readonly string[],
+        p14: keyof Foo,
+        p15: 10n,
+        p16: string | (number | boolean),
+        p17: (string)
+    ) {}
+}
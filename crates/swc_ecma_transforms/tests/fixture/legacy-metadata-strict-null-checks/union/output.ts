@@ -0,0 +1,29 @@
+class Foo {
+}
+let Sample = class Sample {
+    constructor(p0: string | null, p1: string | undefined, p2: null | undefined, p3: string | never, p4: string & never, p5: string | unknown, p6: string & unknown, p7: string | any, p8: Foo | Foo, p9: Foo | Bar, p10: number | 1 | 2, p11: never | never, p12: `prefix-${string}`, p13: readonly string[], p14: keyof Foo, p15: 10n, p16: string | (number | boolean), p17: (string)){}
+};
+Sample = __decorate([
+    Injectable(),
+    __metadata("design:type", Function),
+    __metadata("design:paramtypes", [
+        Object,
+        Object,
+        void 0,
+        String,
+        void 0,
+        Object,
+        String,
+        Object,
+        typeof Foo === "undefined" ? Object : Foo,
+        Object,
+        Number,
+        void 0,
+        String,
+        Array,
+        Object,
+        typeof BigInt === "undefined" ? Object : BigInt,
+        Object,
+        String
+    ])
+], Sample);
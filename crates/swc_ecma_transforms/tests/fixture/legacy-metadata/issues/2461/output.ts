@@ -4,5 +4,5 @@ class Thing {
 }
 __decorate([
     ThingDecorator,
-    __metadata("design:type", Object)
+    __metadata("design:type", String)
 ], Thing.prototype, "thing", void 0);
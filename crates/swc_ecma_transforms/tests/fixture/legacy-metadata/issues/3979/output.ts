@@ -7,5 +7,6 @@ class A {
     }
 }
 __decorate([
-    foo
+    foo,
+    __metadata("design:type", Object)
 ], A.prototype, "x", void 0);
@@ -0,0 +1,14 @@
+class Sample {
+    @Decorate()
+    get value(): string {
+        return "";
+    }
+
+    @Decorate()
+    set other(v: number) {}
+
+    @Decorate()
+    get untyped() {
+        return 1;
+    }
+}
@@ -0,0 +1,24 @@
+class Sample {
+    get value(): string {
+        return "";
+    }
+    set other(v: number) {}
+    get untyped() {
+        return 1;
+    }
+}
+__decorate([
+    Decorate(),
+    __metadata("design:type", String)
+], Sample.prototype, "value", null);
+__decorate([
+    Decorate(),
+    __metadata("design:type", Number),
+    __metadata("design:paramtypes", [
+        Number
+    ])
+], Sample.prototype, "other", null);
+__decorate([
+    Decorate(),
+    __metadata("design:type", Object)
+], Sample.prototype, "untyped", null);
@@ -9,7 +9,8 @@ __decorate([
     __metadata("design:paramtypes", [
         typeof Inter === "undefined" ? Object : Inter,
         typeof InterGen === "undefined" ? Object : InterGen
-    ])
+    ]),
+    __metadata("design:returntype", void 0)
 ], MyClass.prototype, "method", null);
 MyClass = __decorate([
     Decorate,
@@ -27,7 +27,8 @@ __decorate([
 __decorate([
     Get(),
     __metadata("design:type", Function),
-    __metadata("design:paramtypes", [])
+    __metadata("design:paramtypes", []),
+    __metadata("design:returntype", String)
 ], AppController.prototype, "getHello", null);
 __decorate([
     Get("/callback"),
@@ -37,7 +38,8 @@ __decorate([
     __metadata("design:paramtypes", [
         typeof express === "undefined" || typeof express.Response === "undefined" ? Object : express.Response,
         typeof express === "undefined" || typeof express.Express === "undefined" || typeof express.Express.Session === "undefined" ? Object : express.Express.Session
-    ])
+    ]),
+    __metadata("design:returntype", Promise)
 ], AppController.prototype, "callback", null);
 AppController = __decorate([
     Controller(),
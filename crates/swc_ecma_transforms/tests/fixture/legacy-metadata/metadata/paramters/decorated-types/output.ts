@@ -20,8 +20,9 @@ __decorate([
     __metadata("design:type", Function),
     __metadata("design:paramtypes", [
         String,
-        void 0
-    ])
+        Object
+    ]),
+    __metadata("design:returntype", void 0)
 ], MyOtherClass.prototype, "methodUndecorated", null);
 __decorate([
     decorate("named"),
@@ -31,7 +32,8 @@ __decorate([
     __metadata("design:paramtypes", [
         typeof Injected === "undefined" ? Object : Injected,
         typeof Schema === "undefined" ? Object : Schema
-    ])
+    ]),
+    __metadata("design:returntype", void 0)
 ], MyOtherClass.prototype, "method", null);
 MyOtherClass = __decorate([
     __param(0, inject()),
@@ -52,7 +54,8 @@ __decorate([
     __metadata("design:type", Function),
     __metadata("design:paramtypes", [
         String
-    ])
+    ]),
+    __metadata("design:returntype", void 0)
 ], DecoratedClass.prototype, "method", null);
 DecoratedClass = __decorate([
     Decorate,
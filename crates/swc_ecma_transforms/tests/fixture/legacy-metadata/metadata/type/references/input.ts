@@ -0,0 +1,56 @@
+import type { TypeOnly } from "./types";
+import { type InlineTypeOnly, Service } from "./service";
+import type * as ns from "./ns";
+import * as values from "./values";
+
+interface Options {
+    name: string;
+}
+
+interface Merged {}
+class Merged {}
+
+type Id = string;
+type Handler = (e: Event) => void;
+type MaybeService = Service | null;
+type Circular = Circular;
+
+enum Num {
+    A,
+    B,
+}
+
+enum Str {
+    A = "a",
+}
+
+enum Mixed {
+    A = 1,
+    B = "b",
+}
+
+@Injectable()
+class Sample<T> {
+    constructor(
+        p0: TypeOnly,
+        p1: InlineTypeOnly,
+        p2: Service,
+        p3: ns.Type,
+        p4: values.Value,
+        p5: Options,
+        p6: Merged,
+        p7: Id,
+        p8: Handler,
+        p9: MaybeService,
+        p10: Circular,
+        p11: Num,
+        p12: Str,
+        p13: Mixed,
+        p14: Num.A,
+        p15: T,
+        p16: Unknown
+    ) {}
+
+    @Decorate()
+    method<U>(p0: U, p1: T, p2: Array<T>, p3: import("./mod").Type) {}
+}
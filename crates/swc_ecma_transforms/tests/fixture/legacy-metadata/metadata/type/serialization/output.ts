@@ -18,10 +18,10 @@ __decorate([
     __metadata("design:paramtypes", [
         typeof Symbol === "undefined" ? Object : Symbol,
         Object,
-        Object,
+        String,
         void 0,
         String,
-        Object,
+        String,
         typeof Maybe === "undefined" ? Object : Maybe,
         Object,
         Object,
@@ -29,12 +29,13 @@ __decorate([
         Array,
         void 0,
         Boolean,
-        Object,
-        Object,
+        void 0,
+        String,
         typeof Object === "undefined" ? Object : Object,
         Object,
         typeof BigInt === "undefined" ? Object : BigInt
-    ])
+    ]),
+    __metadata("design:returntype", void 0)
 ], Sample.prototype, "method", null);
 __decorate([
     Decorate(),
@@ -42,14 +43,16 @@ __decorate([
     __metadata("design:paramtypes", [
         typeof Decorate === "undefined" || typeof Decorate.Name === "undefined" ? Object : Decorate.Name,
         typeof Decorate === "undefined" || typeof Decorate.Name === "undefined" ? Object : Decorate.Name
-    ])
+    ]),
+    __metadata("design:returntype", void 0)
 ], Sample.prototype, "method2", null);
 __decorate([
     Decorate(),
     __metadata("design:type", Function),
     __metadata("design:paramtypes", [
         String
-    ])
+    ]),
+    __metadata("design:returntype", void 0)
 ], Sample.prototype, "assignments", null);
 Sample = __decorate([
     Decorate(),
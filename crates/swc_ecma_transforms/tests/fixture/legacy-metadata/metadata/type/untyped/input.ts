@@ -0,0 +1,10 @@
+class Sample {
+    @Inject()
+    untyped;
+
+    @Inject()
+    initialized = 1;
+
+    @Inject()
+    typed: string;
+}
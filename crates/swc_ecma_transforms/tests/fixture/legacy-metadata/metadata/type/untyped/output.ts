@@ -0,0 +1,20 @@
+class Sample {
+    constructor(){
+        this.initialized = 1;
+    }
+    untyped;
+    initialized;
+    typed: string;
+}
+__decorate([
+    Inject(),
+    __metadata("design:type", Object)
+], Sample.prototype, "untyped", void 0);
+__decorate([
+    Inject(),
+    __metadata("design:type", Object)
+], Sample.prototype, "initialized", void 0);
+__decorate([
+    Inject(),
+    __metadata("design:type", String)
+], Sample.prototype, "typed", void 0);
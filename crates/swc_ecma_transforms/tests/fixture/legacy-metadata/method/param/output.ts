@@ -9,7 +9,8 @@ __decorate([
     __metadata("design:paramtypes", [
         String,
         String
-    ])
+    ]),
+    __metadata("design:returntype", void 0)
 ], Foo.prototype, "foo", null);
 __decorate([
     __param(0, dec1()),
@@ -18,5 +19,6 @@ __decorate([
     __metadata("design:paramtypes", [
         String,
         String
-    ])
+    ]),
+    __metadata("design:returntype", void 0)
 ], Foo, "bar", null);
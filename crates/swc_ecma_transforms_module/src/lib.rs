@@ -12,7 +12,9 @@ pub mod common_js;
 pub mod import_analysis;
 pub(crate) mod module_decl_strip;
 pub(crate) mod module_ref_rewriter;
+pub mod node_next;
 pub mod path;
+pub mod rewrite_rules;
 pub mod rewriter;
 pub mod system_js;
 pub mod umd;
@@ -0,0 +1,193 @@
+//! Support for `"module": "node16"` / `"nodenext"` of TypeScript.
+//!
+//! Node.js decides if a file is an ES module or a CommonJS module by its
+//! extension and the `type` field of the nearest `package.json`, so the
+//! output format is picked per file.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Error;
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use swc_atoms::JsWord;
+use swc_common::FileName;
+use tracing::debug;
+
+use crate::path::ImportResolver;
+
+/// The module format a file is emitted as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModuleFormat {
+    Esm,
+    CommonJs,
+}
+
+impl ModuleFormat {
+    /// Determines the module format of `file` like node.js and `tsc` do.
+    ///
+    /// - `.mts`, `.mjs` are ES modules.
+    /// - `.cts`, `.cjs` are CommonJS modules.
+    /// - Other files are ES modules if the `type` field of the nearest
+    ///   `package.json` is `module` and CommonJS modules otherwise.
+    ///
+    /// Files without a real path are treated as ES modules.
+    ///
+    /// The lookup of `package.json` is cached per directory.
+    pub fn detect(file: &FileName) -> Self {
+        let path = match file {
+            FileName::Real(path) => path,
+            _ => return ModuleFormat::Esm,
+        };
+
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("mts" | "mjs") => return ModuleFormat::Esm,
+            Some("cts" | "cjs") => return ModuleFormat::CommonJs,
+            _ => {}
+        }
+
+        match package_type(path) {
+            Some(ty) if &*ty == "module" => ModuleFormat::Esm,
+            _ => ModuleFormat::CommonJs,
+        }
+    }
+
+    pub fn is_esm(self) -> bool {
+        self == ModuleFormat::Esm
+    }
+
+    pub fn is_common_js(self) -> bool {
+        self == ModuleFormat::CommonJs
+    }
+}
+
+#[derive(Deserialize)]
+struct PackageJson {
+    #[serde(default, rename = "type")]
+    ty: Option<JsWord>,
+}
+
+/// The `type` field of the `package.json` nearest to a directory.
+///
+/// Like node.js, changes made to `package.json` files after the first lookup
+/// are not picked up.
+static PACKAGE_TYPE_CACHE: Lazy<DashMap<PathBuf, Option<JsWord>, ahash::RandomState>> =
+    Lazy::new(Default::default);
+
+/// Returns the `type` field of the `package.json` nearest to `path`.
+fn package_type(path: &Path) -> Option<JsWord> {
+    let path = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir().ok()?.join(path)
+    };
+
+    let mut dir = path.parent();
+    // Directories without a `package.json`, which share the result of their
+    // ancestor.
+    let mut visited = vec![];
+
+    let ty = loop {
+        let d = match dir {
+            Some(v) => v,
+            None => break None,
+        };
+
+        if let Some(ty) = PACKAGE_TYPE_CACHE.get(d) {
+            break ty.clone();
+        }
+
+        let pkg_path = d.join("package.json");
+
+        if let Ok(content) = fs::read_to_string(&pkg_path) {
+            debug!(
+                "Using {} to determine the module format",
+                pkg_path.display()
+            );
+
+            // `package.json` files which are not valid are ignored by node.js
+            let ty = serde_json::from_str::<PackageJson>(&content)
+                .ok()
+                .and_then(|pkg| pkg.ty);
+            PACKAGE_TYPE_CACHE.insert(d.to_path_buf(), ty.clone());
+            break ty;
+        }
+
+        visited.push(d.to_path_buf());
+        dir = d.parent();
+    };
+
+    for d in visited {
+        PACKAGE_TYPE_CACHE.insert(d, ty.clone());
+    }
+
+    ty
+}
+
+/// [ImportResolver] which rewrites TypeScript extensions of relative
+/// specifiers to the extensions of the output files, before resolving them
+/// with the inner resolver.
+///
+/// e.g. `./a.ts` => `./a.js`, `./b.mts` => `./b.mjs`, `./c.cts` => `./c.cjs`
+#[derive(Debug, Clone, Default)]
+pub struct NodeNextImportResolver<R>
+where
+    R: ImportResolver,
+{
+    inner: R,
+}
+
+impl<R> NodeNextImportResolver<R>
+where
+    R: ImportResolver,
+{
+    pub fn new(inner: R) -> Self {
+        Self { inner }
+    }
+}
+
+impl<R> ImportResolver for NodeNextImportResolver<R>
+where
+    R: ImportResolver,
+{
+    fn resolve_import(&self, base: &FileName, module_specifier: &str) -> Result<JsWord, Error> {
+        match rewrite_extension(module_specifier) {
+            Some(s) => self.inner.resolve_import(base, &s),
+            None => self.inner.resolve_import(base, module_specifier),
+        }
+    }
+}
+
+/// Returns `None` if `specifier` does not need to be rewritten.
+fn rewrite_extension(specifier: &str) -> Option<String> {
+    if !(specifier.starts_with("./") || specifier.starts_with("../")) {
+        return None;
+    }
+
+    let path = PathBuf::from(specifier);
+    let file_name = path.file_name()?.to_str()?;
+
+    // Declaration files do not have outputs.
+    if file_name.ends_with(".d.ts")
+        || file_name.ends_with(".d.mts")
+        || file_name.ends_with(".d.cts")
+    {
+        return None;
+    }
+
+    let ext = match path.extension()?.to_str()? {
+        "ts" | "tsx" => "js",
+        "mts" => "mjs",
+        "cts" => "cjs",
+        _ => return None,
+    };
+
+    Some(format!(
+        "{}.{}",
+        &specifier[..specifier.len() - path.extension()?.len() - 1],
+        ext
+    ))
+}
@@ -256,7 +256,7 @@ macro_rules! impl_ref {
     ($P:ident, $T:ty) => {
         impl<$P> ImportResolver for $T
         where
-            $P: ImportResolver,
+            $P: ImportResolver + ?Sized,
         {
             fn resolve_import(&self, base: &FileName, target: &str) -> Result<JsWord, Error> {
                 (**self).resolve_import(base, target)
@@ -0,0 +1,64 @@
+//! User-defined rules to rewrite module specifiers.
+
+use std::borrow::Cow;
+
+use anyhow::Error;
+use serde::{Deserialize, Serialize};
+use swc_atoms::JsWord;
+use swc_cached::regex::CachedRegex;
+use swc_common::FileName;
+
+use crate::path::ImportResolver;
+
+/// A rule to rewrite module specifiers.
+///
+/// `replacement` can refer to capture groups of `pattern` with `$1` or
+/// `$name`.
+///
+/// e.g. `{ "pattern": "^lodash$", "replacement": "lodash-es" }`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct RewriteRule {
+    pub pattern: CachedRegex,
+    pub replacement: String,
+}
+
+impl RewriteRule {
+    /// Returns `None` if `specifier` does not match the pattern.
+    pub fn apply(&self, specifier: &str) -> Option<String> {
+        match self.pattern.replace(specifier, &*self.replacement) {
+            Cow::Owned(s) => Some(s),
+            Cow::Borrowed(..) => None,
+        }
+    }
+}
+
+/// [ImportResolver] which rewrites specifiers with [RewriteRule]s.
+///
+/// Rules are applied in order, and each rule is applied to the result of the
+/// previous ones. So a rule which strips `?raw` can be followed by a rule
+/// which adds `.js` to relative specifiers.
+#[derive(Debug, Clone, Default)]
+pub struct RuleImportResolver {
+    rules: Vec<RewriteRule>,
+}
+
+impl RuleImportResolver {
+    pub fn new(rules: Vec<RewriteRule>) -> Self {
+        Self { rules }
+    }
+}
+
+impl ImportResolver for RuleImportResolver {
+    fn resolve_import(&self, _: &FileName, module_specifier: &str) -> Result<JsWord, Error> {
+        let mut specifier = Cow::Borrowed(module_specifier);
+
+        for rule in &self.rules {
+            if let Some(s) = rule.apply(&specifier) {
+                specifier = Cow::Owned(s);
+            }
+        }
+
+        Ok(specifier.into())
+    }
+}
@@ -1,5 +1,6 @@
 use anyhow::Context;
-use swc_common::FileName;
+use swc_atoms::js_word;
+use swc_common::{FileName, Mark};
 use swc_ecma_ast::*;
 use swc_ecma_visit::{as_folder, noop_visit_mut_type, Fold, VisitMut, VisitMutWith};
 
@@ -10,7 +11,30 @@ pub fn import_rewriter<R>(base: FileName, resolver: R) -> impl Fold + VisitMut
 where
     R: ImportResolver,
 {
-    as_folder(Rewriter { base, resolver })
+    as_folder(Rewriter {
+        base,
+        resolver,
+        unresolved_mark: None,
+    })
+}
+
+/// Same as [import_rewriter], but `require("mod")` calls are rewritten too.
+///
+/// `unresolved_mark` is used to ignore `require` bindings declared by the
+/// user.
+pub fn import_rewriter_with_require<R>(
+    base: FileName,
+    resolver: R,
+    unresolved_mark: Mark,
+) -> impl Fold + VisitMut
+where
+    R: ImportResolver,
+{
+    as_folder(Rewriter {
+        base,
+        resolver,
+        unresolved_mark: Some(unresolved_mark),
+    })
 }
 
 struct Rewriter<R>
@@ -19,6 +43,24 @@ where
 {
     base: FileName,
     resolver: R,
+    unresolved_mark: Option<Mark>,
+}
+
+impl<R> Rewriter<R>
+where
+    R: ImportResolver,
+{
+    fn is_require(&self, callee: &Callee) -> bool {
+        match (callee, self.unresolved_mark) {
+            (Callee::Expr(callee), Some(unresolved_mark)) => match &**callee {
+                Expr::Ident(i) => {
+                    i.sym == js_word!("require") && i.span.ctxt.outer() == unresolved_mark
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
 }
 
 impl<R> VisitMut for Rewriter<R>
@@ -30,7 +72,7 @@ where
     fn visit_mut_call_expr(&mut self, e: &mut CallExpr) {
         e.visit_mut_children_with(self);
 
-        if let Callee::Import(_) = &e.callee {
+        if matches!(e.callee, Callee::Import(_)) || self.is_require(&e.callee) {
             if let Some(ExprOrSpread { spread: None, expr }) = &mut e.args.get_mut(0) {
                 if let Expr::Lit(Lit::Str(s)) = &mut **expr {
                     let src = self
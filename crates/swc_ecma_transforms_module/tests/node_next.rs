@@ -0,0 +1,125 @@
+use std::{env::current_dir, path::PathBuf};
+
+use swc_common::{chain, FileName, Mark};
+use swc_ecma_parser::{Syntax, TsConfig};
+use swc_ecma_transforms_base::{feature::FeatureFlag, resolver};
+use swc_ecma_transforms_module::{
+    common_js::common_js_with_resolver,
+    node_next::{ModuleFormat, NodeNextImportResolver},
+    path::NoopImportResolver,
+    rewriter::import_rewriter,
+};
+use swc_ecma_transforms_testing::test;
+
+fn fixture_dir() -> PathBuf {
+    current_dir()
+        .unwrap()
+        .join("tests")
+        .join("fixture-manual")
+        .join("node-next")
+}
+
+fn syntax() -> Syntax {
+    Syntax::Typescript(TsConfig::default())
+}
+
+#[test]
+fn format_from_extension() {
+    let dir = fixture_dir();
+
+    assert_eq!(
+        ModuleFormat::detect(&FileName::Real(dir.join("cjs").join("index.mts"))),
+        ModuleFormat::Esm
+    );
+    assert_eq!(
+        ModuleFormat::detect(&FileName::Real(dir.join("esm").join("index.cts"))),
+        ModuleFormat::CommonJs
+    );
+}
+
+#[test]
+fn format_from_package_json() {
+    let dir = fixture_dir();
+
+    assert_eq!(
+        ModuleFormat::detect(&FileName::Real(dir.join("esm").join("index.ts"))),
+        ModuleFormat::Esm
+    );
+    assert_eq!(
+        ModuleFormat::detect(&FileName::Real(
+            dir.join("esm").join("src").join("index.ts")
+        )),
+        ModuleFormat::Esm
+    );
+    assert_eq!(
+        ModuleFormat::detect(&FileName::Real(dir.join("cjs").join("index.ts"))),
+        ModuleFormat::CommonJs
+    );
+}
+
+#[test]
+fn format_of_anon() {
+    assert_eq!(ModuleFormat::detect(&FileName::Anon), ModuleFormat::Esm);
+}
+
+test!(
+    syntax(),
+    |_| import_rewriter(
+        FileName::Real(fixture_dir().join("esm").join("index.ts")),
+        NodeNextImportResolver::new(NoopImportResolver)
+    ),
+    esm_extensions,
+    r#"
+import a from "./a.ts";
+import b from "../b.tsx";
+import c from "./c.mts";
+import d from "./d.js";
+import e from "e.ts";
+export * from "./f.cts";
+export { g } from "./g.ts";
+import("./h.ts");
+"#,
+    r#"
+import a from "./a.js";
+import b from "../b.js";
+import c from "./c.mjs";
+import d from "./d.js";
+import e from "e.ts";
+export * from "./f.cjs";
+export { g } from "./g.js";
+import("./h.js");
+"#
+);
+
+test!(
+    syntax(),
+    |_| {
+        let unresolved_mark = Mark::new();
+        let top_level_mark = Mark::new();
+
+        chain!(
+            resolver(unresolved_mark, top_level_mark, true),
+            common_js_with_resolver(
+                Box::new(NodeNextImportResolver::new(NoopImportResolver)),
+                FileName::Real(fixture_dir().join("cjs").join("index.ts")),
+                unresolved_mark,
+                Default::default(),
+                FeatureFlag::all(),
+                None::<swc_common::comments::SingleThreadedComments>,
+            )
+        )
+    },
+    cjs_extensions,
+    r#"
+import a from "./a.ts";
+export * from "./b.mts";
+"#,
+    r#"
+"use strict";
+Object.defineProperty(exports, "__esModule", {
+    value: true
+});
+const _aTs = _interopRequireDefault(require("./a.js"));
+_exportStar(require("./b.mjs"), exports);
+"#
+);
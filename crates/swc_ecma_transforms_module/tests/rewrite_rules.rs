@@ -0,0 +1,76 @@
+use swc_common::{chain, FileName, Mark};
+use swc_ecma_parser::Syntax;
+use swc_ecma_transforms_base::resolver;
+use swc_ecma_transforms_module::{
+    path::ImportResolver,
+    rewrite_rules::{RewriteRule, RuleImportResolver},
+    rewriter::import_rewriter_with_require,
+};
+use swc_ecma_transforms_testing::test;
+
+fn rules() -> Vec<RewriteRule> {
+    serde_json::from_str(
+        r#"[
+            { "pattern": "^lodash$", "replacement": "lodash-es" },
+            { "pattern": "\\?raw$", "replacement": "" },
+            { "pattern": "^(\\.{1,2}/(?:[^./]+/)*[^./]+)$", "replacement": "$1.js" }
+        ]"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn rules_are_applied_in_order() {
+    let resolver = RuleImportResolver::new(rules());
+
+    let resolve = |s: &str| resolver.resolve_import(&FileName::Anon, s).unwrap();
+
+    assert_eq!(&*resolve("lodash"), "lodash-es");
+    assert_eq!(&*resolve("lodash/fp"), "lodash/fp");
+    assert_eq!(&*resolve("./a"), "./a.js");
+    assert_eq!(&*resolve("./a.js"), "./a.js");
+    assert_eq!(&*resolve("../b.txt?raw"), "../b.txt");
+    assert_eq!(&*resolve("react"), "react");
+}
+
+test!(
+    Syntax::default(),
+    |_| {
+        let unresolved_mark = Mark::new();
+        let top_level_mark = Mark::new();
+
+        chain!(
+            resolver(unresolved_mark, top_level_mark, false),
+            import_rewriter_with_require(
+                FileName::Anon,
+                RuleImportResolver::new(rules()),
+                unresolved_mark
+            )
+        )
+    },
+    rewrite_specifiers,
+    r#"
+import _ from "lodash";
+import "./style.css?raw";
+export * from "./a";
+export { b } from "../b.js";
+import("./c");
+require("./d");
+
+function f(require) {
+    require("./e");
+}
+"#,
+    r#"
+import _ from "lodash-es";
+import "./style.css";
+export * from "./a.js";
+export { b } from "../b.js";
+import("./c.js");
+require("./d.js");
+
+function f(require1) {
+    require1("./e");
+}
+"#
+);
@@ -435,6 +435,7 @@ test!(
             decorators(decorators::Config {
                 legacy: true,
                 emit_metadata: false,
+                strict_null_checks: false,
                 use_define_for_class_fields: false,
             }),
             resolver(unresolved_mark, top_level_mark, false),
@@ -482,6 +483,7 @@ test!(
             decorators(decorators::Config {
                 legacy: true,
                 emit_metadata: false,
+                strict_null_checks: false,
                 use_define_for_class_fields: false,
             }),
             resolver(unresolved_mark, top_level_mark, false),
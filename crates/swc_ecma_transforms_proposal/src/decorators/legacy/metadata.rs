@@ -2,7 +2,7 @@ use swc_atoms::{js_word, JsWord};
 use swc_common::{
     collections::AHashMap,
     util::{move_map::MoveMap, take::Take},
-    Spanned, DUMMY_SP,
+    EqIgnoreSpan, Spanned, DUMMY_SP,
 };
 use swc_ecma_ast::*;
 use swc_ecma_transforms_base::helper;
@@ -75,59 +75,60 @@ impl ParamMetadata {
 pub(super) struct Metadata<'a> {
     pub(super) enums: &'a AHashMap<JsWord, EnumKind>,
 
+    pub(super) types: &'a AHashMap<JsWord, TypeDecl>,
+
     pub(super) class_name: Option<&'a Ident>,
+
+    /// Type parameters of the current class.
+    pub(super) type_params: Vec<JsWord>,
+
+    pub(super) strict_null_checks: bool,
 }
 
 impl VisitMut for Metadata<'_> {
     fn visit_mut_class(&mut self, c: &mut Class) {
+        let old = self.type_params.len();
+        self.type_params
+            .extend(type_param_names(c.type_params.as_deref()));
+
         c.visit_mut_children_with(self);
 
-        if c.decorators.is_empty() {
-            return;
-        }
+        if !c.decorators.is_empty() {
+            let constructor = c.body.iter().find_map(|m| match m {
+                ClassMember::Constructor(c) => Some(c),
+                _ => None,
+            });
+
+            if let Some(constructor) = constructor {
+                let mut serializer = self.serializer(None);
+                let param_types = constructor
+                    .params
+                    .iter()
+                    .map(|v| match v {
+                        ParamOrTsParamProp::TsParamProp(p) => match &p.param {
+                            TsParamPropParam::Ident(i) => i.type_ann.as_deref(),
+                            TsParamPropParam::Assign(a) => get_type_ann_of_pat(&a.left),
+                        },
+                        ParamOrTsParamProp::Param(p) => get_type_ann_of_pat(&p.pat),
+                    })
+                    .map(|ann| serializer.serialize(ann))
+                    .collect();
 
-        let constructor = c.body.iter().find_map(|m| match m {
-            ClassMember::Constructor(c) => Some(c),
-            _ => None,
-        });
-        if constructor.is_none() {
-            return;
-        }
+                let dec = self.create_metadata_design_decorator(
+                    "design:type",
+                    quote_ident!("Function").as_arg(),
+                );
+                c.decorators.push(dec);
 
-        {
-            let dec = self
-                .create_metadata_design_decorator("design:type", quote_ident!("Function").as_arg());
-            c.decorators.push(dec);
-        }
-        {
-            let dec = self.create_metadata_design_decorator(
-                "design:paramtypes",
-                ArrayLit {
-                    span: DUMMY_SP,
-                    elems: constructor
-                        .as_ref()
-                        .unwrap()
-                        .params
-                        .iter()
-                        .map(|v| match v {
-                            ParamOrTsParamProp::TsParamProp(p) => {
-                                let ann = match &p.param {
-                                    TsParamPropParam::Ident(i) => i.type_ann.as_deref(),
-                                    TsParamPropParam::Assign(a) => get_type_ann_of_pat(&a.left),
-                                };
-                                Some(serialize_type(self.class_name, ann).as_arg())
-                            }
-                            ParamOrTsParamProp::Param(p) => Some(
-                                serialize_type(self.class_name, get_type_ann_of_pat(&p.pat))
-                                    .as_arg(),
-                            ),
-                        })
-                        .collect(),
-                }
-                .as_arg(),
-            );
-            c.decorators.push(dec);
+                let dec = self.create_metadata_design_decorator(
+                    "design:paramtypes",
+                    param_types_arg(param_types),
+                );
+                c.decorators.push(dec);
+            }
         }
+
+        self.type_params.truncate(old);
     }
 
     fn visit_mut_class_method(&mut self, m: &mut ClassMethod) {
@@ -135,31 +136,63 @@ impl VisitMut for Metadata<'_> {
             return;
         }
 
-        {
-            let dec = self
-                .create_metadata_design_decorator("design:type", quote_ident!("Function").as_arg());
-            m.function.decorators.push(dec);
-        }
-        {
-            let dec = self.create_metadata_design_decorator(
-                "design:paramtypes",
-                ArrayLit {
-                    span: DUMMY_SP,
-                    elems: m
-                        .function
+        let mut serializer = self.serializer(m.function.type_params.as_deref());
+
+        match m.kind {
+            MethodKind::Method => {
+                let param_types = m
+                    .function
+                    .params
+                    .iter()
+                    .map(|v| serializer.serialize(get_type_ann_of_pat(&v.pat)))
+                    .collect();
+
+                let dec = self.create_metadata_design_decorator(
+                    "design:type",
+                    quote_ident!("Function").as_arg(),
+                );
+                m.function.decorators.push(dec);
+
+                let dec = self.create_metadata_design_decorator(
+                    "design:paramtypes",
+                    param_types_arg(param_types),
+                );
+                m.function.decorators.push(dec);
+
+                let return_type = match m.function.return_type.as_deref() {
+                    Some(ann) => serializer.serialize_type_node(&ann.type_ann),
+                    None if m.function.is_async => quote_ident!("Promise").into(),
+                    None => *undefined(DUMMY_SP),
+                };
+                let dec = self
+                    .create_metadata_design_decorator("design:returntype", return_type.as_arg());
+                m.function.decorators.push(dec);
+            }
+
+            // The type of an accessor is the type of the property.
+            MethodKind::Getter => {
+                let ty = serializer.serialize(m.function.return_type.as_deref());
+
+                let dec = self.create_metadata_design_decorator("design:type", ty.as_arg());
+                m.function.decorators.push(dec);
+            }
+            MethodKind::Setter => {
+                let ty = serializer.serialize(
+                    m.function
                         .params
-                        .iter()
-                        .map(|v| {
-                            Some(
-                                serialize_type(self.class_name, get_type_ann_of_pat(&v.pat))
-                                    .as_arg(),
-                            )
-                        })
-                        .collect(),
-                }
-                .as_arg(),
-            );
-            m.function.decorators.push(dec);
+                        .first()
+                        .and_then(|p| get_type_ann_of_pat(&p.pat)),
+                );
+
+                let dec = self.create_metadata_design_decorator("design:type", ty.clone().as_arg());
+                m.function.decorators.push(dec);
+
+                let dec = self.create_metadata_design_decorator(
+                    "design:paramtypes",
+                    param_types_arg(vec![ty]),
+                );
+                m.function.decorators.push(dec);
+            }
         }
     }
 
@@ -168,40 +201,10 @@ impl VisitMut for Metadata<'_> {
             return;
         }
 
-        if p.type_ann.is_none() {
-            return;
-        }
-        if let Some(name) = p
-            .type_ann
-            .as_ref()
-            .map(|ty| &ty.type_ann)
-            .and_then(|type_ann| match &**type_ann {
-                TsType::TsTypeRef(r) => Some(r),
-                _ => None,
-            })
-            .and_then(|r| match &r.type_name {
-                TsEntityName::TsQualifiedName(_) => None,
-                TsEntityName::Ident(i) => Some(i),
-            })
-        {
-            if let Some(kind) = self.enums.get(&name.sym) {
-                let dec = self.create_metadata_design_decorator(
-                    "design:type",
-                    match kind {
-                        EnumKind::Mixed => quote_ident!("Object").as_arg(),
-                        EnumKind::Str => quote_ident!("String").as_arg(),
-                        EnumKind::Num => quote_ident!("Number").as_arg(),
-                    },
-                );
-                p.decorators.push(dec);
-                return;
-            }
-        }
+        // Properties without a type annotation are serialized as `Object`
+        let ty = self.serializer(None).serialize(p.type_ann.as_deref());
 
-        let dec = self.create_metadata_design_decorator(
-            "design:type",
-            serialize_type(self.class_name, p.type_ann.as_deref()).as_arg(),
-        );
+        let dec = self.create_metadata_design_decorator("design:type", ty.as_arg());
         p.decorators.push(dec);
     }
 }
@@ -218,171 +221,69 @@ impl Metadata<'_> {
             })),
         }
     }
-}
-
-fn serialize_type(class_name: Option<&Ident>, param: Option<&TsTypeAnn>) -> Expr {
-    fn check_object_existed(expr: Box<Expr>) -> Box<Expr> {
-        match *expr {
-            Expr::Member(ref member_expr) => {
-                let obj_expr = member_expr.obj.clone();
-                Box::new(Expr::Bin(BinExpr {
-                    span: DUMMY_SP,
-                    left: check_object_existed(obj_expr),
-                    op: op!("||"),
-                    right: Box::new(Expr::Bin(BinExpr {
-                        span: DUMMY_SP,
-                        left: Box::new(Expr::Unary(UnaryExpr {
-                            span: DUMMY_SP,
-                            op: op!("typeof"),
-                            arg: expr,
-                        })),
-                        op: op!("==="),
-                        right: Box::new(Expr::Lit(Lit::Str(Str {
-                            span: DUMMY_SP,
-                            value: "undefined".into(),
-                            raw: None,
-                        }))),
-                    })),
-                }))
-            }
-            _ => Box::new(Expr::Bin(BinExpr {
-                span: DUMMY_SP,
-                left: Box::new(Expr::Unary(UnaryExpr {
-                    span: DUMMY_SP,
-                    op: op!("typeof"),
-                    arg: expr,
-                })),
-                op: op!("==="),
-                right: Box::new(Expr::Lit(Lit::Str(Str {
-                    span: DUMMY_SP,
-                    value: "undefined".into(),
-                    raw: None,
-                }))),
-            })),
-        }
-    }
 
-    fn serialize_type_ref(class_name: &str, ty: &TsTypeRef) -> Expr {
-        match &ty.type_name {
-            // We should omit references to self (class) since it will throw a ReferenceError at
-            // runtime due to babel transpile output.
-            TsEntityName::Ident(i) if &*i.sym == class_name => {
-                return quote_ident!("Object").into()
-            }
-            _ => {}
+    fn serializer(&self, fn_type_params: Option<&TsTypeParamDecl>) -> Serializer<'_> {
+        let mut type_params = self.type_params.clone();
+        type_params.extend(type_param_names(fn_type_params));
+
+        Serializer {
+            class_name: self.class_name.map(|v| &*v.sym).unwrap_or(""),
+            enums: self.enums,
+            types: self.types,
+            type_params,
+            aliases: Default::default(),
+            strict_null_checks: self.strict_null_checks,
         }
-
-        let member_expr = ts_entity_to_member_expr(&ty.type_name);
-
-        // We don't know if type is just a type (interface, etc.) or a concrete value
-        // (class, etc.)
-        //
-        // `typeof` operator allows us to use the expression even if it is not defined,
-        // fallback is just `Object`.
-
-        Expr::Cond(CondExpr {
-            span: DUMMY_SP,
-            test: check_object_existed(Box::new(member_expr.clone())),
-            cons: Box::new(quote_ident!("Object").into()),
-            alt: Box::new(member_expr),
-        })
     }
+}
 
-    fn serialize_type_list(class_name: &str, types: &[Box<TsType>]) -> Expr {
-        let mut u = None;
-        for ty in types {
-            // Skip parens if need be
-            let ty = match &**ty {
-                TsType::TsParenthesizedType(ty) => &ty.type_ann,
-                _ => ty,
-            };
-            match &**ty {
-                // Always elide `never` from the union/intersection if possible
-                TsType::TsKeywordType(TsKeywordType {
-                    kind: TsKeywordTypeKind::TsNeverKeyword,
-                    ..
-                }) => {
-                    continue;
-                }
-
-                // Elide null and undefined from unions for metadata, just like what we did prior to
-                // the implementation of strict null checks
-                TsType::TsKeywordType(TsKeywordType {
-                    kind: TsKeywordTypeKind::TsNullKeyword,
-                    ..
-                })
-                | TsType::TsKeywordType(TsKeywordType {
-                    kind: TsKeywordTypeKind::TsUndefinedKeyword,
-                    ..
-                }) => {
-                    return quote_ident!("Object").into();
-                }
-
-                _ => {}
-            }
-
-            let item = serialize_type_node(class_name, ty);
-
-            // One of the individual is global object, return immediately
-            if let Expr::Ident(Ident {
-                sym: js_word!("Object"),
-                ..
-            }) = item
-            {
-                return item;
-            }
-
-            // If there exists union that is not void 0 expression, check if the
-            // the common type is identifier. anything more complex
-            // and we will just default to Object
+/// What a name used as a type refers to.
+#[derive(Debug, Clone)]
+pub(super) enum TypeDecl {
+    /// Classes, functions, variables and imports, which may exist at runtime.
+    Value,
+    /// Interfaces and type-only imports, which do not exist at runtime.
+    Opaque,
+    /// `type Foo = ...`
+    Alias(Box<TsType>),
+}
 
-            //
-            match &u {
-                None => {
-                    u = Some(item);
-                }
+/// Serializes types like `tsc` does.
+///
+/// See `serializeTypeNode` in `typeSerializer.ts` of TypeScript.
+struct Serializer<'a> {
+    class_name: &'a str,
+    enums: &'a AHashMap<JsWord, EnumKind>,
+    types: &'a AHashMap<JsWord, TypeDecl>,
+    type_params: Vec<JsWord>,
 
-                Some(prev) => {
-                    // Check for different types
-                    match prev {
-                        Expr::Ident(prev) => match &item {
-                            Expr::Ident(item) if prev.sym == item.sym => {}
-                            _ => return quote_ident!("Object").into(),
-                        },
+    /// Type aliases being serialized, used to stop at circular aliases.
+    aliases: Vec<JsWord>,
 
-                        _ => return quote_ident!("Object").into(),
-                    }
-                }
-            }
-        }
+    strict_null_checks: bool,
+}
 
-        match u {
-            Some(i) => i,
-            _ => quote_ident!("Object").into(),
+impl Serializer<'_> {
+    fn serialize(&mut self, ann: Option<&TsTypeAnn>) -> Expr {
+        match ann {
+            Some(ann) => self.serialize_type_node(&ann.type_ann),
+            None => quote_ident!("Object").into(),
         }
     }
 
-    fn serialize_type_node(class_name: &str, ty: &TsType) -> Expr {
+    fn serialize_type_node(&mut self, ty: &TsType) -> Expr {
         let span = ty.span();
         match ty {
             TsType::TsKeywordType(TsKeywordType {
-                kind: TsKeywordTypeKind::TsVoidKeyword,
-                ..
-            })
-            | TsType::TsKeywordType(TsKeywordType {
-                kind: TsKeywordTypeKind::TsUndefinedKeyword,
-                ..
-            })
-            | TsType::TsKeywordType(TsKeywordType {
-                kind: TsKeywordTypeKind::TsNullKeyword,
-                ..
-            })
-            | TsType::TsKeywordType(TsKeywordType {
-                kind: TsKeywordTypeKind::TsNeverKeyword,
+                kind:
+                    TsKeywordTypeKind::TsVoidKeyword
+                    | TsKeywordTypeKind::TsUndefinedKeyword
+                    | TsKeywordTypeKind::TsNullKeyword
+                    | TsKeywordTypeKind::TsNeverKeyword,
                 ..
             }) => *undefined(span),
 
-            TsType::TsParenthesizedType(ty) => serialize_type_node(class_name, &ty.type_ann),
+            TsType::TsParenthesizedType(ty) => self.serialize_type_node(&ty.type_ann),
 
             TsType::TsFnOrConstructorType(_) => quote_ident!("Function").into(),
 
@@ -400,11 +301,6 @@ fn serialize_type(class_name: Option<&Ident>, param: Option<&TsTypeAnn>) -> Expr
 
             ty if is_str(ty) => quote_ident!("String").into(),
 
-            TsType::TsKeywordType(TsKeywordType {
-                kind: TsKeywordTypeKind::TsObjectKeyword,
-                ..
-            }) => quote_ident!("Object").into(),
-
             TsType::TsLitType(TsLitType {
                 lit: TsLit::Number(..),
                 ..
@@ -414,7 +310,11 @@ fn serialize_type(class_name: Option<&Ident>, param: Option<&TsTypeAnn>) -> Expr
                 ..
             }) => quote_ident!("Number").into(),
 
-            TsType::TsKeywordType(TsKeywordType {
+            TsType::TsLitType(TsLitType {
+                lit: TsLit::BigInt(..),
+                ..
+            })
+            | TsType::TsKeywordType(TsKeywordType {
                 kind: TsKeywordTypeKind::TsBigIntKeyword,
                 ..
             }) => Expr::Cond(CondExpr {
@@ -424,56 +324,232 @@ fn serialize_type(class_name: Option<&Ident>, param: Option<&TsTypeAnn>) -> Expr
                 alt: quote_ident!("BigInt").into(),
             }),
 
-            TsType::TsLitType(ty) => {
-                // TODO: Proper error reporting
-                panic!("Bad type for decoration: {:?}", ty);
-            }
-
             TsType::TsKeywordType(TsKeywordType {
                 kind: TsKeywordTypeKind::TsSymbolKeyword,
                 ..
             }) => quote_ident!("Symbol").into(),
 
-            TsType::TsTypeQuery(_)
-            | TsType::TsTypeOperator(_)
-            | TsType::TsIndexedAccessType(_)
-            | TsType::TsTypeLit(_)
-            | TsType::TsMappedType(_)
-            | TsType::TsKeywordType(TsKeywordType {
-                kind: TsKeywordTypeKind::TsAnyKeyword,
+            TsType::TsTypeOperator(TsTypeOperator {
+                op: TsTypeOperatorOp::ReadOnly,
+                type_ann,
                 ..
-            })
-            | TsType::TsKeywordType(TsKeywordType {
-                kind: TsKeywordTypeKind::TsUnknownKeyword,
-                ..
-            })
-            | TsType::TsThisType(..) => quote_ident!("Object").into(),
+            }) => self.serialize_type_node(type_ann),
 
             TsType::TsUnionOrIntersectionType(ty) => match ty {
                 TsUnionOrIntersectionType::TsUnionType(ty) => {
-                    serialize_type_list(class_name, &ty.types)
+                    self.serialize_type_list(&ty.types, false)
                 }
                 TsUnionOrIntersectionType::TsIntersectionType(ty) => {
-                    serialize_type_list(class_name, &ty.types)
+                    self.serialize_type_list(&ty.types, true)
                 }
             },
 
             TsType::TsConditionalType(ty) => {
-                serialize_type_list(class_name, &[ty.true_type.clone(), ty.false_type.clone()])
+                self.serialize_type_list(&[ty.true_type.clone(), ty.false_type.clone()], false)
             }
 
-            TsType::TsTypeRef(ty) => serialize_type_ref(class_name, ty),
+            TsType::TsTypeRef(ty) => self.serialize_type_ref(ty),
 
-            _ => panic!("Bad type for decorator: {:?}", ty),
+            // Type queries, type literals, mapped types, `any`, `unknown`, `object`, `this`, ...
+            _ => quote_ident!("Object").into(),
         }
     }
 
-    let param = match param {
-        Some(v) => &v.type_ann,
-        None => return *undefined(DUMMY_SP),
-    };
+    fn serialize_type_list(&mut self, types: &[Box<TsType>], is_intersection: bool) -> Expr {
+        let mut serialized: Option<Expr> = None;
 
-    serialize_type_node(class_name.map(|v| &*v.sym).unwrap_or(""), param)
+        for ty in types {
+            // Skip parens if need be
+            let mut ty = &**ty;
+            while let TsType::TsParenthesizedType(p) = ty {
+                ty = &p.type_ann;
+            }
+
+            if let TsType::TsKeywordType(TsKeywordType { kind, .. }) = ty {
+                match kind {
+                    // Reduce to `never` in an intersection, and elide `never` from the union.
+                    TsKeywordTypeKind::TsNeverKeyword => {
+                        if is_intersection {
+                            return *undefined(DUMMY_SP);
+                        }
+                        continue;
+                    }
+                    // Reduce to `unknown` in a union, and elide `unknown` from the intersection.
+                    TsKeywordTypeKind::TsUnknownKeyword => {
+                        if !is_intersection {
+                            return quote_ident!("Object").into();
+                        }
+                        continue;
+                    }
+                    TsKeywordTypeKind::TsAnyKeyword => return quote_ident!("Object").into(),
+                    // Elide null and undefined from unions for metadata, just like what tsc did
+                    // prior to the implementation of strict null checks
+                    TsKeywordTypeKind::TsNullKeyword | TsKeywordTypeKind::TsUndefinedKeyword
+                        if !self.strict_null_checks =>
+                    {
+                        continue
+                    }
+                    _ => {}
+                }
+            }
+
+            let item = self.serialize_type_node(ty);
+
+            // One of the individual is global object, return immediately
+            if let Expr::Ident(Ident {
+                sym: js_word!("Object"),
+                ..
+            }) = item
+            {
+                return item;
+            }
+
+            // If there exists union that is not void 0 expression, check if the
+            // the common type is identifier. anything more complex
+            // and we will just default to Object
+            match &serialized {
+                None => serialized = Some(item),
+                Some(prev) => {
+                    if !prev.eq_ignore_span(&item) {
+                        return quote_ident!("Object").into();
+                    }
+                }
+            }
+        }
+
+        // Only hit if all constituents are `never`
+        serialized.unwrap_or_else(|| *undefined(DUMMY_SP))
+    }
+
+    fn serialize_type_ref(&mut self, ty: &TsTypeRef) -> Expr {
+        let root = root_of_entity(&ty.type_name);
+
+        match &ty.type_name {
+            // We should omit references to self (class) since it will throw a ReferenceError at
+            // runtime due to babel transpile output.
+            TsEntityName::Ident(i) if &*i.sym == self.class_name => {
+                return quote_ident!("Object").into()
+            }
+
+            // Type parameters do not exist at runtime.
+            TsEntityName::Ident(i) if self.type_params.contains(&i.sym) => {
+                return quote_ident!("Object").into()
+            }
+
+            _ => {}
+        }
+
+        // `Enum` or `Enum.Member`
+        let enum_name = match &ty.type_name {
+            TsEntityName::Ident(i) => Some(i),
+            TsEntityName::TsQualifiedName(q) => match &q.left {
+                TsEntityName::Ident(i) => Some(i),
+                _ => None,
+            },
+        };
+        if let Some(kind) = enum_name.and_then(|i| self.enums.get(&i.sym)) {
+            return match kind {
+                EnumKind::Mixed => quote_ident!("Object").into(),
+                EnumKind::Str => quote_ident!("String").into(),
+                EnumKind::Num => quote_ident!("Number").into(),
+            };
+        }
+
+        let types = self.types;
+        match types.get(&root.sym) {
+            Some(TypeDecl::Opaque) => return quote_ident!("Object").into(),
+            Some(TypeDecl::Alias(alias)) => {
+                if let TsEntityName::Ident(i) = &ty.type_name {
+                    if self.aliases.contains(&i.sym) {
+                        return quote_ident!("Object").into();
+                    }
+
+                    self.aliases.push(i.sym.clone());
+                    let expr = self.serialize_type_node(alias);
+                    self.aliases.pop();
+                    return expr;
+                }
+            }
+            Some(TypeDecl::Value) | None => {}
+        }
+
+        let member_expr = ts_entity_to_member_expr(&ty.type_name);
+
+        // We don't know if type is just a type (interface, etc.) or a concrete value
+        // (class, etc.)
+        //
+        // `typeof` operator allows us to use the expression even if it is not defined,
+        // fallback is just `Object`.
+
+        Expr::Cond(CondExpr {
+            span: DUMMY_SP,
+            test: check_object_existed(Box::new(member_expr.clone())),
+            cons: Box::new(quote_ident!("Object").into()),
+            alt: Box::new(member_expr),
+        })
+    }
+}
+
+fn check_object_existed(expr: Box<Expr>) -> Box<Expr> {
+    match *expr {
+        Expr::Member(ref member_expr) => {
+            let obj_expr = member_expr.obj.clone();
+            Box::new(Expr::Bin(BinExpr {
+                span: DUMMY_SP,
+                left: check_object_existed(obj_expr),
+                op: op!("||"),
+                right: Box::new(Expr::Bin(BinExpr {
+                    span: DUMMY_SP,
+                    left: Box::new(Expr::Unary(UnaryExpr {
+                        span: DUMMY_SP,
+                        op: op!("typeof"),
+                        arg: expr,
+                    })),
+                    op: op!("==="),
+                    right: Box::new(Expr::Lit(Lit::Str(Str {
+                        span: DUMMY_SP,
+                        value: "undefined".into(),
+                        raw: None,
+                    }))),
+                })),
+            }))
+        }
+        _ => Box::new(Expr::Bin(BinExpr {
+            span: DUMMY_SP,
+            left: Box::new(Expr::Unary(UnaryExpr {
+                span: DUMMY_SP,
+                op: op!("typeof"),
+                arg: expr,
+            })),
+            op: op!("==="),
+            right: Box::new(Expr::Lit(Lit::Str(Str {
+                span: DUMMY_SP,
+                value: "undefined".into(),
+                raw: None,
+            }))),
+        })),
+    }
+}
+
+fn param_types_arg(types: Vec<Expr>) -> ExprOrSpread {
+    ArrayLit {
+        span: DUMMY_SP,
+        elems: types.into_iter().map(|ty| Some(ty.as_arg())).collect(),
+    }
+    .as_arg()
+}
+
+fn type_param_names(params: Option<&TsTypeParamDecl>) -> impl '_ + Iterator<Item = JsWord> {
+    params
+        .into_iter()
+        .flat_map(|decl| decl.params.iter().map(|p| p.name.sym.clone()))
+}
+
+fn root_of_entity(type_name: &TsEntityName) -> &Ident {
+    match type_name {
+        TsEntityName::TsQualifiedName(q) => root_of_entity(&q.left),
+        TsEntityName::Ident(i) => i,
+    }
 }
 
 fn ts_entity_to_member_expr(type_name: &TsEntityName) -> Expr {
@@ -511,7 +587,7 @@ fn get_type_ann_of_pat(p: &Pat) -> Option<&TsTypeAnn> {
 fn is_str(ty: &TsType) -> bool {
     match ty {
         TsType::TsLitType(TsLitType {
-            lit: TsLit::Str(..),
+            lit: TsLit::Str(..) | TsLit::Tpl(..),
             ..
         })
         | TsType::TsKeywordType(TsKeywordType {
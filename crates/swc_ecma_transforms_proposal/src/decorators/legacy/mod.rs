@@ -8,7 +8,7 @@ use swc_ecma_utils::{
 };
 use swc_ecma_visit::{Visit, VisitMut, VisitMutWith, VisitWith};
 
-use self::metadata::{Metadata, ParamMetadata};
+use self::metadata::{Metadata, ParamMetadata, TypeDecl};
 use super::contains_decorator;
 
 mod metadata;
@@ -20,11 +20,17 @@ enum EnumKind {
     Num,
 }
 
-pub(super) fn new(metadata: bool, use_define_for_class_fields: bool) -> TscDecorator {
+pub(super) fn new(
+    metadata: bool,
+    strict_null_checks: bool,
+    use_define_for_class_fields: bool,
+) -> TscDecorator {
     TscDecorator {
         metadata,
+        strict_null_checks,
         use_define_for_class_fields,
         enums: Default::default(),
+        types: Default::default(),
         vars: Default::default(),
         appended_exprs: Default::default(),
         prepended_exprs: Default::default(),
@@ -36,10 +42,15 @@ pub(super) fn new(metadata: bool, use_define_for_class_fields: bool) -> TscDecor
 
 pub(super) struct TscDecorator {
     metadata: bool,
+    strict_null_checks: bool,
     use_define_for_class_fields: bool,
 
     enums: AHashMap<JsWord, EnumKind>,
 
+    /// Interfaces, type aliases and type-only imports, used to serialize
+    /// type references.
+    types: AHashMap<JsWord, TypeDecl>,
+
     /// Used for computed keys, and this variables are not initialized.
     vars: Vec<VarDeclarator>,
     appended_exprs: Vec<Box<Expr>>,
@@ -183,6 +194,55 @@ impl TscDecorator {
 }
 
 impl Visit for TscDecorator {
+    fn visit_import_decl(&mut self, n: &ImportDecl) {
+        for s in &n.specifiers {
+            let (local, is_type_only) = match s {
+                ImportSpecifier::Named(s) => (&s.local, s.is_type_only),
+                ImportSpecifier::Default(s) => (&s.local, false),
+                ImportSpecifier::Namespace(s) => (&s.local, false),
+            };
+
+            let decl = if n.type_only || is_type_only {
+                TypeDecl::Opaque
+            } else {
+                TypeDecl::Value
+            };
+            self.types.insert(local.sym.clone(), decl);
+        }
+    }
+
+    fn visit_class_decl(&mut self, n: &ClassDecl) {
+        self.types.insert(n.ident.sym.clone(), TypeDecl::Value);
+
+        n.visit_children_with(self);
+    }
+
+    fn visit_fn_decl(&mut self, n: &FnDecl) {
+        self.types.insert(n.ident.sym.clone(), TypeDecl::Value);
+
+        n.visit_children_with(self);
+    }
+
+    fn visit_ts_interface_decl(&mut self, n: &TsInterfaceDecl) {
+        // An interface can be merged with a class or a variable.
+        self.types
+            .entry(n.id.sym.clone())
+            .or_insert(TypeDecl::Opaque);
+    }
+
+    fn visit_ts_type_alias_decl(&mut self, n: &TsTypeAliasDecl) {
+        self.types
+            .insert(n.id.sym.clone(), TypeDecl::Alias(n.type_ann.clone()));
+    }
+
+    fn visit_var_declarator(&mut self, n: &VarDeclarator) {
+        if let Pat::Ident(i) = &n.name {
+            self.types.insert(i.id.sym.clone(), TypeDecl::Value);
+        }
+
+        n.visit_children_with(self);
+    }
+
     fn visit_ts_enum_decl(&mut self, e: &TsEnumDecl) {
         let enum_kind = e
             .members
@@ -234,7 +294,10 @@ impl VisitMut for TscDecorator {
 
             n.visit_mut_with(&mut Metadata {
                 enums: &self.enums,
+                types: &self.types,
                 class_name: i.as_ref(),
+                type_params: Default::default(),
+                strict_null_checks: self.strict_null_checks,
             });
         }
 
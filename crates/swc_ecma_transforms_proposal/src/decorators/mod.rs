@@ -59,6 +59,7 @@ pub fn decorators(c: Config) -> impl Fold {
     if c.legacy {
         Either::Left(as_folder(self::legacy::new(
             c.emit_metadata,
+            c.strict_null_checks,
             c.use_define_for_class_fields,
         )))
     } else {
@@ -79,6 +80,11 @@ pub struct Config {
     #[serde(default)]
     pub emit_metadata: bool,
 
+    /// Serializes types for metadata like `tsc` with `strictNullChecks`,
+    /// which does not elide `null` and `undefined` from union types.
+    #[serde(default)]
+    pub strict_null_checks: bool,
+
     pub use_define_for_class_fields: bool,
 }
 
@@ -0,0 +1,374 @@
+use swc_atoms::js_word;
+use swc_common::{collections::AHashSet, util::take::Take, Mark, Spanned, SyntaxContext, DUMMY_SP};
+use swc_ecma_ast::*;
+use swc_ecma_utils::{find_pat_ids, private_ident, StmtOrModuleItem};
+use swc_ecma_visit::{
+    as_folder, noop_visit_mut_type, noop_visit_type, Fold, Visit, VisitMut, VisitMutWith, VisitWith,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// `@babel/plugin-transform-react-constant-elements`
+///
+/// Hoists JSX elements which can never change between renders to the module
+/// scope, so they are created only once.
+///
+/// An element is hoisted only if it is nested in a function and every
+/// identifier it references is a top-level binding which is never reassigned
+/// and is already initialized where the element is hoisted to. Elements with a
+/// `ref`, spread attributes or mutable values (objects, functions, ...) are
+/// left as is.
+///
+/// This pass should be applied before [crate::jsx].
+///
+/// `top_level_mark` should be [Mark] passed to
+/// [swc_ecma_transforms_base::resolver::resolver_with_mark].
+pub fn constant_elements(top_level_mark: Mark) -> impl Fold + VisitMut {
+    as_folder(ConstantElements {
+        top_level_ctxt: SyntaxContext::empty().apply_mark(top_level_mark),
+        mutated: Default::default(),
+        available: Default::default(),
+        in_fn: false,
+        hoisted: Default::default(),
+    })
+}
+
+struct ConstantElements {
+    top_level_ctxt: SyntaxContext,
+
+    /// Bindings which are reassigned somewhere in the file.
+    mutated: AHashSet<Id>,
+
+    /// Immutable top-level bindings which are initialized before the current
+    /// top-level item.
+    available: AHashSet<Id>,
+
+    in_fn: bool,
+
+    /// Declarators to insert before the current top-level item.
+    hoisted: Vec<VarDeclarator>,
+}
+
+impl ConstantElements {
+    fn visit_mut_top_level<T>(&mut self, items: &mut Vec<T>)
+    where
+        T: StmtOrModuleItem + VisitMutWith<Self> + VisitWith<MutationFinder>,
+    {
+        let mut finder = MutationFinder::default();
+        for item in items.iter() {
+            item.visit_with(&mut finder);
+        }
+        self.mutated = finder.mutated;
+
+        // Imports and function declarations are initialized before any code runs.
+        for item in items.iter() {
+            match item.as_stmt() {
+                Ok(Stmt::Decl(Decl::Fn(f))) => self.add_available(f.ident.to_id()),
+                Err(ModuleDecl::ExportDecl(ExportDecl {
+                    decl: Decl::Fn(f), ..
+                })) => self.add_available(f.ident.to_id()),
+                Err(ModuleDecl::Import(import)) => {
+                    for s in &import.specifiers {
+                        let local = match s {
+                            ImportSpecifier::Named(s) => &s.local,
+                            ImportSpecifier::Default(s) => &s.local,
+                            ImportSpecifier::Namespace(s) => &s.local,
+                        };
+                        self.add_available(local.to_id());
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let mut buf = Vec::with_capacity(items.len());
+
+        for mut item in items.take() {
+            item.visit_mut_with(self);
+
+            if !self.hoisted.is_empty() {
+                buf.push(T::from_stmt(Stmt::Decl(Decl::Var(Box::new(VarDecl {
+                    span: DUMMY_SP,
+                    kind: VarDeclKind::Var,
+                    declare: false,
+                    decls: self.hoisted.take(),
+                })))));
+            }
+
+            let decl = match item.as_stmt() {
+                Ok(Stmt::Decl(decl)) => Some(decl),
+                Err(ModuleDecl::ExportDecl(ExportDecl { decl, .. })) => Some(decl),
+                _ => None,
+            };
+            match decl {
+                Some(Decl::Class(c)) => self.add_available(c.ident.to_id()),
+                Some(Decl::Var(v)) => {
+                    for id in find_pat_ids::<_, Id>(&v.decls) {
+                        self.add_available(id);
+                    }
+                }
+                _ => {}
+            }
+
+            buf.push(item);
+        }
+
+        *items = buf;
+    }
+
+    fn add_available(&mut self, id: Id) {
+        if !self.mutated.contains(&id) {
+            self.available.insert(id);
+        }
+    }
+
+    fn is_available(&self, i: &Ident) -> bool {
+        i.span.ctxt == self.top_level_ctxt && self.available.contains(&i.to_id())
+    }
+
+    fn hoist(&mut self, expr: Expr) -> Ident {
+        let id = private_ident!("_ref");
+        self.hoisted.push(VarDeclarator {
+            span: DUMMY_SP,
+            name: id.clone().into(),
+            init: Some(Box::new(expr)),
+            definite: false,
+        });
+        id
+    }
+
+    fn is_constant_element(&self, el: &JSXElement) -> bool {
+        if !self.is_constant_element_name(&el.opening.name) {
+            return false;
+        }
+
+        let attrs_are_constant = el.opening.attrs.iter().all(|attr| match attr {
+            JSXAttrOrSpread::JSXAttr(attr) => {
+                if let JSXAttrName::Ident(i) = &attr.name {
+                    if &*i.sym == "ref" {
+                        return false;
+                    }
+                }
+
+                match &attr.value {
+                    None | Some(JSXAttrValue::Lit(..)) => true,
+                    Some(JSXAttrValue::JSXExprContainer(c)) => self.is_constant_expr_container(c),
+                    Some(JSXAttrValue::JSXElement(el)) => self.is_constant_element(el),
+                    Some(JSXAttrValue::JSXFragment(f)) => self.is_constant_fragment(f),
+                }
+            }
+            JSXAttrOrSpread::SpreadElement(..) => false,
+        });
+
+        attrs_are_constant && self.are_constant_children(&el.children)
+    }
+
+    fn is_constant_fragment(&self, f: &JSXFragment) -> bool {
+        self.are_constant_children(&f.children)
+    }
+
+    fn are_constant_children(&self, children: &[JSXElementChild]) -> bool {
+        children.iter().all(|c| match c {
+            JSXElementChild::JSXText(..) => true,
+            JSXElementChild::JSXExprContainer(c) => self.is_constant_expr_container(c),
+            JSXElementChild::JSXSpreadChild(..) => false,
+            JSXElementChild::JSXElement(el) => self.is_constant_element(el),
+            JSXElementChild::JSXFragment(f) => self.is_constant_fragment(f),
+        })
+    }
+
+    fn is_constant_element_name(&self, name: &JSXElementName) -> bool {
+        fn root(obj: &JSXObject) -> &Ident {
+            match obj {
+                JSXObject::Ident(i) => i,
+                JSXObject::JSXMemberExpr(e) => root(&e.obj),
+            }
+        }
+
+        match name {
+            JSXElementName::Ident(i) => {
+                if i.sym == js_word!("this") {
+                    return false;
+                }
+
+                i.sym.starts_with(|c: char| c.is_ascii_lowercase()) || self.is_available(i)
+            }
+            JSXElementName::JSXMemberExpr(e) => {
+                let i = root(&e.obj);
+                i.sym != js_word!("this") && self.is_available(i)
+            }
+            JSXElementName::JSXNamespacedName(..) => true,
+        }
+    }
+
+    fn is_constant_expr_container(&self, c: &JSXExprContainer) -> bool {
+        match &c.expr {
+            JSXExpr::JSXEmptyExpr(..) => true,
+            JSXExpr::Expr(e) => self.is_constant_expr(e),
+        }
+    }
+
+    /// Objects, arrays, functions and regular expressions are mutable, so
+    /// elements using them are not hoisted.
+    fn is_constant_expr(&self, e: &Expr) -> bool {
+        match e {
+            Expr::Lit(Lit::Regex(..)) => false,
+            Expr::Lit(..) => true,
+            Expr::Tpl(tpl) => tpl.exprs.iter().all(|e| self.is_constant_expr(e)),
+            Expr::Ident(i) => self.is_available(i),
+            Expr::Unary(e) => e.op != op!("delete") && self.is_constant_expr(&e.arg),
+            Expr::Bin(e) => self.is_constant_expr(&e.left) && self.is_constant_expr(&e.right),
+            Expr::Cond(e) => {
+                self.is_constant_expr(&e.test)
+                    && self.is_constant_expr(&e.cons)
+                    && self.is_constant_expr(&e.alt)
+            }
+            Expr::Paren(e) => self.is_constant_expr(&e.expr),
+            Expr::JSXElement(el) => self.is_constant_element(el),
+            Expr::JSXFragment(f) => self.is_constant_fragment(f),
+            _ => false,
+        }
+    }
+
+    fn with_fn<F>(&mut self, op: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        let old = self.in_fn;
+        self.in_fn = true;
+        op(self);
+        self.in_fn = old;
+    }
+}
+
+impl VisitMut for ConstantElements {
+    noop_visit_mut_type!();
+
+    fn visit_mut_arrow_expr(&mut self, f: &mut ArrowExpr) {
+        self.with_fn(|v| f.visit_mut_children_with(v));
+    }
+
+    fn visit_mut_constructor(&mut self, c: &mut Constructor) {
+        self.with_fn(|v| c.visit_mut_children_with(v));
+    }
+
+    fn visit_mut_expr(&mut self, e: &mut Expr) {
+        if self.in_fn {
+            match e {
+                Expr::JSXElement(el) if self.is_constant_element(el) => {
+                    let el = el.take();
+                    *e = Expr::Ident(self.hoist(Expr::JSXElement(el)));
+                    return;
+                }
+                Expr::JSXFragment(f) if self.is_constant_fragment(f) => {
+                    let f = f.take();
+                    *e = Expr::Ident(self.hoist(Expr::JSXFragment(f)));
+                    return;
+                }
+                _ => {}
+            }
+        }
+
+        e.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_function(&mut self, f: &mut Function) {
+        self.with_fn(|v| f.visit_mut_children_with(v));
+    }
+
+    fn visit_mut_getter_prop(&mut self, p: &mut GetterProp) {
+        self.with_fn(|v| p.visit_mut_children_with(v));
+    }
+
+    fn visit_mut_jsx_element_child(&mut self, c: &mut JSXElementChild) {
+        if self.in_fn {
+            let expr = match c {
+                JSXElementChild::JSXElement(el) if self.is_constant_element(el) => {
+                    Some(Expr::JSXElement(el.take()))
+                }
+                JSXElementChild::JSXFragment(f) if self.is_constant_fragment(f) => {
+                    Some(Expr::JSXFragment(f.take()))
+                }
+                _ => None,
+            };
+
+            if let Some(expr) = expr {
+                let span = c.span();
+                let id = self.hoist(expr);
+                *c = JSXElementChild::JSXExprContainer(JSXExprContainer {
+                    span,
+                    expr: JSXExpr::Expr(Box::new(Expr::Ident(id))),
+                });
+                return;
+            }
+        }
+
+        c.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_module(&mut self, m: &mut Module) {
+        self.visit_mut_top_level(&mut m.body);
+    }
+
+    fn visit_mut_script(&mut self, s: &mut Script) {
+        self.visit_mut_top_level(&mut s.body);
+    }
+
+    fn visit_mut_setter_prop(&mut self, p: &mut SetterProp) {
+        self.with_fn(|v| p.visit_mut_children_with(v));
+    }
+}
+
+/// Collects bindings which are reassigned.
+#[derive(Default)]
+struct MutationFinder {
+    mutated: AHashSet<Id>,
+}
+
+impl MutationFinder {
+    fn add_pat(&mut self, p: &Pat) {
+        self.mutated.extend(find_pat_ids::<_, Id>(p));
+    }
+}
+
+impl Visit for MutationFinder {
+    noop_visit_type!();
+
+    fn visit_assign_expr(&mut self, e: &AssignExpr) {
+        e.visit_children_with(self);
+
+        match &e.left {
+            PatOrExpr::Pat(p) => self.add_pat(p),
+            PatOrExpr::Expr(e) => {
+                if let Expr::Ident(i) = &**e {
+                    self.mutated.insert(i.to_id());
+                }
+            }
+        }
+    }
+
+    fn visit_for_in_stmt(&mut self, s: &ForInStmt) {
+        s.visit_children_with(self);
+
+        if let VarDeclOrPat::Pat(p) = &s.left {
+            self.add_pat(p);
+        }
+    }
+
+    fn visit_for_of_stmt(&mut self, s: &ForOfStmt) {
+        s.visit_children_with(self);
+
+        if let VarDeclOrPat::Pat(p) = &s.left {
+            self.add_pat(p);
+        }
+    }
+
+    fn visit_update_expr(&mut self, e: &UpdateExpr) {
+        e.visit_children_with(self);
+
+        if let Expr::Ident(i) = &*e.arg {
+            self.mutated.insert(i.to_id());
+        }
+    }
+}
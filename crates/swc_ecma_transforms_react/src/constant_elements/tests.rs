@@ -0,0 +1,242 @@
+use swc_common::{chain, Mark};
+use swc_ecma_parser::{EsConfig, Syntax};
+use swc_ecma_transforms_base::resolver;
+use swc_ecma_transforms_testing::test;
+
+use super::*;
+
+fn syntax() -> Syntax {
+    Syntax::Es(EsConfig {
+        jsx: true,
+        ..Default::default()
+    })
+}
+
+fn tr() -> impl Fold {
+    let unresolved_mark = Mark::new();
+    let top_level_mark = Mark::new();
+
+    chain!(
+        resolver(unresolved_mark, top_level_mark, false),
+        constant_elements(top_level_mark)
+    )
+}
+
+test!(
+    syntax(),
+    |_| tr(),
+    basic,
+    r#"
+function render() {
+    return <div className="foo" />;
+}
+"#,
+    r#"
+var _ref = <div className="foo" />;
+function render() {
+    return _ref;
+}
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    top_level_element,
+    r#"
+const el = <div className="foo" />;
+"#,
+    r#"
+const el = <div className="foo" />;
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    children,
+    r#"
+const Foo = () => <div>
+    <span>{"text"}</span>
+    {`template`}
+</div>;
+"#,
+    r#"
+var _ref = <div>
+    <span>{"text"}</span>
+    {`template`}
+</div>;
+const Foo = ()=>_ref;
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    dynamic_children,
+    r#"
+function render(text) {
+    return <div>
+        <span>static</span>
+        {text}
+    </div>;
+}
+"#,
+    r#"
+var _ref = <span>static</span>;
+function render(text) {
+    return <div>
+        {_ref}
+        {text}
+    </div>;
+}
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    immutable_binding,
+    r#"
+import Foo from "foo";
+const title = "hello";
+function render() {
+    return <Foo title={title} />;
+}
+"#,
+    r#"
+import Foo from "foo";
+const title = "hello";
+var _ref = <Foo title={title}/>;
+function render() {
+    return _ref;
+}
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    mutated_binding,
+    r#"
+let title = "hello";
+function render() {
+    return <div title={title} />;
+}
+title = "world";
+"#,
+    r#"
+let title = "hello";
+function render() {
+    return <div title={title} />;
+}
+title = "world";
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    binding_declared_later,
+    r#"
+function render() {
+    return <div title={title} />;
+}
+const title = "hello";
+"#,
+    r#"
+function render() {
+    return <div title={title} />;
+}
+const title = "hello";
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    local_binding,
+    r#"
+function render(title) {
+    return <div title={title} />;
+}
+"#,
+    r#"
+function render(title) {
+    return <div title={title} />;
+}
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    mutable_props,
+    r#"
+function render() {
+    return <div>
+        <div style={{ color: "red" }} />
+        <div ref="foo" />
+        <div {...props} />
+        <div onClick={() => {}} />
+    </div>;
+}
+"#,
+    r#"
+function render() {
+    return <div>
+        <div style={{ color: "red" }} />
+        <div ref="foo" />
+        <div {...props} />
+        <div onClick={() => {}} />
+    </div>;
+}
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    fragment,
+    r#"
+function render() {
+    return <>
+        <span>a</span>
+        <span>b</span>
+    </>;
+}
+"#,
+    r#"
+var _ref = <>
+        <span>a</span>
+        <span>b</span>
+    </>;
+function render() {
+    return _ref;
+}
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    multiple,
+    r#"
+function a() {
+    return <div>a</div>;
+}
+function b() {
+    return <div>b</div>;
+}
+"#,
+    r#"
+var _ref = <div>a</div>;
+function a() {
+    return _ref;
+}
+var _ref1 = <div>b</div>;
+function b() {
+    return _ref1;
+}
+"#
+);
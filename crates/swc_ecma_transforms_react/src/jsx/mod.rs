@@ -81,6 +81,23 @@ pub struct Options {
     #[serde(default)]
     pub use_spread: Option<bool>,
 
+    /// Hoist JSX elements which never change to the module scope.
+    ///
+    /// `@babel/plugin-transform-react-constant-elements`
+    ///
+    /// This is ignored if `development` is `true`.
+    #[serde(default)]
+    pub constant_elements: Option<bool>,
+
+    /// Create react elements as object literals using the `jsx` helper instead
+    /// of calling `createElement`.
+    ///
+    /// `@babel/plugin-transform-react-inline-elements`
+    ///
+    /// This is ignored if `development` is `true`.
+    #[serde(default)]
+    pub inline_elements: Option<bool>,
+
     #[serde(default, deserialize_with = "deserialize_refresh")]
     // default to disabled since this is still considered as experimental by now
     pub refresh: Option<RefreshOptions>,
@@ -212,6 +229,7 @@ where
         use_builtins: options.use_builtins.unwrap_or_default(),
         use_spread: options.use_spread.unwrap_or_default(),
         development: options.development.unwrap_or_default(),
+        inline_elements: options.inline_elements.unwrap_or_default(),
         throw_if_namespace: options
             .throw_if_namespace
             .unwrap_or_else(default_throw_if_namespace),
@@ -247,6 +265,7 @@ where
     use_builtins: bool,
     use_spread: bool,
     development: bool,
+    inline_elements: bool,
     throw_if_namespace: bool,
 }
 
@@ -480,6 +499,10 @@ where
     ///
     /// <div></div> => React.createElement('div', null);
     fn jsx_elem_to_expr(&mut self, el: JSXElement) -> Expr {
+        if self.inline_elements && !self.development && can_inline(&el.opening.attrs) {
+            return self.jsx_elem_to_inline_expr(el);
+        }
+
         let top_level_node = self.top_level_node;
         let span = el.span();
         let use_create_element = should_use_create_element(&el.opening.attrs);
@@ -740,6 +763,63 @@ where
         }
     }
 
+    /// `@babel/plugin-transform-react-inline-elements`
+    ///
+    /// <div key="k">foo</div> => _jsx('div', {}, 'k', 'foo');
+    fn jsx_elem_to_inline_expr(&mut self, el: JSXElement) -> Expr {
+        let span = el.span();
+
+        let name = self.jsx_name(el.opening.name);
+
+        if let Some(comments) = &self.comments {
+            comments.add_pure_comment(span.lo);
+        }
+
+        let mut key = None;
+        let mut props = vec![];
+
+        for attr in el.opening.attrs {
+            if let JSXAttrOrSpread::JSXAttr(attr) = attr {
+                if let JSXAttrName::Ident(i) = &attr.name {
+                    if i.sym == js_word!("key") {
+                        key = attr.value.and_then(jsx_attr_value_to_expr);
+                        continue;
+                    }
+                }
+
+                props.push(PropOrSpread::Prop(Box::new(self.attr_to_prop(attr))));
+            }
+        }
+
+        let children = el
+            .children
+            .into_iter()
+            .filter_map(|c| self.jsx_elem_child_to_expr(c))
+            .collect::<Vec<_>>();
+
+        let args = once(name.as_arg()).chain(once(
+            ObjectLit {
+                span: DUMMY_SP,
+                props,
+            }
+            .as_arg(),
+        ));
+        let args = if children.is_empty() {
+            args.chain(key.map(|key| key.as_arg())).collect()
+        } else {
+            args.chain(once(key.unwrap_or_else(|| undefined(DUMMY_SP)).as_arg()))
+                .chain(children)
+                .collect()
+        };
+
+        Expr::Call(CallExpr {
+            span,
+            callee: helper!(jsx, "jsx"),
+            args,
+            type_args: None,
+        })
+    }
+
     fn jsx_elem_child_to_expr(&mut self, c: JSXElementChild) -> Option<ExprOrSpread> {
         self.top_level_node = false;
 
@@ -1314,6 +1394,17 @@ fn jsx_attr_value_to_expr(v: JSXAttrValue) -> Option<Box<Expr>> {
     })
 }
 
+/// Elements with `ref` or spread attributes need `createElement`.
+fn can_inline(attrs: &[JSXAttrOrSpread]) -> bool {
+    attrs.iter().all(|attr| match attr {
+        JSXAttrOrSpread::JSXAttr(attr) => match &attr.name {
+            JSXAttrName::Ident(i) => &*i.sym != "ref",
+            JSXAttrName::JSXNamespacedName(..) => true,
+        },
+        JSXAttrOrSpread::SpreadElement(..) => false,
+    })
+}
+
 fn count_children(children: &[JSXElementChild]) -> usize {
     children
         .iter()
@@ -1,9 +1,10 @@
 #![deny(clippy::all)]
 
-use swc_common::{chain, comments::Comments, sync::Lrc, Mark, SourceMap};
+use swc_common::{chain, comments::Comments, pass::Optional, sync::Lrc, Mark, SourceMap};
 use swc_ecma_visit::{Fold, VisitMut};
 
 pub use self::{
+    constant_elements::constant_elements,
     display_name::display_name,
     jsx::*,
     jsx_self::jsx_self,
@@ -12,6 +13,7 @@ pub use self::{
     refresh::{options::RefreshOptions, refresh},
 };
 
+mod constant_elements;
 mod display_name;
 mod jsx;
 mod jsx_self;
@@ -43,6 +45,7 @@ where
 {
     let Options { development, .. } = options;
     let development = development.unwrap_or(false);
+    let hoist_constant_elements = !development && options.constant_elements.unwrap_or(false);
 
     let refresh_options = options.refresh.take();
 
@@ -56,6 +59,7 @@ where
             comments.clone(),
             top_level_mark
         ),
+        Optional::new(constant_elements(top_level_mark), hoist_constant_elements),
         jsx(cm, comments.clone(), options, top_level_mark),
         display_name(),
         pure_annotations(comments),
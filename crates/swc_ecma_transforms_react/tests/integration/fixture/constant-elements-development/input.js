@@ -0,0 +1,12 @@
+import React from "react";
+
+const title = "Hello";
+
+export function App({ name }) {
+    return (
+        <main>
+            <h1 className="title">{title}</h1>
+            <p>{name}</p>
+        </main>
+    );
+}
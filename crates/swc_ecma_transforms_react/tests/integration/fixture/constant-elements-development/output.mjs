@@ -0,0 +1,27 @@
+import React from "react";
+const title = "Hello";
+export function App({ name  }) {
+    return /*#__PURE__*/ React.createElement("main", {
+        __source: {
+            fileName: "input.js",
+            lineNumber: 7,
+            columnNumber: 9
+        },
+        __self: this
+    }, /*#__PURE__*/ React.createElement("h1", {
+        className: "title",
+        __source: {
+            fileName: "input.js",
+            lineNumber: 8,
+            columnNumber: 13
+        },
+        __self: this
+    }, title), /*#__PURE__*/ React.createElement("p", {
+        __source: {
+            fileName: "input.js",
+            lineNumber: 9,
+            columnNumber: 13
+        },
+        __self: this
+    }, name));
+}
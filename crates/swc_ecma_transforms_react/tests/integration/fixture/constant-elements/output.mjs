@@ -0,0 +1,8 @@
+import React from "react";
+const title = "Hello";
+var _ref = /*#__PURE__*/ React.createElement("h1", {
+    className: "title"
+}, title);
+export function App({ name  }) {
+    return /*#__PURE__*/ React.createElement("main", null, _ref, /*#__PURE__*/ React.createElement("p", null, name));
+}
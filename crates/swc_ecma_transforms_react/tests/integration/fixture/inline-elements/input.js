@@ -0,0 +1,12 @@
+import React from "react";
+
+export function App({ name }) {
+    return (
+        <main key="app">
+            <h1 className="title">Hello</h1>
+            <p>{name}</p>
+            <input ref={ref} />
+            <Foo {...props} />
+        </main>
+    );
+}
@@ -0,0 +1,8 @@
+import React from "react";
+export function App({ name  }) {
+    return /*#__PURE__*/ _jsx("main", {}, "app", /*#__PURE__*/ _jsx("h1", {
+        className: "title"
+    }, void 0, "Hello"), /*#__PURE__*/ _jsx("p", {}, void 0, name), /*#__PURE__*/ React.createElement("input", {
+        ref: ref
+    }), /*#__PURE__*/ React.createElement(Foo, _extends({}, props)));
+}
@@ -0,0 +1,309 @@
+//! Inlining of `const enum`s declared in other modules.
+//!
+//! Callers collect the values of `const enum`s from other modules with
+//! [collect_const_enums], store them in a [ConstEnumTable] and pass the table
+//! to [inline_imported_const_enums]. Imports which are only used to access
+//! members of those enums are removed by [crate::strip()], like `tsc` does
+//! with `isolatedModules: false`.
+
+use swc_atoms::JsWord;
+use swc_common::{collections::AHashMap, Span, Spanned};
+use swc_ecma_ast::*;
+use swc_ecma_visit::{as_folder, noop_visit_mut_type, Fold, VisitMut, VisitMutWith};
+
+use crate::strip::{compute_enum_value, EnumValues};
+
+/// Values of `const enum`s exported by a module.
+///
+/// Keyed by the name of the enum, and then by the name of the member.
+pub type ConstEnumValues = AHashMap<JsWord, AHashMap<JsWord, Lit>>;
+
+/// Values of `const enum`s declared in other modules.
+#[derive(Debug, Clone, Default)]
+pub struct ConstEnumTable {
+    modules: AHashMap<JsWord, ConstEnumValues>,
+}
+
+impl ConstEnumTable {
+    /// Adds `enums` exported by the module imported as `src`.
+    ///
+    /// `src` should be the module specifier used in the file which will be
+    /// transformed by [inline_imported_const_enums].
+    pub fn insert(&mut self, src: JsWord, enums: ConstEnumValues) {
+        self.modules.entry(src).or_default().extend(enums);
+    }
+
+    pub fn get(&self, src: &JsWord) -> Option<&ConstEnumValues> {
+        self.modules.get(src)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.modules.is_empty()
+    }
+}
+
+/// Collects the values of `const enum`s exported by `module`.
+///
+/// Declaration files are supported, so `export declare const enum` is also
+/// collected. Members which cannot be computed statically are ignored.
+pub fn collect_const_enums(module: &Module) -> ConstEnumValues {
+    let mut decls = AHashMap::<Id, &TsEnumDecl>::default();
+    let mut exports = vec![];
+
+    for item in &module.body {
+        match item {
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                decl: Decl::TsEnum(e),
+                ..
+            })) if e.is_const => {
+                exports.push((e.id.sym.clone(), e.id.to_id()));
+                decls.insert(e.id.to_id(), e);
+            }
+            ModuleItem::Stmt(Stmt::Decl(Decl::TsEnum(e))) if e.is_const => {
+                decls.insert(e.id.to_id(), e);
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+                src: None,
+                specifiers,
+                ..
+            })) => {
+                for s in specifiers {
+                    if let ExportSpecifier::Named(ExportNamedSpecifier {
+                        orig: ModuleExportName::Ident(orig),
+                        exported,
+                        ..
+                    }) = s
+                    {
+                        let exported = match exported {
+                            Some(ModuleExportName::Ident(i)) => i.sym.clone(),
+                            Some(ModuleExportName::Str(s)) => s.value.clone(),
+                            None => orig.sym.clone(),
+                        };
+                        exports.push((exported, orig.to_id()));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut enums = ConstEnumValues::default();
+
+    for (exported, id) in exports {
+        if let Some(e) = decls.get(&id) {
+            let members = enum_member_values(e);
+            if !members.is_empty() {
+                enums.entry(exported).or_default().extend(members);
+            }
+        }
+    }
+
+    enums
+}
+
+fn enum_member_values(e: &TsEnumDecl) -> AHashMap<JsWord, Lit> {
+    let mut default = 0;
+    let mut values = EnumValues::default();
+    let mut members = AHashMap::default();
+
+    for m in &e.members {
+        let key = m.id.as_ref().clone();
+
+        match compute_enum_value(
+            e,
+            m.id.span(),
+            &mut values,
+            Some(default),
+            m.init.as_deref(),
+        ) {
+            Ok(value) => {
+                if let TsLit::Number(n) = &value {
+                    default = n.value as i64 + 1;
+                }
+                values.insert(key.clone(), Some(value.clone()));
+
+                if let Some(lit) = ts_lit_to_lit(value) {
+                    members.insert(key, lit);
+                }
+            }
+            Err(()) => {
+                values.insert(key, None);
+            }
+        }
+    }
+
+    members
+}
+
+fn ts_lit_to_lit(v: TsLit) -> Option<Lit> {
+    Some(match v {
+        TsLit::Number(v) => Lit::Num(v),
+        TsLit::Str(v) => Lit::Str(v),
+        TsLit::Bool(v) => Lit::Bool(v),
+        TsLit::BigInt(v) => Lit::BigInt(v),
+        TsLit::Tpl(..) => return None,
+    })
+}
+
+/// Inlines members of `const enum`s imported from modules in `table`.
+///
+/// This should be applied after the resolver and before [crate::strip()].
+pub fn inline_imported_const_enums(table: ConstEnumTable) -> impl Fold + VisitMut {
+    as_folder(InlineImportedConstEnums {
+        table,
+        enums: Default::default(),
+        namespaces: Default::default(),
+        is_lhs: false,
+    })
+}
+
+struct InlineImportedConstEnums {
+    table: ConstEnumTable,
+
+    /// `import { E } from "mod"`
+    enums: AHashMap<Id, AHashMap<JsWord, Lit>>,
+
+    /// `import * as ns from "mod"`
+    namespaces: AHashMap<Id, ConstEnumValues>,
+
+    is_lhs: bool,
+}
+
+impl InlineImportedConstEnums {
+    fn collect_imports(&mut self, m: &Module) {
+        for item in &m.body {
+            let import = match item {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => import,
+                _ => continue,
+            };
+
+            let enums = match self.table.get(&import.src.value) {
+                Some(v) => v,
+                None => continue,
+            };
+
+            for s in &import.specifiers {
+                match s {
+                    ImportSpecifier::Named(ImportNamedSpecifier {
+                        local, imported, ..
+                    }) => {
+                        let imported = match imported {
+                            Some(ModuleExportName::Ident(i)) => &i.sym,
+                            Some(ModuleExportName::Str(s)) => &s.value,
+                            None => &local.sym,
+                        };
+
+                        if let Some(members) = enums.get(imported) {
+                            self.enums.insert(local.to_id(), members.clone());
+                        }
+                    }
+                    ImportSpecifier::Namespace(ImportStarAsSpecifier { local, .. }) => {
+                        self.namespaces.insert(local.to_id(), enums.clone());
+                    }
+                    ImportSpecifier::Default(..) => {}
+                }
+            }
+        }
+    }
+
+    fn members_of(&self, obj: &Expr) -> Option<&AHashMap<JsWord, Lit>> {
+        match obj {
+            Expr::Ident(i) => self.enums.get(&i.to_id()),
+            Expr::Member(MemberExpr { obj, prop, .. }) => match &**obj {
+                Expr::Ident(ns) => self.namespaces.get(&ns.to_id())?.get(&member_key(prop)?),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+impl VisitMut for InlineImportedConstEnums {
+    noop_visit_mut_type!();
+
+    fn visit_mut_assign_expr(&mut self, a: &mut AssignExpr) {
+        let is_lhs = self.is_lhs;
+
+        self.is_lhs = true;
+        a.left.visit_mut_with(self);
+
+        self.is_lhs = false;
+        a.right.visit_mut_with(self);
+
+        self.is_lhs = is_lhs;
+    }
+
+    fn visit_mut_assign_pat(&mut self, a: &mut AssignPat) {
+        let is_lhs = self.is_lhs;
+
+        self.is_lhs = true;
+        a.left.visit_mut_with(self);
+
+        self.is_lhs = false;
+        a.right.visit_mut_with(self);
+
+        self.is_lhs = is_lhs;
+    }
+
+    fn visit_mut_expr(&mut self, e: &mut Expr) {
+        if !self.is_lhs {
+            if let Expr::Member(MemberExpr { span, obj, prop }) = e {
+                let lit = self
+                    .members_of(obj)
+                    .and_then(|members| members.get(&member_key(prop)?))
+                    .cloned();
+
+                if let Some(lit) = lit {
+                    *e = Expr::Lit(with_span(lit, *span));
+                    return;
+                }
+            }
+        }
+
+        e.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_module(&mut self, m: &mut Module) {
+        if self.table.is_empty() {
+            return;
+        }
+
+        self.collect_imports(m);
+
+        if self.enums.is_empty() && self.namespaces.is_empty() {
+            return;
+        }
+
+        m.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_update_expr(&mut self, e: &mut UpdateExpr) {
+        let is_lhs = self.is_lhs;
+
+        self.is_lhs = true;
+        e.arg.visit_mut_with(self);
+
+        self.is_lhs = is_lhs;
+    }
+}
+
+fn member_key(prop: &MemberProp) -> Option<JsWord> {
+    match prop {
+        MemberProp::Ident(i) => Some(i.sym.clone()),
+        MemberProp::Computed(ComputedPropName { expr, .. }) => match &**expr {
+            Expr::Lit(Lit::Str(s)) => Some(s.value.clone()),
+            _ => None,
+        },
+        MemberProp::PrivateName(..) => None,
+    }
+}
+
+fn with_span(lit: Lit, span: Span) -> Lit {
+    match lit {
+        Lit::Num(n) => Lit::Num(Number { span, ..n }),
+        Lit::Str(s) => Lit::Str(Str { span, ..s }),
+        Lit::Bool(b) => Lit::Bool(Bool { span, ..b }),
+        Lit::BigInt(b) => Lit::BigInt(BigInt { span, ..b }),
+        lit => lit,
+    }
+}
@@ -0,0 +1,285 @@
+//! Strips flow types.
+//!
+//! Flow code is parsed by the typescript parser of `swc_ecma_parser` into
+//! typescript nodes, so this pass removes those nodes like [crate::strip],
+//! but it keeps everything which has a runtime behavior as is. Imports are
+//! never elided and class fields are not moved. Typescript-only syntax like
+//! enums is rejected by the parser.
+
+use serde::{Deserialize, Serialize};
+use swc_atoms::js_word;
+use swc_common::util::take::Take;
+use swc_ecma_ast::*;
+use swc_ecma_visit::{as_folder, noop_visit_mut_type, Fold, VisitMut, VisitMutWith};
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct Config {
+    /// Keep class fields without a value, like `allowDeclareFields` of
+    /// `@babel/plugin-transform-flow-strip-types`. Defaults to `false`.
+    ///
+    /// By default, `x: T;` only declares the type of the field and it's
+    /// removed. If this is true, only fields with `declare` are removed and
+    /// the other ones are kept as `x;`, which defines the field.
+    #[serde(default)]
+    pub allow_declare_fields: bool,
+}
+
+/// Strips flow type annotations, type-only declarations and type-only
+/// imports and exports out.
+///
+/// Use this with [swc_ecma_parser::Syntax::Flow] instead of [crate::strip].
+pub fn strip_with_config(config: Config) -> impl Fold + VisitMut {
+    as_folder(Strip { config })
+}
+
+pub fn strip() -> impl Fold + VisitMut {
+    strip_with_config(Default::default())
+}
+
+struct Strip {
+    config: Config,
+}
+
+impl Strip {
+    /// Returns `true` if `decl` only exists in types.
+    fn is_type_only_decl(decl: &Decl) -> bool {
+        match decl {
+            Decl::TsInterface(..) | Decl::TsTypeAlias(..) => true,
+            Decl::Class(ClassDecl { declare, .. }) | Decl::Fn(FnDecl { declare, .. }) => *declare,
+            Decl::Var(v) => v.declare,
+            Decl::TsEnum(v) => v.declare,
+            Decl::TsModule(v) => v.declare,
+        }
+    }
+
+    fn is_type_only_module_item(item: &ModuleItem) -> bool {
+        match item {
+            ModuleItem::Stmt(Stmt::Decl(decl))
+            | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { decl, .. })) => {
+                Self::is_type_only_decl(decl)
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => {
+                // `import { type A } from 'a'` is removed, but `import {} from 'a'` is not.
+                import.type_only
+                    || (!import.specifiers.is_empty()
+                        && import.specifiers.iter().all(|s| {
+                            matches!(
+                                s,
+                                ImportSpecifier::Named(ImportNamedSpecifier {
+                                    is_type_only: true,
+                                    ..
+                                })
+                            )
+                        }))
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)) => {
+                export.type_only
+                    || (!export.specifiers.is_empty()
+                        && export.specifiers.iter().all(|s| {
+                            matches!(
+                                s,
+                                ExportSpecifier::Named(ExportNamedSpecifier {
+                                    is_type_only: true,
+                                    ..
+                                })
+                            )
+                        }))
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                decl: DefaultDecl::TsInterfaceDecl(..),
+                ..
+            })) => true,
+            _ => false,
+        }
+    }
+}
+
+impl VisitMut for Strip {
+    noop_visit_mut_type!();
+
+    fn visit_mut_array_pat(&mut self, n: &mut ArrayPat) {
+        n.visit_mut_children_with(self);
+
+        n.optional = false;
+        n.type_ann = None;
+    }
+
+    fn visit_mut_arrow_expr(&mut self, n: &mut ArrowExpr) {
+        n.visit_mut_children_with(self);
+
+        n.type_params = None;
+        n.return_type = None;
+    }
+
+    fn visit_mut_assign_pat(&mut self, n: &mut AssignPat) {
+        n.visit_mut_children_with(self);
+
+        n.type_ann = None;
+    }
+
+    fn visit_mut_binding_ident(&mut self, n: &mut BindingIdent) {
+        n.visit_mut_children_with(self);
+
+        n.id.optional = false;
+        n.type_ann = None;
+    }
+
+    fn visit_mut_call_expr(&mut self, n: &mut CallExpr) {
+        n.visit_mut_children_with(self);
+
+        n.type_args = None;
+    }
+
+    fn visit_mut_class(&mut self, n: &mut Class) {
+        n.visit_mut_children_with(self);
+
+        n.type_params = None;
+        n.super_type_params = None;
+        n.implements.clear();
+    }
+
+    fn visit_mut_class_members(&mut self, n: &mut Vec<ClassMember>) {
+        n.retain(|member| match member {
+            ClassMember::TsIndexSignature(..) => false,
+            ClassMember::ClassProp(ClassProp { declare: true, .. }) => false,
+            ClassMember::ClassProp(ClassProp {
+                value: None,
+                decorators,
+                ..
+            }) => !decorators.is_empty() || self.config.allow_declare_fields,
+            ClassMember::Method(ClassMethod { function, .. }) => function.body.is_some(),
+            _ => true,
+        });
+
+        n.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_class_prop(&mut self, n: &mut ClassProp) {
+        n.visit_mut_children_with(self);
+
+        n.type_ann = None;
+        n.readonly = false;
+        n.is_optional = false;
+        n.definite = false;
+    }
+
+    fn visit_mut_export_specifiers(&mut self, n: &mut Vec<ExportSpecifier>) {
+        n.retain(|s| {
+            !matches!(
+                s,
+                ExportSpecifier::Named(ExportNamedSpecifier {
+                    is_type_only: true,
+                    ..
+                })
+            )
+        });
+    }
+
+    fn visit_mut_expr(&mut self, n: &mut Expr) {
+        n.visit_mut_children_with(self);
+
+        // `(a: T)` is parsed as `a as T`
+        while let Expr::TsAs(TsAsExpr { expr, .. })
+        | Expr::TsTypeAssertion(TsTypeAssertion { expr, .. })
+        | Expr::TsNonNull(TsNonNullExpr { expr, .. })
+        | Expr::TsConstAssertion(TsConstAssertion { expr, .. }) = n
+        {
+            *n = *expr.take();
+        }
+    }
+
+    fn visit_mut_function(&mut self, n: &mut Function) {
+        n.visit_mut_children_with(self);
+
+        n.type_params = None;
+        n.return_type = None;
+    }
+
+    fn visit_mut_import_specifiers(&mut self, n: &mut Vec<ImportSpecifier>) {
+        n.retain(|s| {
+            !matches!(
+                s,
+                ImportSpecifier::Named(ImportNamedSpecifier {
+                    is_type_only: true,
+                    ..
+                })
+            )
+        });
+    }
+
+    fn visit_mut_jsx_opening_element(&mut self, n: &mut JSXOpeningElement) {
+        n.visit_mut_children_with(self);
+
+        n.type_args = None;
+    }
+
+    fn visit_mut_module_items(&mut self, n: &mut Vec<ModuleItem>) {
+        n.retain(|item| !Self::is_type_only_module_item(item));
+
+        n.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_new_expr(&mut self, n: &mut NewExpr) {
+        n.visit_mut_children_with(self);
+
+        n.type_args = None;
+    }
+
+    fn visit_mut_object_pat(&mut self, n: &mut ObjectPat) {
+        n.visit_mut_children_with(self);
+
+        n.optional = false;
+        n.type_ann = None;
+    }
+
+    fn visit_mut_opt_call(&mut self, n: &mut OptCall) {
+        n.visit_mut_children_with(self);
+
+        n.type_args = None;
+    }
+
+    fn visit_mut_params(&mut self, n: &mut Vec<Param>) {
+        // `function f(this: T) {}`
+        n.retain(|param| {
+            !matches!(
+                &param.pat,
+                Pat::Ident(BindingIdent {
+                    id: Ident {
+                        sym: js_word!("this"),
+                        ..
+                    },
+                    ..
+                })
+            )
+        });
+
+        n.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_private_prop(&mut self, n: &mut PrivateProp) {
+        n.visit_mut_children_with(self);
+
+        n.type_ann = None;
+        n.readonly = false;
+        n.is_optional = false;
+        n.definite = false;
+    }
+
+    fn visit_mut_rest_pat(&mut self, n: &mut RestPat) {
+        n.visit_mut_children_with(self);
+
+        n.type_ann = None;
+    }
+
+    fn visit_mut_stmts(&mut self, n: &mut Vec<Stmt>) {
+        n.retain(|stmt| !matches!(stmt, Stmt::Decl(decl) if Self::is_type_only_decl(decl)));
+
+        n.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_tagged_tpl(&mut self, n: &mut TaggedTpl) {
+        n.visit_mut_children_with(self);
+
+        n.type_params = None;
+    }
+}
@@ -1,7 +1,9 @@
 #![deny(clippy::all)]
 #![allow(clippy::vec_box)]
 
-pub use self::strip::*;
+pub use self::{const_enum::*, strip::*};
+pub mod const_enum;
+pub mod flow;
 mod import_export_assign;
 mod inline_enum;
 pub mod strip;
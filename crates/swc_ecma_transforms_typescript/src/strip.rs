@@ -27,7 +27,7 @@ use crate::{
 };
 
 /// Value does not contain TsLit::Bool
-type EnumValues = AHashMap<JsWord, Option<TsLit>>;
+pub(crate) type EnumValues = AHashMap<JsWord, Option<TsLit>>;
 
 #[derive(Debug, Serialize, Deserialize)]
 #[non_exhaustive]
@@ -242,6 +242,148 @@ where
     )
 }
 
+/// Called only for enums.
+///
+/// If both of the default value and the initialization is None, this
+/// method returns [Err].
+pub(crate) fn compute_enum_value(
+    e: &TsEnumDecl,
+    span: Span,
+    values: &mut EnumValues,
+    default: Option<i64>,
+    init: Option<&Expr>,
+) -> Result<TsLit, ()> {
+    fn compute_bin(
+        e: &TsEnumDecl,
+        span: Span,
+        values: &mut EnumValues,
+        expr: &BinExpr,
+    ) -> Result<TsLit, ()> {
+        let l = compute_enum_value(e, span, values, None, Some(&expr.left))?;
+        let r = compute_enum_value(e, span, values, None, Some(&expr.right))?;
+
+        Ok(match (l, r) {
+            (TsLit::Number(Number { value: l, .. }), TsLit::Number(Number { value: r, .. })) => {
+                TsLit::Number(Number {
+                    span,
+                    value: match expr.op {
+                        op!(bin, "+") => l + r,
+                        op!(bin, "-") => l - r,
+                        op!("*") => l * r,
+                        op!("/") => l / r,
+
+                        // TODO
+                        op!("&") => ((l.round() as i64) & (r.round() as i64)) as _,
+                        op!("|") => ((l.round() as i64) | (r.round() as i64)) as _,
+                        op!("^") => ((l.round() as i64) ^ (r.round() as i64)) as _,
+
+                        op!("<<") => ((l.round() as i64) << (r.round() as i64)) as _,
+                        op!(">>") => ((l.round() as i64) >> (r.round() as i64)) as _,
+                        // TODO: Verify this
+                        op!(">>>") => ((l.round() as u64) >> (r.round() as u64)) as _,
+                        _ => return Err(()),
+                    },
+                    raw: None,
+                })
+            }
+            (TsLit::Str(l), TsLit::Str(r)) if expr.op == op!(bin, "+") => {
+                let value = format!("{}{}", l.value, r.value);
+
+                TsLit::Str(Str {
+                    span,
+                    raw: None,
+                    value: value.into(),
+                })
+            }
+            (TsLit::Number(l), TsLit::Str(r)) if expr.op == op!(bin, "+") => {
+                let value = format!("{}{}", l.value, r.value);
+
+                TsLit::Str(Str {
+                    span,
+                    raw: None,
+                    value: value.into(),
+                })
+            }
+            (TsLit::Str(l), TsLit::Number(r)) if expr.op == op!(bin, "+") => {
+                let value = format!("{}{}", l.value, r.value);
+
+                TsLit::Str(Str {
+                    span,
+                    raw: None,
+                    value: value.into(),
+                })
+            }
+            _ => return Err(()),
+        })
+    }
+
+    if let Some(expr) = init {
+        match expr {
+            Expr::Lit(Lit::Str(s)) => return Ok(TsLit::Str(s.clone())),
+            Expr::Lit(Lit::Num(s)) => return Ok(TsLit::Number(s.clone())),
+            Expr::Bin(ref bin) => return compute_bin(e, span, values, bin),
+            Expr::Paren(ref paren) => {
+                return compute_enum_value(e, span, values, default, Some(&paren.expr))
+            }
+
+            Expr::Ident(ref id) => {
+                if let Some(Some(v)) = values.get(&id.sym) {
+                    return Ok(v.clone());
+                }
+                return Err(());
+            }
+            Expr::Unary(ref expr) => {
+                let v = compute_enum_value(e, span, values, None, Some(&expr.arg))?;
+                match v {
+                    TsLit::BigInt(BigInt { .. }) => {}
+                    TsLit::Number(Number { value: v, .. }) => {
+                        return Ok(TsLit::Number(Number {
+                            span,
+                            value: match expr.op {
+                                op!(unary, "+") => v,
+                                op!(unary, "-") => -v,
+                                op!("!") => {
+                                    if v == 0.0f64 {
+                                        0.0
+                                    } else {
+                                        1.0
+                                    }
+                                }
+                                op!("~") => (!(v as i32)) as f64,
+                                _ => return Err(()),
+                            },
+                            raw: None,
+                        }))
+                    }
+                    TsLit::Str(_) => {}
+                    TsLit::Bool(_) => {}
+                    TsLit::Tpl(_) => {}
+                }
+            }
+
+            Expr::Tpl(ref t) if t.exprs.is_empty() => {
+                if let Some(v) = &t.quasis[0].cooked {
+                    return Ok(TsLit::Str(Str {
+                        span,
+                        raw: None,
+                        value: JsWord::from(&**v),
+                    }));
+                }
+            }
+
+            _ => {}
+        }
+    } else if let Some(value) = default {
+        return Ok(TsLit::Number(Number {
+            span,
+            value: value as _,
+            raw: None,
+        }));
+    }
+
+    Err(())
+}
+
 /// Get an [Id] which will used by expression.
 ///
 /// For `React#1.createElement`, this returns `React#1`.
@@ -503,151 +645,6 @@ where
         e: Box<TsEnumDecl>,
         module_name: Option<&Ident>,
     ) -> (Option<Decl>, Stmt) {
-        /// Called only for enums.
-        ///
-        /// If both of the default value and the initialization is None, this
-        /// method returns [Err].
-        fn compute(
-            e: &TsEnumDecl,
-            span: Span,
-            values: &mut EnumValues,
-            default: Option<i64>,
-            init: Option<&Expr>,
-        ) -> Result<TsLit, ()> {
-            fn compute_bin(
-                e: &TsEnumDecl,
-                span: Span,
-                values: &mut EnumValues,
-                expr: &BinExpr,
-            ) -> Result<TsLit, ()> {
-                let l = compute(e, span, values, None, Some(&expr.left))?;
-                let r = compute(e, span, values, None, Some(&expr.right))?;
-
-                Ok(match (l, r) {
-                    (
-                        TsLit::Number(Number { value: l, .. }),
-                        TsLit::Number(Number { value: r, .. }),
-                    ) => {
-                        TsLit::Number(Number {
-                            span,
-                            value: match expr.op {
-                                op!(bin, "+") => l + r,
-                                op!(bin, "-") => l - r,
-                                op!("*") => l * r,
-                                op!("/") => l / r,
-
-                                // TODO
-                                op!("&") => ((l.round() as i64) & (r.round() as i64)) as _,
-                                op!("|") => ((l.round() as i64) | (r.round() as i64)) as _,
-                                op!("^") => ((l.round() as i64) ^ (r.round() as i64)) as _,
-
-                                op!("<<") => ((l.round() as i64) << (r.round() as i64)) as _,
-                                op!(">>") => ((l.round() as i64) >> (r.round() as i64)) as _,
-                                // TODO: Verify this
-                                op!(">>>") => ((l.round() as u64) >> (r.round() as u64)) as _,
-                                _ => return Err(()),
-                            },
-                            raw: None,
-                        })
-                    }
-                    (TsLit::Str(l), TsLit::Str(r)) if expr.op == op!(bin, "+") => {
-                        let value = format!("{}{}", l.value, r.value);
-
-                        TsLit::Str(Str {
-                            span,
-                            raw: None,
-                            value: value.into(),
-                        })
-                    }
-                    (TsLit::Number(l), TsLit::Str(r)) if expr.op == op!(bin, "+") => {
-                        let value = format!("{}{}", l.value, r.value);
-
-                        TsLit::Str(Str {
-                            span,
-                            raw: None,
-                            value: value.into(),
-                        })
-                    }
-                    (TsLit::Str(l), TsLit::Number(r)) if expr.op == op!(bin, "+") => {
-                        let value = format!("{}{}", l.value, r.value);
-
-                        TsLit::Str(Str {
-                            span,
-                            raw: None,
-                            value: value.into(),
-                        })
-                    }
-                    _ => return Err(()),
-                })
-            }
-
-            if let Some(expr) = init {
-                match expr {
-                    Expr::Lit(Lit::Str(s)) => return Ok(TsLit::Str(s.clone())),
-                    Expr::Lit(Lit::Num(s)) => return Ok(TsLit::Number(s.clone())),
-                    Expr::Bin(ref bin) => return compute_bin(e, span, values, bin),
-                    Expr::Paren(ref paren) => {
-                        return compute(e, span, values, default, Some(&paren.expr))
-                    }
-
-                    Expr::Ident(ref id) => {
-                        if let Some(Some(v)) = values.get(&id.sym) {
-                            return Ok(v.clone());
-                        }
-                        return Err(());
-                    }
-                    Expr::Unary(ref expr) => {
-                        let v = compute(e, span, values, None, Some(&expr.arg))?;
-                        match v {
-                            TsLit::BigInt(BigInt { .. }) => {}
-                            TsLit::Number(Number { value: v, .. }) => {
-                                return Ok(TsLit::Number(Number {
-                                    span,
-                                    value: match expr.op {
-                                        op!(unary, "+") => v,
-                                        op!(unary, "-") => -v,
-                                        op!("!") => {
-                                            if v == 0.0f64 {
-                                                0.0
-                                            } else {
-                                                1.0
-                                            }
-                                        }
-                                        op!("~") => (!(v as i32)) as f64,
-                                        _ => return Err(()),
-                                    },
-                                    raw: None,
-                                }))
-                            }
-                            TsLit::Str(_) => {}
-                            TsLit::Bool(_) => {}
-                            TsLit::Tpl(_) => {}
-                        }
-                    }
-
-                    Expr::Tpl(ref t) if t.exprs.is_empty() => {
-                        if let Some(v) = &t.quasis[0].cooked {
-                            return Ok(TsLit::Str(Str {
-                                span,
-                                raw: None,
-                                value: JsWord::from(&**v),
-                            }));
-                        }
-                    }
-
-                    _ => {}
-                }
-            } else if let Some(value) = default {
-                return Ok(TsLit::Number(Number {
-                    span,
-                    value: value as _,
-                    raw: None,
-                }));
-            }
-
-            Err(())
-        }
-
         // let Foo;
         // (function (Foo) {
         //     Foo[Foo["a"] = 0] = "a";
@@ -677,44 +674,45 @@ where
             .into_iter()
             .map(|m| -> Result<_, ()> {
                 let id_span = m.id.span();
-                let val = compute(&e, id_span, &mut values, Some(default), m.init.as_deref())
-                    .map(|val| {
-                        if let TsLit::Number(ref n) = val {
-                            default = n.value as i64 + 1;
-                        }
-                        values.insert(m.id.as_ref().clone(), Some(val.clone()));
-
-                        match val {
-                            TsLit::Number(v) => Expr::Lit(Lit::Num(v)),
-                            TsLit::Str(v) => Expr::Lit(Lit::Str(v)),
-                            TsLit::Bool(v) => Expr::Lit(Lit::Bool(v)),
-                            TsLit::Tpl(v) => {
-                                let value = v.quasis.into_iter().next().unwrap().raw;
-
-                                Expr::Lit(Lit::Str(Str {
-                                    span: v.span,
-                                    raw: None,
-                                    value: JsWord::from(&*value),
-                                }))
+                let val =
+                    compute_enum_value(&e, id_span, &mut values, Some(default), m.init.as_deref())
+                        .map(|val| {
+                            if let TsLit::Number(ref n) = val {
+                                default = n.value as i64 + 1;
                             }
-                            TsLit::BigInt(v) => Expr::Lit(Lit::BigInt(v)),
-                        }
-                    })
-                    .or_else(|err| match &m.init {
-                        None => Err(err),
-                        Some(v) => {
-                            let mut v = *v.clone();
-                            let mut visitor = EnumValuesVisitor {
-                                previous: &values,
-                                ident: &id,
-                            };
-                            visitor.visit_mut_expr(&mut v);
+                            values.insert(m.id.as_ref().clone(), Some(val.clone()));
+
+                            match val {
+                                TsLit::Number(v) => Expr::Lit(Lit::Num(v)),
+                                TsLit::Str(v) => Expr::Lit(Lit::Str(v)),
+                                TsLit::Bool(v) => Expr::Lit(Lit::Bool(v)),
+                                TsLit::Tpl(v) => {
+                                    let value = v.quasis.into_iter().next().unwrap().raw;
+
+                                    Expr::Lit(Lit::Str(Str {
+                                        span: v.span,
+                                        raw: None,
+                                        value: JsWord::from(&*value),
+                                    }))
+                                }
+                                TsLit::BigInt(v) => Expr::Lit(Lit::BigInt(v)),
+                            }
+                        })
+                        .or_else(|err| match &m.init {
+                            None => Err(err),
+                            Some(v) => {
+                                let mut v = *v.clone();
+                                let mut visitor = EnumValuesVisitor {
+                                    previous: &values,
+                                    ident: &id,
+                                };
+                                visitor.visit_mut_expr(&mut v);
 
-                            values.insert(m.id.as_ref().clone(), None);
+                                values.insert(m.id.as_ref().clone(), None);
 
-                            Ok(v)
-                        }
-                    })?;
+                                Ok(v)
+                            }
+                        })?;
 
                 Ok((m, val))
             })
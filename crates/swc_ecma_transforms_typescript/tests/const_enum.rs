@@ -0,0 +1,146 @@
+use swc_common::{chain, sync::Lrc, FileName, Mark, SourceMap};
+use swc_ecma_ast::EsVersion;
+use swc_ecma_parser::{parse_file_as_module, Syntax, TsConfig};
+use swc_ecma_transforms_base::resolver;
+use swc_ecma_transforms_testing::test;
+use swc_ecma_transforms_typescript::{
+    collect_const_enums, inline_imported_const_enums, strip, ConstEnumTable,
+};
+use swc_ecma_visit::Fold;
+
+const ENUMS: &str = r#"
+export declare const enum Direction {
+    Up = 1,
+    Down,
+}
+declare const enum Color {
+    Red = "RED",
+    Green = "GREEN",
+}
+export { Color, Color as Colour };
+export declare const enum Flags {
+    A = 1 << 0,
+    B = 1 << 1,
+    AB = A | B,
+}
+export declare enum NotConst {
+    A,
+}
+"#;
+
+fn table() -> ConstEnumTable {
+    let cm = Lrc::new(SourceMap::default());
+    let fm = cm.new_source_file(FileName::Anon, ENUMS.into());
+    let module = parse_file_as_module(
+        &fm,
+        Syntax::Typescript(TsConfig {
+            dts: true,
+            ..Default::default()
+        }),
+        EsVersion::latest(),
+        None,
+        &mut vec![],
+    )
+    .unwrap();
+
+    let mut table = ConstEnumTable::default();
+    table.insert("./enums".into(), collect_const_enums(&module));
+    table
+}
+
+fn tr() -> impl Fold {
+    let unresolved_mark = Mark::new();
+    let top_level_mark = Mark::new();
+
+    chain!(
+        resolver(unresolved_mark, top_level_mark, true),
+        inline_imported_const_enums(table()),
+        strip(top_level_mark)
+    )
+}
+
+#[test]
+fn collect() {
+    let table = table();
+    let enums = table.get(&"./enums".into()).unwrap();
+
+    let mut names = enums.keys().map(|v| &**v).collect::<Vec<_>>();
+    names.sort_unstable();
+    assert_eq!(names, vec!["Color", "Colour", "Direction", "Flags"]);
+    assert_eq!(enums[&"Direction".into()].len(), 2);
+    assert_eq!(enums[&"Flags".into()].len(), 3);
+}
+
+test!(
+    Syntax::Typescript(Default::default()),
+    |_| tr(),
+    named_import,
+    r#"
+import { Direction, Colour as C } from "./enums";
+console.log(Direction.Up, Direction.Down, C["Red"]);
+"#,
+    r#"
+console.log(1, 2, "RED");
+export { };
+"#
+);
+
+test!(
+    Syntax::Typescript(Default::default()),
+    |_| tr(),
+    namespace_import,
+    r#"
+import * as enums from "./enums";
+console.log(enums.Flags.AB, enums.Color.Green);
+"#,
+    r#"
+console.log(3, "GREEN");
+export { };
+"#
+);
+
+test!(
+    Syntax::Typescript(Default::default()),
+    |_| tr(),
+    import_used_as_value,
+    r#"
+import { Direction, NotConst } from "./enums";
+console.log(Direction.Up, NotConst.A, Direction);
+"#,
+    r#"
+import { Direction, NotConst } from "./enums";
+console.log(1, NotConst.A, Direction);
+"#
+);
+
+test!(
+    Syntax::Typescript(Default::default()),
+    |_| tr(),
+    other_module,
+    r#"
+import { Direction } from "./other";
+console.log(Direction.Up);
+"#,
+    r#"
+import { Direction } from "./other";
+console.log(Direction.Up);
+"#
+);
+
+test!(
+    Syntax::Typescript(Default::default()),
+    |_| tr(),
+    shadowed,
+    r#"
+import { Direction } from "./enums";
+function f(Direction) {
+    return Direction.Up;
+}
+"#,
+    r#"
+function f(Direction) {
+    return Direction.Up;
+}
+export { };
+"#
+);
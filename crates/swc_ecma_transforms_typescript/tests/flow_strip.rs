@@ -0,0 +1,165 @@
+use swc_ecma_parser::{FlowConfig, Syntax};
+use swc_ecma_transforms_testing::test;
+use swc_ecma_transforms_typescript::flow::{strip, strip_with_config, Config};
+
+fn syntax() -> Syntax {
+    Syntax::Flow(FlowConfig { jsx: true })
+}
+
+macro_rules! to {
+    ($name:ident, $from:expr, $to:expr) => {
+        test!(syntax(), |_| strip(), $name, $from, $to, ok_if_code_eq);
+    };
+}
+
+to!(
+    annotations,
+    "
+function f<T>(x: ?T, cb: (T) => void = () => {}, ...rest: Array<mixed>): Promise<T[]> {}
+const g = async (x: number, y?: string): ?number => x;
+let a: { +a: number, ...B } = {};
+",
+    "
+function f(x, cb = () => {}, ...rest) {}
+const g = async (x, y) => x;
+let a = {};
+"
+);
+
+to!(
+    type_cast,
+    "
+const a = (x: any);
+foo((bar: Baz), ((qux: any): string));
+",
+    "
+const a = x;
+foo(bar, qux);
+"
+);
+
+to!(
+    type_decls,
+    "
+type A = string;
+opaque type B: string = string;
+interface C { +x: number }
+export type D = A;
+export opaque type E = number;
+export interface F {}
+const x = 1;
+",
+    "
+const x = 1;
+"
+);
+
+to!(
+    declare,
+    "
+declare var x: number;
+declare function f(): void;
+declare class C {}
+declare module 'mod' {
+  declare module.exports: number;
+}
+declare export default string;
+declare module.exports: { a: number };
+declare opaque type T;
+f(x);
+",
+    "
+f(x);
+"
+);
+
+to!(
+    imports_and_exports,
+    "
+import type A from 'a';
+import typeof B from 'b';
+import { type C, typeof D } from 'c';
+import { type E, F } from 'e';
+import {} from 'side-effect';
+export type { A };
+export { type C, F };
+",
+    "
+import { F } from 'e';
+import {} from 'side-effect';
+export { F };
+"
+);
+
+to!(
+    class,
+    "
+class A<+T> extends B<T> implements I {
+  +x: T;
+  -y: number = 1;
+  static z: ?string;
+  declare w: string;
+  m<U>(this: A<T>, u: U): this {
+    return this;
+  }
+}
+",
+    "
+class A extends B {
+  y = 1;
+  m(u) {
+    return this;
+  }
+}
+"
+);
+
+test!(
+    syntax(),
+    |_| strip_with_config(Config {
+        allow_declare_fields: true,
+    }),
+    class_allow_declare_fields,
+    "
+class A {
+  +x: T;
+  -y: number = 1;
+  static z: ?string;
+  declare w: string;
+  #p: number;
+}
+",
+    "
+class A {
+  x;
+  y = 1;
+  static z;
+  #p;
+}
+",
+    ok_if_code_eq
+);
+
+to!(
+    predicate,
+    "
+function isString(x: mixed): boolean %checks {
+  return typeof x === 'string';
+}
+",
+    "
+function isString(x) {
+  return typeof x === 'string';
+}
+"
+);
+
+to!(
+    jsx,
+    "
+const el = <Foo bar={(baz: any)} />;
+",
+    "
+const el = <Foo bar={baz} />;
+"
+);
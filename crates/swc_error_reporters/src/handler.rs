@@ -10,7 +10,7 @@ use swc_common::{
     SourceMap,
 };
 
-use crate::{PrettyEmitter, PrettyEmitterConfig};
+use crate::{lsp::to_lsp, sarif::to_sarif, CollectingEmitter, PrettyEmitter, PrettyEmitterConfig};
 
 #[derive(Clone, Default)]
 struct LockedWriter(Arc<Mutex<Vec<u8>>>);
@@ -45,6 +45,9 @@ pub struct HandlerOpts {
 
     /// Defaults to `false`.
     pub skip_filename: bool,
+
+    /// Defaults to [DiagnosticFormat::Pretty].
+    pub format: DiagnosticFormat,
 }
 
 impl Default for HandlerOpts {
@@ -52,10 +55,28 @@ impl Default for HandlerOpts {
         Self {
             color: ColorConfig::Auto,
             skip_filename: false,
+            format: Default::default(),
         }
     }
 }
 
+/// The format of the errors returned by [try_with_handler].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticFormat {
+    /// Human-readable reports with code snippets.
+    Pretty,
+    /// A SARIF 2.1.0 log, see [crate::sarif].
+    Sarif,
+    /// A JSON array of LSP `PublishDiagnosticsParams`, see [crate::lsp].
+    Lsp,
+}
+
+impl Default for DiagnosticFormat {
+    fn default() -> Self {
+        Self::Pretty
+    }
+}
+
 fn to_miette_reporter(color: ColorConfig) -> GraphicalReportHandler {
     match color {
         ColorConfig::Auto => {
@@ -80,6 +101,10 @@ fn to_miette_reporter(color: ColorConfig) -> GraphicalReportHandler {
 
 /// Try operation with a [Handler] and prints the errors as a [String] wrapped
 /// by [Err].
+///
+/// With [DiagnosticFormat::Sarif] and [DiagnosticFormat::Lsp], the message of
+/// the error is a JSON document, and an error returned by `op` without
+/// emitting a diagnostic is reported as a diagnostic without a location.
 pub fn try_with_handler<F, Ret>(
     cm: Lrc<SourceMap>,
     config: HandlerOpts,
@@ -88,6 +113,10 @@ pub fn try_with_handler<F, Ret>(
 where
     F: FnOnce(&Handler) -> Result<Ret, Error>,
 {
+    if config.format != DiagnosticFormat::Pretty {
+        return try_with_structured_handler(cm, config.format, op);
+    }
+
     let wr = Box::new(LockedWriter::default());
 
     let emitter = PrettyEmitter::new(
@@ -118,3 +147,37 @@ where
         ret
     }
 }
+
+fn try_with_structured_handler<F, Ret>(
+    cm: Lrc<SourceMap>,
+    format: DiagnosticFormat,
+    op: F,
+) -> Result<Ret, Error>
+where
+    F: FnOnce(&Handler) -> Result<Ret, Error>,
+{
+    let emitter = CollectingEmitter::default();
+    let handler = Handler::with_emitter(true, false, Box::new(emitter.clone()));
+
+    let ret = HANDLER.set(&handler, || op(&handler));
+
+    if let Err(err) = &ret {
+        if !handler.has_errors() {
+            handler.err(&format!("{:?}", err));
+        }
+    }
+
+    if handler.has_errors() {
+        let diagnostics = emitter.take();
+        let msg = match format {
+            DiagnosticFormat::Sarif => to_sarif(&cm, &diagnostics),
+            DiagnosticFormat::Lsp => to_lsp(&cm, &diagnostics),
+            DiagnosticFormat::Pretty => unreachable!(),
+        };
+
+        // The causes are not appended, so the error is a valid JSON document
+        Err(anyhow::anyhow!(msg))
+    } else {
+        ret
+    }
+}
@@ -1,19 +1,25 @@
 use std::{
     fmt::{self, Write},
     intrinsics::transmute,
+    mem::take,
+    sync::Arc,
 };
 
 pub use miette::{GraphicalReportHandler, GraphicalTheme};
 use miette::{
     LabeledSpan, MietteError, Severity, SourceCode, SourceOffset, SourceSpan, SpanContents,
 };
+use parking_lot::Mutex;
 use swc_common::{
-    errors::{DiagnosticBuilder, DiagnosticId, Emitter, Level, SubDiagnostic},
+    errors::{Diagnostic, DiagnosticBuilder, Emitter, Level, SubDiagnostic},
     sync::Lrc,
     BytePos, FileName, SourceMap, Span,
 };
 
 pub mod handler;
+mod location;
+pub mod lsp;
+pub mod sarif;
 
 pub struct PrettyEmitter {
     cm: Lrc<SourceMap>,
@@ -46,6 +52,24 @@ impl PrettyEmitter {
     }
 }
 
+/// Collects diagnostics, to render them at once with [sarif::to_sarif] or
+/// [lsp::to_lsp].
+#[derive(Clone, Default)]
+pub struct CollectingEmitter(Arc<Mutex<Vec<Diagnostic>>>);
+
+impl CollectingEmitter {
+    /// Returns the diagnostics emitted so far.
+    pub fn take(&self) -> Vec<Diagnostic> {
+        take(&mut *self.0.lock())
+    }
+}
+
+impl Emitter for CollectingEmitter {
+    fn emit(&mut self, db: &DiagnosticBuilder) {
+        self.0.lock().push((**db).clone());
+    }
+}
+
 struct WriterWrapper(Box<dyn Write + Send + Sync>);
 
 impl Write for WriterWrapper {
@@ -189,15 +213,10 @@ struct MietteDiagnostic<'a> {
 }
 
 impl miette::Diagnostic for MietteDiagnostic<'_> {
+    /// Codes are meant for tools and are reported by the structured formats
+    /// (see [handler::DiagnosticFormat]), so they are not rendered here.
     fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
-        self.d
-            .code
-            .as_ref()
-            .map(|v| match v {
-                DiagnosticId::Error(v) => v,
-                DiagnosticId::Lint(v) => v,
-            })
-            .map(|code| Box::new(code) as Box<dyn fmt::Display>)
+        None
     }
 
     fn severity(&self) -> Option<Severity> {
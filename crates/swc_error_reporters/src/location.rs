@@ -0,0 +1,150 @@
+use std::{env, fmt::Write, path::Path};
+
+use swc_common::{
+    errors::{Applicability, Diagnostic, Level, SubDiagnostic},
+    FileName, SourceMap, Span,
+};
+
+/// A location in a file, lines and columns are zero-based and columns are
+/// counted in UTF-16 code units.
+pub(crate) struct Range {
+    /// [None] if the file has no URI, i.e. [FileName::Anon].
+    pub uri: Option<String>,
+    pub start: (usize, usize),
+    pub end: (usize, usize),
+}
+
+pub(crate) fn lookup_range(cm: &SourceMap, span: Span) -> Option<Range> {
+    if span.is_dummy() || span.lo > span.hi {
+        return None;
+    }
+
+    // Spans of removed files are ignored
+    let file = cm.try_lookup_source_file(span.lo).ok()?;
+
+    if span.hi > file.end_pos {
+        return None;
+    }
+
+    let uri = file_uri(&file.name);
+    let start = cm.lookup_char_pos_with(file.clone(), span.lo);
+    let end = cm.lookup_char_pos_with(file, span.hi);
+
+    Some(Range {
+        uri,
+        start: (start.line.saturating_sub(1), start.col.0),
+        end: (end.line.saturating_sub(1), end.col.0),
+    })
+}
+
+fn file_uri(name: &FileName) -> Option<String> {
+    match name {
+        FileName::Real(path) => {
+            if path.is_absolute() {
+                return Some(format!("file://{}", encode_path(path)));
+            }
+
+            // `current_dir` is not available in wasm, relative references are used
+            // instead
+            match env::current_dir() {
+                Ok(dir) => Some(format!("file://{}", encode_path(&dir.join(path)))),
+                Err(..) => Some(encode_path(path).trim_start_matches('/').to_string()),
+            }
+        }
+        FileName::Url(url) => Some(url.to_string()),
+        FileName::Custom(name) => Some(name.clone()),
+        _ => None,
+    }
+}
+
+/// Percent-encodes `path` with `/` as the separator and a leading `/`, i.e.
+/// `C:\a b` is `/C:/a%20b`.
+fn encode_path(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let mut encoded = String::with_capacity(path.len() + 1);
+
+    if !path.starts_with('/') {
+        encoded.push('/');
+    }
+
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => {
+                encoded.push(byte as char)
+            }
+            _ => {
+                let _ = write!(encoded, "%{:02X}", byte);
+            }
+        }
+    }
+
+    encoded
+}
+
+/// Appends the children without a location to `message`, i.e. `help: ...`.
+pub(crate) fn message_with_notes(message: String, children: &[SubDiagnostic]) -> String {
+    children
+        .iter()
+        .filter(|child| child.span.primary_span().is_none())
+        .fold(message, |mut message, child| {
+            if child.level == Level::FailureNote {
+                let _ = write!(message, "\n{}", child.message());
+            } else {
+                let _ = write!(message, "\n{}: {}", child.level, child.message());
+            }
+
+            message
+        })
+}
+
+/// A substitution of a suggestion, i.e. one way to fix a diagnostic.
+pub(crate) struct Fix {
+    pub title: String,
+    /// `true` if the fix can be applied without a review.
+    pub preferred: bool,
+    /// Replacements grouped by the URI of the file.
+    pub changes: Vec<(String, Vec<(Range, String)>)>,
+}
+
+/// Suggestions with placeholders, and substitutions touching a file without a
+/// URI, are skipped.
+pub(crate) fn fixes(cm: &SourceMap, d: &Diagnostic) -> Vec<Fix> {
+    let mut fixes = vec![];
+
+    for suggestion in &d.suggestions {
+        if suggestion.applicability == Applicability::HasPlaceholders {
+            continue;
+        }
+
+        'substitution: for substitution in &suggestion.substitutions {
+            let mut changes: Vec<(String, Vec<(Range, String)>)> = vec![];
+
+            for part in &substitution.parts {
+                let mut range = match lookup_range(cm, part.span) {
+                    Some(range) => range,
+                    None => continue 'substitution,
+                };
+                let uri = match range.uri.take() {
+                    Some(uri) => uri,
+                    None => continue 'substitution,
+                };
+                let replacement = (range, part.snippet.clone());
+
+                match changes.iter_mut().find(|(u, _)| *u == uri) {
+                    Some((_, replacements)) => replacements.push(replacement),
+                    None => changes.push((uri, vec![replacement])),
+                }
+            }
+
+            if !changes.is_empty() {
+                fixes.push(Fix {
+                    title: suggestion.msg.clone(),
+                    preferred: suggestion.applicability == Applicability::MachineApplicable,
+                    changes,
+                });
+            }
+        }
+    }
+
+    fixes
+}
@@ -0,0 +1,222 @@
+//! Output shaped like the `textDocument/publishDiagnostics` notifications of
+//! the [Language Server Protocol](https://microsoft.github.io/language-server-protocol/specifications/specification-current/#textDocument_publishDiagnostics).
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use swc_common::{
+    errors::{Diagnostic, DiagnosticId, Level},
+    SourceMap,
+};
+
+use crate::location::{fixes, lookup_range, message_with_notes, Range};
+
+#[derive(Serialize)]
+struct PublishDiagnosticsParams {
+    uri: String,
+    diagnostics: Vec<LspDiagnostic>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LspDiagnostic {
+    range: LspRange,
+    severity: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<String>,
+    source: &'static str,
+    message: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    related_information: Vec<RelatedInformation>,
+    /// Quick fixes as `CodeAction`s, which can be returned as is for a
+    /// `textDocument/codeAction` request.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    data: Vec<CodeAction>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CodeAction {
+    title: String,
+    kind: &'static str,
+    is_preferred: bool,
+    edit: WorkspaceEdit,
+}
+
+#[derive(Serialize)]
+struct WorkspaceEdit {
+    changes: BTreeMap<String, Vec<TextEdit>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TextEdit {
+    range: LspRange,
+    new_text: String,
+}
+
+#[derive(Serialize)]
+struct RelatedInformation {
+    location: Location,
+    message: String,
+}
+
+#[derive(Serialize)]
+struct Location {
+    uri: String,
+    range: LspRange,
+}
+
+#[derive(Serialize, Default)]
+struct LspRange {
+    start: Position,
+    end: Position,
+}
+
+/// Lines and characters are zero-based, characters are counted in UTF-16
+/// code units.
+#[derive(Serialize, Default)]
+struct Position {
+    line: usize,
+    character: usize,
+}
+
+impl From<&Range> for LspRange {
+    fn from(range: &Range) -> Self {
+        LspRange {
+            start: Position {
+                line: range.start.0,
+                character: range.start.1,
+            },
+            end: Position {
+                line: range.end.0,
+                character: range.end.1,
+            },
+        }
+    }
+}
+
+fn severity(level: Level) -> u8 {
+    match level {
+        Level::Bug
+        | Level::Fatal
+        | Level::PhaseFatal
+        | Level::Error
+        | Level::FailureNote
+        | Level::Cancelled => 1,
+        Level::Warning => 2,
+        Level::Note => 3,
+        Level::Help => 4,
+    }
+}
+
+fn related_information(range: Option<Range>, message: String) -> Option<RelatedInformation> {
+    let range = range?;
+
+    Some(RelatedInformation {
+        location: Location {
+            range: LspRange::from(&range),
+            uri: range.uri?,
+        },
+        message,
+    })
+}
+
+/// Renders `diagnostics` as a JSON array of `PublishDiagnosticsParams`, one for
+/// each file in the order of their first diagnostic.
+///
+/// Diagnostics without a location, or in a file without a URI like
+/// [swc_common::FileName::Anon], are reported with an empty URI and range.
+pub fn to_lsp(cm: &SourceMap, diagnostics: &[Diagnostic]) -> String {
+    let mut params: Vec<PublishDiagnosticsParams> = vec![];
+
+    for d in diagnostics.iter().filter(|d| d.level != Level::Cancelled) {
+        let range = d
+            .span
+            .primary_span()
+            .and_then(|span| lookup_range(cm, span));
+        let mut related = vec![];
+
+        for label in d.span.span_labels() {
+            if label.is_primary && Some(label.span) == d.span.primary_span() {
+                continue;
+            }
+
+            if let Some(message) = label.label {
+                related.extend(related_information(lookup_range(cm, label.span), message));
+            }
+        }
+
+        for child in &d.children {
+            if let Some(span) = child.span.primary_span() {
+                related.extend(related_information(
+                    lookup_range(cm, span),
+                    format!("{}: {}", child.level, child.message()),
+                ));
+            }
+        }
+
+        let mut message = d.message();
+
+        // The label of the primary span is not rendered otherwise
+        if let Some(label) = d
+            .span
+            .span_labels()
+            .into_iter()
+            .find(|label| label.is_primary)
+            .and_then(|label| label.label)
+        {
+            if label != message {
+                message = format!("{}: {}", message, label);
+            }
+        }
+
+        let diagnostic = LspDiagnostic {
+            range: range.as_ref().map(LspRange::from).unwrap_or_default(),
+            severity: severity(d.level),
+            code: d.code.as_ref().map(|code| match code {
+                DiagnosticId::Error(code) => code.clone(),
+                DiagnosticId::Lint(code) => code.clone(),
+            }),
+            source: "swc",
+            message: message_with_notes(message, &d.children),
+            related_information: related,
+            data: fixes(cm, d)
+                .into_iter()
+                .map(|fix| CodeAction {
+                    title: fix.title,
+                    kind: "quickfix",
+                    is_preferred: fix.preferred,
+                    edit: WorkspaceEdit {
+                        changes: fix
+                            .changes
+                            .into_iter()
+                            .map(|(uri, replacements)| {
+                                let edits = replacements
+                                    .into_iter()
+                                    .map(|(range, new_text)| TextEdit {
+                                        range: LspRange::from(&range),
+                                        new_text,
+                                    })
+                                    .collect();
+
+                                (uri, edits)
+                            })
+                            .collect(),
+                    },
+                })
+                .collect(),
+        };
+        let uri = range.and_then(|range| range.uri).unwrap_or_default();
+
+        match params.iter_mut().find(|params| params.uri == uri) {
+            Some(params) => params.diagnostics.push(diagnostic),
+            None => params.push(PublishDiagnosticsParams {
+                uri,
+                diagnostics: vec![diagnostic],
+            }),
+        }
+    }
+
+    serde_json::to_string(&params).expect("failed to serialize diagnostics")
+}
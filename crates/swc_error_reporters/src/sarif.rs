@@ -0,0 +1,251 @@
+//! [SARIF 2.1.0](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html)
+//! output, for code scanning services.
+
+use serde::Serialize;
+use swc_common::{
+    errors::{Diagnostic, DiagnosticId, Level},
+    SourceMap, Span,
+};
+
+use crate::location::{fixes, lookup_range, message_with_notes, Range};
+
+#[derive(Serialize)]
+struct Log<'a> {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: [Run<'a>; 1],
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Run<'a> {
+    tool: Tool<'a>,
+    column_kind: &'static str,
+    results: Vec<SarifResult<'a>>,
+}
+
+#[derive(Serialize)]
+struct Tool<'a> {
+    driver: Driver<'a>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Driver<'a> {
+    name: &'static str,
+    information_uri: &'static str,
+    rules: Vec<Rule<'a>>,
+}
+
+#[derive(Serialize)]
+struct Rule<'a> {
+    id: &'a str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule_id: Option<&'a str>,
+    level: &'static str,
+    message: Message,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    locations: Vec<Location>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    related_locations: Vec<Location>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    fixes: Vec<Fix>,
+}
+
+#[derive(Serialize)]
+struct Message {
+    text: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<usize>,
+    physical_location: PhysicalLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<Message>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    artifact_location: Option<ArtifactLocation>,
+    region: Region,
+}
+
+#[derive(Serialize)]
+struct ArtifactLocation {
+    uri: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Fix {
+    description: Message,
+    artifact_changes: Vec<ArtifactChange>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ArtifactChange {
+    artifact_location: ArtifactLocation,
+    replacements: Vec<Replacement>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Replacement {
+    /// An empty region for insertions.
+    deleted_region: Region,
+    inserted_content: Message,
+}
+
+/// Lines and columns are one-based.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    start_line: usize,
+    start_column: usize,
+    end_line: usize,
+    end_column: usize,
+}
+
+fn level(level: Level) -> &'static str {
+    match level {
+        Level::Bug | Level::Fatal | Level::PhaseFatal | Level::Error | Level::FailureNote => {
+            "error"
+        }
+        Level::Warning => "warning",
+        Level::Note | Level::Help => "note",
+        Level::Cancelled => "none",
+    }
+}
+
+fn rule_id(d: &Diagnostic) -> Option<&str> {
+    d.code.as_ref().map(|code| match code {
+        DiagnosticId::Error(code) => &**code,
+        DiagnosticId::Lint(code) => &**code,
+    })
+}
+
+impl From<&Range> for Region {
+    fn from(range: &Range) -> Self {
+        Region {
+            start_line: range.start.0 + 1,
+            start_column: range.start.1 + 1,
+            end_line: range.end.0 + 1,
+            end_column: range.end.1 + 1,
+        }
+    }
+}
+
+fn location(cm: &SourceMap, span: Span, message: Option<String>) -> Option<Location> {
+    let range = lookup_range(cm, span)?;
+
+    Some(Location {
+        id: None,
+        physical_location: PhysicalLocation {
+            region: Region::from(&range),
+            artifact_location: range.uri.map(|uri| ArtifactLocation { uri }),
+        },
+        message: message.map(|text| Message { text }),
+    })
+}
+
+fn to_result<'a>(cm: &SourceMap, d: &'a Diagnostic) -> SarifResult<'a> {
+    let mut locations = vec![];
+    let mut related_locations = vec![];
+
+    for label in d.span.span_labels() {
+        if label.is_primary && locations.is_empty() {
+            locations.extend(location(cm, label.span, label.label));
+        } else {
+            related_locations.extend(location(cm, label.span, label.label));
+        }
+    }
+
+    // Children with a location, i.e. notes pointing to a declaration
+    for child in &d.children {
+        if let Some(span) = child.span.primary_span() {
+            let message = format!("{}: {}", child.level, child.message());
+
+            related_locations.extend(location(cm, span, Some(message)));
+        }
+    }
+
+    for (id, location) in related_locations.iter_mut().enumerate() {
+        location.id = Some(id);
+    }
+
+    SarifResult {
+        rule_id: rule_id(d),
+        level: level(d.level),
+        message: Message {
+            text: message_with_notes(d.message(), &d.children),
+        },
+        locations,
+        related_locations,
+        fixes: fixes(cm, d)
+            .into_iter()
+            .map(|fix| Fix {
+                description: Message { text: fix.title },
+                artifact_changes: fix
+                    .changes
+                    .into_iter()
+                    .map(|(uri, replacements)| ArtifactChange {
+                        artifact_location: ArtifactLocation { uri },
+                        replacements: replacements
+                            .into_iter()
+                            .map(|(range, text)| Replacement {
+                                deleted_region: Region::from(&range),
+                                inserted_content: Message { text },
+                            })
+                            .collect(),
+                    })
+                    .collect(),
+            })
+            .collect(),
+    }
+}
+
+/// Renders `diagnostics` as a SARIF log with a single run.
+pub fn to_sarif(cm: &SourceMap, diagnostics: &[Diagnostic]) -> String {
+    let diagnostics = diagnostics
+        .iter()
+        .filter(|d| d.level != Level::Cancelled)
+        .collect::<Vec<_>>();
+
+    let mut rules = diagnostics
+        .iter()
+        .filter_map(|d| rule_id(d))
+        .collect::<Vec<_>>();
+
+    rules.sort_unstable();
+    rules.dedup();
+
+    let log = Log {
+        schema: "https://json.schemastore.org/sarif-2.1.0.json",
+        version: "2.1.0",
+        runs: [Run {
+            tool: Tool {
+                driver: Driver {
+                    name: "swc",
+                    information_uri: "https://swc.rs",
+                    rules: rules.into_iter().map(|id| Rule { id }).collect(),
+                },
+            },
+            column_kind: "utf16CodeUnits",
+            results: diagnostics.into_iter().map(|d| to_result(cm, d)).collect(),
+        }],
+    };
+
+    serde_json::to_string(&log).expect("failed to serialize a SARIF log")
+}
@@ -3,11 +3,14 @@
 use std::{fmt, fmt::Write, fs, path::Path};
 
 use swc_common::{
-    errors::{Handler, Level},
+    errors::{Applicability, DiagnosticId, Handler, Level},
     sync::{Lock, Lrc},
     BytePos, FileName, SourceMap, Span,
 };
-use swc_error_reporters::PrettyEmitter;
+use swc_error_reporters::{
+    handler::{try_with_handler, DiagnosticFormat, HandlerOpts},
+    PrettyEmitter,
+};
 
 #[derive(Clone, Default)]
 struct Writer(Lrc<Lock<String>>);
@@ -76,3 +79,144 @@ fn test_2() {
         d.emit();
     });
 }
+
+fn structured_output<F>(file: &str, format: DiagnosticFormat, op: F) -> serde_json::Value
+where
+    F: FnOnce(Lrc<SourceMap>, &Handler),
+{
+    let cm = Lrc::new(SourceMap::default());
+
+    let err = try_with_handler(
+        cm.clone(),
+        HandlerOpts {
+            format,
+            ..Default::default()
+        },
+        |handler| {
+            op(cm, handler);
+
+            Ok(())
+        },
+    )
+    .expect_err("should fail");
+
+    let output = Path::new("tests").join("fixture").join(file);
+
+    let s = err.to_string();
+    println!("{}", s);
+    fs::write(&output, &s).expect("failed to write");
+
+    serde_json::from_str(&s).expect("should be valid json")
+}
+
+fn emit_with_related(cm: Lrc<SourceMap>, h: &Handler) {
+    // Columns are counted in UTF-16 code units
+    let _fm = cm.new_source_file(
+        FileName::Custom("foo.js".into()),
+        "const 𝒶 = 1;\nconst 𝒶 = 2;".into(),
+    );
+
+    let mut d = h.struct_span_err_with_code(
+        span(23, 27),
+        "the name `𝒶` is defined multiple times",
+        DiagnosticId::Error("duplicate-binding".into()),
+    );
+
+    d.span_label(span(23, 27), "`𝒶` redefined here")
+        .span_note(span(7, 11), "previous definition of `𝒶` here")
+        .help("rename the binding")
+        .span_suggestion_with_applicability(
+            span(23, 27),
+            "rename to `b`",
+            "b".into(),
+            Applicability::MachineApplicable,
+        );
+
+    d.emit();
+
+    h.struct_warn("no location").emit();
+}
+
+#[test]
+fn test_sarif() {
+    let log = structured_output("sarif.json", DiagnosticFormat::Sarif, emit_with_related);
+    let run = &log["runs"][0];
+
+    assert_eq!(run["columnKind"], "utf16CodeUnits");
+    assert_eq!(run["tool"]["driver"]["rules"][0]["id"], "duplicate-binding");
+
+    let result = &run["results"][0];
+    let region = &result["locations"][0]["physicalLocation"]["region"];
+
+    assert_eq!(result["ruleId"], "duplicate-binding");
+    assert_eq!(result["level"], "error");
+    assert_eq!(region["startLine"], 2);
+    assert_eq!(region["startColumn"], 7);
+    assert_eq!(region["endColumn"], 9);
+    assert_eq!(
+        result["relatedLocations"][0]["physicalLocation"]["region"]["startLine"],
+        1
+    );
+
+    let fix = &result["fixes"][0];
+    let replacement = &fix["artifactChanges"][0]["replacements"][0];
+
+    assert_eq!(fix["description"]["text"], "rename to `b`");
+    assert_eq!(
+        fix["artifactChanges"][0]["artifactLocation"]["uri"],
+        "foo.js"
+    );
+    assert_eq!(replacement["deletedRegion"]["startColumn"], 7);
+    assert_eq!(replacement["insertedContent"]["text"], "b");
+
+    assert_eq!(run["results"][1]["level"], "warning");
+    assert!(run["results"][1].get("locations").is_none());
+}
+
+#[test]
+fn test_lsp() {
+    let params = structured_output("lsp.json", DiagnosticFormat::Lsp, emit_with_related);
+    let diagnostic = &params[0]["diagnostics"][0];
+
+    assert_eq!(params[0]["uri"], "foo.js");
+    assert_eq!(diagnostic["severity"], 1);
+    assert_eq!(diagnostic["code"], "duplicate-binding");
+    assert_eq!(diagnostic["range"]["start"]["line"], 1);
+    assert_eq!(diagnostic["range"]["start"]["character"], 6);
+    assert_eq!(diagnostic["range"]["end"]["character"], 8);
+    assert_eq!(
+        diagnostic["relatedInformation"][0]["location"]["range"]["start"]["character"],
+        6
+    );
+
+    let action = &diagnostic["data"][0];
+    let edit = &action["edit"]["changes"]["foo.js"][0];
+
+    assert_eq!(action["title"], "rename to `b`");
+    assert_eq!(action["kind"], "quickfix");
+    assert_eq!(action["isPreferred"], true);
+    assert_eq!(edit["range"]["start"]["character"], 6);
+    assert_eq!(edit["newText"], "b");
+
+    assert_eq!(params[1]["uri"], "");
+    assert_eq!(params[1]["diagnostics"][0]["severity"], 2);
+}
+
+#[test]
+fn test_structured_error_without_diagnostic() {
+    let cm = Lrc::new(SourceMap::default());
+
+    let err = try_with_handler(
+        cm,
+        HandlerOpts {
+            format: DiagnosticFormat::Lsp,
+            ..Default::default()
+        },
+        |_| -> Result<(), _> { Err(anyhow::anyhow!("failed to load")) },
+    )
+    .expect_err("should fail");
+
+    let params: serde_json::Value = serde_json::from_str(&err.to_string()).unwrap();
+
+    assert_eq!(params[0]["diagnostics"][0]["message"], "failed to load");
+}
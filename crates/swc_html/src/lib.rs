@@ -1,4 +1,10 @@
+#[cfg(feature = "assets")]
+#[cfg_attr(docsrs, doc(cfg(feature = "assets")))]
+pub extern crate swc_html_assets as assets;
 pub extern crate swc_html_ast as ast;
 pub extern crate swc_html_codegen as codegen;
 pub extern crate swc_html_parser as parser;
+#[cfg(feature = "sanitizer")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sanitizer")))]
+pub extern crate swc_html_sanitizer as sanitizer;
 pub extern crate swc_html_visit as visit;
@@ -0,0 +1,612 @@
+#![deny(clippy::all)]
+
+//! Assets referenced by an HTML document.
+//!
+//! [extract_assets] lists the local files a document depends on, i.e. scripts,
+//! stylesheets, images, `url()` in inline styles and imports of inline module
+//! scripts, so a bundler can use the document as an entry point.
+//! [rewrite_assets] replaces those references, i.e. with the hashed names of
+//! the output files.
+//!
+//! References with a scheme (`https:`, `data:`), protocol-relative URLs and
+//! fragments are not assets.
+
+use std::ops::Range;
+
+use swc_common::{sync::Lrc, FileName, FilePathMapping, SourceMap, Span, Spanned};
+use swc_css_ast::{DeclarationOrAtRule, ImportHref, Str, Stylesheet, Url, UrlValue};
+use swc_css_visit::{Visit as CssVisit, VisitWith as CssVisitWith};
+use swc_ecma_ast::{
+    CallExpr, Callee, EsVersion, ExportAll, ExprOrSpread, ImportDecl, Lit, NamedExport,
+};
+use swc_ecma_visit::{Visit as EcmaVisit, VisitWith as EcmaVisitWith};
+use swc_html_ast::*;
+use swc_html_visit::{Visit, VisitMut, VisitMutWith, VisitWith};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AssetKind {
+    /// `<script src>`
+    Script,
+    /// `<script type="module" src>`
+    ModuleScript,
+    /// `<link rel="stylesheet">`
+    Stylesheet,
+    /// `<link rel="preload">`
+    Preload,
+    /// `<link rel="modulepreload">`
+    ModulePreload,
+    /// `<link rel="icon">`, `<link rel="apple-touch-icon">` and etc
+    Icon,
+    /// `src` and `srcset` of images, and `poster` of `<video>`
+    Image,
+    /// `src` of `<audio>`, `<video>`, `<source>` and `<track>`
+    Media,
+    /// `url()` in `<style>` elements and `style` attributes
+    CssUrl,
+    /// `@import` in `<style>` elements
+    CssImport,
+    /// `import`, `export from` and `import()` with a string literal in inline
+    /// `<script type="module">` elements
+    ModuleImport,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Asset {
+    pub kind: AssetKind,
+    pub specifier: String,
+    /// Span of the attribute or the text which contains the reference.
+    pub span: Span,
+}
+
+/// How a reference is written in the source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Syntax {
+    Plain,
+    CssUrl,
+    CssString,
+    JsString,
+}
+
+impl Syntax {
+    fn format(self, specifier: &str) -> String {
+        match self {
+            Syntax::Plain => specifier.to_string(),
+            Syntax::CssUrl => format!("url({})", css_string(specifier)),
+            Syntax::CssString => css_string(specifier),
+            Syntax::JsString => js_string(specifier),
+        }
+    }
+}
+
+fn css_string(value: &str) -> String {
+    let mut s = String::with_capacity(value.len() + 2);
+
+    s.push('"');
+    for c in value.chars() {
+        match c {
+            '"' | '\\' => {
+                s.push('\\');
+                s.push(c);
+            }
+            '\n' => s.push_str("\\a "),
+            _ => s.push(c),
+        }
+    }
+    s.push('"');
+
+    s
+}
+
+fn js_string(value: &str) -> String {
+    let mut s = String::with_capacity(value.len() + 2);
+
+    s.push('"');
+    for c in value.chars() {
+        match c {
+            '"' | '\\' => {
+                s.push('\\');
+                s.push(c);
+            }
+            '\n' => s.push_str("\\n"),
+            '\r' => s.push_str("\\r"),
+            '\u{2028}' => s.push_str("\\u2028"),
+            '\u{2029}' => s.push_str("\\u2029"),
+            _ => s.push(c),
+        }
+    }
+    s.push('"');
+
+    s
+}
+
+#[derive(Debug)]
+struct Reference {
+    kind: AssetKind,
+    specifier: String,
+    /// Byte range in the attribute value or the text
+    range: Range<usize>,
+    syntax: Syntax,
+}
+
+fn is_local(specifier: &str) -> bool {
+    if specifier.is_empty() || specifier.starts_with('#') || specifier.starts_with("//") {
+        return false;
+    }
+
+    !matches!(
+        specifier.find([':', '/', '?', '#']),
+        Some(index) if specifier[index..].starts_with(':')
+    )
+}
+
+fn get_attribute<'a>(element: &'a Element, name: &str) -> Option<&'a str> {
+    element
+        .attributes
+        .iter()
+        .find(|attribute| attribute.namespace.is_none() && &*attribute.name == name)
+        .map(|attribute| attribute.value.as_deref().unwrap_or_default())
+}
+
+static JS_MIME_TYPES: &[&str] = &[
+    "application/ecmascript",
+    "application/javascript",
+    "text/ecmascript",
+    "text/javascript",
+];
+
+fn script_kind(element: &Element) -> Option<AssetKind> {
+    let ty = match get_attribute(element, "type") {
+        Some(ty) => ty.trim().to_ascii_lowercase(),
+        None => return Some(AssetKind::Script),
+    };
+
+    match &*ty {
+        "" => Some(AssetKind::Script),
+        "module" => Some(AssetKind::ModuleScript),
+        _ if JS_MIME_TYPES.contains(&&*ty) => Some(AssetKind::Script),
+        // Templates, JSON, import maps and etc
+        _ => None,
+    }
+}
+
+fn link_kind(element: &Element) -> Option<AssetKind> {
+    let rel = get_attribute(element, "rel")?.to_ascii_lowercase();
+
+    rel.split_ascii_whitespace().find_map(|token| match token {
+        "stylesheet" => Some(AssetKind::Stylesheet),
+        "preload" => Some(AssetKind::Preload),
+        "modulepreload" => Some(AssetKind::ModulePreload),
+        "icon" | "apple-touch-icon" | "apple-touch-icon-precomposed" | "mask-icon" => {
+            Some(AssetKind::Icon)
+        }
+        _ => None,
+    })
+}
+
+/// Returns ranges of the URLs in a `srcset` attribute.
+fn srcset_ranges(value: &str) -> Vec<Range<usize>> {
+    let bytes = value.as_bytes();
+    let mut ranges = vec![];
+    let mut i = 0;
+
+    loop {
+        while i < bytes.len() && (bytes[i].is_ascii_whitespace() || bytes[i] == b',') {
+            i += 1;
+        }
+
+        if i >= bytes.len() {
+            break;
+        }
+
+        let start = i;
+
+        while i < bytes.len() && !bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+
+        // URL followed by commas does not have descriptors
+        if bytes[i - 1] == b',' {
+            let mut end = i;
+
+            while end > start && bytes[end - 1] == b',' {
+                end -= 1;
+            }
+
+            ranges.push(start..end);
+
+            continue;
+        }
+
+        ranges.push(start..i);
+
+        // Skip descriptors, i.e. `2x` or `100w`
+        let mut depth = 0;
+
+        while i < bytes.len() {
+            match bytes[i] {
+                b'(' => depth += 1,
+                b')' if depth > 0 => depth -= 1,
+                b',' if depth == 0 => {
+                    i += 1;
+
+                    break;
+                }
+                _ => {}
+            }
+
+            i += 1;
+        }
+    }
+
+    ranges
+}
+
+fn plain_references(value: &str, ranges: Vec<Range<usize>>, kind: AssetKind) -> Vec<Reference> {
+    ranges
+        .into_iter()
+        .filter(|range| is_local(&value[range.clone()]))
+        .map(|range| Reference {
+            kind,
+            specifier: value[range.clone()].to_string(),
+            range,
+            syntax: Syntax::Plain,
+        })
+        .collect()
+}
+
+fn trimmed_range(value: &str) -> Option<Range<usize>> {
+    let start = value.len() - value.trim_start().len();
+    let end = value.trim_end().len();
+
+    if start < end {
+        Some(start..end)
+    } else {
+        None
+    }
+}
+
+fn attribute_references(element: &Element, attribute: &Attribute) -> Vec<Reference> {
+    if element.namespace != Namespace::HTML || attribute.namespace.is_some() {
+        return vec![];
+    }
+
+    let value = match &attribute.value {
+        Some(value) => &**value,
+        None => return vec![],
+    };
+
+    let kind = match (&*element.tag_name, &*attribute.name) {
+        (_, "style") => return css_references(value, true),
+        ("img" | "source", "srcset") => {
+            return plain_references(value, srcset_ranges(value), AssetKind::Image)
+        }
+        ("script", "src") => script_kind(element),
+        ("link", "href") => link_kind(element),
+        ("img", "src") | ("video", "poster") => Some(AssetKind::Image),
+        ("input", "src")
+            if get_attribute(element, "type")
+                .map(|ty| ty.eq_ignore_ascii_case("image"))
+                .unwrap_or(false) =>
+        {
+            Some(AssetKind::Image)
+        }
+        ("audio" | "video" | "source" | "track", "src") => Some(AssetKind::Media),
+        _ => None,
+    };
+
+    match kind {
+        Some(kind) => plain_references(value, trimmed_range(value).into_iter().collect(), kind),
+        None => vec![],
+    }
+}
+
+fn is_style_element(element: &Element) -> bool {
+    element.namespace == Namespace::HTML && &*element.tag_name == "style"
+}
+
+struct CssReferenceCollector {
+    start_pos: u32,
+    references: Vec<Reference>,
+}
+
+impl CssReferenceCollector {
+    fn add(&mut self, span: Span, specifier: &str, kind: AssetKind, syntax: Syntax) {
+        if !is_local(specifier) {
+            return;
+        }
+
+        self.references.push(Reference {
+            kind,
+            specifier: specifier.to_string(),
+            range: (span.lo.0 - self.start_pos) as usize..(span.hi.0 - self.start_pos) as usize,
+            syntax,
+        });
+    }
+
+    fn add_url(&mut self, n: &Url, kind: AssetKind) {
+        let specifier = match n.value.as_deref() {
+            Some(UrlValue::Str(Str { value, .. })) => value,
+            Some(UrlValue::Raw(raw)) => &raw.value,
+            None => return,
+        };
+
+        self.add(n.span, specifier, kind, Syntax::CssUrl);
+    }
+}
+
+impl CssVisit for CssReferenceCollector {
+    fn visit_url(&mut self, n: &Url) {
+        self.add_url(n, AssetKind::CssUrl);
+    }
+
+    fn visit_import_href(&mut self, n: &ImportHref) {
+        match n {
+            ImportHref::Url(url) => self.add_url(url, AssetKind::CssImport),
+            ImportHref::Str(s) => {
+                self.add(s.span, &s.value, AssetKind::CssImport, Syntax::CssString)
+            }
+        }
+    }
+}
+
+/// Returns references in a stylesheet, or a list of declarations if
+/// `is_declaration_list` is true.
+fn css_references(code: &str, is_declaration_list: bool) -> Vec<Reference> {
+    let cm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+    let fm = cm.new_source_file(FileName::Anon, code.into());
+
+    let mut collector = CssReferenceCollector {
+        start_pos: fm.start_pos.0,
+        references: vec![],
+    };
+
+    let mut errors = vec![];
+
+    if is_declaration_list {
+        if let Ok(declarations) = swc_css_parser::parse_file::<Vec<DeclarationOrAtRule>>(
+            &fm,
+            Default::default(),
+            &mut errors,
+        ) {
+            for declaration in &declarations {
+                declaration.visit_with(&mut collector);
+            }
+        }
+    } else if let Ok(stylesheet) =
+        swc_css_parser::parse_file::<Stylesheet>(&fm, Default::default(), &mut errors)
+    {
+        stylesheet.visit_with(&mut collector);
+    }
+
+    collector.references
+}
+
+struct ModuleReferenceCollector {
+    start_pos: u32,
+    references: Vec<Reference>,
+}
+
+impl ModuleReferenceCollector {
+    fn add(&mut self, src: &swc_ecma_ast::Str) {
+        if !is_local(&src.value) {
+            return;
+        }
+
+        self.references.push(Reference {
+            kind: AssetKind::ModuleImport,
+            specifier: src.value.to_string(),
+            range: (src.span.lo.0 - self.start_pos) as usize
+                ..(src.span.hi.0 - self.start_pos) as usize,
+            syntax: Syntax::JsString,
+        });
+    }
+}
+
+impl EcmaVisit for ModuleReferenceCollector {
+    fn visit_import_decl(&mut self, n: &ImportDecl) {
+        self.add(&n.src);
+    }
+
+    fn visit_export_all(&mut self, n: &ExportAll) {
+        self.add(&n.src);
+    }
+
+    fn visit_named_export(&mut self, n: &NamedExport) {
+        if let Some(src) = &n.src {
+            self.add(src);
+        }
+    }
+
+    fn visit_call_expr(&mut self, n: &CallExpr) {
+        if let Callee::Import(..) = n.callee {
+            if let [ExprOrSpread {
+                spread: None, expr, ..
+            }, ..] = &*n.args
+            {
+                if let swc_ecma_ast::Expr::Lit(Lit::Str(src)) = &**expr {
+                    self.add(src);
+                }
+            }
+        }
+
+        n.visit_children_with(self);
+    }
+}
+
+/// Returns the static imports and the imports with a string literal in a
+/// module.
+fn module_references(code: &str) -> Vec<Reference> {
+    let cm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+    let fm = cm.new_source_file(FileName::Anon, code.into());
+
+    let mut collector = ModuleReferenceCollector {
+        start_pos: fm.start_pos.0,
+        references: vec![],
+    };
+
+    if let Ok(module) = swc_ecma_parser::parse_file_as_module(
+        &fm,
+        Default::default(),
+        EsVersion::latest(),
+        None,
+        &mut vec![],
+    ) {
+        module.visit_with(&mut collector);
+    }
+
+    collector.references
+}
+
+/// Returns true for `<script type="module">` with the code in the element.
+fn is_inline_module_script(element: &Element) -> bool {
+    element.namespace == Namespace::HTML
+        && &*element.tag_name == "script"
+        && get_attribute(element, "src").is_none()
+        && script_kind(element) == Some(AssetKind::ModuleScript)
+}
+
+/// Returns references in the text of `<style>` and inline module scripts.
+fn text_references(element: &Element) -> Vec<(usize, Vec<Reference>)> {
+    let references: fn(&str) -> Vec<Reference> = if is_style_element(element) {
+        |code| css_references(code, false)
+    } else if is_inline_module_script(element) {
+        module_references
+    } else {
+        return vec![];
+    };
+
+    element
+        .children
+        .iter()
+        .enumerate()
+        .filter_map(|(index, child)| match child {
+            Child::Text(text) => Some((index, references(&text.data))),
+            _ => None,
+        })
+        .collect()
+}
+
+struct AssetExtractor {
+    assets: Vec<Asset>,
+}
+
+impl AssetExtractor {
+    fn add(&mut self, references: Vec<Reference>, span: Span) {
+        self.assets
+            .extend(references.into_iter().map(|reference| Asset {
+                kind: reference.kind,
+                specifier: reference.specifier,
+                span,
+            }));
+    }
+}
+
+impl Visit for AssetExtractor {
+    fn visit_element(&mut self, n: &Element) {
+        for attribute in &n.attributes {
+            self.add(attribute_references(n, attribute), attribute.span);
+        }
+
+        for (index, references) in text_references(n) {
+            self.add(references, n.children[index].span());
+        }
+
+        n.visit_children_with(self);
+    }
+}
+
+struct AssetRewriter<F>
+where
+    F: FnMut(&Asset) -> Option<String>,
+{
+    rewrite: F,
+}
+
+impl<F> AssetRewriter<F>
+where
+    F: FnMut(&Asset) -> Option<String>,
+{
+    /// Returns the new value if any reference is rewritten.
+    fn rewrite(&mut self, value: &str, references: Vec<Reference>, span: Span) -> Option<String> {
+        let mut rewritten = String::with_capacity(value.len());
+        let mut last = 0;
+        let mut changed = false;
+
+        for reference in references {
+            let asset = Asset {
+                kind: reference.kind,
+                specifier: reference.specifier,
+                span,
+            };
+
+            if let Some(specifier) = (self.rewrite)(&asset) {
+                rewritten.push_str(&value[last..reference.range.start]);
+                rewritten.push_str(&reference.syntax.format(&specifier));
+                last = reference.range.end;
+                changed = true;
+            }
+        }
+
+        if !changed {
+            return None;
+        }
+
+        rewritten.push_str(&value[last..]);
+
+        Some(rewritten)
+    }
+}
+
+impl<F> VisitMut for AssetRewriter<F>
+where
+    F: FnMut(&Asset) -> Option<String>,
+{
+    fn visit_mut_element(&mut self, n: &mut Element) {
+        for index in 0..n.attributes.len() {
+            let references = attribute_references(n, &n.attributes[index]);
+
+            if references.is_empty() {
+                continue;
+            }
+
+            let attribute = &n.attributes[index];
+            let value = attribute.value.as_deref().unwrap_or_default();
+
+            if let Some(value) = self.rewrite(value, references, attribute.span) {
+                let attribute = &mut n.attributes[index];
+
+                attribute.value = Some(value.into());
+                attribute.raw_value = None;
+            }
+        }
+
+        for (index, references) in text_references(n) {
+            if let Child::Text(text) = &mut n.children[index] {
+                if let Some(data) = self.rewrite(&text.data, references, text.span) {
+                    text.data = data.into();
+                    text.raw = None;
+                }
+            }
+        }
+
+        n.visit_mut_children_with(self);
+    }
+}
+
+/// Returns the local assets referenced by `document`, in the source order.
+pub fn extract_assets(document: &Document) -> Vec<Asset> {
+    let mut extractor = AssetExtractor { assets: vec![] };
+
+    document.visit_with(&mut extractor);
+
+    extractor.assets
+}
+
+/// Replaces the references to the assets in `document` with the specifiers
+/// returned by `rewrite`. References are kept if `rewrite` returns [None].
+pub fn rewrite_assets<F>(document: &mut Document, rewrite: F)
+where
+    F: FnMut(&Asset) -> Option<String>,
+{
+    document.visit_mut_with(&mut AssetRewriter { rewrite });
+}
@@ -0,0 +1,198 @@
+#![deny(warnings)]
+
+//! Bundles the module scripts of a document with `swc_bundler`, using the
+//! document as the entry point.
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Error};
+use swc_bundler::{Bundle, BundleKind, Bundler, Load, ModuleData, ModuleRecord, Resolve};
+use swc_common::{sync::Lrc, FileName, Globals, SourceMap, Span};
+use swc_ecma_ast::KeyValueProp;
+use swc_ecma_codegen::{text_writer::JsWriter, Emitter};
+use swc_html_assets::{extract_assets, rewrite_assets, AssetKind};
+use swc_html_ast::Document;
+use swc_html_codegen::{
+    writer::basic::{BasicHtmlWriter, BasicHtmlWriterConfig},
+    CodeGenerator, CodegenConfig, Emit,
+};
+use swc_html_parser::parse_file_as_document;
+
+static FILES: &[(&str, &str)] = &[
+    (
+        "/main.js",
+        "import { greet } from './greet.js'; greet('main');",
+    ),
+    (
+        "/inline.js",
+        "import { greet } from './greet.js'; export const inline = () => greet('inline');",
+    ),
+    (
+        "/greet.js",
+        "export function greet(name) { console.log('hello ' + name); }",
+    ),
+];
+
+struct Loader {
+    cm: Lrc<SourceMap>,
+}
+
+impl Load for Loader {
+    fn load(&self, f: &FileName) -> Result<ModuleData, Error> {
+        let code = FILES
+            .iter()
+            .find(|(name, _)| f == &FileName::Custom(name.to_string()))
+            .map(|(_, code)| code.to_string())
+            .ok_or_else(|| anyhow!("file not found: {}", f))?;
+
+        let fm = self.cm.new_source_file(f.clone(), code);
+        let module = swc_ecma_parser::parse_file_as_module(
+            &fm,
+            Default::default(),
+            Default::default(),
+            None,
+            &mut vec![],
+        )
+        .map_err(|err| anyhow!("failed to parse {}: {:?}", f, err))?;
+
+        Ok(ModuleData {
+            fm,
+            module,
+            helpers: Default::default(),
+        })
+    }
+}
+
+/// All files are in the root directory.
+struct Resolver;
+
+impl Resolve for Resolver {
+    fn resolve(&self, _: &FileName, specifier: &str) -> Result<FileName, Error> {
+        Ok(FileName::Custom(format!(
+            "/{}",
+            specifier.trim_start_matches("./")
+        )))
+    }
+}
+
+struct Hook;
+
+impl swc_bundler::Hook for Hook {
+    fn get_import_meta_props(&self, _: Span, _: &ModuleRecord) -> Result<Vec<KeyValueProp>, Error> {
+        Ok(vec![])
+    }
+}
+
+fn print_bundle(cm: &Lrc<SourceMap>, bundle: &Bundle) -> String {
+    let mut buf = vec![];
+
+    {
+        let mut emitter = Emitter {
+            cfg: Default::default(),
+            cm: cm.clone(),
+            comments: None,
+            wr: JsWriter::new(cm.clone(), "\n", &mut buf, None),
+        };
+
+        emitter.emit_module(&bundle.module).unwrap();
+    }
+
+    String::from_utf8(buf).unwrap()
+}
+
+#[test]
+fn module_scripts_are_bundle_entries() {
+    let cm = Lrc::new(SourceMap::default());
+    let fm = cm.new_source_file(
+        FileName::Custom("/index.html".into()),
+        r#"<!doctype html>
+<html>
+<head>
+  <script type="module" src="./main.js"></script>
+  <script type="module">import { inline } from "./inline.js"; inline();</script>
+  <script src="./legacy.js"></script>
+</head>
+<body></body>
+</html>
+"#
+        .into(),
+    );
+
+    let mut document: Document =
+        parse_file_as_document(&fm, Default::default(), &mut vec![]).unwrap();
+
+    // Module scripts and the imports of inline module scripts are the entries.
+    let entries = extract_assets(&document)
+        .into_iter()
+        .filter(|asset| {
+            matches!(
+                asset.kind,
+                AssetKind::ModuleScript | AssetKind::ModuleImport
+            )
+        })
+        .map(|asset| {
+            let file_name = Resolver.resolve(&fm.name, &asset.specifier).unwrap();
+
+            (asset.specifier, file_name)
+        })
+        .collect::<HashMap<_, _>>();
+
+    assert_eq!(entries.len(), 2);
+
+    let globals = Globals::default();
+    let mut bundler = Bundler::new(
+        &globals,
+        cm.clone(),
+        Loader { cm: cm.clone() },
+        Resolver,
+        swc_bundler::Config {
+            disable_inliner: true,
+            ..Default::default()
+        },
+        Box::new(Hook),
+    );
+
+    let bundles = bundler.bundle(entries).unwrap();
+
+    let mut outputs = HashMap::new();
+    for (index, bundle) in bundles.iter().enumerate() {
+        if let BundleKind::Named { name } = &bundle.kind {
+            let code = print_bundle(&cm, bundle);
+
+            // Both entries include `greet.js`
+            assert!(code.contains("hello "), "{}", code);
+
+            outputs.insert(name.clone(), format!("/dist/{}.js", index));
+        }
+    }
+
+    rewrite_assets(&mut document, |asset| {
+        outputs.get(&asset.specifier).cloned()
+    });
+
+    let mut html = String::new();
+    {
+        let wr = BasicHtmlWriter::new(&mut html, None, BasicHtmlWriterConfig::default());
+        let mut gen = CodeGenerator::new(wr, CodegenConfig::default());
+
+        gen.emit(&document).unwrap();
+    }
+
+    assert!(
+        html.contains(&format!(
+            r#"<script type="module" src="{}">"#,
+            outputs["./main.js"]
+        )),
+        "{}",
+        html
+    );
+    assert!(
+        html.contains(&format!(
+            r#"import {{ inline }} from "{}";"#,
+            outputs["./inline.js"]
+        )),
+        "{}",
+        html
+    );
+    assert!(html.contains(r#"<script src="./legacy.js">"#), "{}", html);
+}
@@ -0,0 +1,65 @@
+#![deny(warnings)]
+
+use std::{fmt::Write, path::PathBuf};
+
+use swc_html_assets::{extract_assets, rewrite_assets};
+use swc_html_ast::Document;
+use swc_html_codegen::{
+    writer::basic::{BasicHtmlWriter, BasicHtmlWriterConfig},
+    CodeGenerator, CodegenConfig, Emit,
+};
+use swc_html_parser::parse_file_as_document;
+use testing::NormalizedOutput;
+
+#[testing::fixture("tests/fixture/**/input.html")]
+fn test_assets(input: PathBuf) {
+    let dir = input.parent().unwrap();
+
+    testing::run_test(false, |cm, handler| {
+        let fm = cm.load_file(&input).unwrap();
+
+        let mut errors = vec![];
+        let result: Result<Document, _> =
+            parse_file_as_document(&fm, Default::default(), &mut errors);
+
+        for err in errors {
+            err.to_diagnostics(handler).emit();
+        }
+
+        if handler.has_errors() {
+            return Err(());
+        }
+
+        let mut document = result.unwrap();
+
+        let mut assets_str = String::new();
+        for asset in extract_assets(&document) {
+            writeln!(assets_str, "{:?} {}", asset.kind, asset.specifier).unwrap();
+        }
+
+        NormalizedOutput::new_raw(assets_str)
+            .compare_to_file(dir.join("assets.txt"))
+            .unwrap();
+
+        rewrite_assets(&mut document, |asset| {
+            let name = asset.specifier.trim_start_matches("./");
+
+            Some(format!("/dist/{}", name.replace('.', ".hash.")))
+        });
+
+        let mut html_str = String::new();
+        {
+            let wr = BasicHtmlWriter::new(&mut html_str, None, BasicHtmlWriterConfig::default());
+            let mut gen = CodeGenerator::new(wr, CodegenConfig::default());
+
+            gen.emit(&document).unwrap();
+        }
+
+        NormalizedOutput::new_raw(html_str)
+            .compare_to_file(dir.join("output.html"))
+            .unwrap();
+
+        Ok(())
+    })
+    .unwrap();
+}
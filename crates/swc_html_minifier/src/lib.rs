@@ -8,20 +8,27 @@ use serde_json::Value;
 use swc_atoms::{js_word, JsWord};
 use swc_cached::regex::CachedRegex;
 use swc_common::{
-    collections::AHashMap, comments::SingleThreadedComments, sync::Lrc, EqIgnoreSpan, FileName,
-    FilePathMapping, Mark, SourceMap, DUMMY_SP,
+    collections::AHashMap, comments::SingleThreadedComments, input::StringInput, sync::Lrc,
+    BytePos, EqIgnoreSpan, FileName, FilePathMapping, Mark, SourceMap, DUMMY_SP,
 };
 use swc_html_ast::*;
 use swc_html_parser::parser::ParserConfig;
 use swc_html_utils::{HTML_ELEMENTS_AND_ATTRIBUTES, SVG_ELEMENTS_AND_ATTRIBUTES};
 use swc_html_visit::{VisitMut, VisitMutWith};
 
-use crate::option::{
-    CollapseWhitespaces, CssOptions, JsOptions, JsParserOptions, JsonOptions, MinifierType,
-    MinifyCssOption, MinifyJsOption, MinifyJsonOption, MinifyOptions, RemoveRedundantAttributes,
+use crate::{
+    option::{
+        CollapseWhitespaces, CssOptions, JsOptions, JsParserOptions, JsonOptions, MinifierType,
+        MinifyCssOption, MinifyJsOption, MinifyJsonOption, MinifyOptions,
+        RemoveRedundantAttributes,
+    },
+    source::SourceContext,
 };
 
 pub mod option;
+#[cfg(feature = "plugin")]
+mod plugin;
+pub mod source;
 
 static ALLOW_TO_TRIM_HTML_ATTRIBUTES: &[(&JsWord, &JsWord)] = &[
     (&js_word!("head"), &js_word!("profile")),
@@ -227,11 +234,15 @@ pub static CONDITIONAL_COMMENT_END: Lazy<CachedRegex> =
 
 struct Minifier<'a> {
     options: &'a MinifyOptions,
+    source: Option<&'a SourceContext<'a>>,
 
     current_element: Option<Element>,
     latest_element: Option<Child>,
     descendant_of_pre: bool,
     attribute_name_counter: Option<AHashMap<JsWord, usize>>,
+
+    #[cfg(feature = "plugin")]
+    css_plugins: plugin::LazyCssPlugins,
 }
 
 fn get_white_space(namespace: Namespace, tag_name: &JsWord) -> WhiteSpace {
@@ -2136,12 +2147,45 @@ impl Minifier<'_> {
         Some(code)
     }
 
-    // TODO source map url output for JS and CSS?
-    fn minify_js(&self, data: String, is_module: bool, is_attribute: bool) -> Option<String> {
+    fn attribute_position_of(&self, attribute: &Attribute, code: &str) -> Option<BytePos> {
+        self.source?.attribute_position_of(attribute, code)
+    }
+
+    fn text_position_of(&self, text: &Text) -> Option<BytePos> {
+        self.source?.position_of(text.span, &text.data)
+    }
+
+    /// Returns the source to parse `data` at `position` of the document, or
+    /// [None] if the position is not known.
+    fn embedded_source(&self, position: Option<BytePos>) -> Option<(&SourceContext<'_>, BytePos)> {
+        Some((self.source?, position?))
+    }
+
+    fn minify_js(
+        &self,
+        data: String,
+        is_module: bool,
+        is_attribute: bool,
+        position: Option<BytePos>,
+    ) -> Option<String> {
         let mut errors: Vec<_> = vec![];
 
-        let cm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
-        let fm = cm.new_source_file(FileName::Anon, data);
+        let source = self.embedded_source(position);
+        let cm = match source {
+            Some((source, _)) => source.source_map.clone(),
+            None => Lrc::new(SourceMap::new(FilePathMapping::empty())),
+        };
+        let fm;
+        let input = match source {
+            Some((_, position)) => {
+                StringInput::new(&data, position, position + BytePos(data.len() as u32))
+            }
+            None => {
+                fm = cm.new_source_file(FileName::Anon, data);
+
+                StringInput::from(&*fm)
+            }
+        };
         let mut options = self.get_js_options();
 
         if let swc_ecma_parser::Syntax::Es(es_config) = &mut options.parser.syntax {
@@ -2150,42 +2194,39 @@ impl Minifier<'_> {
 
         let comments = SingleThreadedComments::default();
 
-        let mut program = if is_module {
-            match swc_ecma_parser::parse_file_as_module(
-                &fm,
-                options.parser.syntax,
-                options.parser.target,
-                if options.parser.comments {
-                    Some(&comments)
-                } else {
-                    None
-                },
-                &mut errors,
-            ) {
-                Ok(module) => swc_ecma_ast::Program::Module(module),
-                _ => return None,
-            }
+        let lexer = swc_ecma_parser::lexer::Lexer::new(
+            options.parser.syntax,
+            options.parser.target,
+            input,
+            if options.parser.comments {
+                Some(&comments)
+            } else {
+                None
+            },
+        );
+        let mut parser = swc_ecma_parser::Parser::new_from(lexer);
+
+        let program = if is_module {
+            parser.parse_module().map(swc_ecma_ast::Program::Module)
         } else {
-            match swc_ecma_parser::parse_file_as_script(
-                &fm,
-                options.parser.syntax,
-                options.parser.target,
-                if options.parser.comments {
-                    Some(&comments)
-                } else {
-                    None
-                },
-                &mut errors,
-            ) {
-                Ok(script) => swc_ecma_ast::Program::Script(script),
-                _ => return None,
-            }
+            parser.parse_script().map(swc_ecma_ast::Program::Script)
         };
 
-        // Avoid compress potential invalid JS
-        if !errors.is_empty() {
-            return None;
-        }
+        errors.extend(parser.take_errors());
+
+        let mut program = match program {
+            // Avoid compress potential invalid JS
+            Ok(program) if errors.is_empty() => program,
+            result => {
+                if let Some((source, _)) = source {
+                    for error in errors.into_iter().chain(result.err()) {
+                        source.warn(error.into_diagnostic(source.handler));
+                    }
+                }
+
+                return None;
+            }
+        };
 
         if let Some(compress_options) = &mut options.minifier.compress {
             compress_options.module = is_module;
@@ -2227,13 +2268,18 @@ impl Minifier<'_> {
         );
 
         let mut buf = vec![];
+        let mut srcmap = vec![];
 
         {
             let mut wr = Box::new(swc_ecma_codegen::text_writer::JsWriter::new(
                 cm.clone(),
                 "\n",
                 &mut buf,
-                None,
+                // Only the contents of `<script>` are mapped
+                match source {
+                    Some(..) if !is_attribute => Some(&mut srcmap),
+                    _ => None,
+                },
             )) as Box<dyn swc_ecma_codegen::text_writer::WriteJs>;
 
             wr = Box::new(swc_ecma_codegen::text_writer::omit_trailing_semi(wr));
@@ -2263,6 +2309,12 @@ impl Minifier<'_> {
             _ => return None,
         };
 
+        if let Some((source, position)) = source {
+            if !is_attribute {
+                source.mappings.borrow_mut().insert(position, srcmap);
+            }
+        }
+
         Some(minified)
     }
 
@@ -2288,7 +2340,7 @@ impl Minifier<'_> {
 
         let media_condition =
             // It should be `MediaCondition`, but `<media-query> = <media-condition>` and other values is just invalid size
-            match self.minify_css(values[1].to_string(), CssMinificationMode::MediaQueryList) {
+            match self.minify_css(values[1].to_string(), CssMinificationMode::MediaQueryList, None) {
                 Some(minified) => minified,
                 _ => return None,
             };
@@ -2309,29 +2361,61 @@ impl Minifier<'_> {
                 parser: swc_css_parser::parser::ParserConfig::default(),
                 minifier: swc_css_minifier::options::MinifyOptions::default(),
                 codegen: swc_css_codegen::CodegenConfig::default(),
+                #[cfg(feature = "plugin")]
+                plugins: Default::default(),
             },
             MinifyCssOption::Options(css_options) => *css_options.clone(),
         }
     }
 
-    fn minify_css(&self, data: String, mode: CssMinificationMode) -> Option<String> {
+    fn minify_css(
+        &self,
+        data: String,
+        mode: CssMinificationMode,
+        position: Option<BytePos>,
+    ) -> Option<String> {
         let mut errors: Vec<_> = vec![];
 
-        let cm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
-        let fm = cm.new_source_file(FileName::Anon, data);
+        let source = self.embedded_source(position);
+        let fm;
+        let input = match source {
+            Some((_, position)) => {
+                StringInput::new(&data, position, position + BytePos(data.len() as u32))
+            }
+            None => {
+                let cm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+
+                fm = cm.new_source_file(FileName::Anon, data);
+
+                StringInput::from(&*fm)
+            }
+        };
+
+        let report_errors = |errors: Vec<swc_css_parser::error::Error>| {
+            if let Some((source, _)) = source {
+                for error in errors {
+                    source.warn(error.to_diagnostics(source.handler));
+                }
+            }
+        };
 
         let mut options = self.get_css_options();
 
         let mut stylesheet = match mode {
             CssMinificationMode::Stylesheet => {
-                match swc_css_parser::parse_file(&fm, options.parser, &mut errors) {
+                match swc_css_parser::parse_string_input(input, options.parser, &mut errors) {
                     Ok(stylesheet) => stylesheet,
-                    _ => return None,
+                    Err(error) => {
+                        errors.push(error);
+                        report_errors(errors);
+
+                        return None;
+                    }
                 }
             }
             CssMinificationMode::ListOfDeclarations => {
-                match swc_css_parser::parse_file::<Vec<swc_css_ast::DeclarationOrAtRule>>(
-                    &fm,
+                match swc_css_parser::parse_string_input::<Vec<swc_css_ast::DeclarationOrAtRule>>(
+                    input,
                     options.parser,
                     &mut errors,
                 ) {
@@ -2366,12 +2450,17 @@ impl Minifier<'_> {
                             )],
                         }
                     }
-                    _ => return None,
+                    Err(error) => {
+                        errors.push(error);
+                        report_errors(errors);
+
+                        return None;
+                    }
                 }
             }
             CssMinificationMode::MediaQueryList => {
-                match swc_css_parser::parse_file::<swc_css_ast::MediaQueryList>(
-                    &fm,
+                match swc_css_parser::parse_string_input::<swc_css_ast::MediaQueryList>(
+                    input,
                     options.parser,
                     &mut errors,
                 ) {
@@ -2409,22 +2498,51 @@ impl Minifier<'_> {
                             .into(),
                         )],
                     },
-                    _ => return None,
+                    Err(error) => {
+                        errors.push(error);
+                        report_errors(errors);
+
+                        return None;
+                    }
                 }
             }
         };
 
         // Avoid compress potential invalid CSS
         if !errors.is_empty() {
+            report_errors(errors);
+
             return None;
         }
 
+        #[cfg(feature = "plugin")]
+        if !options.plugins.is_empty() {
+            let cm = source.map(|(source, _)| &source.source_map);
+
+            stylesheet = match self.css_plugins.apply(stylesheet, &options.plugins, cm) {
+                Ok(stylesheet) => stylesheet,
+                Err(err) => {
+                    if let Some((source, _)) = source {
+                        source.warn(source.handler.struct_err(&format!("{:?}", err)));
+                    }
+
+                    return None;
+                }
+            };
+        }
+
         swc_css_minifier::minify(&mut stylesheet, options.minifier);
 
+        let is_stylesheet = matches!(mode, CssMinificationMode::Stylesheet);
         let mut minified = String::new();
+        let mut srcmap = vec![];
         let wr = swc_css_codegen::writer::basic::BasicCssWriter::new(
             &mut minified,
-            None,
+            // Only the contents of `<style>` are mapped
+            match source {
+                Some(..) if is_stylesheet => Some(&mut srcmap),
+                _ => None,
+            },
             swc_css_codegen::writer::basic::BasicCssWriterConfig::default(),
         );
 
@@ -2469,6 +2587,12 @@ impl Minifier<'_> {
             }
         }
 
+        if let Some((source, position)) = source {
+            if is_stylesheet {
+                source.mappings.borrow_mut().insert(position, srcmap);
+            }
+        }
+
         Some(minified)
     }
 
@@ -2626,7 +2750,9 @@ impl Minifier<'_> {
                     }
 
                     if self.need_minify_js() {
-                        if let Some(minified) = self.minify_js(value, false, true) {
+                        let position = self.attribute_position_of(n, &value);
+
+                        if let Some(minified) = self.minify_js(value, false, true, position) {
                             n.value = Some(minified.into());
                         };
                     } else {
@@ -2728,8 +2854,10 @@ impl Minifier<'_> {
                     }
 
                     if self.need_minify_css() && n.name == js_word!("media") && !value.is_empty() {
+                        let position = self.attribute_position_of(n, &value);
+
                         if let Some(minified) =
-                            self.minify_css(value, CssMinificationMode::MediaQueryList)
+                            self.minify_css(value, CssMinificationMode::MediaQueryList, position)
                         {
                             n.value = Some(minified.into());
                         }
@@ -2748,10 +2876,13 @@ impl Minifier<'_> {
 
                     if self.need_minify_css() && n.name == js_word!("style") && !value.is_empty() {
                         let value = value.trim();
+                        let position = self.attribute_position_of(n, value);
 
-                        if let Some(minified) = self
-                            .minify_css(value.to_string(), CssMinificationMode::ListOfDeclarations)
-                        {
+                        if let Some(minified) = self.minify_css(
+                            value.to_string(),
+                            CssMinificationMode::ListOfDeclarations,
+                            position,
+                        ) {
                             n.value = Some(minified.into());
                         } else {
                             fallback(n);
@@ -2760,7 +2891,9 @@ impl Minifier<'_> {
                         if value.trim().to_lowercase().starts_with("javascript:") {
                             value = value.trim().chars().skip(11).collect();
 
-                            if let Some(minified) = self.minify_js(value, false, true) {
+                            let position = self.attribute_position_of(n, &value);
+
+                            if let Some(minified) = self.minify_js(value, false, true, position) {
                                 let mut with_javascript =
                                     String::with_capacity(11 + minified.len());
 
@@ -2779,12 +2912,20 @@ impl Minifier<'_> {
                 _ if self.options.minify_additional_attributes.is_some() => {
                     match self.is_additional_minifier_attribute(&n.name) {
                         Some(MinifierType::JsScript) if self.need_minify_js() => {
-                            if let Some(minified) = self.minify_js(value.to_string(), false, true) {
+                            let position = self.attribute_position_of(n, value);
+
+                            if let Some(minified) =
+                                self.minify_js(value.to_string(), false, true, position)
+                            {
                                 n.value = Some(minified.into());
                             }
                         }
                         Some(MinifierType::JsModule) if self.need_minify_js() => {
-                            if let Some(minified) = self.minify_js(value.to_string(), true, true) {
+                            let position = self.attribute_position_of(n, value);
+
+                            if let Some(minified) =
+                                self.minify_js(value.to_string(), true, true, position)
+                            {
                                 n.value = Some(minified.into());
                             }
                         }
@@ -2794,9 +2935,12 @@ impl Minifier<'_> {
                             }
                         }
                         Some(MinifierType::Css) if self.need_minify_css() => {
+                            let position = self.attribute_position_of(n, value);
+
                             if let Some(minified) = self.minify_css(
                                 value.to_string(),
                                 CssMinificationMode::ListOfDeclarations,
+                                position,
                             ) {
                                 n.value = Some(minified.into());
                             }
@@ -3064,7 +3208,8 @@ impl VisitMut for Minifier<'_> {
 
         match text_type {
             Some(MinifierType::JsScript) => {
-                let minified = match self.minify_js(n.data.to_string(), false, false) {
+                let position = self.text_position_of(n);
+                let minified = match self.minify_js(n.data.to_string(), false, false, position) {
                     Some(minified) => minified,
                     None => return,
                 };
@@ -3072,7 +3217,8 @@ impl VisitMut for Minifier<'_> {
                 n.data = minified.into();
             }
             Some(MinifierType::JsModule) => {
-                let minified = match self.minify_js(n.data.to_string(), true, false) {
+                let position = self.text_position_of(n);
+                let minified = match self.minify_js(n.data.to_string(), true, false, position) {
                     Some(minified) => minified,
                     None => return,
                 };
@@ -3088,11 +3234,15 @@ impl VisitMut for Minifier<'_> {
                 n.data = minified.into();
             }
             Some(MinifierType::Css) => {
-                let minified =
-                    match self.minify_css(n.data.to_string(), CssMinificationMode::Stylesheet) {
-                        Some(minified) => minified,
-                        None => return,
-                    };
+                let position = self.text_position_of(n);
+                let minified = match self.minify_css(
+                    n.data.to_string(),
+                    CssMinificationMode::Stylesheet,
+                    position,
+                ) {
+                    Some(minified) => minified,
+                    None => return,
+                };
 
                 n.data = minified.into();
             }
@@ -3167,6 +3317,7 @@ impl VisitMut for AttributeNameCounter {
 fn create_minifier<'a>(
     context_element: Option<&Element>,
     options: &'a MinifyOptions,
+    source: Option<&'a SourceContext<'a>>,
 ) -> Minifier<'a> {
     let mut current_element = None;
     let mut is_pre = false;
@@ -3179,16 +3330,41 @@ fn create_minifier<'a>(
 
     Minifier {
         options,
+        source,
 
         current_element,
         latest_element: None,
         descendant_of_pre: is_pre,
         attribute_name_counter: None,
+
+        #[cfg(feature = "plugin")]
+        css_plugins: Default::default(),
     }
 }
 
 pub fn minify_document(document: &mut Document, options: &MinifyOptions) {
-    let mut minifier = create_minifier(None, options);
+    minify_document_inner(document, options, None);
+}
+
+/// Same as [minify_document], but JavaScript and CSS embedded in `document`
+/// are parsed at their positions in the source map of `source`.
+///
+/// Use [SourceContext::extend_source_map] to map the minified code in the
+/// source map of the generated document.
+pub fn minify_document_with_source(
+    document: &mut Document,
+    options: &MinifyOptions,
+    source: &SourceContext,
+) {
+    minify_document_inner(document, options, Some(source));
+}
+
+fn minify_document_inner(
+    document: &mut Document,
+    options: &MinifyOptions,
+    source: Option<&SourceContext>,
+) {
+    let mut minifier = create_minifier(None, options, source);
 
     if options.sort_attributes {
         let mut attribute_name_counter = AttributeNameCounter {
@@ -3208,7 +3384,28 @@ pub fn minify_document_fragment(
     context_element: &Element,
     options: &MinifyOptions,
 ) {
-    let mut minifier = create_minifier(Some(context_element), options);
+    minify_document_fragment_inner(document_fragment, context_element, options, None);
+}
+
+/// Same as [minify_document_fragment], but JavaScript and CSS embedded in
+/// `document_fragment` are parsed at their positions in the source map of
+/// `source`.
+pub fn minify_document_fragment_with_source(
+    document_fragment: &mut DocumentFragment,
+    context_element: &Element,
+    options: &MinifyOptions,
+    source: &SourceContext,
+) {
+    minify_document_fragment_inner(document_fragment, context_element, options, Some(source));
+}
+
+fn minify_document_fragment_inner(
+    document_fragment: &mut DocumentFragment,
+    context_element: &Element,
+    options: &MinifyOptions,
+    source: Option<&SourceContext>,
+) {
+    let mut minifier = create_minifier(Some(context_element), options, source);
 
     if options.sort_attributes {
         let mut attribute_name_counter = AttributeNameCounter {
@@ -134,6 +134,13 @@ pub struct CssOptions {
     pub minifier: CssMinifyOptions,
     #[serde(default)]
     pub codegen: CssCodegenOptions,
+    /// CSS transform plugins, applied in order before the minifier.
+    ///
+    /// Paths to the plugins are resolved in the same way as ECMAScript
+    /// plugins.
+    #[cfg(feature = "plugin")]
+    #[serde(default)]
+    pub plugins: Vec<swc_plugin_runner::css::CssPluginConfig>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
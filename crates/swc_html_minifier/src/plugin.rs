@@ -0,0 +1,100 @@
+//! CSS transform plugins, which run on the embedded CSS before the minifier.
+
+use std::{cell::RefCell, env, path::PathBuf, sync::Arc};
+
+use anyhow::{anyhow, bail, Error};
+use swc_common::{
+    plugin::metadata::TransformPluginMetadataContext, FileName, FilePathMapping, SourceMap,
+};
+use swc_css_ast::Stylesheet;
+use swc_ecma_loader::{
+    resolve::Resolve,
+    resolvers::{lru::CachingResolver, node::NodeModulesResolver},
+    TargetEnv,
+};
+use swc_plugin_runner::{
+    cache::{init_plugin_module_cache_once, PLUGIN_MODULE_CACHE},
+    css::{CssPluginConfig, CssPlugins},
+};
+
+/// The CSS plugins of a minification.
+///
+/// The plugins are resolved and instantiated on the first use, and the
+/// instances are reused for every `<style>` and `style` attribute of the
+/// document.
+#[derive(Default)]
+pub(crate) struct LazyCssPlugins {
+    plugins: RefCell<Option<Result<CssPlugins, String>>>,
+}
+
+impl LazyCssPlugins {
+    /// Applies `plugins` to `stylesheet`.
+    ///
+    /// `source_map` is the source map of the document, if any.
+    pub(crate) fn apply(
+        &self,
+        stylesheet: Stylesheet,
+        plugins: &[CssPluginConfig],
+        source_map: Option<&Arc<SourceMap>>,
+    ) -> Result<Stylesheet, Error> {
+        let mut state = self.plugins.borrow_mut();
+        let state = state.get_or_insert_with(|| {
+            let source_map = match source_map {
+                Some(source_map) => source_map.clone(),
+                None => Arc::new(SourceMap::new(FilePathMapping::empty())),
+            };
+
+            create_css_plugins(plugins, &source_map).map_err(|err| format!("{:?}", err))
+        });
+
+        match state {
+            Ok(plugins) => plugins.apply(stylesheet, None),
+            Err(err) => Err(anyhow!("{}", err)),
+        }
+    }
+}
+
+/// Instantiates `plugins`, after resolving the paths to the plugins like `swc`
+/// resolves the paths to ECMAScript plugins.
+fn create_css_plugins(
+    plugins: &[CssPluginConfig],
+    source_map: &Arc<SourceMap>,
+) -> Result<CssPlugins, Error> {
+    let resolver = CachingResolver::new(
+        40,
+        NodeModulesResolver::new(TargetEnv::Node, Default::default(), true),
+    );
+
+    let plugins = plugins
+        .iter()
+        .map(|p| {
+            let path = match resolver.resolve(&FileName::Real(PathBuf::from(&p.0)), &p.0)? {
+                FileName::Real(path) => path,
+                resolved_path => bail!("Failed to resolve plugin path: {:?}", resolved_path),
+            };
+
+            Ok(CssPluginConfig(
+                path.to_string_lossy().into_owned(),
+                p.1.clone(),
+                p.2,
+            ))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    // Compiled plugins are cached in `.swc` of the working directory, like
+    // ECMAScript plugins without `jsc.experimental.cacheRoot`.
+    init_plugin_module_cache_once(&None);
+
+    // Same as the default `envName` of `swc`.
+    let env_name = env::var("SWC_ENV")
+        .or_else(|_| env::var("NODE_ENV"))
+        .unwrap_or_else(|_| "development".to_string());
+    let metadata_context = Arc::new(TransformPluginMetadataContext::new(None, env_name, None));
+
+    CssPlugins::new(
+        &plugins,
+        &PLUGIN_MODULE_CACHE,
+        source_map,
+        &metadata_context,
+    )
+}
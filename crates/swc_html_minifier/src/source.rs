@@ -0,0 +1,137 @@
+use std::cell::RefCell;
+
+use swc_common::{
+    collections::AHashMap,
+    errors::{DiagnosticBuilder, Handler, Level},
+    sync::Lrc,
+    BytePos, LineCol, SourceMap, SourceMapper, Span,
+};
+use swc_html_ast::Attribute;
+
+/// The source map and the error handler of the document being minified.
+///
+/// With this, JavaScript and CSS embedded in the document are parsed at their
+/// positions in `source_map`, so syntax errors in them are reported to
+/// `handler` as warnings pointing into the document. Those errors do not fail
+/// the minification, the code is kept as-is instead.
+pub struct SourceContext<'a> {
+    pub source_map: Lrc<SourceMap>,
+    pub handler: &'a Handler,
+    /// Source map entries of the minified `<script>` and `<style>` contents
+    /// by the start of the text. Columns and lines are relative to the text.
+    pub(crate) mappings: RefCell<AHashMap<BytePos, Vec<(BytePos, LineCol)>>>,
+}
+
+impl<'a> SourceContext<'a> {
+    pub fn new(source_map: Lrc<SourceMap>, handler: &'a Handler) -> Self {
+        SourceContext {
+            source_map,
+            handler,
+            mappings: Default::default(),
+        }
+    }
+
+    /// Returns the start of `code` if it's the source text of `span`, i.e. it
+    /// does not contain character references.
+    pub(crate) fn position_of(&self, span: Span, code: &str) -> Option<BytePos> {
+        if span.is_dummy() {
+            return None;
+        }
+
+        match self.source_map.span_to_snippet(span) {
+            Ok(snippet) if snippet == code => Some(span.lo),
+            _ => None,
+        }
+    }
+
+    /// Returns the start of `code` if it's the end of the source text of the
+    /// value of `attribute`, i.e. the value without leading whitespaces or the
+    /// `javascript:` scheme.
+    pub(crate) fn attribute_position_of(
+        &self,
+        attribute: &Attribute,
+        code: &str,
+    ) -> Option<BytePos> {
+        let (value, raw_value) = match (&attribute.value, &attribute.raw_value) {
+            (Some(value), Some(raw_value)) => (value, raw_value),
+            _ => return None,
+        };
+
+        if attribute.span.is_dummy() {
+            return None;
+        }
+
+        let snippet = self.source_map.span_to_snippet(attribute.span).ok()?;
+        // The value is the last part of the attribute, the span can include the
+        // character after it
+        let raw_start = snippet.rfind(&**raw_value)?;
+        let quote = usize::from(raw_value.starts_with(['"', '\'']));
+
+        // Character references and newlines are not kept as-is in the value
+        if raw_value.get(quote..raw_value.len() - quote) != Some(&**value) {
+            return None;
+        }
+
+        let value = value.trim_end();
+
+        if !value.ends_with(code) {
+            return None;
+        }
+
+        let start = raw_start + quote + value.len() - code.len();
+
+        Some(attribute.span.lo + BytePos(start as u32))
+    }
+
+    pub(crate) fn warn(&self, mut db: DiagnosticBuilder) {
+        db.level = Level::Warning;
+        db.emit();
+    }
+
+    /// Adds the entries of the minified `<script>` and `<style>` contents to
+    /// `srcmap` collected by `swc_html_codegen`, so the source map of the
+    /// document points into the original code.
+    pub fn extend_source_map(&self, srcmap: &mut Vec<(BytePos, LineCol)>) {
+        let mappings = self.mappings.borrow();
+
+        if mappings.is_empty() {
+            return;
+        }
+
+        // The text is written after the start tag, so the last entry at its
+        // start is the one of the text
+        let mut last_index = AHashMap::default();
+
+        for (index, (pos, _)) in srcmap.iter().enumerate() {
+            if mappings.contains_key(pos) {
+                last_index.insert(*pos, index);
+            }
+        }
+
+        let mut extended = Vec::with_capacity(srcmap.len());
+
+        for (index, &(pos, loc)) in srcmap.iter().enumerate() {
+            extended.push((pos, loc));
+
+            if last_index.get(&pos) != Some(&index) {
+                continue;
+            }
+
+            extended.extend(mappings[&pos].iter().map(|&(original, relative)| {
+                (
+                    original,
+                    LineCol {
+                        line: loc.line + relative.line,
+                        col: if relative.line == 0 {
+                            loc.col + relative.col
+                        } else {
+                            relative.col
+                        },
+                    },
+                )
+            }));
+        }
+
+        *srcmap = extended;
+    }
+}
@@ -0,0 +1,170 @@
+#![cfg(feature = "plugin")]
+
+use std::{fs, path::PathBuf};
+
+use swc_common::{
+    plugin::{diagnostics::PluginCorePkgDiagnostics, serialized::PluginSerializedBytes},
+    FileName,
+};
+use swc_html_ast::Document;
+use swc_html_codegen::{
+    writer::basic::{BasicHtmlWriter, BasicHtmlWriterConfig},
+    CodeGenerator, CodegenConfig, Emit,
+};
+use swc_html_minifier::{
+    minify_document_with_source, option::MinifyOptions, source::SourceContext,
+};
+use swc_html_parser::parse_file_as_document;
+
+const INPUT: &str = "<!doctype html><html><head><style>a { color: red; }</style></head></html>";
+
+/// Writes a CSS plugin which runs `transform` as its transform, and returns
+/// the path to it.
+fn write_plugin(name: &str, transform: &str) -> PathBuf {
+    let diagnostics = PluginSerializedBytes::try_serialize(&PluginCorePkgDiagnostics {
+        pkg_version: "0.0.0".to_string(),
+        git_sha: "".to_string(),
+        cargo_features: "".to_string(),
+        ast_schema_version: 1,
+    })
+    .unwrap();
+    let diagnostics_len = diagnostics.as_slice().len();
+    let diagnostics = diagnostics
+        .as_slice()
+        .iter()
+        .map(|byte| format!("\\{:02x}", byte))
+        .collect::<String>();
+
+    let wat = format!(
+        r#"(module
+            (import "env" "__set_transform_plugin_core_pkg_diagnostics"
+                (func $set_diagnostics (param i32 i32)))
+            (import "env" "__set_transform_result" (func $set_result (param i32 i32)))
+            (memory (export "memory") 1)
+            (global $calls (mut i32) (i32.const 0))
+            (data (i32.const 16) "{diagnostics}")
+            (func (export "__get_transform_plugin_core_pkg_diag") (result i32)
+                (call $set_diagnostics (i32.const 16) (i32.const {diagnostics_len}))
+                (i32.const 0))
+            (func (export "__alloc") (param i32) (result i32) (i32.const 1024))
+            (func (export "__free") (param i32 i32) (result i32) (i32.const 0))
+            (func (export "__transform_plugin_process_impl")
+                (param $ptr i32) (param $len i32) (param i32 i32) (result i32)
+                {transform}))"#
+    );
+
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("css_plugins");
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(format!("{}.wasm", name));
+    fs::write(&path, wat::parse_str(wat).unwrap()).unwrap();
+
+    path
+}
+
+/// Minifies [INPUT] with `config`, and returns the output and the reported
+/// warnings.
+fn minify(config: serde_json::Value) -> (String, String) {
+    minify_input(INPUT, config)
+}
+
+fn minify_input(input: &str, config: serde_json::Value) -> (String, String) {
+    let options: MinifyOptions = serde_json::from_value(config).unwrap();
+    let mut output = String::new();
+
+    let stderr = testing::run_test(false, |cm, handler| {
+        let fm = cm.new_source_file(FileName::Anon, input.into());
+        let mut document: Document =
+            parse_file_as_document(&fm, Default::default(), &mut vec![]).unwrap();
+        let source = SourceContext::new(cm.clone(), handler);
+
+        minify_document_with_source(&mut document, &options, &source);
+
+        {
+            let wr = BasicHtmlWriter::new(&mut output, None, BasicHtmlWriterConfig::default());
+            let mut gen = CodeGenerator::new(
+                wr,
+                CodegenConfig {
+                    minify: true,
+                    ..Default::default()
+                },
+            );
+
+            gen.emit(&document).unwrap();
+        }
+
+        Err::<(), _>(())
+    })
+    .unwrap_err();
+
+    (output, stderr.to_string())
+}
+
+#[test]
+fn css_plugins_run_before_minifier() {
+    let noop = write_plugin(
+        "noop",
+        "(call $set_result (local.get $ptr) (local.get $len)) (i32.const 0)",
+    );
+
+    let (expected, _) = minify(serde_json::json!({}));
+    let (output, warnings) = minify(serde_json::json!({
+        "minifyCss": {
+            "plugins": [
+                [noop.display().to_string(), {}],
+                [noop.display().to_string(), {}]
+            ]
+        }
+    }));
+
+    assert_eq!(warnings, "");
+    assert_eq!(output, expected);
+    assert!(output.contains("<style>a{color:red}</style>"), "{}", output);
+}
+
+#[test]
+fn failing_css_plugin_leaves_css_as_is() {
+    let failing = write_plugin("failing", "unreachable");
+
+    let (output, warnings) = minify(serde_json::json!({
+        "minifyCss": {
+            "plugins": [[failing.display().to_string(), {}]]
+        }
+    }));
+
+    assert!(
+        output.contains("<style>a { color: red; }</style>"),
+        "{}",
+        output
+    );
+    assert!(warnings.contains("failing.wasm"), "{}", warnings);
+}
+
+#[test]
+fn css_plugins_are_instantiated_once() {
+    // Fails from the second transform of an instance
+    let once = write_plugin(
+        "once",
+        r#"(global.set $calls (i32.add (global.get $calls) (i32.const 1)))
+        (if (i32.gt_u (global.get $calls) (i32.const 1)) (then unreachable))
+        (call $set_result (local.get $ptr) (local.get $len))
+        (i32.const 0)"#,
+    );
+
+    let (output, warnings) = minify_input(
+        "<!doctype html><html><head><style>a { color: red; }</style><style>b { color: blue; \
+         }</style></head></html>",
+        serde_json::json!({
+            "minifyCss": {
+                "plugins": [[once.display().to_string(), {}]]
+            }
+        }),
+    );
+
+    assert!(output.contains("<style>a{color:red}</style>"), "{}", output);
+    assert!(
+        output.contains("<style>b { color: blue; }</style>"),
+        "{}",
+        output
+    );
+    assert!(warnings.contains("once.wasm"), "{}", warnings);
+}
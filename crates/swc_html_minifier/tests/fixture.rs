@@ -10,7 +10,10 @@ use swc_html_codegen::{
     writer::basic::{BasicHtmlWriter, BasicHtmlWriterConfig},
     CodeGenerator, CodegenConfig, Emit,
 };
-use swc_html_minifier::{minify_document, minify_document_fragment, option::MinifyOptions};
+use swc_html_minifier::{
+    minify_document, minify_document_fragment, minify_document_with_source, option::MinifyOptions,
+    source::SourceContext,
+};
 use swc_html_parser::{parse_file_as_document, parse_file_as_document_fragment};
 use testing::NormalizedOutput;
 
@@ -251,3 +254,94 @@ fn test_minify_recovery(input: PathBuf) {
         );
     }
 }
+
+#[testing::fixture("tests/source/**/input.html")]
+fn test_minify_with_source(input: PathBuf) {
+    let dir = input.parent().unwrap();
+    let output = dir.join(format!(
+        "output.min.{}",
+        input.extension().unwrap().to_string_lossy()
+    ));
+    let mappings_output = dir.join("output.mappings");
+    let stderr_output = dir.join("output.stderr");
+
+    let stderr = testing::run_test(false, |cm, handler| {
+        let fm = cm.load_file(&input).unwrap();
+
+        let mut errors = vec![];
+        let result: Result<Document, _> =
+            parse_file_as_document(&fm, Default::default(), &mut errors);
+
+        let mut document = result.unwrap();
+        let config = match find_config(dir) {
+            Some(config) => serde_json::from_str(&config).unwrap(),
+            None => MinifyOptions::default(),
+        };
+        let source = SourceContext::new(cm.clone(), handler);
+
+        // Apply transforms
+        minify_document_with_source(&mut document, &config, &source);
+
+        let mut html_str = String::new();
+        let mut srcmap = vec![];
+        {
+            let wr = BasicHtmlWriter::new(
+                &mut html_str,
+                Some(&mut srcmap),
+                BasicHtmlWriterConfig::default(),
+            );
+            let mut gen = CodeGenerator::new(
+                wr,
+                CodegenConfig {
+                    scripting_enabled: false,
+                    minify: true,
+                    ..Default::default()
+                },
+            );
+
+            gen.emit(&document).unwrap();
+        }
+
+        source.extend_source_map(&mut srcmap);
+
+        let lines = html_str.lines().collect::<Vec<_>>();
+        let mut mappings = String::new();
+
+        for (pos, generated) in srcmap {
+            let original = cm.lookup_char_pos(pos);
+            let original_line = fm.get_line(original.line - 1).unwrap_or_default();
+            let generated_line = lines.get(generated.line as usize).unwrap_or(&"");
+
+            mappings.push_str(&format!(
+                "{}:{} {:?} -> {}:{} {:?}\n",
+                original.line,
+                original.col.0,
+                original_line
+                    .chars()
+                    .skip(original.col.0)
+                    .take(10)
+                    .collect::<String>(),
+                generated.line + 1,
+                generated.col,
+                generated_line
+                    .chars()
+                    .skip(generated.col as usize)
+                    .take(10)
+                    .collect::<String>(),
+            ));
+        }
+
+        NormalizedOutput::new_raw(html_str)
+            .compare_to_file(&output)
+            .unwrap();
+        NormalizedOutput::new_raw(mappings)
+            .compare_to_file(&mappings_output)
+            .unwrap();
+
+        // Embedded code with syntax errors is reported as warnings
+        Err::<(), _>(())
+    })
+    .unwrap_err();
+
+    stderr.compare_to_file(&stderr_output).unwrap();
+}
@@ -0,0 +1,376 @@
+#![deny(clippy::all)]
+
+//! Allow-list based sanitizer for [swc_html_ast].
+//!
+//! The sanitizer removes every element, attribute, URL scheme and inline style
+//! which is not explicitly allowed by [SanitizeOptions], so the result can be
+//! emitted by `swc_html_codegen` and inserted into a page as-is.
+
+use std::mem::take;
+
+use swc_atoms::JsWord;
+use swc_common::{
+    collections::{AHashMap, AHashSet},
+    sync::Lrc,
+    FileName, FilePathMapping, SourceMap,
+};
+use swc_css_ast::{DeclarationOrAtRule, Function, Token, TokenAndSpan, Url};
+use swc_css_codegen::{
+    writer::basic::{BasicCssWriter, BasicCssWriterConfig},
+    CodeGenerator, CodegenConfig, Emit,
+};
+use swc_css_visit::{Visit, VisitWith};
+use swc_html_ast::*;
+
+use crate::option::SanitizeOptions;
+
+pub mod option;
+
+/// Attributes which contain a URL.
+static URL_ATTRIBUTES: &[&str] = &[
+    "action",
+    "background",
+    "cite",
+    "classid",
+    "codebase",
+    "data",
+    "dynsrc",
+    "formaction",
+    "href",
+    "icon",
+    "itemid",
+    "longdesc",
+    "lowsrc",
+    "manifest",
+    "poster",
+    "profile",
+    "src",
+    "usemap",
+    "xlink:href",
+];
+
+/// Attributes which contain a list of URLs separated by spaces or commas.
+static URL_LIST_ATTRIBUTES: &[&str] = &["archive", "itemtype", "ping"];
+
+/// Attributes which contain image candidates, i.e. `a.png 1x, b.png 2x`.
+static SRCSET_ATTRIBUTES: &[&str] = &["imagesrcset", "srcset"];
+
+struct Sanitizer {
+    allowed_elements: AHashSet<JsWord>,
+    remove_content_elements: AHashSet<JsWord>,
+    allowed_attributes: AHashSet<JsWord>,
+    allowed_element_attributes: AHashMap<JsWord, AHashSet<JsWord>>,
+    allowed_url_schemes: AHashSet<String>,
+    allow_relative_urls: bool,
+    allowed_style_properties: AHashSet<JsWord>,
+    allowed_style_functions: AHashSet<JsWord>,
+    allow_comments: bool,
+}
+
+fn to_set(list: &[String]) -> AHashSet<JsWord> {
+    list.iter()
+        .map(|s| JsWord::from(s.to_ascii_lowercase()))
+        .collect()
+}
+
+impl Sanitizer {
+    fn new(options: &SanitizeOptions) -> Self {
+        Sanitizer {
+            allowed_elements: to_set(&options.allowed_elements),
+            remove_content_elements: to_set(&options.remove_content_elements),
+            allowed_attributes: to_set(&options.allowed_attributes),
+            allowed_element_attributes: options
+                .allowed_element_attributes
+                .iter()
+                .map(|(element, attributes)| {
+                    (
+                        JsWord::from(element.to_ascii_lowercase()),
+                        to_set(attributes),
+                    )
+                })
+                .collect(),
+            allowed_url_schemes: options
+                .allowed_url_schemes
+                .iter()
+                .map(|scheme| scheme.to_ascii_lowercase())
+                .collect(),
+            allow_relative_urls: options.allow_relative_urls,
+            allowed_style_properties: to_set(&options.allowed_style_properties),
+            allowed_style_functions: to_set(&options.allowed_style_functions),
+            allow_comments: options.allow_comments,
+        }
+    }
+
+    fn sanitize_children(&self, children: Vec<Child>) -> Vec<Child> {
+        let mut sanitized = Vec::with_capacity(children.len());
+
+        for child in children {
+            match child {
+                Child::Element(element) => self.sanitize_element(element, &mut sanitized),
+                Child::Text(..) => sanitized.push(child),
+                Child::Comment(..) if self.allow_comments => sanitized.push(child),
+                Child::Comment(..) | Child::DocumentType(..) => {}
+            }
+        }
+
+        sanitized
+    }
+
+    fn sanitize_element(&self, mut element: Element, parent: &mut Vec<Child>) {
+        if element.namespace != Namespace::HTML
+            || self.remove_content_elements.contains(&element.tag_name)
+        {
+            return;
+        }
+
+        let children = self.sanitize_children(element.children);
+
+        if !self.allowed_elements.contains(&element.tag_name) {
+            // Keep the content of unknown elements
+            parent.extend(children);
+
+            return;
+        }
+
+        element.children = children;
+        element.content = element.content.map(|content| DocumentFragment {
+            children: self.sanitize_children(content.children),
+            ..content
+        });
+
+        let tag_name = element.tag_name.clone();
+
+        element.attributes = element
+            .attributes
+            .into_iter()
+            .filter_map(|attribute| self.sanitize_attribute(&tag_name, attribute))
+            .collect();
+
+        parent.push(Child::Element(element));
+    }
+
+    fn is_allowed_attribute(&self, tag_name: &JsWord, attribute: &Attribute) -> bool {
+        if attribute.namespace.is_some() || attribute.prefix.is_some() {
+            return false;
+        }
+
+        self.allowed_attributes.contains(&attribute.name)
+            || matches!(
+                self.allowed_element_attributes.get(tag_name),
+                Some(attributes) if attributes.contains(&attribute.name)
+            )
+    }
+
+    fn sanitize_attribute(&self, tag_name: &JsWord, mut attribute: Attribute) -> Option<Attribute> {
+        if !self.is_allowed_attribute(tag_name, &attribute) {
+            return None;
+        }
+
+        let name = &*attribute.name;
+        let value = attribute.value.as_deref().unwrap_or_default();
+
+        if URL_ATTRIBUTES.contains(&name) {
+            if !self.is_allowed_url(value) {
+                return None;
+            }
+        } else if URL_LIST_ATTRIBUTES.contains(&name) {
+            let mut urls = value
+                .split(|c: char| c.is_ascii_whitespace() || c == ',')
+                .filter(|url| !url.is_empty());
+
+            if !urls.all(|url| self.is_allowed_url(url)) {
+                return None;
+            }
+        } else if SRCSET_ATTRIBUTES.contains(&name) {
+            if !srcset_urls(value).all(|url| self.is_allowed_url(url)) {
+                return None;
+            }
+        } else if name == "style" {
+            let style = self.sanitize_style(attribute.value.as_deref().unwrap_or_default())?;
+
+            attribute.value = Some(style.into());
+            attribute.raw_value = None;
+        }
+
+        Some(attribute)
+    }
+
+    fn is_allowed_url(&self, url: &str) -> bool {
+        // Browsers ignore leading and trailing spaces, and remove tabs and newlines in
+        // URLs, i.e. `java\tscript:`
+        let url: String = url
+            .trim_matches(|c: char| c <= ' ')
+            .chars()
+            .filter(|c| !matches!(c, '\t' | '\n' | '\r'))
+            .collect();
+
+        let scheme_end = url.find([':', '/', '?', '#']);
+
+        match scheme_end {
+            Some(index) if url[index..].starts_with(':') => self
+                .allowed_url_schemes
+                .contains(&url[..index].to_ascii_lowercase()),
+            _ => self.allow_relative_urls,
+        }
+    }
+
+    /// Returns the declarations of the `style` attribute which are allowed, or
+    /// [None] if nothing is left.
+    fn sanitize_style(&self, style: &str) -> Option<String> {
+        let cm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        let fm = cm.new_source_file(FileName::Anon, style.into());
+
+        let mut errors = vec![];
+        let declarations: Vec<DeclarationOrAtRule> =
+            swc_css_parser::parse_file(&fm, Default::default(), &mut errors).ok()?;
+
+        // Do not guess what browsers would do with invalid CSS
+        if !errors.is_empty() {
+            return None;
+        }
+
+        let mut sanitized = String::new();
+
+        for declaration in declarations {
+            let declaration = match declaration {
+                DeclarationOrAtRule::Declaration(declaration) => declaration,
+                _ => continue,
+            };
+
+            let name = match &declaration.name {
+                swc_css_ast::DeclarationName::Ident(ident) => {
+                    JsWord::from(ident.value.to_ascii_lowercase())
+                }
+                swc_css_ast::DeclarationName::DashedIdent(..) => continue,
+            };
+
+            if !self.allowed_style_properties.contains(&name) {
+                continue;
+            }
+
+            let mut checker = StyleValueChecker {
+                allowed_functions: &self.allowed_style_functions,
+                is_allowed: true,
+            };
+
+            declaration.visit_with(&mut checker);
+
+            if !checker.is_allowed {
+                continue;
+            }
+
+            if !sanitized.is_empty() {
+                sanitized.push(';');
+            }
+
+            let wr = BasicCssWriter::new(&mut sanitized, None, BasicCssWriterConfig::default());
+            let mut gen = CodeGenerator::new(wr, CodegenConfig { minify: true });
+
+            gen.emit(&*declaration).ok()?;
+        }
+
+        if sanitized.is_empty() {
+            None
+        } else {
+            Some(sanitized)
+        }
+    }
+}
+
+/// Returns the URLs of the image candidates in a `srcset` attribute.
+///
+/// See https://html.spec.whatwg.org/multipage/images.html#parse-a-srcset-attribute
+fn srcset_urls(value: &str) -> impl Iterator<Item = &str> {
+    let mut rest = value;
+
+    std::iter::from_fn(move || {
+        rest = rest.trim_start_matches(|c: char| c.is_ascii_whitespace() || c == ',');
+
+        if rest.is_empty() {
+            return None;
+        }
+
+        let end = rest
+            .find(|c: char| c.is_ascii_whitespace())
+            .unwrap_or(rest.len());
+        let url = &rest[..end];
+        rest = &rest[end..];
+
+        // A URL followed by commas does not have descriptors
+        if url.ends_with(',') {
+            return Some(url.trim_end_matches(','));
+        }
+
+        // Skip descriptors, i.e. `2x` or `100w`
+        let mut depth = 0;
+        let mut descriptors_end = rest.len();
+
+        for (i, c) in rest.char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' if depth > 0 => depth -= 1,
+                ',' if depth == 0 => {
+                    descriptors_end = i + 1;
+
+                    break;
+                }
+                _ => {}
+            }
+        }
+
+        rest = &rest[descriptors_end..];
+
+        Some(url)
+    })
+}
+
+/// Rejects values which can load resources or run code, i.e. `url()` and
+/// `expression()`.
+struct StyleValueChecker<'a> {
+    allowed_functions: &'a AHashSet<JsWord>,
+    is_allowed: bool,
+}
+
+impl Visit for StyleValueChecker<'_> {
+    fn visit_url(&mut self, _: &Url) {
+        self.is_allowed = false;
+    }
+
+    fn visit_function(&mut self, n: &Function) {
+        let name = match &n.name {
+            swc_css_ast::FunctionName::Ident(ident) => {
+                JsWord::from(ident.value.to_ascii_lowercase())
+            }
+            swc_css_ast::FunctionName::DashedIdent(..) => {
+                self.is_allowed = false;
+
+                return;
+            }
+        };
+
+        if !self.allowed_functions.contains(&name) {
+            self.is_allowed = false;
+
+            return;
+        }
+
+        n.visit_children_with(self);
+    }
+
+    fn visit_token_and_span(&mut self, n: &TokenAndSpan) {
+        if let Token::Function { .. } | Token::Url { .. } | Token::BadUrl { .. } = n.token {
+            self.is_allowed = false;
+        }
+    }
+}
+
+/// Removes everything which is not allowed by `options` from
+/// `document_fragment`.
+pub fn sanitize_document_fragment(
+    document_fragment: &mut DocumentFragment,
+    options: &SanitizeOptions,
+) {
+    let sanitizer = Sanitizer::new(options);
+
+    document_fragment.children = sanitizer.sanitize_children(take(&mut document_fragment.children));
+}
@@ -0,0 +1,225 @@
+use serde::{Deserialize, Serialize};
+use swc_common::collections::AHashMap;
+
+/// Allow-lists of the sanitizer.
+///
+/// Elements and attributes are matched by their lowercase names in the HTML
+/// namespace. Elements from other namespaces (i.e. `svg` and `math`) are
+/// always removed with their content.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct SanitizeOptions {
+    /// Elements to keep. Other elements are replaced by their children.
+    #[serde(default = "default_allowed_elements")]
+    pub allowed_elements: Vec<String>,
+    /// Elements to remove with their content, i.e. `script` and `style`.
+    #[serde(default = "default_remove_content_elements")]
+    pub remove_content_elements: Vec<String>,
+    /// Attributes allowed on every allowed element.
+    #[serde(default = "default_allowed_attributes")]
+    pub allowed_attributes: Vec<String>,
+    /// Attributes allowed only on the given element.
+    #[serde(default = "default_allowed_element_attributes")]
+    pub allowed_element_attributes: AHashMap<String, Vec<String>>,
+    /// Schemes allowed in URL attributes (i.e. `href` and `src`). Attributes
+    /// with other schemes are removed.
+    #[serde(default = "default_allowed_url_schemes")]
+    pub allowed_url_schemes: Vec<String>,
+    /// Allow URLs without a scheme, i.e. `/path` or `#hash`.
+    #[serde(default = "true_by_default")]
+    pub allow_relative_urls: bool,
+    /// Properties to keep in the `style` attribute. The `style` attribute
+    /// should be allowed to use this.
+    #[serde(default = "default_allowed_style_properties")]
+    pub allowed_style_properties: Vec<String>,
+    /// Functions allowed in the values of the `style` attribute.
+    /// Declarations using other functions or `url()` are removed.
+    #[serde(default = "default_allowed_style_functions")]
+    pub allowed_style_functions: Vec<String>,
+    #[serde(default)]
+    pub allow_comments: bool,
+}
+
+impl Default for SanitizeOptions {
+    fn default() -> Self {
+        SanitizeOptions {
+            allowed_elements: default_allowed_elements(),
+            remove_content_elements: default_remove_content_elements(),
+            allowed_attributes: default_allowed_attributes(),
+            allowed_element_attributes: default_allowed_element_attributes(),
+            allowed_url_schemes: default_allowed_url_schemes(),
+            allow_relative_urls: true,
+            allowed_style_properties: default_allowed_style_properties(),
+            allowed_style_functions: default_allowed_style_functions(),
+            allow_comments: false,
+        }
+    }
+}
+
+const fn true_by_default() -> bool {
+    true
+}
+
+fn to_strings(list: &[&str]) -> Vec<String> {
+    list.iter().map(|s| s.to_string()).collect()
+}
+
+fn default_allowed_elements() -> Vec<String> {
+    to_strings(&[
+        "a",
+        "abbr",
+        "address",
+        "area",
+        "article",
+        "aside",
+        "b",
+        "bdi",
+        "bdo",
+        "blockquote",
+        "br",
+        "caption",
+        "cite",
+        "code",
+        "col",
+        "colgroup",
+        "data",
+        "dd",
+        "del",
+        "details",
+        "dfn",
+        "div",
+        "dl",
+        "dt",
+        "em",
+        "figcaption",
+        "figure",
+        "footer",
+        "h1",
+        "h2",
+        "h3",
+        "h4",
+        "h5",
+        "h6",
+        "header",
+        "hgroup",
+        "hr",
+        "i",
+        "img",
+        "ins",
+        "kbd",
+        "li",
+        "map",
+        "mark",
+        "nav",
+        "ol",
+        "p",
+        "pre",
+        "q",
+        "rp",
+        "rt",
+        "ruby",
+        "s",
+        "samp",
+        "section",
+        "small",
+        "span",
+        "strong",
+        "sub",
+        "summary",
+        "sup",
+        "table",
+        "tbody",
+        "td",
+        "tfoot",
+        "th",
+        "thead",
+        "time",
+        "tr",
+        "u",
+        "ul",
+        "var",
+        "wbr",
+    ])
+}
+
+fn default_remove_content_elements() -> Vec<String> {
+    to_strings(&[
+        "applet",
+        "embed",
+        "frame",
+        "frameset",
+        "iframe",
+        "noembed",
+        "noframes",
+        "noscript",
+        "object",
+        "plaintext",
+        "script",
+        "style",
+        "template",
+        "textarea",
+        "title",
+        "xmp",
+    ])
+}
+
+fn default_allowed_attributes() -> Vec<String> {
+    to_strings(&["dir", "lang", "style", "title"])
+}
+
+fn default_allowed_element_attributes() -> AHashMap<String, Vec<String>> {
+    [
+        ("a", &["href", "hreflang"][..]),
+        ("area", &["alt", "coords", "href", "shape"]),
+        ("blockquote", &["cite"]),
+        ("col", &["span"]),
+        ("colgroup", &["span"]),
+        ("data", &["value"]),
+        ("del", &["cite", "datetime"]),
+        ("details", &["open"]),
+        ("img", &["alt", "height", "src", "width"]),
+        ("ins", &["cite", "datetime"]),
+        ("li", &["value"]),
+        ("map", &["name"]),
+        ("ol", &["reversed", "start", "type"]),
+        ("q", &["cite"]),
+        ("td", &["colspan", "headers", "rowspan"]),
+        ("th", &["abbr", "colspan", "headers", "rowspan", "scope"]),
+        ("time", &["datetime"]),
+    ]
+    .into_iter()
+    .map(|(element, attributes)| (element.to_string(), to_strings(attributes)))
+    .collect()
+}
+
+fn default_allowed_url_schemes() -> Vec<String> {
+    to_strings(&["http", "https", "mailto", "tel"])
+}
+
+fn default_allowed_style_properties() -> Vec<String> {
+    to_strings(&[
+        "background-color",
+        "border",
+        "border-color",
+        "border-style",
+        "border-width",
+        "color",
+        "font-size",
+        "font-style",
+        "font-weight",
+        "height",
+        "line-height",
+        "margin",
+        "padding",
+        "text-align",
+        "text-decoration",
+        "vertical-align",
+        "white-space",
+        "width",
+    ])
+}
+
+fn default_allowed_style_functions() -> Vec<String> {
+    to_strings(&["calc", "hsl", "hsla", "rgb", "rgba"])
+}
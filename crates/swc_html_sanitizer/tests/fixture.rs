@@ -0,0 +1,81 @@
+#![deny(warnings)]
+
+use std::{fs::read_to_string, path::PathBuf};
+
+use swc_html_ast::{DocumentFragment, DocumentMode, Element, Namespace};
+use swc_html_codegen::{
+    writer::basic::{BasicHtmlWriter, BasicHtmlWriterConfig},
+    CodeGenerator, CodegenConfig, Emit,
+};
+use swc_html_parser::parse_file_as_document_fragment;
+use swc_html_sanitizer::{option::SanitizeOptions, sanitize_document_fragment};
+use testing::NormalizedOutput;
+
+#[testing::fixture("tests/fixture/**/input.html")]
+fn test_sanitize_document_fragment(input: PathBuf) {
+    let dir = input.parent().unwrap();
+    let output = dir.join("output.html");
+
+    testing::run_test(false, |cm, handler| {
+        let fm = cm.load_file(&input).unwrap();
+
+        let context_element = Element {
+            span: Default::default(),
+            namespace: Namespace::HTML,
+            tag_name: "div".into(),
+            attributes: vec![],
+            is_self_closing: false,
+            children: vec![],
+            content: None,
+        };
+
+        // User-supplied HTML is often invalid, so recoverable errors are ignored
+        let mut errors = vec![];
+        let result: Result<DocumentFragment, _> = parse_file_as_document_fragment(
+            &fm,
+            &context_element,
+            DocumentMode::NoQuirks,
+            None,
+            Default::default(),
+            &mut errors,
+        );
+
+        let mut document_fragment = match result {
+            Ok(document_fragment) => document_fragment,
+            Err(err) => {
+                err.to_diagnostics(handler).emit();
+
+                return Err(());
+            }
+        };
+        let config = dir.join("config.json");
+        let config = if config.exists() {
+            serde_json::from_str(&read_to_string(&config).unwrap()).unwrap()
+        } else {
+            SanitizeOptions::default()
+        };
+
+        sanitize_document_fragment(&mut document_fragment, &config);
+
+        let mut html_str = String::new();
+        {
+            let wr = BasicHtmlWriter::new(&mut html_str, None, BasicHtmlWriterConfig::default());
+            let mut gen = CodeGenerator::new(
+                wr,
+                CodegenConfig {
+                    context_element: Some(&context_element),
+                    ..Default::default()
+                },
+            );
+
+            gen.emit(&document_fragment).unwrap();
+        }
+
+        NormalizedOutput::new_raw(html_str)
+            .compare_to_file(&output)
+            .unwrap();
+
+        Ok(())
+    })
+    .unwrap();
+}
@@ -36,6 +36,9 @@ extern "C" {
     fn __get_transform_context(key: u32, allocated_ret_ptr: u32) -> u32;
     fn __get_experimental_transform_context(allocated_ret_ptr: u32) -> u32;
     fn __get_raw_experiemtal_transform_context(allocated_ret_ptr: u32) -> u32;
+    fn __copy_module_request_to_host_env(bytes_ptr: u32, bytes_ptr_len: u32);
+    fn __resolve_module_proxy(allocated_ret_ptr: u32) -> u32;
+    fn __read_module_file_proxy(allocated_ret_ptr: u32) -> u32;
 }
 
 #[cfg(feature = "__plugin_mode")]
@@ -95,6 +98,53 @@ impl TransformPluginProgramMetadata {
         None
     }
 
+    /// Asks the host to resolve `specifier` imported from the file at `from`.
+    /// Returns the path to the resolved file.
+    ///
+    /// Host only allows access to the paths configured for the plugin, so
+    /// this returns `None` if the resolved file is not allowed.
+    #[cfg_attr(not(target_arch = "wasm32"), allow(unused))]
+    pub fn resolve(&self, specifier: &str, from: &str) -> Option<String> {
+        #[cfg(target_arch = "wasm32")]
+        return read_returned_result_from_host(|serialized_ptr| unsafe {
+            let serialized =
+                swc_common::plugin::serialized::PluginSerializedBytes::try_serialize(&vec![
+                    from.to_string(),
+                    specifier.to_string(),
+                ])
+                .expect("Should be serializable");
+            let (request_ptr, request_ptr_len) = serialized.as_ptr();
+            __copy_module_request_to_host_env(request_ptr as u32, request_ptr_len as u32);
+
+            __resolve_module_proxy(serialized_ptr)
+        });
+
+        #[cfg(not(target_arch = "wasm32"))]
+        None
+    }
+
+    /// Asks the host to read the file at `path`, which is usually returned
+    /// from [Self::resolve].
+    ///
+    /// Returns `None` if the file does not exist or is not allowed.
+    #[cfg_attr(not(target_arch = "wasm32"), allow(unused))]
+    pub fn read_file(&self, path: &str) -> Option<String> {
+        #[cfg(target_arch = "wasm32")]
+        return read_returned_result_from_host(|serialized_ptr| unsafe {
+            let serialized = swc_common::plugin::serialized::PluginSerializedBytes::try_serialize(
+                &path.to_string(),
+            )
+            .expect("Should be serializable");
+            let (request_ptr, request_ptr_len) = serialized.as_ptr();
+            __copy_module_request_to_host_env(request_ptr as u32, request_ptr_len as u32);
+
+            __read_module_file_proxy(serialized_ptr)
+        });
+
+        #[cfg(not(target_arch = "wasm32"))]
+        None
+    }
+
     /// Returns experimental metadata context, but returns whole value as a
     /// HashMap.
     ///
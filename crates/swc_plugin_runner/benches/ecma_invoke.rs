@@ -79,6 +79,7 @@ fn bench_transform(b: &mut Bencher, plugin_dir: &Path) {
                         None,
                     )),
                     None,
+                    &Default::default(),
                 )
                 .unwrap();
 
@@ -3,6 +3,7 @@
 use std::{
     env::current_dir,
     path::{Path, PathBuf},
+    sync::Arc,
 };
 
 use anyhow::{Context, Error};
@@ -13,10 +14,16 @@ use swc_common::{
     sync::{Lazy, OnceCell},
 };
 #[cfg(not(target_arch = "wasm32"))]
-use wasmer::{BaseTunables, CpuFeature, Engine, Target, Triple};
+use wasmer::{BaseTunables, CompilerConfig, CpuFeature, Engine, Target, Triple};
 use wasmer::{Module, Store};
 #[cfg(all(not(target_arch = "wasm32"), feature = "filesystem_cache"))]
 use wasmer_cache::{Cache as WasmerCache, FileSystemCache, Hash};
+#[cfg(not(target_arch = "wasm32"))]
+use wasmer_middlewares::Metering;
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::limits::{instruction_cost, PluginTunables, StopCheck};
+use crate::limits::{Instrumentation, PluginLimits};
 
 #[cfg(all(not(feature = "filesystem_cache"), not(feature = "memory_cache")))]
 compile_error!("Plugin_runner should enable either filesystem, or memory cache");
@@ -35,7 +42,7 @@ compile_error!(
 /// however it is not gauranteed to be compatible across wasmer's
 /// internal changes.
 /// https://github.com/wasmerio/wasmer/issues/2781
-const MODULE_SERIALIZATION_VERSION: &str = "v4";
+const MODULE_SERIALIZATION_VERSION: &str = "v5";
 
 /// A shared instance to plugin's module bytecode cache.
 pub static PLUGIN_MODULE_CACHE: Lazy<PluginModuleCache> = Lazy::new(Default::default);
@@ -47,7 +54,9 @@ pub struct CacheInner {
     // A naive hashmap to the compiled plugin modules.
     // Current it doesn't have any invalidation or expiration logics like lru,
     // having a lot of plugins may create some memory pressure.
-    loaded_module_bytes: AHashMap<PathBuf, Module>,
+    // A plugin is compiled with the instrumentation for its limits, so the
+    // same binary may be stored multiple times.
+    loaded_module_bytes: AHashMap<(PathBuf, Instrumentation), Module>,
 }
 
 #[cfg(feature = "memory_cache")]
@@ -136,25 +145,32 @@ impl PluginModuleCache {
     /// [This code](https://github.com/swc-project/swc/blob/fc4c6708f24cda39640fbbfe56123f2f6eeb2474/crates/swc/src/plugin.rs#L19-L44)
     /// includes previous incorrect attempt to workaround file read issues.
     /// In actual transform, `plugins` is also being called per each transform.
+    ///
+    /// The module is compiled with the instrumentation required by `limits`.
     #[cfg(feature = "filesystem_cache")]
     #[tracing::instrument(level = "info", skip_all)]
-    pub fn load_module(&self, binary_path: &Path) -> Result<Module, Error> {
+    pub fn load_module(&self, binary_path: &Path, limits: &PluginLimits) -> Result<Module, Error> {
         let binary_path = binary_path.to_path_buf();
+        let instrumentation = Instrumentation::new(limits);
         let mut inner_cache = self.inner.get().expect("Cache should be available").lock();
 
         // if constructed Module is available in-memory, directly return it.
         // Note we do not invalidate in-memory cache currently: if wasm binary is
         // replaced in-process lifecycle (i.e devserver) it won't be reflected.
-        let in_memory_module = inner_cache.loaded_module_bytes.get(&binary_path);
+        let in_memory_module = inner_cache
+            .loaded_module_bytes
+            .get(&(binary_path.clone(), instrumentation));
         if let Some(module) = in_memory_module {
             return Ok(module.clone());
         }
 
         let module_bytes =
             std::fs::read(&binary_path).context("Cannot read plugin from specified path")?;
-        let module_bytes_hash = Hash::generate(&module_bytes);
+        // Compiled bytes differ by the instrumentation.
+        let module_bytes_hash =
+            Hash::generate(&[&module_bytes[..], instrumentation.cache_key().as_bytes()].concat());
 
-        let wasmer_store = new_store();
+        let wasmer_store = new_store(instrumentation);
 
         let load_cold_wasm_bytes = || {
             let span = tracing::span!(
@@ -187,14 +203,14 @@ impl PluginModuleCache {
 
         inner_cache
             .loaded_module_bytes
-            .insert(binary_path, module.clone());
+            .insert((binary_path, instrumentation), module.clone());
 
         Ok(module)
     }
 
     #[cfg(feature = "memory_cache")]
     #[tracing::instrument(level = "info", skip_all)]
-    pub fn load_module(&self, binary_path: &Path) -> Result<Module, Error> {
+    pub fn load_module(&self, binary_path: &Path, limits: &PluginLimits) -> Result<Module, Error> {
         let binary_path = binary_path.to_path_buf();
         let mut inner_cache = self.inner.get().expect("Cache should be available").lock();
 
@@ -210,7 +226,7 @@ impl PluginModuleCache {
         //TODO: In native runtime we have to reconstruct module using raw bytes in
         // memory cache. requires https://github.com/wasmerio/wasmer/pull/2821
 
-        let wasmer_store = new_store();
+        let wasmer_store = new_store(Instrumentation::new(limits));
         let module = Module::new(&wasmer_store, in_memory_module_bytes)?;
 
         Ok(module)
@@ -242,25 +258,37 @@ impl PluginModuleCache {
 
 /// Creates an instnace of  [Store].
 ///
-/// This function exists because we need to disable simd.
+/// This function exists because we need to disable simd, and to add the
+/// middlewares enforcing `PluginLimits`.
 #[cfg(not(target_arch = "wasm32"))]
 #[allow(unused_mut)]
-fn new_store() -> Store {
+fn new_store(instrumentation: Instrumentation) -> Store {
     // Use empty enumset to disable simd.
     let mut set = EnumSet::new();
     #[cfg(target_arch = "x86_64")]
     set.insert(CpuFeature::SSE2);
     let target = Target::new(Triple::host(), set);
 
-    let config = wasmer_compiler_cranelift::Cranelift::default();
+    // Middlewares are created per store, as they can't be shared across
+    // modules. Remaining points are reset for each transform.
+    let mut config = wasmer_compiler_cranelift::Cranelift::default();
+    if instrumentation.metering {
+        config.push_middleware(Arc::new(Metering::new(u64::MAX, instruction_cost)));
+    }
+    if instrumentation.stop_check {
+        config.push_middleware(Arc::new(StopCheck::default()));
+    }
+
     let engine = wasmer_engine_universal::Universal::new(config)
         .target(target)
         .engine();
-    let tunables = BaseTunables::for_target(engine.target());
+    let tunables = PluginTunables {
+        base: BaseTunables::for_target(engine.target()),
+    };
     Store::new_with_tunables(&engine, tunables)
 }
 
 #[cfg(target_arch = "wasm32")]
-fn new_store() -> Store {
+fn new_store(_: Instrumentation) -> Store {
     Store::default()
 }
@@ -0,0 +1,197 @@
+//! Host side of the transform plugins targeting `Stylesheet`, which are
+//! written with `#[css_plugin_transform]`.
+//!
+//! CSS plugins share the exported interface and the imported fns
+//! (metadata, diagnostics, comments, source map) with ECMAScript plugins, so
+//! this wraps [TransformExecutor] with the typed entrypoints for
+//! [Stylesheet].
+
+use std::{path::Path, sync::Arc};
+
+use anyhow::{Context, Error};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use swc_common::{
+    comments::SingleThreadedComments,
+    plugin::{metadata::TransformPluginMetadataContext, serialized::PluginSerializedBytes},
+    Mark, SourceMap,
+};
+use swc_css_ast::Stylesheet;
+
+use crate::{
+    cache::PluginModuleCache, limits::PluginLimits, transform_executor::TransformExecutor,
+};
+
+/// A tuple represents a CSS plugin.
+///
+/// First element is a path to the plugin binary, second is a JSON object
+/// that represents configuration option for the plugin.
+///
+/// Optional third element is the [PluginLimits] of the plugin.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct CssPluginConfig(
+    pub String,
+    pub serde_json::Value,
+    #[serde(default, skip_serializing_if = "PluginLimits::is_empty")] pub PluginLimits,
+);
+
+/// Executes a single CSS transform plugin.
+///
+/// The plugin is instantiated once, and it can transform any number of
+/// stylesheets.
+pub struct CssTransformExecutor {
+    inner: TransformExecutor,
+}
+
+impl CssTransformExecutor {
+    pub fn new(
+        path: &Path,
+        cache: &Lazy<PluginModuleCache>,
+        source_map: &Arc<SourceMap>,
+        metadata_context: &Arc<TransformPluginMetadataContext>,
+        plugin_config: Option<serde_json::Value>,
+        limits: &PluginLimits,
+    ) -> Result<Self, Error> {
+        Ok(Self {
+            inner: TransformExecutor::new(
+                path,
+                cache,
+                source_map,
+                metadata_context,
+                plugin_config,
+                limits,
+            )?,
+        })
+    }
+
+    pub fn is_transform_schema_compatible(&self) -> Result<bool, Error> {
+        self.inner.is_transform_schema_compatible()
+    }
+
+    /// Runs the plugin against a serialized [Stylesheet].
+    ///
+    /// CSS does not have hygiene, so plugins receive [Mark::root] as the
+    /// unresolved mark.
+    pub fn transform(
+        &mut self,
+        stylesheet: &PluginSerializedBytes,
+        should_enable_comments_proxy: bool,
+    ) -> Result<PluginSerializedBytes, Error> {
+        self.inner
+            .transform(stylesheet, Mark::root(), should_enable_comments_proxy)
+    }
+}
+
+/// The instances of a list of CSS plugins, which are created once and applied
+/// to any number of stylesheets, e.g. to every `<style>` of a HTML document.
+pub struct CssPlugins {
+    executors: Vec<(String, CssTransformExecutor)>,
+    metadata_context: Arc<TransformPluginMetadataContext>,
+}
+
+impl CssPlugins {
+    /// Instantiates `plugins`.
+    pub fn new(
+        plugins: &[CssPluginConfig],
+        cache: &Lazy<PluginModuleCache>,
+        source_map: &Arc<SourceMap>,
+        metadata_context: &Arc<TransformPluginMetadataContext>,
+    ) -> Result<Self, Error> {
+        let executors = plugins
+            .iter()
+            .map(|p| {
+                let executor = CssTransformExecutor::new(
+                    Path::new(&p.0),
+                    cache,
+                    source_map,
+                    metadata_context,
+                    Some(p.1.clone()),
+                    &p.2,
+                )?;
+
+                if !executor.is_transform_schema_compatible()? {
+                    anyhow::bail!("Cannot execute incompatible plugin {}", &p.0);
+                }
+
+                Ok((p.0.clone(), executor))
+            })
+            .collect::<Result<_, Error>>()?;
+
+        Ok(Self {
+            executors,
+            metadata_context: metadata_context.clone(),
+        })
+    }
+
+    /// Applies the plugins to `stylesheet`, in order.
+    ///
+    /// The stylesheet is serialized once and the transformed bytes are passed
+    /// from a plugin to the next one as-is.
+    #[tracing::instrument(level = "info", skip_all, name = "apply_css_plugins")]
+    pub fn apply(
+        &mut self,
+        stylesheet: Stylesheet,
+        comments: Option<SingleThreadedComments>,
+    ) -> Result<Stylesheet, Error> {
+        if self.executors.is_empty() {
+            return Ok(stylesheet);
+        }
+
+        // swc_plugin_macro will not inject proxy to the comments if comments is empty
+        let should_enable_comments_proxy = comments.is_some();
+
+        swc_plugin_proxy::COMMENTS
+            .set(
+                &swc_plugin_proxy::HostCommentsStorage { inner: comments },
+                || {
+                    let mut serialized = PluginSerializedBytes::try_serialize(&stylesheet)?;
+
+                    for (path, executor) in &mut self.executors {
+                        serialized = executor
+                            .transform(&serialized, should_enable_comments_proxy)
+                            .with_context(|| {
+                                format!(
+                                    "failed to invoke `{}` as css transform plugin at {}",
+                                    path,
+                                    Path::new(path).display()
+                                )
+                            })?;
+                    }
+
+                    serialized.deserialize()
+                },
+            )
+            .with_context(|| {
+                format!(
+                    "failed to invoke plugin on '{:?}'",
+                    self.metadata_context.filename
+                )
+            })
+    }
+}
+
+/// Applies `plugins` to `stylesheet`, in order.
+///
+/// Use [CssPlugins] to apply the same plugins to multiple stylesheets.
+pub fn apply_css_plugins(
+    stylesheet: Stylesheet,
+    plugins: &[CssPluginConfig],
+    cache: &Lazy<PluginModuleCache>,
+    source_map: &Arc<SourceMap>,
+    metadata_context: &Arc<TransformPluginMetadataContext>,
+    comments: Option<SingleThreadedComments>,
+) -> Result<Stylesheet, Error> {
+    if plugins.is_empty() {
+        return Ok(stylesheet);
+    }
+
+    CssPlugins::new(plugins, cache, source_map, metadata_context)
+        .with_context(|| {
+            format!(
+                "failed to invoke plugin on '{:?}'",
+                metadata_context.filename
+            )
+        })?
+        .apply(stylesheet, comments)
+}
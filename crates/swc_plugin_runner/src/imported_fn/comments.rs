@@ -9,7 +9,10 @@ use swc_common::{
 use swc_plugin_proxy::COMMENTS;
 use wasmer::{LazyInit, Memory, NativeFunc};
 
-use crate::memory_interop::{allocate_return_values_into_guest, copy_bytes_into_host};
+use crate::{
+    limits::{check_stopped, PluginStopped},
+    memory_interop::{allocate_return_values_into_guest, copy_bytes_into_host},
+};
 
 /// External environment state for imported (declared in host, injected into
 /// guest) fn for comments proxy.
@@ -42,11 +45,19 @@ impl CommentHostEnvironment {
 /// Copy given serialized byte into host's comment buffer, subsequent proxy call
 /// in the host can read it.
 #[tracing::instrument(level = "info", skip_all)]
-pub fn copy_comment_to_host_env(env: &CommentHostEnvironment, bytes_ptr: u32, bytes_ptr_len: u32) {
+pub fn copy_comment_to_host_env(
+    env: &CommentHostEnvironment,
+    bytes_ptr: u32,
+    bytes_ptr_len: u32,
+) -> Result<(), PluginStopped> {
+    check_stopped()?;
+
     if let Some(memory) = env.memory_ref() {
         (*env.mutable_comment_buffer.lock()) =
             copy_bytes_into_host(memory, bytes_ptr, bytes_ptr_len);
     }
+
+    Ok(())
 }
 
 /// Utility fn to unwrap necessary values for the comments fn operation when fn
@@ -123,7 +134,12 @@ where
     });
 }
 
-pub fn add_leading_comment_proxy(env: &CommentHostEnvironment, byte_pos: u32) {
+pub fn add_leading_comment_proxy(
+    env: &CommentHostEnvironment,
+    byte_pos: u32,
+) -> Result<(), PluginStopped> {
+    check_stopped()?;
+
     add_comments_inner(env, byte_pos, |comments, byte_pos, serialized| {
         comments.add_leading(
             byte_pos,
@@ -132,10 +148,17 @@ pub fn add_leading_comment_proxy(env: &CommentHostEnvironment, byte_pos: u32) {
                 .expect("Should be able to deserialize"),
         );
     });
+
+    Ok(())
 }
 
 #[tracing::instrument(level = "info", skip_all)]
-pub fn add_leading_comments_proxy(env: &CommentHostEnvironment, byte_pos: u32) {
+pub fn add_leading_comments_proxy(
+    env: &CommentHostEnvironment,
+    byte_pos: u32,
+) -> Result<(), PluginStopped> {
+    check_stopped()?;
+
     add_comments_inner(env, byte_pos, |comments, byte_pos, serialized| {
         comments.add_leading_comments(
             byte_pos,
@@ -144,18 +167,32 @@ pub fn add_leading_comments_proxy(env: &CommentHostEnvironment, byte_pos: u32) {
                 .expect("Should be able to deserialize"),
         );
     });
+
+    Ok(())
 }
 
 #[tracing::instrument(level = "info", skip_all)]
-pub fn has_leading_comments_proxy(byte_pos: u32) -> i32 {
-    unwrap_comments_storage_or_default(|comments| comments.has_leading(BytePos(byte_pos)) as i32, 0)
+pub fn has_leading_comments_proxy(byte_pos: u32) -> Result<i32, PluginStopped> {
+    check_stopped()?;
+
+    Ok(unwrap_comments_storage_or_default(
+        |comments| comments.has_leading(BytePos(byte_pos)) as i32,
+        0,
+    ))
 }
 
 #[tracing::instrument(level = "info", skip_all)]
-pub fn move_leading_comments_proxy(from_byte_pos: u32, to_byte_pos: u32) {
+pub fn move_leading_comments_proxy(
+    from_byte_pos: u32,
+    to_byte_pos: u32,
+) -> Result<(), PluginStopped> {
+    check_stopped()?;
+
     unwrap_comments_storage(|comments| {
         comments.move_leading(BytePos(from_byte_pos), BytePos(to_byte_pos))
     });
+
+    Ok(())
 }
 
 #[tracing::instrument(level = "info", skip_all)]
@@ -163,8 +200,10 @@ pub fn take_leading_comments_proxy(
     env: &CommentHostEnvironment,
     byte_pos: u32,
     allocated_ret_ptr: u32,
-) -> i32 {
-    unwrap_comments_storage_with_env(
+) -> Result<i32, PluginStopped> {
+    check_stopped()?;
+
+    Ok(unwrap_comments_storage_with_env(
         env,
         |comments, memory, alloc_guest_memory| {
             let leading_comments = comments.take_leading(BytePos(byte_pos));
@@ -185,7 +224,7 @@ pub fn take_leading_comments_proxy(
             }
         },
         0,
-    )
+    ))
 }
 
 /// Ask to get leading_comments from currently scoped comments held by
@@ -198,8 +237,10 @@ pub fn get_leading_comments_proxy(
     env: &CommentHostEnvironment,
     byte_pos: u32,
     allocated_ret_ptr: u32,
-) -> i32 {
-    unwrap_comments_storage_with_env(
+) -> Result<i32, PluginStopped> {
+    check_stopped()?;
+
+    Ok(unwrap_comments_storage_with_env(
         env,
         |comments, memory, alloc_guest_memory| {
             let leading_comments = comments.get_leading(BytePos(byte_pos));
@@ -220,11 +261,16 @@ pub fn get_leading_comments_proxy(
             }
         },
         0,
-    )
+    ))
 }
 
 #[tracing::instrument(level = "info", skip_all)]
-pub fn add_trailing_comment_proxy(env: &CommentHostEnvironment, byte_pos: u32) {
+pub fn add_trailing_comment_proxy(
+    env: &CommentHostEnvironment,
+    byte_pos: u32,
+) -> Result<(), PluginStopped> {
+    check_stopped()?;
+
     add_comments_inner(env, byte_pos, |comments, byte_pos, serialized| {
         comments.add_trailing(
             byte_pos,
@@ -233,10 +279,17 @@ pub fn add_trailing_comment_proxy(env: &CommentHostEnvironment, byte_pos: u32) {
                 .expect("Should be able to deserialize"),
         );
     });
+
+    Ok(())
 }
 
 #[tracing::instrument(level = "info", skip_all)]
-pub fn add_trailing_comments_proxy(env: &CommentHostEnvironment, byte_pos: u32) {
+pub fn add_trailing_comments_proxy(
+    env: &CommentHostEnvironment,
+    byte_pos: u32,
+) -> Result<(), PluginStopped> {
+    check_stopped()?;
+
     add_comments_inner(env, byte_pos, |comments, byte_pos, serialized| {
         comments.add_trailing_comments(
             byte_pos,
@@ -245,21 +298,32 @@ pub fn add_trailing_comments_proxy(env: &CommentHostEnvironment, byte_pos: u32)
                 .expect("Should be able to deserialize"),
         );
     });
+
+    Ok(())
 }
 
 #[tracing::instrument(level = "info", skip_all)]
-pub fn has_trailing_comments_proxy(byte_pos: u32) -> i32 {
-    unwrap_comments_storage_or_default(
+pub fn has_trailing_comments_proxy(byte_pos: u32) -> Result<i32, PluginStopped> {
+    check_stopped()?;
+
+    Ok(unwrap_comments_storage_or_default(
         |comments| comments.has_trailing(BytePos(byte_pos)) as i32,
         0,
-    )
+    ))
 }
 
 #[tracing::instrument(level = "info", skip_all)]
-pub fn move_trailing_comments_proxy(from_byte_pos: u32, to_byte_pos: u32) {
+pub fn move_trailing_comments_proxy(
+    from_byte_pos: u32,
+    to_byte_pos: u32,
+) -> Result<(), PluginStopped> {
+    check_stopped()?;
+
     unwrap_comments_storage(|comments| {
         comments.move_trailing(BytePos(from_byte_pos), BytePos(to_byte_pos))
     });
+
+    Ok(())
 }
 
 #[tracing::instrument(level = "info", skip_all)]
@@ -267,8 +331,10 @@ pub fn take_trailing_comments_proxy(
     env: &CommentHostEnvironment,
     byte_pos: u32,
     allocated_ret_ptr: u32,
-) -> i32 {
-    unwrap_comments_storage_with_env(
+) -> Result<i32, PluginStopped> {
+    check_stopped()?;
+
+    Ok(unwrap_comments_storage_with_env(
         env,
         |comments, memory, alloc_guest_memory| {
             let trailing_comments = comments.take_trailing(BytePos(byte_pos));
@@ -289,7 +355,7 @@ pub fn take_trailing_comments_proxy(
             }
         },
         0,
-    )
+    ))
 }
 
 #[tracing::instrument(level = "info", skip_all)]
@@ -297,8 +363,10 @@ pub fn get_trailing_comments_proxy(
     env: &CommentHostEnvironment,
     byte_pos: u32,
     allocated_ret_ptr: u32,
-) -> i32 {
-    unwrap_comments_storage_with_env(
+) -> Result<i32, PluginStopped> {
+    check_stopped()?;
+
+    Ok(unwrap_comments_storage_with_env(
         env,
         |comments, memory, alloc_guest_memory| {
             let trailing_comments = comments.get_trailing(BytePos(byte_pos));
@@ -319,10 +387,14 @@ pub fn get_trailing_comments_proxy(
             }
         },
         0,
-    )
+    ))
 }
 
 #[tracing::instrument(level = "info", skip_all)]
-pub fn add_pure_comment_proxy(byte_pos: u32) {
+pub fn add_pure_comment_proxy(byte_pos: u32) -> Result<(), PluginStopped> {
+    check_stopped()?;
+
     unwrap_comments_storage(|comments| comments.add_pure_comment(BytePos(byte_pos)));
+
+    Ok(())
 }
@@ -3,7 +3,10 @@ use std::sync::Arc;
 use parking_lot::Mutex;
 use wasmer::{LazyInit, Memory};
 
-use crate::memory_interop::copy_bytes_into_host;
+use crate::{
+    limits::{check_stopped, PluginStopped},
+    memory_interop::copy_bytes_into_host,
+};
 
 /// External environment to read swc_core diagnostics from the host.
 #[derive(wasmer::WasmerEnv, Clone)]
@@ -28,7 +31,11 @@ pub fn set_plugin_core_pkg_diagnostics(
     env: &DiagnosticContextHostEnvironment,
     bytes_ptr: u32,
     bytes_ptr_len: u32,
-) {
+) -> Result<(), PluginStopped> {
+    check_stopped()?;
+
     let memory = env.memory_ref().expect("Memory should be initialized");
     (*env.core_diag_buffer.lock()) = copy_bytes_into_host(memory, bytes_ptr, bytes_ptr_len);
+
+    Ok(())
 }
@@ -3,10 +3,20 @@ use swc_common::{
     plugin::serialized::PluginSerializedBytes,
 };
 
-use crate::{host_environment::BaseHostEnvironment, memory_interop::copy_bytes_into_host};
+use crate::{
+    host_environment::BaseHostEnvironment,
+    limits::{check_stopped, PluginStopped},
+    memory_interop::copy_bytes_into_host,
+};
 
 #[tracing::instrument(level = "info", skip_all)]
-pub fn emit_diagnostics(env: &BaseHostEnvironment, bytes_ptr: u32, bytes_ptr_len: u32) {
+pub fn emit_diagnostics(
+    env: &BaseHostEnvironment,
+    bytes_ptr: u32,
+    bytes_ptr_len: u32,
+) -> Result<(), PluginStopped> {
+    check_stopped()?;
+
     if let Some(memory) = env.memory_ref() {
         if HANDLER.is_set() {
             HANDLER.with(|handler| {
@@ -21,4 +31,6 @@ pub fn emit_diagnostics(env: &BaseHostEnvironment, bytes_ptr: u32, bytes_ptr_len
             })
         }
     }
+
+    Ok(())
 }
@@ -2,25 +2,39 @@ use swc_common::{
     hygiene::MutableMarkContext, plugin::serialized::PluginSerializedBytes, Mark, SyntaxContext,
 };
 
-use crate::{host_environment::BaseHostEnvironment, memory_interop::write_into_memory_view};
+use crate::{
+    host_environment::BaseHostEnvironment,
+    limits::{check_stopped, PluginStopped},
+    memory_interop::write_into_memory_view,
+};
 
 /// A proxy to Mark::fresh() that can be used in plugin.
 /// This it not directly called by plugin, instead `impl Mark` will selectively
 /// call this depends on the running context.
-pub fn mark_fresh_proxy(parent: u32) -> u32 {
-    Mark::fresh(Mark::from_u32(parent)).as_u32()
+pub fn mark_fresh_proxy(parent: u32) -> Result<u32, PluginStopped> {
+    check_stopped()?;
+
+    Ok(Mark::fresh(Mark::from_u32(parent)).as_u32())
 }
 
-pub fn mark_parent_proxy(self_mark: u32) -> u32 {
-    Mark::from_u32(self_mark).parent().as_u32()
+pub fn mark_parent_proxy(self_mark: u32) -> Result<u32, PluginStopped> {
+    check_stopped()?;
+
+    Ok(Mark::from_u32(self_mark).parent().as_u32())
 }
 
-pub fn mark_is_builtin_proxy(self_mark: u32) -> u32 {
-    Mark::from_u32(self_mark).is_builtin() as u32
+pub fn mark_is_builtin_proxy(self_mark: u32) -> Result<u32, PluginStopped> {
+    check_stopped()?;
+
+    Ok(Mark::from_u32(self_mark).is_builtin() as u32)
 }
 
-pub fn mark_set_builtin_proxy(self_mark: u32, is_builtin: u32) {
+pub fn mark_set_builtin_proxy(self_mark: u32, is_builtin: u32) -> Result<(), PluginStopped> {
+    check_stopped()?;
+
     Mark::from_u32(self_mark).set_is_builtin(is_builtin != 0);
+
+    Ok(())
 }
 
 /// A proxy to Mark::is_descendant_of_() that can be used in plugin.
@@ -34,7 +48,9 @@ pub fn mark_is_descendant_of_proxy(
     self_mark: u32,
     ancestor: u32,
     allocated_ptr: u32,
-) {
+) -> Result<(), PluginStopped> {
+    check_stopped()?;
+
     let self_mark = Mark::from_u32(self_mark);
     let ancestor = Mark::from_u32(ancestor);
 
@@ -47,10 +63,19 @@ pub fn mark_is_descendant_of_proxy(
 
         write_into_memory_view(memory, &serialized_bytes, |_| allocated_ptr);
     }
+
+    Ok(())
 }
 
 #[tracing::instrument(level = "info", skip_all)]
-pub fn mark_least_ancestor_proxy(env: &BaseHostEnvironment, a: u32, b: u32, allocated_ptr: u32) {
+pub fn mark_least_ancestor_proxy(
+    env: &BaseHostEnvironment,
+    a: u32,
+    b: u32,
+    allocated_ptr: u32,
+) -> Result<(), PluginStopped> {
+    check_stopped()?;
+
     let a = Mark::from_u32(a);
     let b = Mark::from_u32(b);
 
@@ -63,13 +88,20 @@ pub fn mark_least_ancestor_proxy(env: &BaseHostEnvironment, a: u32, b: u32, allo
 
         write_into_memory_view(memory, &serialized_bytes, |_| allocated_ptr);
     }
+
+    Ok(())
 }
 
 #[tracing::instrument(level = "info", skip_all)]
-pub fn syntax_context_apply_mark_proxy(self_syntax_context: u32, mark: u32) -> u32 {
-    SyntaxContext::from_u32(self_syntax_context)
+pub fn syntax_context_apply_mark_proxy(
+    self_syntax_context: u32,
+    mark: u32,
+) -> Result<u32, PluginStopped> {
+    check_stopped()?;
+
+    Ok(SyntaxContext::from_u32(self_syntax_context)
         .apply_mark(Mark::from_u32(mark))
-        .as_u32()
+        .as_u32())
 }
 
 #[tracing::instrument(level = "info", skip_all)]
@@ -77,7 +109,9 @@ pub fn syntax_context_remove_mark_proxy(
     env: &BaseHostEnvironment,
     self_mark: u32,
     allocated_ptr: u32,
-) {
+) -> Result<(), PluginStopped> {
+    check_stopped()?;
+
     let mut self_mark = SyntaxContext::from_u32(self_mark);
 
     let return_value = self_mark.remove_mark();
@@ -89,9 +123,13 @@ pub fn syntax_context_remove_mark_proxy(
 
         write_into_memory_view(memory, &serialized_bytes, |_| allocated_ptr);
     }
+
+    Ok(())
 }
 
 #[tracing::instrument(level = "info", skip_all)]
-pub fn syntax_context_outer_proxy(self_mark: u32) -> u32 {
-    SyntaxContext::from_u32(self_mark).outer().as_u32()
+pub fn syntax_context_outer_proxy(self_mark: u32) -> Result<u32, PluginStopped> {
+    check_stopped()?;
+
+    Ok(SyntaxContext::from_u32(self_mark).outer().as_u32())
 }
@@ -7,7 +7,10 @@ use swc_common::plugin::{
 };
 use wasmer::{LazyInit, Memory, NativeFunc};
 
-use crate::memory_interop::{allocate_return_values_into_guest, copy_bytes_into_host};
+use crate::{
+    limits::{check_stopped, PluginStopped},
+    memory_interop::{allocate_return_values_into_guest, copy_bytes_into_host},
+};
 
 #[derive(wasmer::WasmerEnv, Clone)]
 pub struct MetadataContextHostEnvironment {
@@ -46,18 +49,24 @@ pub fn copy_context_key_to_host_env(
     env: &MetadataContextHostEnvironment,
     bytes_ptr: u32,
     bytes_ptr_len: u32,
-) {
+) -> Result<(), PluginStopped> {
+    check_stopped()?;
+
     if let Some(memory) = env.memory_ref() {
         (*env.mutable_context_key_buffer.lock()) =
             copy_bytes_into_host(memory, bytes_ptr, bytes_ptr_len);
     }
+
+    Ok(())
 }
 
 #[tracing::instrument(level = "info", skip_all)]
 pub fn get_transform_plugin_config(
     env: &MetadataContextHostEnvironment,
     allocated_ret_ptr: u32,
-) -> i32 {
+) -> Result<i32, PluginStopped> {
+    check_stopped()?;
+
     if let Some(memory) = env.memory_ref() {
         if let Some(alloc_guest_memory) = env.alloc_guest_memory_ref() {
             let config_value = &env.transform_plugin_config;
@@ -76,12 +85,12 @@ pub fn get_transform_plugin_config(
                         &serialized,
                     );
 
-                    return 1;
+                    return Ok(1);
                 }
             }
         }
     }
-    0
+    Ok(0)
 }
 
 #[tracing::instrument(level = "info", skip_all)]
@@ -89,7 +98,9 @@ pub fn get_transform_context(
     env: &MetadataContextHostEnvironment,
     key: u32,
     allocated_ret_ptr: u32,
-) -> i32 {
+) -> Result<i32, PluginStopped> {
+    check_stopped()?;
+
     if let Some(memory) = env.memory_ref() {
         if let Some(alloc_guest_memory) = env.alloc_guest_memory_ref() {
             let value = env
@@ -107,18 +118,20 @@ pub fn get_transform_context(
                     &serialized,
                 );
 
-                return 1;
+                return Ok(1);
             }
         }
     }
-    0
+    Ok(0)
 }
 
 #[tracing::instrument(level = "info", skip_all)]
 pub fn get_experimental_transform_context(
     env: &MetadataContextHostEnvironment,
     allocated_ret_ptr: u32,
-) -> i32 {
+) -> Result<i32, PluginStopped> {
+    check_stopped()?;
+
     if let Some(memory) = env.memory_ref() {
         if let Some(alloc_guest_memory) = env.alloc_guest_memory_ref() {
             let context_key_buffer = &*env.mutable_context_key_buffer.lock();
@@ -143,18 +156,20 @@ pub fn get_experimental_transform_context(
                     &serialized,
                 );
 
-                return 1;
+                return Ok(1);
             }
         }
     }
-    0
+    Ok(0)
 }
 
 #[tracing::instrument(level = "info", skip_all)]
 pub fn get_raw_experiemtal_transform_context(
     env: &MetadataContextHostEnvironment,
     allocated_ret_ptr: u32,
-) -> i32 {
+) -> Result<i32, PluginStopped> {
+    check_stopped()?;
+
     if let Some(memory) = env.memory_ref() {
         let experimental_context = &env.metadata_context.experimental;
         let serialized_experimental_context_bytes =
@@ -167,11 +182,11 @@ pub fn get_raw_experiemtal_transform_context(
                 allocated_ret_ptr,
                 &serialized_experimental_context_bytes,
             );
-            1
+            Ok(1)
         } else {
-            0
+            Ok(0)
         }
     } else {
-        0
+        Ok(0)
     }
 }
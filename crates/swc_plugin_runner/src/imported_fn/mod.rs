@@ -68,6 +68,7 @@ mod diagnostics;
 mod handler;
 mod hygiene;
 mod metadata_context;
+mod module_loader;
 mod set_transform_result;
 mod source_map;
 mod span;
@@ -81,6 +82,10 @@ use self::{
         copy_context_key_to_host_env, get_experimental_transform_context, get_transform_context,
         get_transform_plugin_config, MetadataContextHostEnvironment,
     },
+    module_loader::{
+        copy_module_request_to_host_env, read_module_file_proxy, resolve_module_proxy,
+        ModuleLoaderHostEnvironment,
+    },
     source_map::{
         doctest_offset_line_proxy, lookup_byte_offset_proxy, lookup_char_pos_proxy,
         merge_spans_proxy, span_to_filename_proxy, span_to_lines_proxy, span_to_string_proxy,
@@ -293,6 +298,27 @@ pub(crate) fn build_import_object(
         lookup_byte_offset_proxy,
     );
 
+    // module_loader
+    let module_request_buffer = Arc::new(Mutex::new(vec![]));
+
+    let copy_module_request_to_host_env_fn_decl = Function::new_native_with_env(
+        store,
+        ModuleLoaderHostEnvironment::new(&module_request_buffer),
+        copy_module_request_to_host_env,
+    );
+
+    let resolve_module_fn_decl = Function::new_native_with_env(
+        store,
+        ModuleLoaderHostEnvironment::new(&module_request_buffer),
+        resolve_module_proxy,
+    );
+
+    let read_module_file_fn_decl = Function::new_native_with_env(
+        store,
+        ModuleLoaderHostEnvironment::new(&module_request_buffer),
+        read_module_file_proxy,
+    );
+
     imports! {
         "env" => {
             "__set_transform_plugin_core_pkg_diagnostics" => set_transform_plugin_core_pkg_diagnostics_fn_decl,
@@ -340,7 +366,11 @@ pub(crate) fn build_import_object(
             "__span_to_string_proxy" => span_to_string_fn_decl,
             "__span_to_filename_proxy" => span_to_filename_fn_decl,
             "__span_to_lines_proxy" => span_to_lines_fn_decl,
-            "__lookup_byte_offset_proxy" => lookup_byte_offset_fn_decl
+            "__lookup_byte_offset_proxy" => lookup_byte_offset_fn_decl,
+            // module_loader
+            "__copy_module_request_to_host_env" => copy_module_request_to_host_env_fn_decl,
+            "__resolve_module_proxy" => resolve_module_fn_decl,
+            "__read_module_file_proxy" => read_module_file_fn_decl
         }
     }
 }
@@ -0,0 +1,132 @@
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use swc_common::plugin::serialized::PluginSerializedBytes;
+use wasmer::{LazyInit, Memory, NativeFunc};
+
+use crate::{
+    limits::{check_stopped, PluginStopped},
+    memory_interop::{allocate_return_values_into_guest, copy_bytes_into_host},
+    module_loader::{PluginModuleLoader, PLUGIN_MODULE_LOADER},
+};
+
+/// External environment state for imported (declared in host, injected into
+/// guest) fn for module loader proxy.
+#[derive(wasmer::WasmerEnv, Clone)]
+pub struct ModuleLoaderHostEnvironment {
+    #[wasmer(export)]
+    pub memory: wasmer::LazyInit<Memory>,
+    /// Attached imported fn `__alloc` to the hostenvironment to allow any other
+    /// imported fn can allocate guest's memory space from host runtime.
+    #[wasmer(export(name = "__alloc"))]
+    pub alloc_guest_memory: LazyInit<NativeFunc<u32, u32>>,
+    /// A buffer to the arguments of the request plugin need to pass to the
+    /// host, i.e `Vec<String>` of `[base, specifier]` for `resolve`, or
+    /// `String` path for `read_file`.
+    pub mutable_request_buffer: Arc<Mutex<Vec<u8>>>,
+}
+
+impl ModuleLoaderHostEnvironment {
+    pub fn new(mutable_request_buffer: &Arc<Mutex<Vec<u8>>>) -> Self {
+        ModuleLoaderHostEnvironment {
+            memory: LazyInit::default(),
+            alloc_guest_memory: LazyInit::default(),
+            mutable_request_buffer: mutable_request_buffer.clone(),
+        }
+    }
+}
+
+/// Copy given serialized byte into host's request buffer, subsequent proxy
+/// call in the host can read it.
+#[tracing::instrument(level = "info", skip_all)]
+pub fn copy_module_request_to_host_env(
+    env: &ModuleLoaderHostEnvironment,
+    bytes_ptr: u32,
+    bytes_ptr_len: u32,
+) -> Result<(), PluginStopped> {
+    check_stopped()?;
+
+    if let Some(memory) = env.memory_ref() {
+        (*env.mutable_request_buffer.lock()) =
+            copy_bytes_into_host(memory, bytes_ptr, bytes_ptr_len);
+    }
+
+    Ok(())
+}
+
+/// Runs `f` with the module loader and the serialized request in the buffer,
+/// then returns its result to the guest.
+fn handle_module_request<F>(env: &ModuleLoaderHostEnvironment, allocated_ret_ptr: u32, f: F) -> i32
+where
+    F: FnOnce(&PluginModuleLoader, PluginSerializedBytes) -> Option<String>,
+{
+    if !PLUGIN_MODULE_LOADER.is_set() {
+        return 0;
+    }
+
+    if let Some(memory) = env.memory_ref() {
+        if let Some(alloc_guest_memory) = env.alloc_guest_memory_ref() {
+            let request =
+                PluginSerializedBytes::from_slice(&(*env.mutable_request_buffer.lock())[..]);
+
+            let value = PLUGIN_MODULE_LOADER.with(|loader| f(loader, request));
+
+            if let Some(value) = value {
+                let serialized =
+                    PluginSerializedBytes::try_serialize(&value).expect("Should be serializable");
+
+                allocate_return_values_into_guest(
+                    memory,
+                    alloc_guest_memory,
+                    allocated_ret_ptr,
+                    &serialized,
+                );
+
+                return 1;
+            }
+        }
+    }
+    0
+}
+
+/// Resolves `[base, specifier]` in the request buffer, returns the path to the
+/// resolved file.
+#[tracing::instrument(level = "info", skip_all)]
+pub fn resolve_module_proxy(
+    env: &ModuleLoaderHostEnvironment,
+    allocated_ret_ptr: u32,
+) -> Result<i32, PluginStopped> {
+    check_stopped()?;
+
+    Ok(handle_module_request(
+        env,
+        allocated_ret_ptr,
+        |loader, request| {
+            let request: Vec<String> = request.deserialize().expect("Should able to deserialize");
+
+            match &*request {
+                [base, specifier] => loader.resolve(base, specifier),
+                _ => None,
+            }
+        },
+    ))
+}
+
+/// Reads the file at the path in the request buffer.
+#[tracing::instrument(level = "info", skip_all)]
+pub fn read_module_file_proxy(
+    env: &ModuleLoaderHostEnvironment,
+    allocated_ret_ptr: u32,
+) -> Result<i32, PluginStopped> {
+    check_stopped()?;
+
+    Ok(handle_module_request(
+        env,
+        allocated_ret_ptr,
+        |loader, request| {
+            let path: String = request.deserialize().expect("Should able to deserialize");
+
+            loader.read_file(&path)
+        },
+    ))
+}
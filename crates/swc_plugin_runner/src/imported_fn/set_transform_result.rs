@@ -3,7 +3,10 @@ use std::sync::Arc;
 use parking_lot::Mutex;
 use wasmer::{LazyInit, Memory};
 
-use crate::memory_interop::copy_bytes_into_host;
+use crate::{
+    limits::{check_stopped, PluginStopped},
+    memory_interop::copy_bytes_into_host,
+};
 
 /// Environment states allow to return guest's transform result back to the
 /// host, using a buffer `transform_result` attached to the environment.
@@ -35,8 +38,12 @@ pub fn set_transform_result(
     env: &TransformResultHostEnvironment,
     bytes_ptr: u32,
     bytes_ptr_len: u32,
-) {
+) -> Result<(), PluginStopped> {
+    check_stopped()?;
+
     if let Some(memory) = env.memory_ref() {
         (*env.transform_result.lock()) = copy_bytes_into_host(memory, bytes_ptr, bytes_ptr_len);
     }
+
+    Ok(())
 }
@@ -8,7 +8,10 @@ use swc_common::{
 };
 use wasmer::{LazyInit, Memory, NativeFunc};
 
-use crate::memory_interop::{allocate_return_values_into_guest, write_into_memory_view};
+use crate::{
+    limits::{check_stopped, PluginStopped},
+    memory_interop::{allocate_return_values_into_guest, write_into_memory_view},
+};
 
 /// External environment state for imported (declared in host, injected into
 /// guest) fn for source map proxy.
@@ -48,7 +51,9 @@ pub fn lookup_char_pos_proxy(
     byte_pos: u32,
     should_include_source_file: i32,
     allocated_ret_ptr: u32,
-) -> i32 {
+) -> Result<i32, PluginStopped> {
+    check_stopped()?;
+
     if let Some(memory) = env.memory_ref() {
         let original_loc = (env.source_map.lock()).lookup_char_pos(BytePos(byte_pos));
         let ret = PartialLoc {
@@ -72,18 +77,23 @@ pub fn lookup_char_pos_proxy(
                 allocated_ret_ptr,
                 &serialized_loc_bytes,
             );
-            1
+            Ok(1)
         } else {
-            0
+            Ok(0)
         }
     } else {
-        0
+        Ok(0)
     }
 }
 
 #[tracing::instrument(level = "info", skip_all)]
-pub fn doctest_offset_line_proxy(env: &SourceMapHostEnvironment, orig: u32) -> u32 {
-    (env.source_map.lock()).doctest_offset_line(orig as usize) as u32
+pub fn doctest_offset_line_proxy(
+    env: &SourceMapHostEnvironment,
+    orig: u32,
+) -> Result<u32, PluginStopped> {
+    check_stopped()?;
+
+    Ok((env.source_map.lock()).doctest_offset_line(orig as usize) as u32)
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -97,7 +107,9 @@ pub fn merge_spans_proxy(
     rhs_hi: u32,
     rhs_ctxt: u32,
     allocated_ptr: u32,
-) -> i32 {
+) -> Result<i32, PluginStopped> {
+    check_stopped()?;
+
     if let Some(memory) = env.memory_ref() {
         let sp_lhs = Span {
             lo: BytePos(lhs_lo),
@@ -116,12 +128,12 @@ pub fn merge_spans_proxy(
             let serialized_bytes =
                 PluginSerializedBytes::try_serialize(&span).expect("Should be serializable");
             write_into_memory_view(memory, &serialized_bytes, |_| allocated_ptr);
-            1
+            Ok(1)
         } else {
-            0
+            Ok(0)
         }
     } else {
-        0
+        Ok(0)
     }
 }
 
@@ -133,7 +145,9 @@ pub fn span_to_lines_proxy(
     span_ctxt: u32,
     should_request_source_file: i32,
     allocated_ret_ptr: u32,
-) -> i32 {
+) -> Result<i32, PluginStopped> {
+    check_stopped()?;
+
     if let Some(memory) = env.memory_ref() {
         let span = Span {
             lo: BytePos(span_lo),
@@ -162,12 +176,12 @@ pub fn span_to_lines_proxy(
                 allocated_ret_ptr,
                 &serialized_loc_bytes,
             );
-            1
+            Ok(1)
         } else {
-            0
+            Ok(0)
         }
     } else {
-        0
+        Ok(0)
     }
 }
 
@@ -176,7 +190,9 @@ pub fn lookup_byte_offset_proxy(
     env: &SourceMapHostEnvironment,
     byte_pos: u32,
     allocated_ret_ptr: u32,
-) -> i32 {
+) -> Result<i32, PluginStopped> {
+    check_stopped()?;
+
     if let Some(memory) = env.memory_ref() {
         let byte_pos = BytePos(byte_pos);
         let ret = (env.source_map.lock()).lookup_byte_offset(byte_pos);
@@ -191,12 +207,12 @@ pub fn lookup_byte_offset_proxy(
                 allocated_ret_ptr,
                 &serialized_loc_bytes,
             );
-            1
+            Ok(1)
         } else {
-            0
+            Ok(0)
         }
     } else {
-        0
+        Ok(0)
     }
 }
 
@@ -207,7 +223,9 @@ pub fn span_to_string_proxy(
     span_hi: u32,
     span_ctxt: u32,
     allocated_ret_ptr: u32,
-) -> i32 {
+) -> Result<i32, PluginStopped> {
+    check_stopped()?;
+
     if let Some(memory) = env.memory_ref() {
         let span = Span {
             lo: BytePos(span_lo),
@@ -225,12 +243,12 @@ pub fn span_to_string_proxy(
                 allocated_ret_ptr,
                 &serialized_loc_bytes,
             );
-            1
+            Ok(1)
         } else {
-            0
+            Ok(0)
         }
     } else {
-        0
+        Ok(0)
     }
 }
 
@@ -241,7 +259,9 @@ pub fn span_to_filename_proxy(
     span_hi: u32,
     span_ctxt: u32,
     allocated_ret_ptr: u32,
-) -> i32 {
+) -> Result<i32, PluginStopped> {
+    check_stopped()?;
+
     if let Some(memory) = env.memory_ref() {
         let span = Span {
             lo: BytePos(span_lo),
@@ -259,11 +279,11 @@ pub fn span_to_filename_proxy(
                 allocated_ret_ptr,
                 &serialized_loc_bytes,
             );
-            1
+            Ok(1)
         } else {
-            0
+            Ok(0)
         }
     } else {
-        0
+        Ok(0)
     }
 }
@@ -1,4 +1,8 @@
-pub fn span_dummy_with_cmt_proxy() -> u32 {
+use crate::limits::{check_stopped, PluginStopped};
+
+pub fn span_dummy_with_cmt_proxy() -> Result<u32, PluginStopped> {
+    check_stopped()?;
+
     // Instead of trying to serialize whole span, send bytepos only
-    swc_common::Span::dummy_with_cmt().lo.0
+    Ok(swc_common::Span::dummy_with_cmt().lo.0)
 }
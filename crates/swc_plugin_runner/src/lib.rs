@@ -8,13 +8,17 @@ use swc_common::{plugin::metadata::TransformPluginMetadataContext, SourceMap};
 use transform_executor::TransformExecutor;
 
 pub mod cache;
+#[cfg(all(feature = "css", feature = "__rkyv"))]
+pub mod css;
 mod host_environment;
 #[cfg(feature = "__rkyv")]
 mod imported_fn;
+pub mod limits;
 #[cfg(feature = "__rkyv")]
 mod load_plugin;
 #[cfg(feature = "__rkyv")]
 mod memory_interop;
+pub mod module_loader;
 mod transform_executor;
 
 /**
@@ -22,8 +26,11 @@ mod transform_executor;
  * Internally this will try to load binary from given cache which can fail,
  * returns error in that case.
  *
- * Note you CANNOT reuse executor once trasform has been executed: executor
- * is stateful.
+ * Note executor is stateful: the plugin instance, including its memory, is
+ * kept between transforms.
+ *
+ * Plugin is stopped with `limits::PluginLimitExceeded` error if it runs out
+ * of `limits`.
  */
 #[cfg(feature = "__rkyv")]
 pub fn create_plugin_transform_executor(
@@ -32,8 +39,16 @@ pub fn create_plugin_transform_executor(
     source_map: &Arc<SourceMap>,
     metadata_context: &Arc<TransformPluginMetadataContext>,
     plugin_config: Option<serde_json::Value>,
+    limits: &limits::PluginLimits,
 ) -> Result<TransformExecutor, Error> {
-    TransformExecutor::new(path, cache, source_map, metadata_context, plugin_config)
+    TransformExecutor::new(
+        path,
+        cache,
+        source_map,
+        metadata_context,
+        plugin_config,
+        limits,
+    )
 }
 
 #[cfg(not(feature = "__rkyv"))]
@@ -43,6 +58,7 @@ pub fn create_plugin_transform_executor(
     source_map: &Arc<SourceMap>,
     metadata_context: &Arc<TransformPluginMetadataContext>,
     plugin_config: Option<serde_json::Value>,
+    limits: &limits::PluginLimits,
 ) -> Result<TransformExecutor, Error> {
     unimplemented!("Transform plugin cannot be used without serialization support")
 }
@@ -0,0 +1,475 @@
+//! Resource limits for the plugin execution.
+//!
+//! A plugin which runs out of its limits is stopped, and the transform fails
+//! with [PluginLimitExceeded] instead of hanging or aborting the host process.
+
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+pub use swc_common::plugin::limits::PluginLimits;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PluginLimitKind {
+    /// The plugin tried to use more memory than allowed, in bytes.
+    Memory(u64),
+    /// The plugin executed more instructions than allowed.
+    Fuel(u64),
+    /// The plugin did not finish the transform in time.
+    Timeout(Duration),
+}
+
+/// An error returned when a plugin runs out of its [PluginLimits].
+///
+/// Callers can get this from [anyhow::Error::downcast_ref].
+#[derive(Debug, Clone)]
+pub struct PluginLimitExceeded {
+    /// Path to the plugin binary.
+    pub plugin: String,
+    pub kind: PluginLimitKind,
+}
+
+impl fmt::Display for PluginLimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            PluginLimitKind::Memory(bytes) => write!(
+                f,
+                "plugin {} exceeded the memory limit of {} bytes",
+                self.plugin, bytes
+            ),
+            PluginLimitKind::Fuel(fuel) => write!(
+                f,
+                "plugin {} exceeded the fuel limit of {} instructions",
+                self.plugin, fuel
+            ),
+            PluginLimitKind::Timeout(timeout) => write!(
+                f,
+                "plugin {} did not finish in {}ms",
+                self.plugin,
+                timeout.as_millis()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PluginLimitExceeded {}
+
+/// Instrumentation compiled into a plugin to enforce its [PluginLimits].
+///
+/// Plugins without limits are compiled as is, so a module is cached per
+/// instrumentation.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct Instrumentation {
+    /// Counts the executed instructions, for [PluginLimits::fuel].
+    pub metering: bool,
+    /// Polls the stop flag, for [PluginLimits::timeout].
+    pub stop_check: bool,
+}
+
+impl Instrumentation {
+    pub fn new(limits: &PluginLimits) -> Self {
+        Instrumentation {
+            metering: limits.fuel.is_some(),
+            stop_check: limits.timeout().is_some(),
+        }
+    }
+
+    /// Suffix of the key of the compiled module in the filesystem cache.
+    pub fn cache_key(&self) -> &'static str {
+        match (self.metering, self.stop_check) {
+            (false, false) => "",
+            (true, false) => "metering",
+            (false, true) => "stop_check",
+            (true, true) => "metering,stop_check",
+        }
+    }
+}
+
+/// Tells a running plugin to stop.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct StopFlag(Arc<AtomicBool>);
+
+impl StopFlag {
+    pub fn stop(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    /// Must be called only while the plugin is not running.
+    pub fn reset(&self) {
+        self.0.store(false, Ordering::SeqCst);
+    }
+}
+
+better_scoped_tls::scoped_tls!(
+    /// Stop flag of the plugin running its transform on this thread.
+    pub(crate) static STOP_FLAG: StopFlag
+);
+
+/// An error returned by the host functions once the plugin is stopped, which
+/// traps the plugin.
+#[derive(Debug, Clone, Copy)]
+pub struct PluginStopped;
+
+impl fmt::Display for PluginStopped {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "plugin was stopped")
+    }
+}
+
+impl std::error::Error for PluginStopped {}
+
+/// Checked by every host function, so a stopped plugin traps on its next call
+/// into the host.
+pub(crate) fn check_stopped() -> Result<(), PluginStopped> {
+    if STOP_FLAG.is_set() && STOP_FLAG.with(|flag| flag.is_stopped()) {
+        return Err(PluginStopped);
+    }
+
+    Ok(())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use self::native::*;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod native {
+    use std::{
+        ptr::NonNull,
+        sync::{
+            mpsc::{self, RecvTimeoutError},
+            Arc, Mutex,
+        },
+        thread::{self, JoinHandle},
+        time::Duration,
+    };
+
+    use loupe::MemoryUsage;
+    use wasmer::{
+        vm::{self, MemoryError, MemoryStyle, TableStyle, VMMemoryDefinition, VMTableDefinition},
+        wasmparser::{Operator, Type as WpType, TypeOrFuncType},
+        BaseTunables, FunctionMiddleware, GlobalInit, GlobalType, LocalFunctionIndex, MemoryType,
+        MiddlewareError, MiddlewareReaderState, ModuleMiddleware, Mutability, Pages, TableType,
+        Tunables, Type,
+    };
+    use wasmer_types::{GlobalIndex, ModuleInfo};
+
+    use super::{StopFlag, STOP_FLAG};
+
+    better_scoped_tls::scoped_tls!(
+        /// Memory limit of the plugin being instantiated, in wasm pages.
+        ///
+        /// Compiled modules are shared across plugin instances, so the limit
+        /// is passed to [PluginTunables] only while instantiating the plugin.
+        pub(crate) static MEMORY_LIMIT: u32
+    );
+
+    /// Number of checks of [StopCheck] between two polls of the [StopFlag].
+    const POLL_INTERVAL: i32 = 1024;
+
+    /// Cost of each instruction for the metering.
+    pub(crate) fn instruction_cost(_: &Operator) -> u64 {
+        1
+    }
+
+    /// Makes the plugin trap once its [StopFlag] is set.
+    ///
+    /// wasmer cannot interrupt a running instance, and nothing else may touch
+    /// the instance while it's running. So the plugin polls the flag itself at
+    /// the start of every function and loop iteration, once in
+    /// [POLL_INTERVAL] checks: `memory.grow 0` runs [StoppableMemory::grow] on
+    /// the thread of the plugin, which fails once the plugin is stopped.
+    #[derive(Debug, Default, MemoryUsage)]
+    pub(crate) struct StopCheck {
+        /// Global counting down the checks until the next poll. Plugins
+        /// without a memory are not instrumented.
+        #[loupe(skip)]
+        countdown: Mutex<Option<GlobalIndex>>,
+    }
+
+    impl ModuleMiddleware for StopCheck {
+        fn generate_function_middleware(
+            &self,
+            _: LocalFunctionIndex,
+        ) -> Box<dyn FunctionMiddleware> {
+            Box::new(FunctionStopCheck {
+                countdown: *self.countdown.lock().unwrap(),
+                started: false,
+            })
+        }
+
+        fn transform_module_info(&self, module_info: &mut ModuleInfo) {
+            if module_info.memories.is_empty() {
+                return;
+            }
+
+            let countdown = module_info
+                .globals
+                .push(GlobalType::new(Type::I32, Mutability::Var));
+            module_info
+                .global_initializers
+                .push(GlobalInit::I32Const(POLL_INTERVAL));
+
+            *self.countdown.lock().unwrap() = Some(countdown);
+        }
+    }
+
+    #[derive(Debug)]
+    struct FunctionStopCheck {
+        countdown: Option<GlobalIndex>,
+        started: bool,
+    }
+
+    impl FunctionStopCheck {
+        fn push_check<'a>(&self, state: &mut MiddlewareReaderState<'a>) {
+            let global_index = match self.countdown {
+                Some(countdown) => countdown.as_u32(),
+                None => return,
+            };
+
+            // if countdown == 0 {
+            //     countdown = POLL_INTERVAL;
+            //     if memory.grow(0) == -1 { unreachable }
+            // } else {
+            //     countdown -= 1;
+            // }
+            state.push_operator(Operator::GlobalGet { global_index });
+            state.push_operator(Operator::I32Eqz);
+            state.push_operator(Operator::If {
+                ty: TypeOrFuncType::Type(WpType::EmptyBlockType),
+            });
+            state.push_operator(Operator::I32Const {
+                value: POLL_INTERVAL,
+            });
+            state.push_operator(Operator::GlobalSet { global_index });
+            state.push_operator(Operator::I32Const { value: 0 });
+            state.push_operator(Operator::MemoryGrow {
+                mem: 0,
+                mem_byte: 0,
+            });
+            state.push_operator(Operator::I32Const { value: -1 });
+            state.push_operator(Operator::I32Eq);
+            state.push_operator(Operator::If {
+                ty: TypeOrFuncType::Type(WpType::EmptyBlockType),
+            });
+            state.push_operator(Operator::Unreachable);
+            state.push_operator(Operator::End);
+            state.push_operator(Operator::Else);
+            state.push_operator(Operator::GlobalGet { global_index });
+            state.push_operator(Operator::I32Const { value: 1 });
+            state.push_operator(Operator::I32Sub);
+            state.push_operator(Operator::GlobalSet { global_index });
+            state.push_operator(Operator::End);
+        }
+    }
+
+    impl FunctionMiddleware for FunctionStopCheck {
+        fn feed<'a>(
+            &mut self,
+            operator: Operator<'a>,
+            state: &mut MiddlewareReaderState<'a>,
+        ) -> Result<(), MiddlewareError> {
+            if !self.started {
+                self.started = true;
+                self.push_check(state);
+            }
+
+            match operator {
+                Operator::Loop { .. } => {
+                    state.push_operator(operator);
+                    self.push_check(state);
+                }
+                _ => state.push_operator(operator),
+            }
+
+            Ok(())
+        }
+    }
+
+    /// Memory of a plugin with a timeout, which fails to grow once the plugin
+    /// is stopped. See [StopCheck].
+    #[derive(Debug, MemoryUsage)]
+    struct StoppableMemory {
+        inner: Arc<dyn vm::Memory>,
+        #[loupe(skip)]
+        stop: StopFlag,
+    }
+
+    impl vm::Memory for StoppableMemory {
+        fn ty(&self) -> MemoryType {
+            self.inner.ty()
+        }
+
+        fn style(&self) -> &MemoryStyle {
+            self.inner.style()
+        }
+
+        fn size(&self) -> Pages {
+            self.inner.size()
+        }
+
+        fn grow(&self, delta: Pages) -> Result<Pages, MemoryError> {
+            if self.stop.is_stopped() {
+                return Err(MemoryError::Generic("plugin was stopped".to_string()));
+            }
+
+            self.inner.grow(delta)
+        }
+
+        fn vmmemory(&self) -> NonNull<VMMemoryDefinition> {
+            self.inner.vmmemory()
+        }
+    }
+
+    /// Stops the plugin if the transform does not finish in time.
+    ///
+    /// The watchdog only sets the [StopFlag]. The plugin checks it through
+    /// [StopCheck], and the host functions check it on every call.
+    pub(crate) struct Deadline {
+        stop: StopFlag,
+        done: mpsc::Sender<()>,
+        watchdog: JoinHandle<()>,
+    }
+
+    impl Deadline {
+        /// `stop` is the flag the plugin was instantiated with.
+        pub fn start(stop: &StopFlag, timeout: Duration) -> Self {
+            // The plugin may have finished its previous transform right after the
+            // deadline.
+            stop.reset();
+
+            let (done, receiver) = mpsc::channel();
+
+            let watchdog = {
+                let stop = stop.clone();
+
+                thread::spawn(move || {
+                    if let Err(RecvTimeoutError::Timeout) = receiver.recv_timeout(timeout) {
+                        stop.stop();
+                    }
+                })
+            };
+
+            Deadline {
+                stop: stop.clone(),
+                done,
+                watchdog,
+            }
+        }
+
+        /// Runs `f` with the host functions checking this deadline.
+        pub fn run<F, Ret>(&self, f: F) -> Ret
+        where
+            F: FnOnce() -> Ret,
+        {
+            STOP_FLAG.set(&self.stop, f)
+        }
+
+        /// Returns true if the deadline was exceeded.
+        pub fn stop(self) -> bool {
+            let _ = self.done.send(());
+            let _ = self.watchdog.join();
+            self.stop.is_stopped()
+        }
+    }
+
+    /// Makes `memory` a [StoppableMemory], if the plugin is instantiated with a
+    /// [StopFlag].
+    fn stoppable(memory: Arc<dyn vm::Memory>) -> Arc<dyn vm::Memory> {
+        if !STOP_FLAG.is_set() {
+            return memory;
+        }
+
+        Arc::new(StoppableMemory {
+            inner: memory,
+            stop: STOP_FLAG.with(|stop| stop.clone()),
+        })
+    }
+
+    /// [Tunables] which caps the memory of the plugin to [MEMORY_LIMIT], and
+    /// lets [StopCheck] poll the [StopFlag] of the plugin.
+    #[derive(MemoryUsage)]
+    pub(crate) struct PluginTunables {
+        pub base: BaseTunables,
+    }
+
+    impl PluginTunables {
+        fn adjust_memory(&self, requested: &MemoryType) -> Result<MemoryType, MemoryError> {
+            if !MEMORY_LIMIT.is_set() {
+                return Ok(*requested);
+            }
+
+            let limit = Pages(MEMORY_LIMIT.with(|limit| *limit));
+            if requested.minimum > limit {
+                return Err(MemoryError::Generic(
+                    "Minimum exceeds the allowed memory limit".to_string(),
+                ));
+            }
+
+            let mut adjusted = *requested;
+            adjusted.maximum = Some(match requested.maximum {
+                Some(maximum) if maximum < limit => maximum,
+                _ => limit,
+            });
+            Ok(adjusted)
+        }
+    }
+
+    impl Tunables for PluginTunables {
+        fn memory_style(&self, memory: &MemoryType) -> MemoryStyle {
+            self.base.memory_style(memory)
+        }
+
+        fn table_style(&self, table: &TableType) -> TableStyle {
+            self.base.table_style(table)
+        }
+
+        fn create_host_memory(
+            &self,
+            ty: &MemoryType,
+            style: &MemoryStyle,
+        ) -> Result<Arc<dyn vm::Memory>, MemoryError> {
+            let adjusted = self.adjust_memory(ty)?;
+            let memory = self.base.create_host_memory(&adjusted, style)?;
+            Ok(stoppable(memory))
+        }
+
+        unsafe fn create_vm_memory(
+            &self,
+            ty: &MemoryType,
+            style: &MemoryStyle,
+            vm_definition_location: NonNull<VMMemoryDefinition>,
+        ) -> Result<Arc<dyn vm::Memory>, MemoryError> {
+            let adjusted = self.adjust_memory(ty)?;
+            let memory = self
+                .base
+                .create_vm_memory(&adjusted, style, vm_definition_location)?;
+            Ok(stoppable(memory))
+        }
+
+        fn create_host_table(
+            &self,
+            ty: &TableType,
+            style: &TableStyle,
+        ) -> Result<Arc<dyn vm::Table>, String> {
+            self.base.create_host_table(ty, style)
+        }
+
+        unsafe fn create_vm_table(
+            &self,
+            ty: &TableType,
+            style: &TableStyle,
+            vm_definition_location: NonNull<VMTableDefinition>,
+        ) -> Result<Arc<dyn vm::Table>, String> {
+            self.base.create_vm_table(ty, style, vm_definition_location)
+        }
+    }
+}
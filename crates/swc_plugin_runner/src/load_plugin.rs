@@ -6,19 +6,21 @@ use swc_common::{plugin::metadata::TransformPluginMetadataContext, SourceMap};
 use wasmer::{ChainableNamedResolver, Instance};
 use wasmer_wasi::{is_wasi_module, WasiState};
 
-use crate::imported_fn::build_import_object;
+use crate::{imported_fn::build_import_object, limits::PluginLimits};
 
 #[tracing::instrument(level = "info", skip_all)]
+#[cfg_attr(target_arch = "wasm32", allow(unused_variables))]
 pub fn load_plugin(
     plugin_path: &std::path::Path,
     cache: &once_cell::sync::Lazy<crate::cache::PluginModuleCache>,
     source_map: &Arc<SourceMap>,
     metadata_context: &Arc<TransformPluginMetadataContext>,
     plugin_config: Option<serde_json::Value>,
+    limits: &PluginLimits,
     transform_result_buffer: &Arc<Mutex<Vec<u8>>>,
     core_diag_buffer: &Arc<Mutex<Vec<u8>>>,
 ) -> Result<Instance, Error> {
-    let module = cache.load_module(plugin_path)?;
+    let module = cache.load_module(plugin_path, limits)?;
 
     let import_object = build_import_object(
         &module,
@@ -33,39 +35,49 @@ pub fn load_plugin(
     // Wasi specific env need to be initialized if given module targets wasm32-wasi.
     // TODO: wasm host native runtime throws 'Memory should be set on `WasiEnv`
     // first'
-    let instance = if is_wasi_module(&module) {
-        // Create the `WasiEnv`.
-        let mut wasi_env = WasiState::new(
-            plugin_path
-                .file_name()
-                .and_then(|f| f.to_str())
-                .expect("Plugin path missing file name"),
-        );
+    let instantiate = || -> Result<Instance, Error> {
+        let instance = if is_wasi_module(&module) {
+            // Create the `WasiEnv`.
+            let mut wasi_env = WasiState::new(
+                plugin_path
+                    .file_name()
+                    .and_then(|f| f.to_str())
+                    .expect("Plugin path missing file name"),
+            );
 
-        // Implicitly enable filesystem access for the wasi plugin to cwd.
-        //
-        // This allows wasi plugin can read arbitary data (i.e node_modules) or produce
-        // output for post process (i.e .lcov coverage data) directly.
-        //
-        // TODO: this is not finalized decision
-        // - should we support this?
-        // - can we limit to allowlisted input / output only?
-        // - should there be a top-level config from .swcrc to manually override this?
-        let wasi_env = if let Ok(cwd) = env::current_dir() {
-            wasi_env.map_dir("/cwd", cwd)?
+            // Implicitly enable filesystem access for the wasi plugin to cwd.
+            //
+            // This allows wasi plugin can read arbitary data (i.e node_modules) or produce
+            // output for post process (i.e .lcov coverage data) directly.
+            //
+            // TODO: this is not finalized decision
+            // - should we support this?
+            // - can we limit to allowlisted input / output only?
+            // - should there be a top-level config from .swcrc to manually override this?
+            let wasi_env = if let Ok(cwd) = env::current_dir() {
+                wasi_env.map_dir("/cwd", cwd)?
+            } else {
+                &mut wasi_env
+            };
+
+            let mut wasi_env = wasi_env.finalize()?;
+
+            // Generate an `ImportObject` from wasi_env, overwrite into imported_object
+            let wasi_env_import_object = wasi_env.import_object(&module)?;
+            let chained_resolver = import_object.chain_front(wasi_env_import_object);
+            Instance::new(&module, &chained_resolver)
         } else {
-            &mut wasi_env
+            Instance::new(&module, &import_object)
         };
 
-        let mut wasi_env = wasi_env.finalize()?;
-
-        // Generate an `ImportObject` from wasi_env, overwrite into imported_object
-        let wasi_env_import_object = wasi_env.import_object(&module)?;
-        let chained_resolver = import_object.chain_front(wasi_env_import_object);
-        Instance::new(&module, &chained_resolver)
-    } else {
-        Instance::new(&module, &import_object)
+        instance.context("Failed to create plugin instance")
     };
 
-    instance.context("Failed to create plugin instance")
+    // Memory of the plugin is allocated while instantiating it.
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Some(memory_limit) = limits.memory_pages() {
+        return crate::limits::MEMORY_LIMIT.set(&memory_limit, instantiate);
+    }
+
+    instantiate()
 }
@@ -0,0 +1,80 @@
+//! Host-controlled access to other modules for the plugins.
+//!
+//! Plugins can ask the host to resolve an import specifier or to read a file,
+//! but only files under the allowed paths are visible to them.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use swc_common::FileName;
+use swc_ecma_loader::resolve::Resolve;
+
+/// Resolves and reads modules on behalf of the plugins.
+///
+/// The host should set this to [PLUGIN_MODULE_LOADER] while executing
+/// plugins. If it's not set, every request from the plugins fails.
+pub struct PluginModuleLoader {
+    resolver: Box<dyn Resolve>,
+    allowed_paths: Vec<PathBuf>,
+}
+
+impl PluginModuleLoader {
+    /// `allowed_paths` are the files or directories plugins can access.
+    /// Paths which do not exist are ignored.
+    pub fn new(resolver: Box<dyn Resolve>, allowed_paths: &[PathBuf]) -> Self {
+        Self {
+            resolver,
+            allowed_paths: allowed_paths
+                .iter()
+                .filter_map(|path| path.canonicalize().ok())
+                .collect(),
+        }
+    }
+
+    fn allowed_path(&self, path: &Path) -> Option<PathBuf> {
+        let path = path.canonicalize().ok()?;
+
+        if self
+            .allowed_paths
+            .iter()
+            .any(|allowed| path.starts_with(allowed))
+        {
+            Some(path)
+        } else {
+            tracing::debug!("Plugin tried to access {}", path.display());
+            None
+        }
+    }
+
+    /// Resolves `specifier` imported from the file at `base`.
+    ///
+    /// Returns [None] if it cannot be resolved or the resolved file is not
+    /// allowed.
+    pub fn resolve(&self, base: &str, specifier: &str) -> Option<String> {
+        let resolved = self
+            .resolver
+            .resolve(&FileName::Real(PathBuf::from(base)), specifier)
+            .ok()?;
+
+        match resolved {
+            FileName::Real(path) => self
+                .allowed_path(&path)
+                .map(|path| path.to_string_lossy().into_owned()),
+            _ => None,
+        }
+    }
+
+    /// Returns [None] if the file does not exist or is not allowed.
+    pub fn read_file(&self, path: &str) -> Option<String> {
+        let path = self.allowed_path(Path::new(path))?;
+
+        fs::read_to_string(path).ok()
+    }
+}
+
+better_scoped_tls::scoped_tls!(
+    /// [PluginModuleLoader] for the current transform.
+    pub static PLUGIN_MODULE_LOADER: PluginModuleLoader
+);
@@ -14,7 +14,14 @@ use swc_common::{
     SourceMap,
 };
 use wasmer::Instance;
+#[cfg(all(feature = "__rkyv", not(target_arch = "wasm32")))]
+use wasmer_middlewares::metering::{get_remaining_points, set_remaining_points, MeteringPoints};
 
+use crate::limits::PluginLimits;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::limits::StopFlag;
+#[cfg(all(feature = "__rkyv", not(target_arch = "wasm32")))]
+use crate::limits::{Deadline, PluginLimitExceeded, PluginLimitKind, STOP_FLAG};
 #[cfg(feature = "__rkyv")]
 use crate::memory_interop::write_into_memory_view;
 
@@ -32,6 +39,15 @@ pub struct TransformExecutor {
     // Reference to the pointers successfully allocated which'll be freed by Drop.
     allocated_ptr_vec: Vec<(u32, u32)>,
     transform_result: Arc<Mutex<Vec<u8>>>,
+    // Path to the plugin binary, to report errors.
+    plugin_name: String,
+    limits: PluginLimits,
+    // Stops the plugin when it runs out of time.
+    #[cfg(not(target_arch = "wasm32"))]
+    stop: StopFlag,
+    // Set once the plugin is stopped for running out of its limits. The
+    // instance can't run anything after that, including `__free`.
+    stopped: bool,
     // diagnostic metadata for the swc_core plugin binary uses.
     pub plugin_core_diag: PluginCorePkgDiagnostics,
 }
@@ -40,7 +56,7 @@ pub struct TransformExecutor {
 impl TransformExecutor {
     #[tracing::instrument(
         level = "info",
-        skip(cache, source_map, metadata_context, plugin_config)
+        skip(cache, source_map, metadata_context, plugin_config, limits)
     )]
     pub fn new(
         path: &std::path::Path,
@@ -48,19 +64,36 @@ impl TransformExecutor {
         source_map: &Arc<SourceMap>,
         metadata_context: &Arc<TransformPluginMetadataContext>,
         plugin_config: Option<serde_json::Value>,
+        limits: &PluginLimits,
     ) -> Result<TransformExecutor, Error> {
         let transform_result = Arc::new(Mutex::new(vec![]));
         let core_diag_buffer = Arc::new(Mutex::new(vec![]));
 
-        let instance = crate::load_plugin::load_plugin(
-            path,
-            cache,
-            source_map,
-            metadata_context,
-            plugin_config,
-            &transform_result,
-            &core_diag_buffer,
-        )?;
+        let load_plugin = || {
+            crate::load_plugin::load_plugin(
+                path,
+                cache,
+                source_map,
+                metadata_context,
+                plugin_config,
+                limits,
+                &transform_result,
+                &core_diag_buffer,
+            )
+        };
+
+        // The memory of a plugin with a timeout checks the stop flag, see
+        // `StopCheck`.
+        #[cfg(not(target_arch = "wasm32"))]
+        let stop = StopFlag::default();
+        #[cfg(not(target_arch = "wasm32"))]
+        let instance = if limits.timeout().is_some() {
+            STOP_FLAG.set(&stop, load_plugin)?
+        } else {
+            load_plugin()?
+        };
+        #[cfg(target_arch = "wasm32")]
+        let instance = load_plugin()?;
 
         // As soon as instance is ready, host calls a fn to read plugin's swc_core pkg
         // diagnostics as `handshake`. Once read those values will be available across
@@ -93,6 +126,11 @@ impl TransformExecutor {
             instance,
             allocated_ptr_vec: Vec::with_capacity(3),
             transform_result,
+            plugin_name: path.display().to_string(),
+            limits: *limits,
+            #[cfg(not(target_arch = "wasm32"))]
+            stop,
+            stopped: false,
             plugin_core_diag: diag_result,
         };
 
@@ -187,22 +225,108 @@ impl TransformExecutor {
         unresolved_mark: swc_common::Mark,
         should_enable_comments_proxy: bool,
     ) -> Result<PluginSerializedBytes, Error> {
+        if self.stopped {
+            return Err(anyhow!(
+                "plugin {} was stopped by a previous transform",
+                self.plugin_name
+            ));
+        }
+
         let should_enable_comments_proxy = u32::from(should_enable_comments_proxy);
         let guest_program_ptr = self.write_bytes_into_guest(program)?;
 
-        let result = self.exported_plugin_transform.call(
-            guest_program_ptr.0,
-            guest_program_ptr.1,
-            unresolved_mark.as_u32(),
-            should_enable_comments_proxy,
-        )?;
+        #[cfg(not(target_arch = "wasm32"))]
+        let deadline = {
+            if let Some(fuel) = self.limits.fuel {
+                set_remaining_points(&self.instance, fuel);
+            }
+
+            self.limits
+                .timeout()
+                .map(|timeout| Deadline::start(&self.stop, timeout))
+        };
+
+        let call = || {
+            self.exported_plugin_transform.call(
+                guest_program_ptr.0,
+                guest_program_ptr.1,
+                unresolved_mark.as_u32(),
+                should_enable_comments_proxy,
+            )
+        };
+
+        #[cfg(not(target_arch = "wasm32"))]
+        let result = match &deadline {
+            Some(deadline) => deadline.run(call),
+            None => call(),
+        };
+        #[cfg(target_arch = "wasm32")]
+        let result = call();
+
+        #[cfg(not(target_arch = "wasm32"))]
+        let result = {
+            let timed_out = deadline.map(Deadline::stop).unwrap_or(false);
+
+            result.map_err(|err| match self.exceeded_limit(timed_out) {
+                Some(kind) => {
+                    self.stopped = true;
+                    Error::new(PluginLimitExceeded {
+                        plugin: self.plugin_name.clone(),
+                        kind,
+                    })
+                }
+                None => err.into(),
+            })
+        };
+
+        let transformed = self.read_transformed_result_bytes_from_guest(result?);
+
+        // Frees the program now, so the executor can run more transforms
+        self.free_allocated_bytes();
 
-        self.read_transformed_result_bytes_from_guest(result)
+        transformed
+    }
+
+    fn free_allocated_bytes(&mut self) {
+        for ptr in self.allocated_ptr_vec.drain(..) {
+            self.exported_plugin_free
+                .call(ptr.0, ptr.1)
+                .expect("Failed to free memory allocated in the plugin");
+        }
+    }
+
+    /// Finds the limit the plugin ran out of, when the transform trapped.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn exceeded_limit(&self, timed_out: bool) -> Option<PluginLimitKind> {
+        if timed_out {
+            return self.limits.timeout().map(PluginLimitKind::Timeout);
+        }
+
+        if let Some(fuel) = self.limits.fuel {
+            if let MeteringPoints::Exhausted = get_remaining_points(&self.instance) {
+                return Some(PluginLimitKind::Fuel(fuel));
+            }
+        }
+
+        // Plugins abort when they fail to allocate, so a trap with the memory grown
+        // up to the limit is reported as an out of memory.
+        if let (Some(memory), Some(pages)) = (self.limits.memory, self.limits.memory_pages()) {
+            let size = self.instance.exports.get_memory("memory").ok()?.size();
+            if size.0 >= pages {
+                return Some(PluginLimitKind::Memory(memory));
+            }
+        }
+
+        None
     }
 }
 
 impl Drop for TransformExecutor {
     fn drop(&mut self) {
+        if self.stopped {
+            return;
+        }
+
         for ptr in self.allocated_ptr_vec.iter() {
             self.exported_plugin_free
                 .call(ptr.0, ptr.1)
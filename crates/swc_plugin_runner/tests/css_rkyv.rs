@@ -96,6 +96,7 @@ fn invoke(input: PathBuf) -> Result<(), Error> {
                 Some(experimental_metadata),
             )),
             Some(json!({ "pluginConfig": "testValue" })),
+            &Default::default(),
         )
         .expect("Should load plugin");
 
@@ -145,6 +146,7 @@ fn invoke(input: PathBuf) -> Result<(), Error> {
                 Some(experimental_metadata.clone()),
             )),
             Some(json!({ "pluginConfig": "testValue" })),
+            &Default::default(),
         )
         .expect("Should load plugin");
 
@@ -163,6 +165,7 @@ fn invoke(input: PathBuf) -> Result<(), Error> {
                 Some(experimental_metadata),
             )),
             Some(json!({ "pluginConfig": "testValue" })),
+            &Default::default(),
         )
         .expect("Should load plugin");
 
@@ -182,3 +185,94 @@ fn invoke(input: PathBuf) -> Result<(), Error> {
 
     Ok(())
 }
+
+#[cfg(all(feature = "css", feature = "__rkyv"))]
+#[test]
+fn apply_css_plugins() {
+    use swc_css_ast::Stylesheet;
+    use swc_plugin_runner::css::{apply_css_plugins, CssPluginConfig};
+
+    let path = PLUGIN_PATH.to_string_lossy().into_owned();
+
+    testing::run_test(false, |cm, _handler| {
+        let fm = cm.new_source_file(FileName::Anon, "a { color: red; }".into());
+
+        let parsed: Stylesheet =
+            swc_css_parser::parse_file(&fm, Default::default(), &mut vec![]).unwrap();
+
+        let plugins = vec![
+            CssPluginConfig(
+                path.clone(),
+                json!({ "pluginConfig": "testValue" }),
+                Default::default(),
+            ),
+            CssPluginConfig(
+                path.clone(),
+                json!({ "pluginConfig": "testValue" }),
+                Default::default(),
+            ),
+        ];
+
+        let transformed = apply_css_plugins(
+            parsed.clone(),
+            &plugins,
+            &PLUGIN_MODULE_CACHE,
+            &cm,
+            &Arc::new(TransformPluginMetadataContext::new(
+                None,
+                "development".to_string(),
+                None,
+            )),
+            None,
+        )
+        .expect("Plugins should apply transform");
+
+        assert_eq!(parsed, transformed);
+
+        Ok(())
+    })
+    .expect("Should able to apply css plugins");
+}
+
+#[cfg(all(feature = "css", feature = "__rkyv"))]
+#[test]
+fn css_plugins_transform_multiple_stylesheets() {
+    use swc_css_ast::Stylesheet;
+    use swc_plugin_runner::css::{CssPluginConfig, CssPlugins};
+
+    let path = PLUGIN_PATH.to_string_lossy().into_owned();
+
+    testing::run_test(false, |cm, _handler| {
+        let mut plugins = CssPlugins::new(
+            &[CssPluginConfig(
+                path.clone(),
+                json!({ "pluginConfig": "testValue" }),
+                Default::default(),
+            )],
+            &PLUGIN_MODULE_CACHE,
+            &cm,
+            &Arc::new(TransformPluginMetadataContext::new(
+                None,
+                "development".to_string(),
+                None,
+            )),
+        )
+        .expect("Should instantiate plugins");
+
+        for code in ["a { color: red; }", "b { color: blue; }"] {
+            let fm = cm.new_source_file(FileName::Anon, code.into());
+
+            let parsed: Stylesheet =
+                swc_css_parser::parse_file(&fm, Default::default(), &mut vec![]).unwrap();
+
+            let transformed = plugins
+                .apply(parsed.clone(), None)
+                .expect("Plugins should apply transform");
+
+            assert_eq!(parsed, transformed);
+        }
+
+        Ok(())
+    })
+    .expect("Should able to apply css plugins to multiple stylesheets");
+}
@@ -118,6 +118,7 @@ fn internal() -> Result<(), Error> {
                 Some(experimental_metadata),
             )),
             Some(json!({ "pluginConfig": "testValue" })),
+            &Default::default(),
         )
         .expect("Should load plugin");
 
@@ -185,6 +186,7 @@ fn internal() -> Result<(), Error> {
                         Some(experimental_metadata),
                     )),
                     Some(json!({ "pluginConfig": "testValue" })),
+                    &Default::default(),
                 )
                 .expect("Should load plugin");
 
@@ -236,6 +238,7 @@ fn internal() -> Result<(), Error> {
                 Some(experimental_metadata.clone()),
             )),
             Some(json!({ "pluginConfig": "testValue" })),
+            &Default::default(),
         )
         .expect("Should load plugin");
 
@@ -254,6 +257,7 @@ fn internal() -> Result<(), Error> {
                 Some(experimental_metadata),
             )),
             Some(json!({ "pluginConfig": "testValue" })),
+            &Default::default(),
         )
         .expect("Should load plugin");
 
@@ -106,6 +106,7 @@ fn internal(input: PathBuf) -> Result<(), Error> {
                 Some(experimental_metadata),
             )),
             Some(json!({ "pluginConfig": "testValue" })),
+            &Default::default(),
         )
         .expect("Should load plugin");
 
@@ -163,6 +164,7 @@ fn internal(input: PathBuf) -> Result<(), Error> {
                 Some(experimental_metadata.clone()),
             )),
             Some(json!({ "pluginConfig": "testValue" })),
+            &Default::default(),
         )
         .expect("Should load plugin");
 
@@ -181,6 +183,7 @@ fn internal(input: PathBuf) -> Result<(), Error> {
                 Some(experimental_metadata),
             )),
             Some(json!({ "pluginConfig": "testValue" })),
+            &Default::default(),
         )
         .expect("Should load plugin");
 
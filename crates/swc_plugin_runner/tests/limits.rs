@@ -0,0 +1,260 @@
+#![cfg_attr(not(feature = "__rkyv"), allow(warnings))]
+
+use std::{fs, path::PathBuf, sync::Arc, time::Duration};
+
+use anyhow::Error;
+#[cfg(feature = "__rkyv")]
+use swc_common::plugin::{
+    diagnostics::PluginCorePkgDiagnostics, serialized::PluginSerializedBytes,
+};
+use swc_common::{
+    plugin::metadata::TransformPluginMetadataContext, sync::Lazy, Mark, SourceMap, GLOBALS,
+};
+use swc_plugin_runner::{
+    cache::PluginModuleCache,
+    limits::{PluginLimitExceeded, PluginLimitKind, PluginLimits},
+};
+
+/// Writes a plugin which runs `transform` as its transform, and returns the
+/// path to it.
+#[cfg(feature = "__rkyv")]
+fn write_plugin(name: &str, transform: &str) -> Result<PathBuf, Error> {
+    let diagnostics = PluginSerializedBytes::try_serialize(&PluginCorePkgDiagnostics {
+        pkg_version: "0.0.0".to_string(),
+        git_sha: "".to_string(),
+        cargo_features: "".to_string(),
+        ast_schema_version: 1,
+    })?;
+    let diagnostics_len = diagnostics.as_slice().len();
+    let diagnostics = diagnostics
+        .as_slice()
+        .iter()
+        .map(|byte| format!("\\{:02x}", byte))
+        .collect::<String>();
+
+    let wat = format!(
+        r#"(module
+            (import "env" "__set_transform_plugin_core_pkg_diagnostics"
+                (func $set_diagnostics (param i32 i32)))
+            (import "env" "__mark_fresh_proxy" (func $mark_fresh (param i32) (result i32)))
+            (memory (export "memory") 1)
+            (data (i32.const 16) "{diagnostics}")
+            (func (export "__get_transform_plugin_core_pkg_diag") (result i32)
+                (call $set_diagnostics (i32.const 16) (i32.const {diagnostics_len}))
+                (i32.const 0))
+            (func (export "__alloc") (param i32) (result i32) (i32.const 1024))
+            (func (export "__free") (param i32 i32) (result i32) (i32.const 0))
+            (func $fib (param i32) (result i32)
+                (if (result i32) (i32.lt_u (local.get 0) (i32.const 2))
+                    (then (local.get 0))
+                    (else (i32.add
+                        (call $fib (i32.sub (local.get 0) (i32.const 1)))
+                        (call $fib (i32.sub (local.get 0) (i32.const 2)))))))
+            (func (export "__transform_plugin_process_impl")
+                (param i32 i32 i32 i32) (result i32)
+                {transform}
+                (i32.const 0)))"#
+    );
+
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("limits");
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("{}.wasm", name));
+    fs::write(&path, wasmer::wat2wasm(wat.as_bytes())?)?;
+
+    Ok(path)
+}
+
+/// Runs the transform of the plugin written by [write_plugin].
+#[cfg(feature = "__rkyv")]
+fn run_plugin(name: &str, transform: &str, limits: &PluginLimits) -> Result<Error, Error> {
+    let path = write_plugin(name, transform)?;
+    let cache: Lazy<PluginModuleCache> = Lazy::new(PluginModuleCache::new);
+
+    let mut executor = swc_plugin_runner::create_plugin_transform_executor(
+        &path,
+        &cache,
+        &Arc::new(SourceMap::default()),
+        &Arc::new(TransformPluginMetadataContext::new(
+            None,
+            "development".to_string(),
+            None,
+        )),
+        None,
+        limits,
+    )?;
+
+    let program = PluginSerializedBytes::try_serialize(&"program".to_string())?;
+    // The plugin calls into the hygiene of the host.
+    let result = GLOBALS.set(&Default::default(), || {
+        executor.transform(&program, Mark::root(), false)
+    });
+
+    match result {
+        Ok(..) => panic!("plugin {} should not finish", name),
+        Err(err) => Ok(err),
+    }
+}
+
+/// Runs the plugin, and asserts that it's stopped for running out of `kind`.
+#[cfg(feature = "__rkyv")]
+fn assert_exceeded(
+    name: &str,
+    transform: &str,
+    limits: &PluginLimits,
+    kind: PluginLimitKind,
+) -> Result<String, Error> {
+    let err = run_plugin(name, transform, limits)?;
+
+    let exceeded = err
+        .downcast_ref::<PluginLimitExceeded>()
+        .expect("Should fail with PluginLimitExceeded");
+    assert!(
+        exceeded.plugin.ends_with(&format!("{}.wasm", name)),
+        "{}",
+        exceeded.plugin
+    );
+    assert_eq!(exceeded.kind, kind);
+
+    Ok(exceeded.to_string())
+}
+
+#[cfg(feature = "__rkyv")]
+#[test]
+fn timeout_stops_infinite_loop() -> Result<(), Error> {
+    let limits = PluginLimits {
+        timeout: Some(100),
+        ..Default::default()
+    };
+
+    let message = assert_exceeded(
+        "infinite_loop",
+        "(loop $l (br $l))",
+        &limits,
+        PluginLimitKind::Timeout(Duration::from_millis(100)),
+    )?;
+    assert!(message.contains("infinite_loop.wasm"), "{}", message);
+    assert!(message.ends_with("did not finish in 100ms"), "{}", message);
+
+    Ok(())
+}
+
+#[cfg(feature = "__rkyv")]
+#[test]
+fn timeout_stops_plugin_calling_host() -> Result<(), Error> {
+    let limits = PluginLimits {
+        timeout: Some(100),
+        ..Default::default()
+    };
+
+    assert_exceeded(
+        "host_call_loop",
+        "(loop $l (drop (call $mark_fresh (i32.const 0))) (br $l))",
+        &limits,
+        PluginLimitKind::Timeout(Duration::from_millis(100)),
+    )?;
+
+    Ok(())
+}
+
+#[cfg(feature = "__rkyv")]
+#[test]
+fn timeout_stops_recursion() -> Result<(), Error> {
+    let limits = PluginLimits {
+        timeout: Some(100),
+        ..Default::default()
+    };
+
+    assert_exceeded(
+        "recursion",
+        "(drop (call $fib (i32.const 60)))",
+        &limits,
+        PluginLimitKind::Timeout(Duration::from_millis(100)),
+    )?;
+
+    Ok(())
+}
+
+#[cfg(feature = "__rkyv")]
+#[test]
+fn timeout_with_fuel() -> Result<(), Error> {
+    let limits = PluginLimits {
+        timeout: Some(100),
+        fuel: Some(u64::MAX),
+        ..Default::default()
+    };
+
+    assert_exceeded(
+        "infinite_loop_metered",
+        "(loop $l (br $l))",
+        &limits,
+        PluginLimitKind::Timeout(Duration::from_millis(100)),
+    )?;
+
+    Ok(())
+}
+
+#[cfg(feature = "__rkyv")]
+#[test]
+fn fuel_stops_infinite_loop() -> Result<(), Error> {
+    let limits = PluginLimits {
+        fuel: Some(10_000),
+        ..Default::default()
+    };
+
+    let message = assert_exceeded(
+        "fuel_loop",
+        "(loop $l (br $l))",
+        &limits,
+        PluginLimitKind::Fuel(10_000),
+    )?;
+    assert!(
+        message.ends_with("exceeded the fuel limit of 10000 instructions"),
+        "{}",
+        message
+    );
+
+    Ok(())
+}
+
+#[cfg(feature = "__rkyv")]
+#[test]
+fn memory_limit_stops_plugin() -> Result<(), Error> {
+    let limits = PluginLimits {
+        memory: Some(4 * 0x10000),
+        ..Default::default()
+    };
+
+    // Like plugins which abort when they fail to allocate
+    let message = assert_exceeded(
+        "memory_hog",
+        "(loop $l (br_if $l (i32.ne (memory.grow (i32.const 1)) (i32.const -1)))) unreachable",
+        &limits,
+        PluginLimitKind::Memory(4 * 0x10000),
+    )?;
+    assert!(
+        message.ends_with("exceeded the memory limit of 262144 bytes"),
+        "{}",
+        message
+    );
+
+    Ok(())
+}
+
+#[cfg(feature = "__rkyv")]
+#[test]
+fn trap_within_limits_is_not_limit_exceeded() -> Result<(), Error> {
+    let limits = PluginLimits {
+        memory: Some(4 * 0x10000),
+        fuel: Some(10_000),
+        ..Default::default()
+    };
+
+    let err = run_plugin("trap", "unreachable", &limits)?;
+    assert!(
+        err.downcast_ref::<PluginLimitExceeded>().is_none(),
+        "{}",
+        err
+    );
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use std::{env, path::PathBuf};
+
+use anyhow::{bail, Error};
+use swc_common::FileName;
+use swc_ecma_loader::resolve::Resolve;
+use swc_plugin_runner::module_loader::PluginModuleLoader;
+
+/// Resolves relative specifiers against the directory of the base file.
+struct RelativeResolver;
+
+impl Resolve for RelativeResolver {
+    fn resolve(&self, base: &FileName, module_specifier: &str) -> Result<FileName, Error> {
+        match base {
+            FileName::Real(base) => Ok(FileName::Real(
+                base.parent().unwrap().join(module_specifier),
+            )),
+            _ => bail!("not a file: {}", base),
+        }
+    }
+}
+
+fn fixture_dir() -> PathBuf {
+    PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap())
+        .join("tests")
+        .join("fixture")
+        .join("swc_internal_plugin")
+}
+
+#[test]
+fn allow_list() {
+    let dir = fixture_dir();
+    let loader = PluginModuleLoader::new(Box::new(RelativeResolver), &[dir.join("src")]);
+
+    let base = dir.join("src").join("lib.rs");
+    let base = base.to_str().unwrap();
+
+    let resolved = loader.resolve(base, "./lib.rs").expect("should be allowed");
+    assert!(loader
+        .read_file(&resolved)
+        .unwrap()
+        .contains("plugin_transform"));
+
+    // Outside of the allowed paths
+    assert_eq!(loader.resolve(base, "../Cargo.toml"), None);
+    assert_eq!(
+        loader.read_file(dir.join("Cargo.toml").to_str().unwrap()),
+        None
+    );
+    assert_eq!(
+        loader.read_file(
+            dir.join("src")
+                .join("..")
+                .join("Cargo.toml")
+                .to_str()
+                .unwrap()
+        ),
+        None
+    );
+
+    // Does not exist
+    assert_eq!(loader.resolve(base, "./missing.rs"), None);
+}
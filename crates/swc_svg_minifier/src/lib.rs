@@ -0,0 +1,698 @@
+#![deny(clippy::all)]
+
+//! SVG minifier for [swc_xml_ast], similar to SVGO.
+//!
+//! Namespaces are resolved from `xmlns` attributes, so only elements and
+//! attributes in the SVG namespace are minified.
+
+use std::{mem::take, rc::Rc};
+
+use swc_atoms::JsWord;
+use swc_common::{
+    collections::AHashMap, sync::Lrc, FileName, FilePathMapping, SourceMap, DUMMY_SP,
+};
+use swc_xml_ast::*;
+use swc_xml_utils::{
+    namespace::{namespace_declaration, split_qualified_name, NamespaceScope},
+    SVG_NAMESPACE, XML_NAMESPACE,
+};
+use swc_xml_visit::{VisitMut, VisitMutWith};
+
+use crate::{
+    option::{CssOptions, MinifyCssOption, MinifyOptions},
+    path::{minify_number, minify_number_list, minify_path_data},
+};
+
+pub mod option;
+mod path;
+
+/// Namespaces used by editors to store their own data.
+static EDITOR_NAMESPACES: &[&str] = &[
+    "http://creativecommons.org/ns#",
+    "http://inkscape.sourceforge.net/DTD/sodipodi-0.dtd",
+    "http://ns.adobe.com/AdobeIllustrator/10.0/",
+    "http://ns.adobe.com/AdobeSVGViewerExtensions/3.0/",
+    "http://ns.adobe.com/Extensibility/1.0/",
+    "http://ns.adobe.com/Flows/1.0/",
+    "http://ns.adobe.com/GenericCustomNamespace/1.0/",
+    "http://ns.adobe.com/Graphs/1.0/",
+    "http://ns.adobe.com/ImageReplacement/1.0/",
+    "http://ns.adobe.com/SaveForWeb/1.0/",
+    "http://ns.adobe.com/Variables/1.0/",
+    "http://ns.adobe.com/XPath/1.0/",
+    "http://purl.org/dc/elements/1.1/",
+    "http://schemas.microsoft.com/visio/2003/SVGExtensions/",
+    "http://sodipodi.sourceforge.net/DTD/sodipodi-0.dtd",
+    "http://taptrix.com/vectorillustrator/svg_extensions",
+    "http://www.bohemiancoding.com/sketch/ns",
+    "http://www.figma.com/figma/ns",
+    "http://www.inkscape.org/namespaces/inkscape",
+    "http://www.serif.com/",
+    "http://www.vector.evaxdesign.com/svg_extensions",
+    "http://www.w3.org/1999/02/22-rdf-syntax-ns#",
+];
+
+/// Elements where whitespaces are rendered or are a part of the content.
+static TEXT_CONTENT_ELEMENTS: &[&str] = &[
+    "desc",
+    "foreignObject",
+    "text",
+    "textPath",
+    "title",
+    "tspan",
+];
+
+/// Attributes which are a number with an optional unit.
+static NUMBER_ATTRIBUTES: &[&str] = &[
+    "cx",
+    "cy",
+    "dx",
+    "dy",
+    "fill-opacity",
+    "font-size",
+    "fr",
+    "fx",
+    "fy",
+    "height",
+    "offset",
+    "opacity",
+    "r",
+    "rx",
+    "ry",
+    "stop-opacity",
+    "stroke-dashoffset",
+    "stroke-miterlimit",
+    "stroke-opacity",
+    "stroke-width",
+    "width",
+    "x",
+    "x1",
+    "x2",
+    "y",
+    "y1",
+    "y2",
+];
+
+/// Presentation attributes which are inherited by descendants.
+static INHERITABLE_ATTRIBUTES: &[&str] = &[
+    "clip-rule",
+    "color",
+    "color-interpolation",
+    "color-interpolation-filters",
+    "color-rendering",
+    "cursor",
+    "direction",
+    "dominant-baseline",
+    "fill",
+    "fill-opacity",
+    "fill-rule",
+    "font",
+    "font-family",
+    "font-size",
+    "font-size-adjust",
+    "font-stretch",
+    "font-style",
+    "font-variant",
+    "font-weight",
+    "image-rendering",
+    "letter-spacing",
+    "marker",
+    "marker-end",
+    "marker-mid",
+    "marker-start",
+    "paint-order",
+    "pointer-events",
+    "shape-rendering",
+    "stroke",
+    "stroke-dasharray",
+    "stroke-dashoffset",
+    "stroke-linecap",
+    "stroke-linejoin",
+    "stroke-miterlimit",
+    "stroke-opacity",
+    "stroke-width",
+    "text-anchor",
+    "text-rendering",
+    "visibility",
+    "word-spacing",
+    "writing-mode",
+];
+
+/// Initial values of inheritable attributes, they are redundant if no ancestor
+/// sets the attribute.
+static INHERITABLE_DEFAULTS: &[(&str, &str)] = &[
+    ("clip-rule", "nonzero"),
+    ("fill-opacity", "1"),
+    ("fill-rule", "nonzero"),
+    ("font-style", "normal"),
+    ("font-weight", "normal"),
+    ("stroke", "none"),
+    ("stroke-dasharray", "none"),
+    ("stroke-dashoffset", "0"),
+    ("stroke-linecap", "butt"),
+    ("stroke-linejoin", "miter"),
+    ("stroke-miterlimit", "4"),
+    ("stroke-opacity", "1"),
+    ("stroke-width", "1"),
+    ("visibility", "visible"),
+];
+
+/// Elements whose content is rendered where it is referenced, i.e. by `<use>`,
+/// so it inherits properties from there.
+static REFERENCED_CONTENT_ELEMENTS: &[&str] =
+    &["clipPath", "defs", "marker", "mask", "pattern", "symbol"];
+
+/// Default values of attributes by elements, `*` is any element.
+static DEFAULTS: &[(&str, &str, &str)] = &[
+    ("*", "opacity", "1"),
+    ("circle", "cx", "0"),
+    ("circle", "cy", "0"),
+    ("ellipse", "cx", "0"),
+    ("ellipse", "cy", "0"),
+    ("image", "preserveAspectRatio", "xMidYMid meet"),
+    ("image", "preserveAspectRatio", "xMidYMid"),
+    ("image", "x", "0"),
+    ("image", "y", "0"),
+    ("line", "x1", "0"),
+    ("line", "x2", "0"),
+    ("line", "y1", "0"),
+    ("line", "y2", "0"),
+    ("rect", "x", "0"),
+    ("rect", "y", "0"),
+    ("svg", "preserveAspectRatio", "xMidYMid meet"),
+    ("svg", "preserveAspectRatio", "xMidYMid"),
+    ("svg", "x", "0"),
+    ("svg", "y", "0"),
+    ("use", "x", "0"),
+    ("use", "y", "0"),
+];
+
+fn is_editor_namespace(namespace: &Option<JsWord>) -> bool {
+    matches!(namespace, Some(namespace) if EDITOR_NAMESPACES.contains(&&**namespace))
+}
+
+fn is_whitespace(data: &str) -> bool {
+    data.chars().all(|c| matches!(c, ' ' | '\t' | '\n' | '\r'))
+}
+
+fn get_attribute<'a>(element: &'a Element, name: &str) -> Option<&'a JsWord> {
+    element
+        .attributes
+        .iter()
+        .find(|attribute| attribute.name == *name)
+        .and_then(|attribute| attribute.value.as_ref())
+}
+
+fn has_style_element(children: &[Child]) -> bool {
+    children.iter().any(|child| match child {
+        Child::Element(element) => {
+            split_qualified_name(&element.tag_name).1 == "style"
+                || has_style_element(&element.children)
+        }
+        _ => false,
+    })
+}
+
+struct Minifier<'a> {
+    options: &'a MinifyOptions,
+    scope: Rc<NamespaceScope>,
+    /// Inheritable attributes set by ancestors, with the number of ancestors
+    /// setting them
+    inherited_attributes: AHashMap<JsWord, usize>,
+    /// The number of ancestors with the `style` attribute, which can set any
+    /// inheritable property
+    styled_ancestors: usize,
+    /// The number of ancestors, including the current element, whose content
+    /// can be rendered elsewhere and inherit properties from there
+    referenced_ancestors: usize,
+    /// The number of ancestors where whitespaces should be kept
+    whitespace_preserving_ancestors: usize,
+    /// Stylesheets can match attributes and the structure of the document, so
+    /// inheritable defaults and groups are kept
+    has_stylesheet: bool,
+}
+
+impl Minifier<'_> {
+    fn is_svg_element(&self, scope: &NamespaceScope, element: &Element, name: &str) -> bool {
+        let (namespace, local_name) = scope.element_name(element);
+
+        namespace.as_deref() == Some(SVG_NAMESPACE) && local_name == name
+    }
+
+    fn is_removable(&self, child: &Child, preserve_whitespaces: bool) -> bool {
+        match child {
+            Child::Comment(..) => self.options.remove_comments,
+            Child::DocumentType(..) => self.options.remove_doctype,
+            Child::ProcessingInstruction(processing_instruction) => {
+                self.options.remove_xml_declaration && &*processing_instruction.target == "xml"
+            }
+            Child::Text(text) => {
+                self.options.remove_whitespaces
+                    && !preserve_whitespaces
+                    && is_whitespace(&text.data)
+            }
+            Child::CdataSection(..) => false,
+            Child::Element(element) => {
+                let scope = NamespaceScope::enter(&self.scope, element);
+
+                (self.options.remove_metadata && self.is_svg_element(&scope, element, "metadata"))
+                    || (self.options.remove_editor_namespaces
+                        && is_editor_namespace(&scope.element_name(element).0))
+            }
+        }
+    }
+
+    fn need_minify_css(&self) -> bool {
+        match self.options.minify_css {
+            MinifyCssOption::Bool(value) => value,
+            MinifyCssOption::Options(_) => true,
+        }
+    }
+
+    fn get_css_options(&self) -> CssOptions {
+        match &self.options.minify_css {
+            MinifyCssOption::Bool(_) => CssOptions {
+                parser: Default::default(),
+                minifier: Default::default(),
+                codegen: Default::default(),
+            },
+            MinifyCssOption::Options(css_options) => *css_options.clone(),
+        }
+    }
+
+    /// Minifies a stylesheet, or a list of declarations of the `style`
+    /// attribute.
+    fn minify_css(&self, data: String, is_declarations: bool) -> Option<String> {
+        let mut errors: Vec<_> = vec![];
+
+        let cm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        let fm = cm.new_source_file(FileName::Anon, data);
+
+        let mut options = self.get_css_options();
+
+        let mut stylesheet =
+            if is_declarations {
+                let declarations = swc_css_parser::parse_file::<
+                    Vec<swc_css_ast::DeclarationOrAtRule>,
+                >(&fm, options.parser, &mut errors)
+                .ok()?;
+
+                swc_css_ast::Stylesheet {
+                    span: DUMMY_SP,
+                    rules: vec![swc_css_ast::Rule::QualifiedRule(
+                        swc_css_ast::QualifiedRule {
+                            span: DUMMY_SP,
+                            prelude: swc_css_ast::QualifiedRulePrelude::SelectorList(
+                                swc_css_ast::SelectorList {
+                                    span: DUMMY_SP,
+                                    children: vec![],
+                                },
+                            ),
+                            block: swc_css_ast::SimpleBlock {
+                                span: DUMMY_SP,
+                                name: swc_css_ast::TokenAndSpan {
+                                    span: DUMMY_SP,
+                                    token: swc_css_ast::Token::LBrace,
+                                },
+                                value: declarations.into_iter().map(|node| node.into()).collect(),
+                            },
+                        }
+                        .into(),
+                    )],
+                }
+            } else {
+                swc_css_parser::parse_file(&fm, options.parser, &mut errors).ok()?
+            };
+
+        // Avoid compress potential invalid CSS
+        if !errors.is_empty() {
+            return None;
+        }
+
+        swc_css_minifier::minify(&mut stylesheet, options.minifier);
+
+        let mut minified = String::new();
+        let wr = swc_css_codegen::writer::basic::BasicCssWriter::new(
+            &mut minified,
+            None,
+            swc_css_codegen::writer::basic::BasicCssWriterConfig::default(),
+        );
+
+        options.codegen.minify = true;
+
+        let mut gen = swc_css_codegen::CodeGenerator::new(wr, options.codegen);
+
+        if is_declarations {
+            // Because CSS is grammar free, protect for fails
+            if let Some(swc_css_ast::Rule::QualifiedRule(qualified_rule)) = stylesheet.rules.first()
+            {
+                swc_css_codegen::Emit::emit(&mut gen, &qualified_rule.block).ok()?;
+
+                minified = minified[1..minified.len() - 1].to_string();
+            } else {
+                return None;
+            }
+        } else {
+            swc_css_codegen::Emit::emit(&mut gen, &stylesheet).ok()?;
+        }
+
+        Some(minified)
+    }
+
+    fn minify_attribute_value(&self, name: &str, value: &str) -> Option<String> {
+        match name {
+            "d" if self.options.minify_path_data => {
+                minify_path_data(value, self.options.float_precision)
+            }
+            "points" | "viewBox" => minify_number_list(value, self.options.float_precision),
+            "style" if self.need_minify_css() => self.minify_css(value.to_string(), true),
+            _ if NUMBER_ATTRIBUTES.contains(&name) => {
+                minify_number(value, self.options.float_precision)
+            }
+            _ => None,
+        }
+    }
+
+    fn is_default_attribute(&self, element_name: &str, name: &str, value: &str) -> bool {
+        let is_default = DEFAULTS.iter().any(|(element, attribute, default)| {
+            (*element == "*" || *element == element_name) && *attribute == name && *default == value
+        });
+
+        if is_default {
+            return true;
+        }
+
+        // The initial value of an inheritable property is redundant only if nothing
+        // else can set it for an ancestor
+        INHERITABLE_DEFAULTS
+            .iter()
+            .any(|(attribute, default)| *attribute == name && *default == value)
+            && !self.has_stylesheet
+            && self.styled_ancestors == 0
+            && self.referenced_ancestors == 0
+            && !self.inherited_attributes.contains_key(&JsWord::from(name))
+    }
+
+    fn minify_attributes(&self, element: &mut Element, local_name: &str, is_svg: bool) {
+        let scope = self.scope.clone();
+
+        element.attributes.retain_mut(|attribute| {
+            if self.options.remove_editor_namespaces {
+                let is_editor_declaration =
+                    matches!(namespace_declaration(attribute), Some(Some(..)))
+                        && is_editor_namespace(&attribute.value);
+
+                if is_editor_declaration || is_editor_namespace(&scope.attribute_name(attribute).0)
+                {
+                    return false;
+                }
+            }
+
+            // Namespaced attributes, i.e. `xlink:href`, are kept as-is
+            if !is_svg || attribute.name.contains(':') {
+                return true;
+            }
+
+            let value = match &attribute.value {
+                Some(value) => value.clone(),
+                None => return true,
+            };
+
+            if let Some(minified) = self.minify_attribute_value(&attribute.name, &value) {
+                // An empty `style` attribute is redundant
+                if minified.is_empty() && &*attribute.name == "style" {
+                    return false;
+                }
+
+                attribute.value = Some(minified.into());
+                attribute.raw_value = None;
+            }
+
+            !(self.options.remove_default_attributes
+                && self.is_default_attribute(
+                    local_name,
+                    &attribute.name,
+                    attribute.value.as_deref().unwrap_or_default(),
+                ))
+        });
+    }
+
+    fn minify_style_element(&self, element: &mut Element) {
+        if !self.need_minify_css() {
+            return;
+        }
+
+        let mut data = String::new();
+        let mut is_cdata = false;
+
+        for child in &element.children {
+            match child {
+                Child::Text(text) => data.push_str(&text.data),
+                Child::CdataSection(cdata_section) => {
+                    data.push_str(&cdata_section.data);
+                    is_cdata = true;
+                }
+                _ => return,
+            }
+        }
+
+        let minified = match self.minify_css(data, false) {
+            Some(minified) => minified,
+            None => return,
+        };
+
+        element.children = if minified.is_empty() {
+            vec![]
+        } else if is_cdata {
+            vec![Child::CdataSection(CdataSection {
+                span: DUMMY_SP,
+                data: minified.into(),
+                raw: None,
+            })]
+        } else {
+            vec![Child::Text(Text {
+                span: DUMMY_SP,
+                data: minified.into(),
+                raw: None,
+            })]
+        };
+    }
+
+    fn is_group(&self, element: &Element) -> bool {
+        self.is_svg_element(&NamespaceScope::enter(&self.scope, element), element, "g")
+    }
+
+    /// Returns the children replacing a group, `group` was already minified.
+    fn collapse_group(&self, mut group: Element) -> Vec<Child> {
+        if group.attributes.is_empty() {
+            return group.children;
+        }
+
+        let has_id = group
+            .attributes
+            .iter()
+            .any(|attribute| &*attribute.name == "id");
+
+        if group.children.is_empty() && !has_id {
+            return vec![];
+        }
+
+        if !matches!(&group.children[..], [Child::Element(..)]) {
+            return vec![Child::Element(group)];
+        }
+
+        let mut child = match group.children.pop() {
+            Some(Child::Element(child)) => child,
+            _ => unreachable!(),
+        };
+
+        let can_move_attributes = NamespaceScope::enter(&self.scope, &child)
+            .element_name(&child)
+            .0
+            .as_deref()
+            == Some(SVG_NAMESPACE)
+            && group.attributes.iter().all(|attribute| {
+                &*attribute.name == "transform"
+                    || INHERITABLE_ATTRIBUTES.contains(&&*attribute.name)
+                        && get_attribute(&child, &attribute.name).is_none()
+            });
+
+        if !can_move_attributes {
+            group.children = vec![Child::Element(child)];
+
+            return vec![Child::Element(group)];
+        }
+
+        for attribute in take(&mut group.attributes) {
+            if &*attribute.name == "transform" {
+                if let Some(child_attribute) = child
+                    .attributes
+                    .iter_mut()
+                    .find(|child_attribute| &*child_attribute.name == "transform")
+                {
+                    // The transform of the group is applied first
+                    let transform = format!(
+                        "{} {}",
+                        attribute.value.as_deref().unwrap_or_default(),
+                        child_attribute.value.as_deref().unwrap_or_default()
+                    );
+
+                    child_attribute.value = Some(transform.into());
+                    child_attribute.raw_value = None;
+
+                    continue;
+                }
+            }
+
+            child.attributes.push(attribute);
+        }
+
+        if self.is_group(&child) {
+            self.collapse_group(child)
+        } else {
+            vec![Child::Element(child)]
+        }
+    }
+
+    fn collapse_groups(&self, children: &mut Vec<Child>) {
+        // Selectors can depend on groups
+        if !self.options.collapse_groups || self.has_stylesheet {
+            return;
+        }
+
+        let mut collapsed = Vec::with_capacity(children.len());
+
+        for child in take(children) {
+            match child {
+                Child::Element(element) if self.is_group(&element) => {
+                    collapsed.extend(self.collapse_group(element));
+                }
+                _ => collapsed.push(child),
+            }
+        }
+
+        *children = collapsed;
+    }
+
+    fn enter_attributes(&mut self, element: &Element, is_svg: bool) {
+        if !is_svg {
+            return;
+        }
+
+        for attribute in &element.attributes {
+            if INHERITABLE_ATTRIBUTES.contains(&&*attribute.name) {
+                *self
+                    .inherited_attributes
+                    .entry(attribute.name.clone())
+                    .or_default() += 1;
+            } else if &*attribute.name == "style" {
+                self.styled_ancestors += 1;
+            }
+        }
+    }
+
+    fn leave_attributes(&mut self, element: &Element, is_svg: bool) {
+        if !is_svg {
+            return;
+        }
+
+        for attribute in &element.attributes {
+            if INHERITABLE_ATTRIBUTES.contains(&&*attribute.name) {
+                if let Some(count) = self.inherited_attributes.get_mut(&attribute.name) {
+                    *count -= 1;
+
+                    if *count == 0 {
+                        self.inherited_attributes.remove(&attribute.name);
+                    }
+                }
+            } else if &*attribute.name == "style" {
+                self.styled_ancestors -= 1;
+            }
+        }
+    }
+}
+
+impl VisitMut for Minifier<'_> {
+    fn visit_mut_document(&mut self, n: &mut Document) {
+        self.has_stylesheet = has_style_element(&n.children);
+
+        n.children.retain(|child| !self.is_removable(child, false));
+
+        n.visit_mut_children_with(self);
+
+        self.collapse_groups(&mut n.children);
+    }
+
+    fn visit_mut_element(&mut self, n: &mut Element) {
+        let scope = NamespaceScope::enter(&self.scope, n);
+        let parent_scope = std::mem::replace(&mut self.scope, scope);
+        let (namespace, local_name) = self.scope.element_name(n);
+        let is_svg = namespace.as_deref() == Some(SVG_NAMESPACE);
+        let local_name = local_name.to_string();
+
+        // Elements with an `id` can be referenced by `<use>`
+        let is_referenced = is_svg
+            && (REFERENCED_CONTENT_ELEMENTS.contains(&&*local_name)
+                || n.attributes
+                    .iter()
+                    .any(|attribute| &*attribute.name == "id"));
+
+        if is_referenced {
+            self.referenced_ancestors += 1;
+        }
+
+        self.minify_attributes(n, &local_name, is_svg);
+
+        let preserve_whitespaces = self.whitespace_preserving_ancestors > 0
+            || (is_svg && TEXT_CONTENT_ELEMENTS.contains(&&*local_name))
+            || n.attributes.iter().any(|attribute| {
+                self.scope.attribute_name(attribute) == (Some(XML_NAMESPACE.into()), "space")
+                    && attribute.value.as_deref() == Some("preserve")
+            });
+
+        n.children
+            .retain(|child| !self.is_removable(child, preserve_whitespaces));
+
+        self.enter_attributes(n, is_svg);
+
+        if preserve_whitespaces {
+            self.whitespace_preserving_ancestors += 1;
+        }
+
+        n.visit_mut_children_with(self);
+
+        if preserve_whitespaces {
+            self.whitespace_preserving_ancestors -= 1;
+        }
+
+        self.leave_attributes(n, is_svg);
+
+        if is_referenced {
+            self.referenced_ancestors -= 1;
+        }
+
+        if is_svg && local_name == "style" {
+            self.minify_style_element(n);
+        }
+
+        // `<switch>` renders the first child which matches, so its groups are
+        // kept to switch between the whole groups
+        if !(is_svg && local_name == "switch") {
+            self.collapse_groups(&mut n.children);
+        }
+
+        self.scope = parent_scope;
+    }
+}
+
+pub fn minify_document(document: &mut Document, options: &MinifyOptions) {
+    document.visit_mut_with(&mut Minifier {
+        options,
+        scope: Default::default(),
+        inherited_attributes: Default::default(),
+        styled_ancestors: 0,
+        referenced_ancestors: 0,
+        whitespace_preserving_ancestors: 0,
+        has_stylesheet: false,
+    });
+}
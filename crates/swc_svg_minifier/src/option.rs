@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+use swc_css_codegen::CodegenConfig as CssCodegenOptions;
+use swc_css_minifier::options::MinifyOptions as CssMinifyOptions;
+use swc_css_parser::parser::ParserConfig as CssParserOptions;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+#[serde(untagged)]
+pub enum MinifyCssOption {
+    Bool(bool),
+    Options(Box<CssOptions>),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct CssOptions {
+    #[serde(default)]
+    pub parser: CssParserOptions,
+    #[serde(default)]
+    pub minifier: CssMinifyOptions,
+    #[serde(default)]
+    pub codegen: CssCodegenOptions,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct MinifyOptions {
+    #[serde(default = "true_by_default")]
+    pub remove_comments: bool,
+    #[serde(default = "true_by_default")]
+    pub remove_doctype: bool,
+    /// Remove the `<?xml ...?>` declaration, the output is always UTF-8
+    #[serde(default = "true_by_default")]
+    pub remove_xml_declaration: bool,
+    /// Remove `metadata` elements
+    #[serde(default = "true_by_default")]
+    pub remove_metadata: bool,
+    /// Remove elements, attributes and namespace declarations of editors, i.e.
+    /// `sodipodi:namedview` and `inkscape:label`
+    #[serde(default = "true_by_default")]
+    pub remove_editor_namespaces: bool,
+    /// Remove whitespace-only text outside of text content elements (i.e.
+    /// `text` and `title`) and `xml:space="preserve"`
+    #[serde(default = "true_by_default")]
+    pub remove_whitespaces: bool,
+    /// Replace groups without attributes by their children, move the
+    /// inheritable attributes and the transform of a group with a single child
+    /// to the child and remove empty groups
+    #[serde(default = "true_by_default")]
+    pub collapse_groups: bool,
+    /// Remove attributes which have their default value, i.e. `x="0"` on
+    /// `rect` and `stroke-linecap="butt"` if no ancestor sets `stroke-linecap`
+    #[serde(default = "true_by_default")]
+    pub remove_default_attributes: bool,
+    /// Remove redundant commands and separators of the `d` attribute
+    #[serde(default = "true_by_default")]
+    pub minify_path_data: bool,
+    /// Round numbers in path data and numeric attributes to this number of
+    /// decimal places, `None` keeps them as-is
+    #[serde(default = "default_float_precision")]
+    pub float_precision: Option<u8>,
+    /// Minify the `style` element and attribute
+    #[serde(default = "minify_css_by_default")]
+    pub minify_css: MinifyCssOption,
+}
+
+/// Implement default using serde.
+impl Default for MinifyOptions {
+    fn default() -> Self {
+        serde_json::from_value(serde_json::Value::Object(Default::default())).unwrap()
+    }
+}
+
+const fn true_by_default() -> bool {
+    true
+}
+
+const fn default_float_precision() -> Option<u8> {
+    Some(3)
+}
+
+const fn minify_css_by_default() -> MinifyCssOption {
+    MinifyCssOption::Bool(true)
+}
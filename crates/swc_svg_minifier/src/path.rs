@@ -0,0 +1,288 @@
+//! Numbers, lists of numbers and path data.
+
+/// Returns the length of the number at the start of `input`, i.e. `-1.5e3`.
+fn number_len(input: &str) -> Option<usize> {
+    let bytes = input.as_bytes();
+    let mut pos = 0;
+
+    if matches!(bytes.first(), Some(b'+' | b'-')) {
+        pos += 1;
+    }
+
+    let digits = |pos: usize| {
+        bytes[pos..]
+            .iter()
+            .take_while(|byte| byte.is_ascii_digit())
+            .count()
+    };
+
+    let integer_len = digits(pos);
+
+    pos += integer_len;
+
+    let mut fraction_len = 0;
+
+    if bytes.get(pos) == Some(&b'.') {
+        fraction_len = digits(pos + 1);
+
+        if fraction_len > 0 {
+            pos += 1 + fraction_len;
+        }
+    }
+
+    if integer_len == 0 && fraction_len == 0 {
+        return None;
+    }
+
+    if matches!(bytes.get(pos), Some(b'e' | b'E')) {
+        let sign_len = usize::from(matches!(bytes.get(pos + 1), Some(b'+' | b'-')));
+        let exponent_len = digits(pos + 1 + sign_len);
+
+        if exponent_len > 0 {
+            pos += 1 + sign_len + exponent_len;
+        }
+    }
+
+    Some(pos)
+}
+
+/// Formats `value` with at most `precision` decimal places and without
+/// redundant zeros, i.e. `0.50` as `.5`.
+pub(crate) fn format_number(value: f64, precision: Option<u8>) -> String {
+    let mut formatted = match precision {
+        Some(precision) => format!("{:.*}", precision as usize, value),
+        None => format!("{}", value),
+    };
+
+    if formatted.contains('.') {
+        let len = formatted.trim_end_matches('0').trim_end_matches('.').len();
+
+        formatted.truncate(len);
+    }
+
+    if formatted == "-0" {
+        return "0".into();
+    }
+
+    if let Some(fraction) = formatted.strip_prefix("0.") {
+        format!(".{}", fraction)
+    } else if let Some(fraction) = formatted.strip_prefix("-0.") {
+        format!("-.{}", fraction)
+    } else {
+        formatted
+    }
+}
+
+/// Minifies a number with an optional unit, i.e. `10.0px`.
+pub(crate) fn minify_number(value: &str, precision: Option<u8>) -> Option<String> {
+    let value = value.trim();
+    let len = number_len(value)?;
+    let unit = &value[len..];
+
+    if !unit.chars().all(|c| c.is_ascii_alphabetic()) && unit != "%" {
+        return None;
+    }
+
+    let number = value[..len].parse::<f64>().ok()?;
+
+    Some(format!("{}{}", format_number(number, precision), unit))
+}
+
+/// Minifies a list of numbers separated by whitespaces and/or commas, i.e. the
+/// `viewBox` and `points` attributes.
+pub(crate) fn minify_number_list(value: &str, precision: Option<u8>) -> Option<String> {
+    let mut numbers = vec![];
+    let mut rest = value.trim_start_matches(is_separator);
+
+    while !rest.is_empty() {
+        let len = number_len(rest)?;
+
+        numbers.push(format_number(rest[..len].parse().ok()?, precision));
+
+        rest = rest[len..].trim_start_matches(is_separator);
+    }
+
+    Some(numbers.join(" "))
+}
+
+fn is_separator(c: char) -> bool {
+    matches!(c, ' ' | '\t' | '\n' | '\r' | '\x0C' | ',')
+}
+
+fn arity(command: char) -> Option<usize> {
+    match command.to_ascii_uppercase() {
+        'Z' => Some(0),
+        'H' | 'V' => Some(1),
+        'M' | 'L' | 'T' => Some(2),
+        'S' | 'Q' => Some(4),
+        'C' => Some(6),
+        'A' => Some(7),
+        _ => None,
+    }
+}
+
+/// Parses path data into commands with their arguments, implicit commands are
+/// made explicit, i.e. `M0 0 1 1` is `M0 0L1 1`.
+///
+/// Returns [None] for invalid path data, because browsers render it up to the
+/// error, which would be lost otherwise.
+fn parse_path_data(data: &str) -> Option<Vec<(char, Vec<f64>)>> {
+    let mut segments = vec![];
+    let mut rest = data.trim_start_matches(is_separator);
+    let mut command = None;
+
+    while !rest.is_empty() {
+        if let Some(c) = rest.chars().next().filter(|c| arity(*c).is_some()) {
+            command = Some(c);
+            rest = rest[1..].trim_start_matches(is_separator);
+
+            if arity(c) == Some(0) {
+                segments.push((c, vec![]));
+
+                // Arguments are not allowed after `Z`
+                command = None;
+            }
+
+            continue;
+        }
+
+        let current = command?;
+        let mut args = Vec::with_capacity(arity(current)?);
+
+        for index in 0..arity(current)? {
+            // Flags of arcs can be written without separators, i.e. `a1 1 0 00 1 1`
+            let len = if current.eq_ignore_ascii_case(&'a') && (index == 3 || index == 4) {
+                match rest.chars().next() {
+                    Some('0' | '1') => 1,
+                    _ => return None,
+                }
+            } else {
+                number_len(rest)?
+            };
+
+            args.push(rest[..len].parse().ok()?);
+
+            rest = rest[len..].trim_start_matches(is_separator);
+        }
+
+        segments.push((current, args));
+
+        // The following pairs of `M` are `L`
+        command = match current {
+            'M' => Some('L'),
+            'm' => Some('l'),
+            _ => Some(current),
+        };
+    }
+
+    match segments.first() {
+        Some(('M' | 'm', _)) => Some(segments),
+        _ => None,
+    }
+}
+
+/// Returns the axis of each argument of `command`, or [None] if the argument is
+/// not a coordinate.
+fn axes(command: char) -> &'static [Option<usize>] {
+    match command.to_ascii_uppercase() {
+        'M' | 'L' | 'T' => &[Some(0), Some(1)],
+        'H' => &[Some(0)],
+        'V' => &[Some(1)],
+        'S' | 'Q' => &[Some(0), Some(1), Some(0), Some(1)],
+        'C' => &[Some(0), Some(1), Some(0), Some(1), Some(0), Some(1)],
+        'A' => &[None, None, None, None, None, Some(0), Some(1)],
+        _ => &[],
+    }
+}
+
+/// A point of a path, in the source and in the minified path data which has
+/// rounded numbers.
+#[derive(Debug, Clone, Copy, Default)]
+struct Point {
+    source: [f64; 2],
+    minified: [f64; 2],
+}
+
+/// Removes redundant commands and separators of path data, and rounds numbers
+/// to `precision` decimal places.
+///
+/// Relative coordinates are computed from the rounded previous point, so
+/// rounding errors don't accumulate over the segments.
+pub(crate) fn minify_path_data(data: &str, precision: Option<u8>) -> Option<String> {
+    let segments = parse_path_data(data)?;
+    let mut minified = String::with_capacity(data.len());
+    let mut previous_command = None;
+    let mut previous_number: Option<String> = None;
+    let mut current = Point::default();
+    let mut subpath_start = Point::default();
+
+    for (command, args) in segments {
+        // `M` can't be repeated, the following pairs are `L`
+        let is_implicit = match (previous_command, command) {
+            (Some('M'), 'L') | (Some('m'), 'l') => true,
+            (Some(previous), _) => previous == command && !matches!(command, 'M' | 'm' | 'Z' | 'z'),
+            (None, _) => false,
+        };
+
+        if !is_implicit {
+            minified.push(command);
+            previous_number = None;
+        }
+
+        previous_command = Some(command);
+
+        let is_relative = command.is_ascii_lowercase();
+        let start = current;
+
+        for (index, (arg, axis)) in args.into_iter().zip(axes(command)).enumerate() {
+            let is_flag = command.eq_ignore_ascii_case(&'a') && (index == 3 || index == 4);
+            let number = match *axis {
+                Some(axis) if is_relative => {
+                    let source = start.source[axis] + arg;
+                    let delta = match precision {
+                        Some(..) => source - start.minified[axis],
+                        None => arg,
+                    };
+                    let number = format_number(delta, precision);
+
+                    current.source[axis] = source;
+                    current.minified[axis] =
+                        start.minified[axis] + number.parse::<f64>().unwrap_or(delta);
+
+                    number
+                }
+                Some(axis) => {
+                    let number = format_number(arg, precision);
+
+                    current.source[axis] = arg;
+                    current.minified[axis] = number.parse().unwrap_or(arg);
+
+                    number
+                }
+                None if is_flag => format_number(arg, None),
+                None => format_number(arg, precision),
+            };
+
+            if let Some(previous_number) = &previous_number {
+                // `-` and the second `.` start a new number
+                let needs_separator = !(number.starts_with('-')
+                    || number.starts_with('.') && previous_number.contains('.'));
+
+                if needs_separator {
+                    minified.push(' ');
+                }
+            }
+
+            minified.push_str(&number);
+            previous_number = Some(number);
+        }
+
+        match command {
+            'M' | 'm' => subpath_start = current,
+            'Z' | 'z' => current = subpath_start,
+            _ => {}
+        }
+    }
+
+    Some(minified)
+}
@@ -0,0 +1,82 @@
+#![deny(warnings)]
+
+use std::{
+    fs::read_to_string,
+    path::{Path, PathBuf},
+};
+
+use swc_svg_minifier::{minify_document, option::MinifyOptions};
+use swc_xml_ast::Document;
+use swc_xml_codegen::{
+    writer::basic::{BasicXmlWriter, BasicXmlWriterConfig},
+    CodeGenerator, CodegenConfig, Emit,
+};
+use swc_xml_parser::parse_file_as_document;
+use testing::NormalizedOutput;
+
+fn find_config(dir: &Path) -> Option<String> {
+    let config = dir.join("config.json");
+
+    if config.exists() {
+        let config = read_to_string(&config).expect("failed to read config.json");
+
+        return Some(config);
+    }
+
+    None
+}
+
+#[testing::fixture("tests/fixture/**/input.svg")]
+fn test_minify_document(input: PathBuf) {
+    let dir = input.parent().unwrap();
+    let output = dir.join(format!(
+        "output.min.{}",
+        input.extension().unwrap().to_string_lossy()
+    ));
+
+    testing::run_test(false, |cm, handler| {
+        let fm = cm.load_file(&input).unwrap();
+
+        let mut errors = vec![];
+        let result: Result<Document, _> =
+            parse_file_as_document(&fm, Default::default(), &mut errors);
+
+        for err in errors {
+            err.to_diagnostics(handler).emit();
+        }
+
+        if handler.has_errors() {
+            return Err(());
+        }
+
+        let mut document = result.unwrap();
+        let config = match find_config(dir) {
+            Some(config) => serde_json::from_str(&config).unwrap(),
+            None => MinifyOptions::default(),
+        };
+
+        // Apply transforms
+        minify_document(&mut document, &config);
+
+        let mut xml_str = String::new();
+        {
+            let wr = BasicXmlWriter::new(&mut xml_str, None, BasicXmlWriterConfig::default());
+            let mut gen = CodeGenerator::new(
+                wr,
+                CodegenConfig {
+                    minify: true,
+                    ..Default::default()
+                },
+            );
+
+            gen.emit(&document).unwrap();
+        }
+
+        NormalizedOutput::new_raw(xml_str)
+            .compare_to_file(&output)
+            .unwrap();
+
+        Ok(())
+    })
+    .unwrap();
+}
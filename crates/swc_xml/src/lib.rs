@@ -1,4 +1,10 @@
+#[cfg(feature = "svg_minifier")]
+#[cfg_attr(docsrs, doc(cfg(feature = "svg_minifier")))]
+pub extern crate swc_svg_minifier as svg_minifier;
 pub extern crate swc_xml_ast as ast;
 pub extern crate swc_xml_codegen as codegen;
 pub extern crate swc_xml_parser as parser;
+#[cfg(feature = "utils")]
+#[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+pub extern crate swc_xml_utils as utils;
 pub extern crate swc_xml_visit as visit;
@@ -44,9 +44,110 @@ pub struct DocumentType {
     pub name: Option<JsWord>,
     pub public_id: Option<JsWord>,
     pub system_id: Option<JsWord>,
+    /// Declarations between `[` and `]`, i.e. `<!DOCTYPE root [<!ENTITY a
+    /// "b">]>`.
+    pub internal_subset: Vec<DtdDeclaration>,
     pub raw: Option<JsWord>,
 }
 
+#[ast_node]
+#[derive(Eq, Hash, Is, EqIgnoreSpan)]
+pub enum DtdDeclaration {
+    #[tag("ElementDeclaration")]
+    Element(ElementDeclaration),
+    #[tag("AttributeListDeclaration")]
+    AttributeList(AttributeListDeclaration),
+    #[tag("EntityDeclaration")]
+    Entity(EntityDeclaration),
+    #[tag("NotationDeclaration")]
+    Notation(NotationDeclaration),
+    #[tag("ParameterEntityReference")]
+    ParameterEntityReference(ParameterEntityReference),
+    #[tag("Comment")]
+    Comment(Comment),
+    #[tag("ProcessingInstruction")]
+    ProcessingInstruction(ProcessingInstruction),
+}
+
+/// `<!ELEMENT name content>`
+#[ast_node("ElementDeclaration")]
+#[derive(Eq, Hash, EqIgnoreSpan)]
+pub struct ElementDeclaration {
+    pub span: Span,
+    pub name: JsWord,
+    /// The content model as written, i.e. `EMPTY`, `ANY` or `(#PCDATA|b)*`.
+    pub content: JsWord,
+}
+
+/// `<!ATTLIST element name type default>`
+#[ast_node("AttributeListDeclaration")]
+#[derive(Eq, Hash, EqIgnoreSpan)]
+pub struct AttributeListDeclaration {
+    pub span: Span,
+    pub element_name: JsWord,
+    pub attributes: Vec<AttributeDefinition>,
+}
+
+#[ast_node("AttributeDefinition")]
+#[derive(Eq, Hash, EqIgnoreSpan)]
+pub struct AttributeDefinition {
+    pub span: Span,
+    pub name: JsWord,
+    /// The type as written, i.e. `CDATA`, `ID` or `(left|right)`.
+    pub attribute_type: JsWord,
+    /// [None] if the declaration is only a default value.
+    pub default_kind: Option<AttributeDefaultKind>,
+    /// The default value with references replaced.
+    pub default_value: Option<JsWord>,
+    pub raw_default_value: Option<JsWord>,
+}
+
+#[derive(StringEnum, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash, EqIgnoreSpan)]
+pub enum AttributeDefaultKind {
+    /// `#REQUIRED`
+    Required,
+    /// `#IMPLIED`
+    Implied,
+    /// `#FIXED`
+    Fixed,
+}
+
+/// `<!ENTITY name "value">` and `<!ENTITY % name "value">`
+#[ast_node("EntityDeclaration")]
+#[derive(Eq, Hash, EqIgnoreSpan)]
+pub struct EntityDeclaration {
+    pub span: Span,
+    /// `true` for parameter entities, i.e. `<!ENTITY % name "value">`.
+    pub is_parameter: bool,
+    pub name: JsWord,
+    /// The replacement text of internal entities, character references are
+    /// replaced and entity references are kept as-is.
+    pub value: Option<JsWord>,
+    pub raw_value: Option<JsWord>,
+    pub public_id: Option<JsWord>,
+    pub system_id: Option<JsWord>,
+    /// The notation of unparsed entities, i.e. `NDATA gif`.
+    pub notation: Option<JsWord>,
+}
+
+/// `<!NOTATION name SYSTEM "system-id">`
+#[ast_node("NotationDeclaration")]
+#[derive(Eq, Hash, EqIgnoreSpan)]
+pub struct NotationDeclaration {
+    pub span: Span,
+    pub name: JsWord,
+    pub public_id: Option<JsWord>,
+    pub system_id: Option<JsWord>,
+}
+
+/// `%name;` between declarations, it is not expanded.
+#[ast_node("ParameterEntityReference")]
+#[derive(Eq, Hash, EqIgnoreSpan)]
+pub struct ParameterEntityReference {
+    pub span: Span,
+    pub name: JsWord,
+}
+
 #[derive(StringEnum, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash, EqIgnoreSpan)]
 pub enum Namespace {
     /// `http://www.w3.org/1999/xhtml`
@@ -2,6 +2,8 @@ use serde::{Deserialize, Serialize};
 use swc_atoms::JsWord;
 use swc_common::{ast_node, EqIgnoreSpan, Span};
 
+use crate::DtdDeclaration;
+
 #[ast_node("TokenAndSpan")]
 #[derive(Eq, Hash, EqIgnoreSpan)]
 pub struct TokenAndSpan {
@@ -29,6 +31,8 @@ pub enum Token {
         public_id: Option<JsWord>,
         // System identifier
         system_id: Option<JsWord>,
+        // Internal subset
+        internal_subset: Vec<DtdDeclaration>,
         // Raw value
         raw: Option<JsWord>,
     },
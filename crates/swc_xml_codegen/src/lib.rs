@@ -4,6 +4,7 @@
 pub use std::fmt::Result;
 use std::{iter::Peekable, str::Chars};
 
+use swc_atoms::JsWord;
 use swc_common::Spanned;
 use swc_xml_ast::*;
 use swc_xml_codegen_macros::emitter;
@@ -146,9 +147,27 @@ where
             doctype.push(system_id_quote);
         }
 
+        if !n.internal_subset.is_empty() {
+            doctype.push_str(" [");
+
+            for declaration in &n.internal_subset {
+                if !self.config.minify {
+                    doctype.push_str("\n    ");
+                }
+
+                doctype.push_str(&print_dtd_declaration(declaration));
+            }
+
+            if !self.config.minify {
+                doctype.push('\n');
+            }
+
+            doctype.push(']');
+        }
+
         doctype.push('>');
 
-        write_raw!(self, n.span, &doctype);
+        write_multiline_raw!(self, n.span, &doctype);
         formatting_newline!(self);
     }
 
@@ -344,6 +363,128 @@ where
     }
 }
 
+fn print_dtd_declaration(declaration: &DtdDeclaration) -> String {
+    match declaration {
+        DtdDeclaration::Element(n) => format!("<!ELEMENT {} {}>", n.name, n.content),
+        DtdDeclaration::AttributeList(n) => {
+            let mut attribute_list = format!("<!ATTLIST {}", n.element_name);
+
+            for attribute in &n.attributes {
+                attribute_list.push(' ');
+                attribute_list.push_str(&attribute.name);
+                attribute_list.push(' ');
+                attribute_list.push_str(&attribute.attribute_type);
+
+                match attribute.default_kind {
+                    Some(AttributeDefaultKind::Required) => attribute_list.push_str(" #REQUIRED"),
+                    Some(AttributeDefaultKind::Implied) => attribute_list.push_str(" #IMPLIED"),
+                    Some(AttributeDefaultKind::Fixed) => attribute_list.push_str(" #FIXED"),
+                    None => {}
+                }
+
+                if let Some(value) = &attribute.default_value {
+                    attribute_list.push(' ');
+                    attribute_list.push_str(&normalize_attribute_value(value));
+                }
+            }
+
+            attribute_list.push('>');
+
+            attribute_list
+        }
+        DtdDeclaration::Entity(n) => {
+            let mut entity = String::from("<!ENTITY ");
+
+            if n.is_parameter {
+                entity.push_str("% ");
+            }
+
+            entity.push_str(&n.name);
+
+            if let Some(value) = &n.value {
+                entity.push_str(" \"");
+                entity.push_str(&escape_entity_value(value));
+                entity.push('"');
+            } else {
+                entity.push_str(&print_external_id(&n.public_id, &n.system_id));
+            }
+
+            if let Some(notation) = &n.notation {
+                entity.push_str(" NDATA ");
+                entity.push_str(notation);
+            }
+
+            entity.push('>');
+
+            entity
+        }
+        DtdDeclaration::Notation(n) => format!(
+            "<!NOTATION {}{}>",
+            n.name,
+            print_external_id(&n.public_id, &n.system_id)
+        ),
+        DtdDeclaration::ParameterEntityReference(n) => format!("%{};", n.name),
+        DtdDeclaration::Comment(n) => format!("<!--{}-->", n.data),
+        DtdDeclaration::ProcessingInstruction(n) => format!("<?{} {}?>", n.target, n.data),
+    }
+}
+
+fn print_external_id(public_id: &Option<JsWord>, system_id: &Option<JsWord>) -> String {
+    let mut external_id = String::new();
+
+    if let Some(public_id) = public_id {
+        external_id.push_str(" PUBLIC ");
+        external_id.push_str(&quote(public_id));
+    } else if system_id.is_some() {
+        external_id.push_str(" SYSTEM");
+    }
+
+    if let Some(system_id) = system_id {
+        external_id.push(' ');
+        external_id.push_str(&quote(system_id));
+    }
+
+    external_id
+}
+
+fn quote(value: &str) -> String {
+    let quote = if value.contains('"') { '\'' } else { '"' };
+
+    format!("{}{}{}", quote, value, quote)
+}
+
+// Character references are replaced when an entity is declared and the
+// replacement text is parsed again when the entity is referenced, so `&` is
+// only kept as-is for entity references, i.e. `&name;`.
+fn escape_entity_value(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+
+    for (index, c) in value.char_indices() {
+        match c {
+            '&' if !is_entity_reference(&value[index..]) => result.push_str("&#38;"),
+            '%' => result.push_str("&#37;"),
+            '"' => result.push_str("&#34;"),
+            _ => result.push(c),
+        }
+    }
+
+    result
+}
+
+fn is_entity_reference(value: &str) -> bool {
+    match value[1..].split_once(';') {
+        Some((name, _)) => {
+            !name.is_empty()
+                && !name
+                    .starts_with(|c: char| c == '#' || c == '-' || c == '.' || c.is_ascii_digit())
+                && name
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || matches!(c, ':' | '_' | '-' | '.'))
+        }
+        None => false,
+    }
+}
+
 fn normalize_attribute_value(value: &str) -> String {
     if value.is_empty() {
         return "\"\"".to_string();
@@ -452,8 +593,10 @@ fn minify_amp(chars: &mut Peekable<Chars>) -> String {
 // occurrences of the """ character by the string "&quot;".
 //
 // 4. If the algorithm was not invoked in the attribute mode, replace any
-// occurrences of the "<" character by the string "&lt;", and any occurrences of
-// the ">" character by the string "&gt;".
+// occurrences of the ">" character by the string "&gt;".
+//
+// 5. Replace any occurrences of the "<" character by the string "&lt;", it is
+// not allowed in attribute values either.
 fn escape_string(value: &str, is_attribute_mode: bool) -> String {
     let mut result = String::with_capacity(value.len());
 
@@ -463,7 +606,7 @@ fn escape_string(value: &str, is_attribute_mode: bool) -> String {
                 result.push_str("&amp;");
             }
             '"' if is_attribute_mode => result.push_str("&quot;"),
-            '<' if is_attribute_mode => {
+            '<' => {
                 result.push_str("&lt;");
             }
             '>' if !is_attribute_mode => {
@@ -180,6 +180,18 @@ impl VisitMut for DropSpan {
         n.raw_value = None;
     }
 
+    fn visit_mut_attribute_definition(&mut self, n: &mut AttributeDefinition) {
+        n.visit_mut_children_with(self);
+
+        n.raw_default_value = None;
+    }
+
+    fn visit_mut_entity_declaration(&mut self, n: &mut EntityDeclaration) {
+        n.visit_mut_children_with(self);
+
+        n.raw_value = None;
+    }
+
     fn visit_mut_span(&mut self, n: &mut Span) {
         *n = Default::default()
     }
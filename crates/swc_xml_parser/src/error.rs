@@ -108,6 +108,12 @@ impl Error {
             ErrorKind::UnescapedCharacterInAttributeValue(c) => {
                 format!("Unescaped \"{}\" not allowed in attribute values", c).into()
             }
+            ErrorKind::InvalidMarkupDeclaration => "Invalid markup declaration".into(),
+            ErrorKind::RecursiveEntityReference => "Recursive entity reference".into(),
+            ErrorKind::ExternalEntityReference => {
+                "References to external entities are not expanded".into()
+            }
+            ErrorKind::EntityExpansionLimitExceeded => "Entity expansion limit exceeded".into(),
 
             // Parser errors
             ErrorKind::UnexpectedTokenInStartPhase => "Unexpected token in start phase".into(),
@@ -178,6 +184,10 @@ pub enum ErrorKind {
     NoTargetNameInProcessingInstruction,
     MissingWhitespaceBeforeQuestionInProcessingInstruction,
     UnescapedCharacterInAttributeValue(char),
+    InvalidMarkupDeclaration,
+    RecursiveEntityReference,
+    ExternalEntityReference,
+    EntityExpansionLimitExceeded,
 
     // Parser errors
     UnexpectedTokenInStartPhase,
@@ -0,0 +1,817 @@
+//! The internal subset of document types, i.e. `<!DOCTYPE root [...]>`, and
+//! the expansion of the general entities declared there.
+
+use swc_atoms::JsWord;
+use swc_common::{collections::AHashMap, BytePos, Span};
+use swc_xml_ast::*;
+
+use super::{is_name_char, is_name_start_char, is_whitespace};
+use crate::{
+    error::{Error, ErrorKind},
+    parser::EntityExpansionLimits,
+};
+
+/// Parses the declarations of an internal subset starting at `start`.
+///
+/// Invalid declarations are reported and skipped.
+pub(super) fn parse_internal_subset(
+    input: &str,
+    start: BytePos,
+) -> (Vec<DtdDeclaration>, Vec<Error>) {
+    let mut parser = DtdParser {
+        input,
+        pos: 0,
+        start,
+        errors: vec![],
+    };
+    let mut declarations = vec![];
+
+    loop {
+        parser.skip_whitespaces();
+
+        if parser.pos == input.len() {
+            break;
+        }
+
+        let declaration_start = parser.pos;
+
+        match parser.parse_declaration() {
+            Some(declaration) => declarations.push(declaration),
+            None => {
+                parser.recover(declaration_start);
+
+                let span = parser.span(declaration_start, parser.pos);
+
+                parser
+                    .errors
+                    .push(Error::new(span, ErrorKind::InvalidMarkupDeclaration));
+            }
+        }
+    }
+
+    (declarations, parser.errors)
+}
+
+struct DtdParser<'a> {
+    input: &'a str,
+    pos: usize,
+    start: BytePos,
+    errors: Vec<Error>,
+}
+
+impl<'a> DtdParser<'a> {
+    fn span(&self, start: usize, end: usize) -> Span {
+        Span::new(
+            self.start + BytePos(start as u32),
+            self.start + BytePos(end as u32),
+            Default::default(),
+        )
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        if self.rest().starts_with(token) {
+            self.pos += token.len();
+
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_whitespaces(&mut self) -> bool {
+        let rest = self.rest();
+        let len = rest.len() - rest.trim_start_matches(is_whitespace).len();
+
+        self.pos += len;
+
+        len > 0
+    }
+
+    fn expect_whitespaces(&mut self) -> Option<()> {
+        if self.skip_whitespaces() {
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    /// Skips the rest of an invalid declaration.
+    fn recover(&mut self, start: usize) {
+        let rest = &self.input[start..];
+
+        // Text between declarations is skipped up to the next declaration
+        let len = if rest.starts_with('<') {
+            rest.find('>').map(|len| len + 1)
+        } else {
+            rest.find(['<', '%'])
+        };
+
+        self.pos = start + len.unwrap_or(rest.len());
+    }
+
+    fn parse_name(&mut self) -> Option<&'a str> {
+        let rest = self.rest();
+
+        if !rest.starts_with(is_name_start_char) {
+            return None;
+        }
+
+        let len = rest.find(|c| !is_name_char(c)).unwrap_or(rest.len());
+
+        self.pos += len;
+
+        Some(&rest[..len])
+    }
+
+    /// Returns the value and the raw value of a quoted literal.
+    fn parse_literal(&mut self) -> Option<(&'a str, &'a str)> {
+        let rest = self.rest();
+        let quote = rest.chars().next().filter(|c| matches!(c, '"' | '\''))?;
+        let len = rest[1..].find(quote)?;
+
+        self.pos += len + 2;
+
+        Some((&rest[1..len + 1], &rest[..len + 2]))
+    }
+
+    fn parse_declaration(&mut self) -> Option<DtdDeclaration> {
+        let start = self.pos;
+
+        if self.eat("<!--") {
+            let len = self.rest().find("-->")?;
+            let data = &self.rest()[..len];
+
+            self.pos += len + 3;
+
+            return Some(DtdDeclaration::Comment(Comment {
+                span: self.span(start, self.pos),
+                data: data.into(),
+                raw: Some(self.input[start..self.pos].into()),
+            }));
+        }
+
+        if self.eat("<?") {
+            let target = self.parse_name()?;
+            let len = self.rest().find("?>")?;
+            let data = self.rest()[..len].trim_start_matches(is_whitespace);
+
+            if len > 0 && data.len() == len {
+                return None;
+            }
+
+            self.pos += len + 2;
+
+            return Some(DtdDeclaration::ProcessingInstruction(
+                ProcessingInstruction {
+                    span: self.span(start, self.pos),
+                    target: target.into(),
+                    data: data.into(),
+                },
+            ));
+        }
+
+        if self.eat("%") {
+            let name = self.parse_name()?;
+
+            if !self.eat(";") {
+                return None;
+            }
+
+            return Some(DtdDeclaration::ParameterEntityReference(
+                ParameterEntityReference {
+                    span: self.span(start, self.pos),
+                    name: name.into(),
+                },
+            ));
+        }
+
+        let declaration = if self.eat("<!ELEMENT") {
+            self.parse_element_declaration(start)?
+        } else if self.eat("<!ATTLIST") {
+            self.parse_attribute_list_declaration(start)?
+        } else if self.eat("<!ENTITY") {
+            self.parse_entity_declaration(start)?
+        } else if self.eat("<!NOTATION") {
+            self.parse_notation_declaration(start)?
+        } else {
+            return None;
+        };
+
+        Some(declaration)
+    }
+
+    fn parse_element_declaration(&mut self, start: usize) -> Option<DtdDeclaration> {
+        self.expect_whitespaces()?;
+
+        let name = self.parse_name()?;
+
+        self.expect_whitespaces()?;
+
+        let len = self.rest().find('>')?;
+        let content = self.rest()[..len].trim_end_matches(is_whitespace);
+
+        if content.is_empty() {
+            return None;
+        }
+
+        self.pos += len + 1;
+
+        Some(DtdDeclaration::Element(ElementDeclaration {
+            span: self.span(start, self.pos),
+            name: name.into(),
+            content: content.into(),
+        }))
+    }
+
+    fn parse_attribute_list_declaration(&mut self, start: usize) -> Option<DtdDeclaration> {
+        self.expect_whitespaces()?;
+
+        let element_name = self.parse_name()?;
+        let mut attributes = vec![];
+
+        loop {
+            let has_whitespaces = self.skip_whitespaces();
+
+            if self.eat(">") {
+                break;
+            }
+
+            if !has_whitespaces {
+                return None;
+            }
+
+            attributes.push(self.parse_attribute_definition()?);
+        }
+
+        Some(DtdDeclaration::AttributeList(AttributeListDeclaration {
+            span: self.span(start, self.pos),
+            element_name: element_name.into(),
+            attributes,
+        }))
+    }
+
+    fn parse_attribute_definition(&mut self) -> Option<AttributeDefinition> {
+        let start = self.pos;
+        let name = self.parse_name()?;
+
+        self.expect_whitespaces()?;
+
+        let type_start = self.pos;
+
+        if self.rest().starts_with('(') {
+            self.parse_enumeration()?;
+        } else {
+            match self.parse_name()? {
+                "NOTATION" => {
+                    self.expect_whitespaces()?;
+                    self.parse_enumeration()?;
+                }
+                "CDATA" | "ID" | "IDREF" | "IDREFS" | "ENTITY" | "ENTITIES" | "NMTOKEN"
+                | "NMTOKENS" => {}
+                _ => return None,
+            }
+        }
+
+        let attribute_type = &self.input[type_start..self.pos];
+
+        self.expect_whitespaces()?;
+
+        let (default_kind, literal) = if self.eat("#REQUIRED") {
+            (Some(AttributeDefaultKind::Required), None)
+        } else if self.eat("#IMPLIED") {
+            (Some(AttributeDefaultKind::Implied), None)
+        } else if self.eat("#FIXED") {
+            self.expect_whitespaces()?;
+
+            (
+                Some(AttributeDefaultKind::Fixed),
+                Some(self.parse_literal()?),
+            )
+        } else {
+            (None, Some(self.parse_literal()?))
+        };
+
+        Some(AttributeDefinition {
+            span: self.span(start, self.pos),
+            name: name.into(),
+            attribute_type: attribute_type.into(),
+            default_kind,
+            // References are replaced once all entities are declared
+            default_value: literal.map(|(value, _)| value.into()),
+            raw_default_value: literal.map(|(_, raw)| raw.into()),
+        })
+    }
+
+    /// `(a|b)`, whitespaces are allowed around names.
+    fn parse_enumeration(&mut self) -> Option<()> {
+        if !self.eat("(") {
+            return None;
+        }
+
+        loop {
+            self.skip_whitespaces();
+
+            let rest = self.rest();
+            let len = rest.find(|c| !is_name_char(c)).unwrap_or(rest.len());
+
+            if len == 0 {
+                return None;
+            }
+
+            self.pos += len;
+            self.skip_whitespaces();
+
+            if self.eat(")") {
+                return Some(());
+            }
+
+            if !self.eat("|") {
+                return None;
+            }
+        }
+    }
+
+    fn parse_entity_declaration(&mut self, start: usize) -> Option<DtdDeclaration> {
+        self.expect_whitespaces()?;
+
+        let is_parameter = self.eat("%");
+
+        if is_parameter {
+            self.expect_whitespaces()?;
+        }
+
+        let name = self.parse_name()?;
+
+        self.expect_whitespaces()?;
+
+        let literal_start = self.pos;
+        let mut declaration = EntityDeclaration {
+            span: Default::default(),
+            is_parameter,
+            name: name.into(),
+            value: None,
+            raw_value: None,
+            public_id: None,
+            system_id: None,
+            notation: None,
+        };
+
+        if let Some((value, raw)) = self.parse_literal() {
+            declaration.value = Some(self.parse_entity_value(value, literal_start + 1).into());
+            declaration.raw_value = Some(raw.into());
+        } else {
+            let (public_id, system_id) = self.parse_external_id(false)?;
+
+            declaration.public_id = public_id.map(JsWord::from);
+            declaration.system_id = system_id.map(JsWord::from);
+
+            let has_whitespaces = self.skip_whitespaces();
+
+            // Only general entities can be unparsed
+            if !is_parameter && has_whitespaces && self.eat("NDATA") {
+                self.expect_whitespaces()?;
+
+                declaration.notation = Some(self.parse_name()?.into());
+            }
+        }
+
+        self.skip_whitespaces();
+
+        if !self.eat(">") {
+            return None;
+        }
+
+        declaration.span = self.span(start, self.pos);
+
+        Some(DtdDeclaration::Entity(declaration))
+    }
+
+    /// Replaces the character references of an entity value, entity references
+    /// are replaced where the entity is referenced.
+    fn parse_entity_value(&mut self, value: &str, offset: usize) -> String {
+        let mut replaced = String::with_capacity(value.len());
+        let mut rest = value;
+
+        while let Some(index) = rest.find(['&', '%']) {
+            replaced.push_str(&rest[..index]);
+            rest = &rest[index..];
+
+            let position = offset + (value.len() - rest.len());
+
+            match parse_reference(rest) {
+                Some((len, Reference::Character(c))) if rest.starts_with("&#") => {
+                    replaced.push(c);
+                    rest = &rest[len..];
+
+                    continue;
+                }
+                Some((len, _)) if rest.starts_with('&') => {
+                    replaced.push_str(&rest[..len]);
+                    rest = &rest[len..];
+
+                    continue;
+                }
+                _ => {}
+            }
+
+            // Parameter entity references are not allowed in the internal subset
+            let kind = if rest.starts_with('%') {
+                ErrorKind::InvalidMarkupDeclaration
+            } else {
+                ErrorKind::InvalidEntityCharacter
+            };
+
+            self.errors
+                .push(Error::new(self.span(position, position + 1), kind));
+
+            replaced.push_str(&rest[..1]);
+            rest = &rest[1..];
+        }
+
+        replaced.push_str(rest);
+        replaced
+    }
+
+    /// `SYSTEM "system-id"` or `PUBLIC "public-id" "system-id"`, the system id
+    /// is optional for notations.
+    fn parse_external_id(
+        &mut self,
+        is_notation: bool,
+    ) -> Option<(Option<&'a str>, Option<&'a str>)> {
+        if self.eat("SYSTEM") {
+            self.expect_whitespaces()?;
+
+            let (system_id, _) = self.parse_literal()?;
+
+            return Some((None, Some(system_id)));
+        }
+
+        if !self.eat("PUBLIC") {
+            return None;
+        }
+
+        self.expect_whitespaces()?;
+
+        let (public_id, _) = self.parse_literal()?;
+        let pos = self.pos;
+
+        if self.skip_whitespaces() {
+            if let Some((system_id, _)) = self.parse_literal() {
+                return Some((Some(public_id), Some(system_id)));
+            }
+        }
+
+        if !is_notation {
+            return None;
+        }
+
+        self.pos = pos;
+
+        Some((Some(public_id), None))
+    }
+
+    fn parse_notation_declaration(&mut self, start: usize) -> Option<DtdDeclaration> {
+        self.expect_whitespaces()?;
+
+        let name = self.parse_name()?;
+
+        self.expect_whitespaces()?;
+
+        let (public_id, system_id) = self.parse_external_id(true)?;
+
+        self.skip_whitespaces();
+
+        if !self.eat(">") {
+            return None;
+        }
+
+        Some(DtdDeclaration::Notation(NotationDeclaration {
+            span: self.span(start, self.pos),
+            name: name.into(),
+            public_id: public_id.map(JsWord::from),
+            system_id: system_id.map(JsWord::from),
+        }))
+    }
+}
+
+enum Reference<'a> {
+    /// Character references and references to predefined entities
+    Character(char),
+    Entity(&'a str),
+}
+
+/// Parses the reference at the start of `input`, i.e. `&#60;`, `&lt;` or
+/// `&name;`, and returns its length.
+fn parse_reference(input: &str) -> Option<(usize, Reference<'_>)> {
+    let len = input.find(';')? + 1;
+    let reference = &input[1..len - 1];
+
+    if let Some(code) = reference.strip_prefix('#') {
+        let code = match code.strip_prefix('x') {
+            Some(hex) if !hex.is_empty() && hex.chars().all(|c| c.is_ascii_hexdigit()) => {
+                u32::from_str_radix(hex, 16).ok()?
+            }
+            Some(..) => return None,
+            None if !code.is_empty() && code.chars().all(|c| c.is_ascii_digit()) => {
+                code.parse().ok()?
+            }
+            None => return None,
+        };
+
+        return Some((len, Reference::Character(char::from_u32(code)?)));
+    }
+
+    if !reference.starts_with(is_name_start_char) || !reference.chars().all(is_name_char) {
+        return None;
+    }
+
+    let reference = match reference {
+        "lt" => Reference::Character('<'),
+        "gt" => Reference::Character('>'),
+        "amp" => Reference::Character('&'),
+        "apos" => Reference::Character('\''),
+        "quot" => Reference::Character('"'),
+        _ => Reference::Entity(reference),
+    };
+
+    Some((len, reference))
+}
+
+#[derive(Debug)]
+enum Entity {
+    Internal {
+        value: JsWord,
+        /// The number of characters once all references are replaced
+        length: usize,
+        /// The nesting of references, `1` without references
+        depth: usize,
+    },
+    /// Entities referencing themselves, directly or not
+    Recursive,
+    /// External and unparsed entities, they are not loaded
+    External,
+}
+
+/// General entities declared in the internal subset.
+#[derive(Debug, Default)]
+pub(super) struct Entities {
+    entities: AHashMap<JsWord, Entity>,
+}
+
+impl Entities {
+    pub fn new(declarations: &[DtdDeclaration]) -> Self {
+        let mut values: AHashMap<JsWord, Option<JsWord>> = Default::default();
+
+        for declaration in declarations {
+            if let DtdDeclaration::Entity(EntityDeclaration {
+                is_parameter: false,
+                name,
+                value,
+                ..
+            }) = declaration
+            {
+                // The first declaration is binding
+                values.entry(name.clone()).or_insert_with(|| value.clone());
+            }
+        }
+
+        let mut entities = Entities::default();
+        let mut stack = vec![];
+
+        for name in values.keys() {
+            entities.measure(name, &values, &mut stack);
+        }
+
+        entities
+    }
+
+    /// Measures entities ahead of their expansion, so entities expanding
+    /// exponentially are rejected without expanding them.
+    fn measure(
+        &mut self,
+        name: &JsWord,
+        values: &AHashMap<JsWord, Option<JsWord>>,
+        stack: &mut Vec<JsWord>,
+    ) {
+        if self.entities.contains_key(name) || stack.contains(name) {
+            return;
+        }
+
+        let value = match &values[name] {
+            Some(value) => value,
+            None => {
+                self.entities.insert(name.clone(), Entity::External);
+
+                return;
+            }
+        };
+
+        stack.push(name.clone());
+
+        let mut length = 0usize;
+        let mut depth = 1;
+        let mut is_recursive = false;
+        let mut rest = &**value;
+
+        while let Some(index) = rest.find('&') {
+            length = length.saturating_add(rest[..index].chars().count());
+            rest = &rest[index..];
+
+            let len = match parse_reference(rest) {
+                Some((len, Reference::Entity(reference)))
+                    if values.contains_key(&JsWord::from(reference)) =>
+                {
+                    let reference = JsWord::from(reference);
+
+                    self.measure(&reference, values, stack);
+
+                    match self.entities.get(&reference) {
+                        Some(Entity::Internal {
+                            length: reference_length,
+                            depth: reference_depth,
+                            ..
+                        }) => {
+                            length = length.saturating_add(*reference_length);
+                            depth = depth.max(reference_depth.saturating_add(1));
+                        }
+                        Some(Entity::External) => {
+                            length = length.saturating_add(len);
+                        }
+                        // The reference is on the stack
+                        Some(Entity::Recursive) | None => {
+                            is_recursive = true;
+                        }
+                    }
+
+                    len
+                }
+                Some((len, Reference::Character(..))) => {
+                    length = length.saturating_add(1);
+
+                    len
+                }
+                Some((len, Reference::Entity(..))) => {
+                    length = length.saturating_add(len);
+
+                    len
+                }
+                None => {
+                    length = length.saturating_add(1);
+
+                    1
+                }
+            };
+
+            rest = &rest[len..];
+        }
+
+        length = length.saturating_add(rest.chars().count());
+
+        stack.pop();
+
+        let entity = if is_recursive {
+            Entity::Recursive
+        } else {
+            Entity::Internal {
+                value: value.clone(),
+                length,
+                depth,
+            }
+        };
+
+        self.entities.insert(name.clone(), entity);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.entities.contains_key(&JsWord::from(name))
+    }
+
+    /// Returns the replacement text of a reference to `name`, and adds its
+    /// length to `expanded_length`.
+    pub fn resolve(
+        &self,
+        name: &str,
+        limits: &EntityExpansionLimits,
+        expanded_length: &mut usize,
+    ) -> Result<&JsWord, ErrorKind> {
+        match self.entities.get(&JsWord::from(name)) {
+            Some(Entity::Internal {
+                value,
+                length,
+                depth,
+            }) => {
+                let total_length = expanded_length.saturating_add(*length);
+
+                if *depth > limits.max_depth || total_length > limits.max_length {
+                    return Err(ErrorKind::EntityExpansionLimitExceeded);
+                }
+
+                *expanded_length = total_length;
+
+                Ok(value)
+            }
+            Some(Entity::Recursive) => Err(ErrorKind::RecursiveEntityReference),
+            Some(Entity::External) => Err(ErrorKind::ExternalEntityReference),
+            None => Err(ErrorKind::InvalidEntityCharacter),
+        }
+    }
+
+    /// Replaces references in an attribute value, the expansion of each
+    /// reference is limited by `limits`.
+    pub fn expand_attribute_value(
+        &self,
+        value: &str,
+        limits: &EntityExpansionLimits,
+        expanded_length: &mut usize,
+        errors: &mut Vec<ErrorKind>,
+    ) -> String {
+        let mut expanded = String::with_capacity(value.len());
+
+        self.expand_into(
+            value,
+            Some((limits, expanded_length)),
+            &mut expanded,
+            errors,
+        );
+
+        expanded
+    }
+
+    /// Replaces references in the replacement text of an entity referenced in
+    /// an attribute value, the reference was already resolved with limits.
+    pub fn expand_replacement_text(&self, value: &str, errors: &mut Vec<ErrorKind>) -> String {
+        let mut expanded = String::with_capacity(value.len());
+
+        self.expand_into(value, None, &mut expanded, errors);
+
+        expanded
+    }
+
+    fn expand_into(
+        &self,
+        value: &str,
+        mut limits: Option<(&EntityExpansionLimits, &mut usize)>,
+        expanded: &mut String,
+        errors: &mut Vec<ErrorKind>,
+    ) {
+        let mut rest = value;
+
+        while let Some(index) = rest.find(['&', '<']) {
+            expanded.push_str(&rest[..index]);
+            rest = &rest[index..];
+
+            if rest.starts_with('<') {
+                errors.push(ErrorKind::UnescapedCharacterInAttributeValue('<'));
+                expanded.push('<');
+                rest = &rest[1..];
+
+                continue;
+            }
+
+            let (len, reference) = match parse_reference(rest) {
+                Some(reference) => reference,
+                None => {
+                    errors.push(ErrorKind::InvalidEntityCharacter);
+                    expanded.push('&');
+                    rest = &rest[1..];
+
+                    continue;
+                }
+            };
+
+            match reference {
+                Reference::Character(c) => expanded.push(c),
+                Reference::Entity(name) => {
+                    let replacement_text = match &mut limits {
+                        Some((limits, expanded_length)) => {
+                            self.resolve(name, limits, expanded_length)
+                        }
+                        None => self.resolve(name, &EntityExpansionLimits::UNLIMITED, &mut 0),
+                    };
+
+                    match replacement_text {
+                        Ok(replacement_text) => {
+                            self.expand_into(replacement_text, None, expanded, errors)
+                        }
+                        Err(kind) => {
+                            errors.push(kind);
+                            expanded.push_str(&rest[..len]);
+                        }
+                    }
+                }
+            }
+
+            rest = &rest[len..];
+        }
+
+        expanded.push_str(rest);
+    }
+}
@@ -1,14 +1,21 @@
-use std::{collections::VecDeque, mem::take};
+use std::{collections::VecDeque, mem::take, rc::Rc};
 
 use swc_atoms::JsWord;
-use swc_common::{collections::AHashSet, input::Input, BytePos, Span};
-use swc_xml_ast::{AttributeToken, Token, TokenAndSpan};
+use swc_common::{
+    collections::AHashSet,
+    input::{Input, StringInput},
+    BytePos, Span,
+};
+use swc_xml_ast::{AttributeToken, DtdDeclaration, Token, TokenAndSpan};
 
+use self::dtd::{parse_internal_subset, Entities};
 use crate::{
     error::{Error, ErrorKind},
-    parser::input::ParserInput,
+    parser::{input::ParserInput, EntityExpansionLimits},
 };
 
+mod dtd;
+
 #[derive(Debug, Clone)]
 pub enum State {
     Data,
@@ -73,6 +80,7 @@ struct Doctype {
     name: Option<String>,
     public_id: Option<String>,
     system_id: Option<String>,
+    internal_subset: Vec<DtdDeclaration>,
 }
 
 #[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
@@ -116,6 +124,13 @@ struct Cdata {
     raw: String,
 }
 
+enum EntityReference {
+    /// The raw reference and the replacement text
+    Expanded(String, JsWord),
+    /// The reference was reported and is kept as-is
+    Rejected,
+}
+
 pub(crate) type LexResult<T> = Result<T, ErrorKind>;
 
 pub struct Lexer<I>
@@ -139,6 +154,9 @@ where
     current_tag_token: Option<Tag>,
     current_cdata_token: Option<Cdata>,
     attribute_start_position: Option<BytePos>,
+    entities: Rc<Entities>,
+    entity_expansion_limits: Option<EntityExpansionLimits>,
+    expanded_length: usize,
 }
 
 impl<I> Lexer<I>
@@ -166,6 +184,9 @@ where
             current_tag_token: None,
             current_cdata_token: None,
             attribute_start_position: None,
+            entities: Default::default(),
+            entity_expansion_limits: Some(Default::default()),
+            expanded_length: 0,
         };
 
         // A leading Byte Order Mark (BOM) causes the character encoding argument to be
@@ -210,6 +231,10 @@ where
     fn take_errors(&mut self) -> Vec<Error> {
         take(&mut self.errors)
     }
+
+    fn set_entity_expansion_limits(&mut self, limits: Option<EntityExpansionLimits>) {
+        self.entity_expansion_limits = limits;
+    }
 }
 
 impl<I> Lexer<I>
@@ -526,6 +551,77 @@ where
         None
     }
 
+    /// Consumes a reference to a general entity declared in the internal
+    /// subset, i.e. `&name;`.
+    fn consume_entity_reference(&mut self) -> Option<EntityReference> {
+        let limits = self.entity_expansion_limits?;
+
+        if self.entities.is_empty() {
+            return None;
+        }
+
+        let start = self.input.cur_pos();
+
+        if !self.input.cur().map_or(false, is_name_start_char) {
+            return None;
+        }
+
+        let name = self.input.uncons_while(is_name_char).to_string();
+
+        if self.input.cur() != Some(';') || !self.entities.contains(&name) {
+            self.input.reset_to(start);
+
+            return None;
+        }
+
+        self.input.bump();
+
+        match self
+            .entities
+            .resolve(&name, &limits, &mut self.expanded_length)
+        {
+            Ok(value) => Some(EntityReference::Expanded(
+                format!("&{};", name),
+                value.clone(),
+            )),
+            Err(kind) => {
+                self.emit_error(kind);
+                self.input.reset_to(start);
+
+                Some(EntityReference::Rejected)
+            }
+        }
+    }
+
+    /// Emits the tokens of the replacement text of an entity referenced in
+    /// content, they are spanned to the reference.
+    fn emit_replacement_text(&mut self, value: &str) {
+        let span = Span::new(self.cur_pos, self.input.cur_pos(), Default::default());
+        let mut lexer = Lexer::new(StringInput::new(
+            value,
+            span.lo,
+            span.lo + BytePos(value.len() as u32),
+        ));
+
+        lexer.entities = self.entities.clone();
+        lexer.entity_expansion_limits = self.entity_expansion_limits;
+
+        for token_and_span in &mut lexer {
+            self.pending_tokens.push_back(TokenAndSpan {
+                span,
+                token: token_and_span.token,
+            });
+        }
+
+        for error in lexer.take_errors() {
+            let (_, kind) = *error.into_inner();
+
+            self.errors.push(Error::new(span, kind));
+        }
+
+        self.last_token_pos = self.input.cur_pos();
+    }
+
     fn create_doctype_token(&mut self, name_c: Option<char>) {
         let mut new_name = None;
 
@@ -540,6 +636,7 @@ where
             name: new_name,
             public_id: None,
             system_id: None,
+            internal_subset: vec![],
         });
     }
 
@@ -633,12 +730,97 @@ where
             name: current_doctype_token.name.map(JsWord::from),
             public_id: current_doctype_token.public_id.map(JsWord::from),
             system_id: current_doctype_token.system_id.map(JsWord::from),
+            internal_subset: current_doctype_token.internal_subset,
             raw: Some(JsWord::from(raw)),
         };
 
         self.emit_token(token);
     }
 
+    /// Consumes the internal subset up to the `]` which is not a part of a
+    /// declaration, and returns it with whether the `]` was found.
+    fn consume_internal_subset(&mut self) -> (String, bool) {
+        #[derive(PartialEq, Eq)]
+        enum Context {
+            Subset,
+            Declaration,
+            Literal(char),
+            Comment,
+            ProcessingInstruction,
+        }
+
+        let mut subset = String::new();
+        let mut context = Context::Subset;
+
+        loop {
+            let c = match self.consume_next_char() {
+                Some(']') if context == Context::Subset => return (subset, true),
+                Some(c) => c,
+                None => return (subset, false),
+            };
+
+            self.validate_input_stream_character(c);
+
+            if let Some(doctype_raw) = &mut self.doctype_raw {
+                doctype_raw.push(c);
+            }
+
+            subset.push(c);
+
+            context = match context {
+                Context::Subset if subset.ends_with("<!") => Context::Declaration,
+                Context::Subset if subset.ends_with("<?") => Context::ProcessingInstruction,
+                Context::Declaration if subset.ends_with("<!--") => Context::Comment,
+                Context::Declaration if c == '"' || c == '\'' => Context::Literal(c),
+                Context::Declaration if c == '>' => Context::Subset,
+                Context::Literal(quote) if c == quote => Context::Declaration,
+                Context::Comment if subset.ends_with("-->") => Context::Subset,
+                Context::ProcessingInstruction if subset.ends_with("?>") => Context::Subset,
+                context => context,
+            };
+        }
+    }
+
+    fn set_doctype_token_internal_subset(&mut self, subset: &str, start: BytePos) {
+        let (mut declarations, errors) = parse_internal_subset(subset, start);
+
+        self.errors.extend(errors);
+
+        if let Some(limits) = self.entity_expansion_limits {
+            self.entities = Rc::new(Entities::new(&declarations));
+
+            // Default values of attributes are expanded like attribute values
+            for declaration in &mut declarations {
+                if let DtdDeclaration::AttributeList(attribute_list) = declaration {
+                    for attribute in &mut attribute_list.attributes {
+                        if let Some(value) = &attribute.default_value {
+                            let mut errors = vec![];
+                            let value = self.entities.expand_attribute_value(
+                                value,
+                                &limits,
+                                &mut self.expanded_length,
+                                &mut errors,
+                            );
+
+                            for kind in errors {
+                                self.errors.push(Error::new(attribute.span, kind));
+                            }
+
+                            attribute.default_value = Some(value.into());
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some(Doctype {
+            internal_subset, ..
+        }) = &mut self.current_doctype_token
+        {
+            internal_subset.extend(declarations);
+        }
+    }
+
     fn create_tag_token(&mut self, kind: TagKind) {
         self.current_tag_token = Some(Tag {
             kind,
@@ -726,6 +908,21 @@ where
         }
     }
 
+    fn append_replacement_text_to_attribute(&mut self, value: &str, raw: &str) {
+        if let Some(Tag { attributes, .. }) = &mut self.current_tag_token {
+            if let Some(attribute) = attributes.last_mut() {
+                attribute
+                    .value
+                    .get_or_insert_with(|| String::with_capacity(255))
+                    .push_str(value);
+                attribute
+                    .raw_value
+                    .get_or_insert_with(|| String::with_capacity(255))
+                    .push_str(raw);
+            }
+        }
+    }
+
     fn append_to_attribute_with_entity(&mut self, value: Option<(Option<char>, Option<&str>)>) {
         if let Some(Tag { attributes, .. }) = &mut self.current_tag_token {
             if let Some(attribute) = attributes.last_mut() {
@@ -1064,6 +1261,20 @@ where
                 // Otherwise, emit character tokens that were returned.
                 self.state = State::Data;
 
+                match self.consume_entity_reference() {
+                    Some(EntityReference::Expanded(_, value)) => {
+                        self.emit_replacement_text(&value);
+
+                        return Ok(());
+                    }
+                    Some(EntityReference::Rejected) => {
+                        self.emit_character_token(('&', '&'));
+
+                        return Ok(());
+                    }
+                    None => {}
+                }
+
                 let character_reference = self.consume_character_reference();
 
                 if let Some((c, raw)) = character_reference {
@@ -2218,12 +2429,29 @@ where
                 //
                 // Finally, switch back to attribute value state that switched to this state.
 
-                let character_reference = self.consume_character_reference();
+                match self.consume_entity_reference() {
+                    Some(EntityReference::Expanded(raw, value)) => {
+                        let mut errors = vec![];
+                        let value = self.entities.expand_replacement_text(&value, &mut errors);
 
-                if let Some((c, raw)) = character_reference {
-                    self.append_to_attribute_with_entity(Some((Some(c), Some(&raw))));
-                } else {
-                    self.append_to_attribute(None, Some((false, Some('&'), Some('&'))));
+                        for kind in errors {
+                            self.emit_error(kind);
+                        }
+
+                        self.append_replacement_text_to_attribute(&value, &raw);
+                    }
+                    Some(EntityReference::Rejected) => {
+                        self.append_to_attribute(None, Some((false, Some('&'), Some('&'))));
+                    }
+                    None => {
+                        let character_reference = self.consume_character_reference();
+
+                        if let Some((c, raw)) = character_reference {
+                            self.append_to_attribute_with_entity(Some((Some(c), Some(&raw))));
+                        } else {
+                            self.append_to_attribute(None, Some((false, Some('&'), Some('&'))));
+                        }
+                    }
                 }
 
                 if let Some(return_state) = &self.return_state {
@@ -3014,31 +3242,25 @@ where
                 }
             }
             State::DoctypeTypeInternalSubSet => {
-                // Consume the next input character:
-                match self.consume_next_char() {
-                    // U+005D RIGHT SQUARE BRACKET (])
-                    // Switch to the CDATA bracket state.
-                    Some(c @ ']') => {
-                        self.append_raw_to_doctype_token(c);
-                        self.state = State::AfterDoctypeName;
-                    }
+                // Consume the declarations up to the U+005D RIGHT SQUARE BRACKET (]) and parse
+                // them at once, `]` can be a part of literals and comments.
+                let start = self.input.cur_pos();
+                let (subset, is_closed) = self.consume_internal_subset();
+
+                self.set_doctype_token_internal_subset(&subset, start);
+
+                if is_closed {
+                    // Switch to the after DOCTYPE name state.
+                    self.append_raw_to_doctype_token(']');
+                    self.state = State::AfterDoctypeName;
+                } else {
                     // EOF
                     // Parse error. Switch to the data state. Emit DOCTYPE token. Reconsume the EOF
                     // character.
-                    None => {
-                        self.emit_error(ErrorKind::EofInDoctype);
-                        self.state = State::Data;
-                        self.emit_doctype_token();
-                        self.reconsume();
-                    }
-                    // Anything else
-                    // Append the current input character to the current DOCTYPE token's system
-                    // identifier.
-                    Some(c) => {
-                        // TODO improve parse legacy declarations
-                        self.validate_input_stream_character(c);
-                        self.append_raw_to_doctype_token(c);
-                    }
+                    self.emit_error(ErrorKind::EofInDoctype);
+                    self.state = State::Data;
+                    self.emit_doctype_token();
+                    self.reconsume();
                 }
             }
             State::BogusDoctype => {
@@ -3,7 +3,7 @@ use std::{fmt::Debug, mem::take};
 use swc_common::{BytePos, Span};
 use swc_xml_ast::{Token, TokenAndSpan};
 
-use super::PResult;
+use super::{EntityExpansionLimits, PResult};
 use crate::error::Error;
 
 pub trait ParserInput: Iterator<Item = TokenAndSpan> {
@@ -12,6 +12,9 @@ pub trait ParserInput: Iterator<Item = TokenAndSpan> {
     fn last_pos(&mut self) -> BytePos;
 
     fn take_errors(&mut self) -> Vec<Error>;
+
+    /// [None] disables the expansion of entities.
+    fn set_entity_expansion_limits(&mut self, limits: Option<EntityExpansionLimits>);
 }
 
 #[derive(Debug)]
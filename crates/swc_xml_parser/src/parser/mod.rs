@@ -2,7 +2,8 @@ use std::{cell::RefCell, mem, rc::Rc};
 
 use node::*;
 use open_elements_stack::*;
-use swc_common::{Span, DUMMY_SP};
+use swc_atoms::JsWord;
+use swc_common::{collections::AHashMap, Span, DUMMY_SP};
 use swc_xml_ast::*;
 
 use self::input::{Buffer, ParserInput};
@@ -16,8 +17,66 @@ mod open_elements_stack;
 
 pub type PResult<T> = Result<T, Error>;
 
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct ParserConfig {}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ParserConfig {
+    /// Replace references to the general entities declared in the internal
+    /// subset of the document type, i.e. `&name;`.
+    ///
+    /// Defaults to `true`.
+    pub expand_entities: bool,
+
+    pub entity_expansion_limits: EntityExpansionLimits,
+
+    /// Add the default values declared with `<!ATTLIST>` to elements without
+    /// the attributes.
+    ///
+    /// Defaults to `false`.
+    pub apply_default_attributes: bool,
+}
+
+impl Default for ParserConfig {
+    fn default() -> Self {
+        ParserConfig {
+            expand_entities: true,
+            entity_expansion_limits: Default::default(),
+            apply_default_attributes: false,
+        }
+    }
+}
+
+/// Limits of entity expansion, entities referencing other entities many times
+/// expand exponentially, i.e. the "billion laughs" attack.
+///
+/// References exceeding the limits are reported and kept as-is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EntityExpansionLimits {
+    /// The maximum nesting of entity references.
+    ///
+    /// Defaults to `16`.
+    pub max_depth: usize,
+
+    /// The maximum number of characters produced by entity references in a
+    /// document.
+    ///
+    /// Defaults to `1_000_000`.
+    pub max_length: usize,
+}
+
+impl EntityExpansionLimits {
+    pub const UNLIMITED: EntityExpansionLimits = EntityExpansionLimits {
+        max_depth: usize::MAX,
+        max_length: usize::MAX,
+    };
+}
+
+impl Default for EntityExpansionLimits {
+    fn default() -> Self {
+        EntityExpansionLimits {
+            max_depth: 16,
+            max_length: 1_000_000,
+        }
+    }
+}
 
 pub enum Phase {
     StartPhase,
@@ -43,13 +102,20 @@ where
     open_elements_stack: OpenElementsStack,
     errors: Vec<Error>,
     phase: Phase,
+    default_attributes: AHashMap<JsWord, Vec<(JsWord, JsWord)>>,
 }
 
 impl<I> Parser<I>
 where
     I: ParserInput,
 {
-    pub fn new(input: I, config: ParserConfig) -> Self {
+    pub fn new(mut input: I, config: ParserConfig) -> Self {
+        input.set_entity_expansion_limits(if config.expand_entities {
+            Some(config.entity_expansion_limits)
+        } else {
+            None
+        });
+
         Parser {
             config,
             input: Buffer::new(input),
@@ -58,6 +124,7 @@ where
             open_elements_stack: OpenElementsStack::new(),
             errors: Default::default(),
             phase: Phase::default(),
+            default_attributes: Default::default(),
         }
     }
 
@@ -121,12 +188,14 @@ where
                 name,
                 public_id,
                 system_id,
+                internal_subset,
                 raw,
             } => Child::DocumentType(DocumentType {
                 span: start_span,
                 name,
                 public_id,
                 system_id,
+                internal_subset,
                 raw,
             }),
             Data::Element {
@@ -296,7 +365,13 @@ where
 
                     self.process_token(token_and_info, Some(Phase::EndPhase))?;
                 }
-                Token::Doctype { .. } => {
+                Token::Doctype {
+                    internal_subset, ..
+                } => {
+                    if self.config.apply_default_attributes {
+                        self.set_default_attributes(internal_subset);
+                    }
+
                     let document_type = self.create_document_type_for_token(token_and_info);
 
                     self.append_node(self.document.as_ref().unwrap(), document_type);
@@ -434,17 +509,47 @@ where
             .expect("no current element")
     }
 
+    fn set_default_attributes(&mut self, internal_subset: &[DtdDeclaration]) {
+        for declaration in internal_subset {
+            let attribute_list = match declaration {
+                DtdDeclaration::AttributeList(attribute_list) => attribute_list,
+                _ => continue,
+            };
+            let default_attributes = self
+                .default_attributes
+                .entry(attribute_list.element_name.clone())
+                .or_default();
+
+            for attribute in &attribute_list.attributes {
+                let value = match &attribute.default_value {
+                    Some(value) => value,
+                    None => continue,
+                };
+
+                // The first declaration is binding
+                if default_attributes
+                    .iter()
+                    .all(|(name, _)| *name != attribute.name)
+                {
+                    default_attributes.push((attribute.name.clone(), value.clone()));
+                }
+            }
+        }
+    }
+
     fn create_document_type_for_token(&self, token_and_info: &mut TokenAndInfo) -> RcNode {
-        let (name, public_id, system_id, raw) = match &token_and_info.token {
+        let (name, public_id, system_id, internal_subset, raw) = match &token_and_info.token {
             Token::Doctype {
                 name,
                 public_id,
                 system_id,
+                internal_subset,
                 raw,
             } => (
                 name.clone(),
                 public_id.clone(),
                 system_id.clone(),
+                internal_subset.clone(),
                 raw.clone(),
             ),
             _ => {
@@ -457,6 +562,7 @@ where
                 name,
                 public_id,
                 system_id,
+                internal_subset,
                 raw,
             },
             token_and_info.span,
@@ -480,7 +586,7 @@ where
                 attributes,
                 ..
             } => {
-                let attributes = attributes
+                let mut attributes: Vec<_> = attributes
                     .into_iter()
                     .map(|attribute_token| Attribute {
                         span: attribute_token.span,
@@ -493,6 +599,24 @@ where
                     })
                     .collect();
 
+                if let Some(default_attributes) = self.default_attributes.get(&tag_name) {
+                    for (name, value) in default_attributes {
+                        if attributes.iter().any(|attribute| attribute.name == *name) {
+                            continue;
+                        }
+
+                        attributes.push(Attribute {
+                            span: DUMMY_SP,
+                            namespace: None,
+                            prefix: None,
+                            name: name.clone(),
+                            raw_name: None,
+                            value: Some(value.clone()),
+                            raw_value: None,
+                        });
+                    }
+                }
+
                 Data::Element {
                     tag_name,
                     attributes: RefCell::new(attributes),
@@ -22,6 +22,7 @@ pub enum Data {
         name: Option<JsWord>,
         public_id: Option<JsWord>,
         system_id: Option<JsWord>,
+        internal_subset: Vec<DtdDeclaration>,
         raw: Option<JsWord>,
     },
     Element {
@@ -217,6 +217,20 @@ impl Visit for SpanVisualizer<'_> {
 
     mtd!(DocumentType, visit_document_type);
 
+    mtd!(DtdDeclaration, visit_dtd_declaration);
+
+    mtd!(ElementDeclaration, visit_element_declaration);
+
+    mtd!(AttributeListDeclaration, visit_attribute_list_declaration);
+
+    mtd!(AttributeDefinition, visit_attribute_definition);
+
+    mtd!(EntityDeclaration, visit_entity_declaration);
+
+    mtd!(NotationDeclaration, visit_notation_declaration);
+
+    mtd!(ParameterEntityReference, visit_parameter_entity_reference);
+
     mtd!(Element, visit_element);
 
     mtd!(Attribute, visit_attribute);
@@ -402,6 +416,17 @@ fn recovery(input: PathBuf) {
     )
 }
 
+#[testing::fixture("tests/default-attributes/**/*.xml")]
+fn default_attributes(input: PathBuf) {
+    document_test(
+        input,
+        ParserConfig {
+            apply_default_attributes: true,
+            ..Default::default()
+        },
+    )
+}
+
 #[testing::fixture("tests/fixture/**/*.xml")]
 #[testing::fixture("tests/recovery/**/*.xml")]
 fn span_visualizer(input: PathBuf) {
@@ -0,0 +1,380 @@
+#![deny(clippy::all)]
+
+//! Namespace-aware querying and mutation of [swc_xml_ast] documents.
+//!
+//! Elements are selected with a subset of XPath, see [Selector].
+//!
+//! ```ignore
+//! let namespaces = NamespaceContext::default().with_prefix("svg", SVG_NAMESPACE);
+//! let selector = Selector::parse("//svg:rect[@fill = 'red']", &namespaces)?;
+//!
+//! modify(&mut document, &selector, |element| {
+//!     set_attribute(element, "fill", "blue");
+//! });
+//! ```
+
+use std::rc::Rc;
+
+use swc_atoms::JsWord;
+use swc_common::DUMMY_SP;
+use swc_xml_ast::{Attribute, Child, Document, Element};
+
+use crate::{
+    namespace::{namespace_declaration, NamespaceScope},
+    selector::{Axis, NameTest, Operator, Predicate},
+};
+pub use crate::{
+    namespace::{NamespaceContext, SVG_NAMESPACE, XLINK_NAMESPACE, XML_NAMESPACE},
+    selector::{Selector, SelectorError},
+};
+
+pub mod namespace;
+mod selector;
+
+/// The document or an element, with the indexes of the children leading to it.
+#[derive(Clone)]
+struct Node<'a> {
+    path: Vec<usize>,
+    element: Option<&'a Element>,
+    scope: Rc<NamespaceScope>,
+}
+
+impl<'a> Node<'a> {
+    fn child_elements(&self, document: &'a Document) -> Vec<Node<'a>> {
+        let children = match self.element {
+            Some(element) => &element.children,
+            None => &document.children,
+        };
+
+        children
+            .iter()
+            .enumerate()
+            .filter_map(|(index, child)| match child {
+                Child::Element(element) => {
+                    let mut path = self.path.clone();
+
+                    path.push(index);
+
+                    Some(Node {
+                        path,
+                        element: Some(element),
+                        scope: NamespaceScope::enter(&self.scope, element),
+                    })
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn descendants_or_self(&self, document: &'a Document, nodes: &mut Vec<Node<'a>>) {
+        nodes.push(self.clone());
+
+        for child in self.child_elements(document) {
+            child.descendants_or_self(document, nodes);
+        }
+    }
+}
+
+fn matches_name(test: &NameTest, (namespace, name): (Option<JsWord>, &str)) -> bool {
+    match test {
+        NameTest::Any => true,
+        NameTest::Name {
+            namespace: expected_namespace,
+            local_name,
+        } => {
+            *expected_namespace == namespace
+                && local_name
+                    .as_ref()
+                    .map_or(true, |local_name| local_name == name)
+        }
+    }
+}
+
+fn matches_comparison(comparison: &Option<(Operator, JsWord)>, value: &str) -> bool {
+    match comparison {
+        Some((Operator::Equal, expected)) => value == &**expected,
+        Some((Operator::NotEqual, expected)) => value != &**expected,
+        None => true,
+    }
+}
+
+fn matches_predicate(
+    document: &Document,
+    predicate: &Predicate,
+    node: &Node,
+    position: usize,
+    size: usize,
+) -> bool {
+    match predicate {
+        Predicate::Or(left, right) => {
+            matches_predicate(document, left, node, position, size)
+                || matches_predicate(document, right, node, position, size)
+        }
+        Predicate::And(left, right) => {
+            matches_predicate(document, left, node, position, size)
+                && matches_predicate(document, right, node, position, size)
+        }
+        Predicate::Not(predicate) => !matches_predicate(document, predicate, node, position, size),
+        Predicate::Position(expected) => position == *expected,
+        Predicate::Last => position == size,
+        Predicate::Attribute { test, comparison } => {
+            let element = match node.element {
+                Some(element) => element,
+                None => return false,
+            };
+
+            // Like in XPath, namespace declarations are not attributes
+            element
+                .attributes
+                .iter()
+                .filter(|attribute| namespace_declaration(attribute).is_none())
+                .any(|attribute| {
+                    matches_name(test, node.scope.attribute_name(attribute))
+                        && matches_comparison(
+                            comparison,
+                            attribute.value.as_deref().unwrap_or_default(),
+                        )
+                })
+        }
+        Predicate::Child {
+            test,
+            predicates,
+            comparison,
+        } => {
+            let children = node
+                .child_elements(document)
+                .into_iter()
+                .filter(|child| {
+                    matches_name(test, child.scope.element_name(child.element.unwrap()))
+                })
+                .collect();
+
+            filter_by_predicates(document, predicates, children)
+                .iter()
+                .any(|child| {
+                    comparison.is_none()
+                        || matches_comparison(comparison, &text_content(child.element.unwrap()))
+                })
+        }
+    }
+}
+
+fn filter_by_predicates<'a>(
+    document: &Document,
+    predicates: &[Predicate],
+    mut candidates: Vec<Node<'a>>,
+) -> Vec<Node<'a>> {
+    // Positions are relative to the candidates of each node, i.e. `//g[1]` is the
+    // first `g` of every parent
+    for predicate in predicates {
+        let size = candidates.len();
+
+        candidates = candidates
+            .into_iter()
+            .enumerate()
+            .filter(|(index, candidate)| {
+                matches_predicate(document, predicate, candidate, index + 1, size)
+            })
+            .map(|(_, candidate)| candidate)
+            .collect();
+    }
+
+    candidates
+}
+
+/// Returns the nodes selected by `selector` in document order.
+fn evaluate<'a>(document: &'a Document, selector: &Selector) -> Vec<Node<'a>> {
+    let mut nodes = vec![Node {
+        path: vec![],
+        element: None,
+        scope: Default::default(),
+    }];
+
+    for step in &selector.steps {
+        let mut selected = vec![];
+
+        for node in &nodes {
+            let candidates = match step.axis {
+                Axis::Child => node
+                    .child_elements(document)
+                    .into_iter()
+                    .filter(|child| {
+                        matches_name(&step.test, child.scope.element_name(child.element.unwrap()))
+                    })
+                    .collect(),
+                Axis::SelfNode => vec![node.clone()],
+                Axis::DescendantOrSelf => {
+                    let mut descendants = vec![];
+
+                    node.descendants_or_self(document, &mut descendants);
+
+                    descendants
+                }
+            };
+
+            selected.extend(filter_by_predicates(document, &step.predicates, candidates));
+        }
+
+        selected.sort_by(|a, b| a.path.cmp(&b.path));
+        selected.dedup_by(|a, b| a.path == b.path);
+
+        nodes = selected;
+    }
+
+    nodes.retain(|node| node.element.is_some());
+    nodes
+}
+
+/// Returns the elements selected by `selector` in document order.
+pub fn select<'a>(document: &'a Document, selector: &Selector) -> Vec<&'a Element> {
+    evaluate(document, selector)
+        .into_iter()
+        .filter_map(|node| node.element)
+        .collect()
+}
+
+/// Returns the first element selected by `selector` in document order.
+pub fn select_first<'a>(document: &'a Document, selector: &Selector) -> Option<&'a Element> {
+    select(document, selector).into_iter().next()
+}
+
+fn element_at_mut<'a>(document: &'a mut Document, path: &[usize]) -> &'a mut Element {
+    let mut children = &mut document.children;
+    let (last, path) = path.split_last().expect("the document is not an element");
+
+    for index in path {
+        children = match &mut children[*index] {
+            Child::Element(element) => &mut element.children,
+            _ => unreachable!(),
+        };
+    }
+
+    match &mut children[*last] {
+        Child::Element(element) => element,
+        _ => unreachable!(),
+    }
+}
+
+/// Calls `op` with every element selected by `selector`.
+///
+/// Elements are visited in reverse document order, so descendants are visited
+/// before their ancestors and `op` can modify the children of an element
+/// without affecting the other selected elements.
+pub fn modify<F>(document: &mut Document, selector: &Selector, mut op: F)
+where
+    F: FnMut(&mut Element),
+{
+    let paths: Vec<_> = evaluate(document, selector)
+        .into_iter()
+        .map(|node| node.path)
+        .collect();
+
+    for path in paths.iter().rev() {
+        op(element_at_mut(document, path));
+    }
+}
+
+/// Removes the elements selected by `selector` and returns them in document
+/// order.
+///
+/// Selected descendants of removed elements are removed with them, they are not
+/// returned separately.
+pub fn remove(document: &mut Document, selector: &Selector) -> Vec<Element> {
+    let mut paths: Vec<_> = evaluate(document, selector)
+        .into_iter()
+        .map(|node| node.path)
+        .collect();
+
+    // In document order, ancestors are followed by their descendants
+    paths.dedup_by(|descendant, ancestor| descendant.starts_with(ancestor));
+
+    let mut removed = Vec::with_capacity(paths.len());
+
+    for path in paths.iter().rev() {
+        let (last, parent) = path.split_last().unwrap();
+        let children = if parent.is_empty() {
+            &mut document.children
+        } else {
+            &mut element_at_mut(document, parent).children
+        };
+
+        if let Child::Element(element) = children.remove(*last) {
+            removed.push(element);
+        }
+    }
+
+    removed.reverse();
+    removed
+}
+
+/// Returns the text of `element` and its descendants.
+pub fn text_content(element: &Element) -> String {
+    fn collect(children: &[Child], text: &mut String) {
+        for child in children {
+            match child {
+                Child::Text(n) => text.push_str(&n.data),
+                Child::CdataSection(n) => text.push_str(&n.data),
+                Child::Element(n) => collect(&n.children, text),
+                _ => {}
+            }
+        }
+    }
+
+    let mut text = String::new();
+
+    collect(&element.children, &mut text);
+
+    text
+}
+
+/// Returns the value of the attribute with the qualified name `name`.
+pub fn get_attribute<'a>(element: &'a Element, name: &str) -> Option<&'a JsWord> {
+    element
+        .attributes
+        .iter()
+        .find(|attribute| attribute.name == *name)
+        .and_then(|attribute| attribute.value.as_ref())
+}
+
+/// Sets the value of the attribute with the qualified name `name`, the
+/// attribute is appended if it doesn't exist.
+pub fn set_attribute(element: &mut Element, name: &str, value: &str) {
+    match element
+        .attributes
+        .iter_mut()
+        .find(|attribute| attribute.name == *name)
+    {
+        Some(attribute) => {
+            attribute.value = Some(value.into());
+            attribute.raw_value = None;
+        }
+        None => element.attributes.push(Attribute {
+            span: DUMMY_SP,
+            namespace: None,
+            prefix: None,
+            name: name.into(),
+            raw_name: None,
+            value: Some(value.into()),
+            raw_value: None,
+        }),
+    }
+}
+
+/// Removes the attribute with the qualified name `name`.
+pub fn remove_attribute(element: &mut Element, name: &str) -> Option<Attribute> {
+    let index = element
+        .attributes
+        .iter()
+        .position(|attribute| attribute.name == *name)?;
+
+    Some(element.attributes.remove(index))
+}
+
+/// Replaces the children of `element` with a text.
+pub fn set_text_content(element: &mut Element, text: &str) {
+    element.children = vec![Child::Text(swc_xml_ast::Text {
+        span: DUMMY_SP,
+        data: text.into(),
+        raw: None,
+    })];
+}
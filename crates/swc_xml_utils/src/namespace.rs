@@ -0,0 +1,149 @@
+use std::rc::Rc;
+
+use swc_atoms::JsWord;
+use swc_common::collections::AHashMap;
+use swc_xml_ast::{Attribute, Element};
+
+/// `http://www.w3.org/XML/1998/namespace`, always bound to the `xml` prefix.
+pub const XML_NAMESPACE: &str = "http://www.w3.org/XML/1998/namespace";
+/// `http://www.w3.org/2000/xmlns/`, the namespace of `xmlns` attributes.
+pub const XMLNS_NAMESPACE: &str = "http://www.w3.org/2000/xmlns/";
+/// `http://www.w3.org/2000/svg`
+pub const SVG_NAMESPACE: &str = "http://www.w3.org/2000/svg";
+/// `http://www.w3.org/1999/xlink`
+pub const XLINK_NAMESPACE: &str = "http://www.w3.org/1999/xlink";
+
+/// Splits a qualified name into its prefix and local name, i.e. `xlink:href`
+/// into `(Some("xlink"), "href")`.
+pub fn split_qualified_name(name: &str) -> (Option<&str>, &str) {
+    match name.split_once(':') {
+        Some((prefix, local_name)) if !prefix.is_empty() && !local_name.is_empty() => {
+            (Some(prefix), local_name)
+        }
+        _ => (None, name),
+    }
+}
+
+/// Returns the prefix declared by `attribute` if it's a namespace
+/// declaration, i.e. `Some(None)` for `xmlns` and `Some(Some("svg"))` for
+/// `xmlns:svg`.
+pub fn namespace_declaration(attribute: &Attribute) -> Option<Option<&str>> {
+    match split_qualified_name(&attribute.name) {
+        (None, "xmlns") => Some(None),
+        (Some("xmlns"), prefix) => Some(Some(prefix)),
+        _ => None,
+    }
+}
+
+/// Namespaces in scope of an element.
+///
+/// `swc_xml_parser` keeps qualified names as-is, so namespaces are resolved by
+/// walking down the tree and collecting the `xmlns` attributes of ancestors.
+#[derive(Debug, Default, Clone)]
+pub struct NamespaceScope {
+    default_namespace: Option<JsWord>,
+    prefixes: AHashMap<JsWord, JsWord>,
+}
+
+impl NamespaceScope {
+    /// Returns the scope of `element`, which is shared with its parent if it
+    /// does not declare namespaces.
+    pub fn enter(parent: &Rc<NamespaceScope>, element: &Element) -> Rc<NamespaceScope> {
+        let mut scope: Option<NamespaceScope> = None;
+
+        for attribute in &element.attributes {
+            let prefix = match namespace_declaration(attribute) {
+                Some(prefix) => prefix,
+                None => continue,
+            };
+            let scope = scope.get_or_insert_with(|| (**parent).clone());
+            let uri = attribute.value.clone().filter(|uri| !uri.is_empty());
+
+            match (prefix, uri) {
+                (None, uri) => {
+                    scope.default_namespace = uri;
+                }
+                (Some(prefix), Some(uri)) => {
+                    scope.prefixes.insert(prefix.into(), uri);
+                }
+                (Some(prefix), None) => {
+                    scope.prefixes.remove(&JsWord::from(prefix));
+                }
+            }
+        }
+
+        match scope {
+            Some(scope) => Rc::new(scope),
+            None => parent.clone(),
+        }
+    }
+
+    /// Returns the namespace bound to `prefix`, or the default namespace if
+    /// `prefix` is [None].
+    pub fn lookup(&self, prefix: Option<&str>) -> Option<JsWord> {
+        match prefix {
+            Some("xml") => Some(XML_NAMESPACE.into()),
+            Some("xmlns") => Some(XMLNS_NAMESPACE.into()),
+            Some(prefix) => self.prefixes.get(&JsWord::from(prefix)).cloned(),
+            None => self.default_namespace.clone(),
+        }
+    }
+
+    /// Returns the namespace and the local name of `element`.
+    ///
+    /// An undeclared prefix is kept as a part of the local name.
+    pub fn element_name<'a>(&self, element: &'a Element) -> (Option<JsWord>, &'a str) {
+        match split_qualified_name(&element.tag_name) {
+            (Some(prefix), local_name) => match self.lookup(Some(prefix)) {
+                Some(namespace) => (Some(namespace), local_name),
+                None => (None, &element.tag_name),
+            },
+            (None, local_name) => (self.lookup(None), local_name),
+        }
+    }
+
+    /// Returns the namespace and the local name of `attribute`.
+    ///
+    /// Unlike elements, unprefixed attributes have no namespace.
+    pub fn attribute_name<'a>(&self, attribute: &'a Attribute) -> (Option<JsWord>, &'a str) {
+        match split_qualified_name(&attribute.name) {
+            (Some(prefix), local_name) => match self.lookup(Some(prefix)) {
+                Some(namespace) => (Some(namespace), local_name),
+                None => (None, &attribute.name),
+            },
+            (None, local_name) => (None, local_name),
+        }
+    }
+}
+
+/// Prefixes used in selectors.
+///
+/// They are independent from the prefixes of the document, i.e. `svg:rect`
+/// matches `<rect xmlns="http://www.w3.org/2000/svg"/>` if the `svg` prefix is
+/// bound to the SVG namespace here.
+#[derive(Debug, Default, Clone)]
+pub struct NamespaceContext {
+    /// The namespace of unprefixed element names. Like in XPath 1.0, they
+    /// match elements without a namespace by default.
+    pub default_element_namespace: Option<JsWord>,
+    pub prefixes: AHashMap<JsWord, JsWord>,
+}
+
+impl NamespaceContext {
+    pub fn with_prefix(mut self, prefix: &str, namespace: &str) -> Self {
+        self.prefixes.insert(prefix.into(), namespace.into());
+        self
+    }
+
+    pub fn with_default_element_namespace(mut self, namespace: &str) -> Self {
+        self.default_element_namespace = Some(namespace.into());
+        self
+    }
+
+    pub(crate) fn lookup(&self, prefix: &str) -> Option<JsWord> {
+        match prefix {
+            "xml" => Some(XML_NAMESPACE.into()),
+            _ => self.prefixes.get(&JsWord::from(prefix)).cloned(),
+        }
+    }
+}
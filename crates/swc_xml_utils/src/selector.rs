@@ -0,0 +1,441 @@
+use std::fmt;
+
+use swc_atoms::JsWord;
+
+use crate::namespace::NamespaceContext;
+
+/// A compiled selector, a subset of XPath 1.0 location paths.
+///
+/// Supported syntax:
+///
+///  - `/` and `//` separated steps, relative paths are evaluated from the
+///    document, i.e. `svg/g` is the same as `/svg/g`
+///  - name tests: `name`, `prefix:name`, `prefix:*`, `*` and `.`
+///  - predicates: `[@name]`, `[@prefix:name = 'value']`, `[@* != "value"]`,
+///    `[name]`, `[name[@name]]`, `[name = 'text']`, `[1]`, `[last()]`,
+///    `not(...)`, `and`, `or` and parentheses
+///
+/// Prefixes are resolved with the [NamespaceContext] passed to
+/// [Selector::parse].
+#[derive(Debug, Clone)]
+pub struct Selector {
+    pub(crate) steps: Vec<Step>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Step {
+    pub axis: Axis,
+    pub test: NameTest,
+    pub predicates: Vec<Predicate>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Axis {
+    Child,
+    SelfNode,
+    /// `//` is `/descendant-or-self::node()/`
+    DescendantOrSelf,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum NameTest {
+    /// `*`, or `node()` for the implicit step of `//`
+    Any,
+    /// `None` as the local name is `prefix:*`
+    Name {
+        namespace: Option<JsWord>,
+        local_name: Option<JsWord>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Operator {
+    Equal,
+    NotEqual,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum Predicate {
+    Or(Box<Predicate>, Box<Predicate>),
+    And(Box<Predicate>, Box<Predicate>),
+    Not(Box<Predicate>),
+    Position(usize),
+    Last,
+    Attribute {
+        test: NameTest,
+        comparison: Option<(Operator, JsWord)>,
+    },
+    Child {
+        test: NameTest,
+        predicates: Vec<Predicate>,
+        comparison: Option<(Operator, JsWord)>,
+    },
+}
+
+/// An invalid or unsupported selector.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelectorError {
+    /// Byte offset in the selector.
+    pub position: usize,
+    pub message: String,
+}
+
+impl fmt::Display for SelectorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {}", self.message, self.position)
+    }
+}
+
+impl std::error::Error for SelectorError {}
+
+impl Selector {
+    pub fn parse(selector: &str, namespaces: &NamespaceContext) -> Result<Self, SelectorError> {
+        let mut parser = SelectorParser {
+            input: selector,
+            pos: 0,
+            namespaces,
+        };
+
+        parser.parse_selector()
+    }
+}
+
+struct SelectorParser<'a> {
+    input: &'a str,
+    pos: usize,
+    namespaces: &'a NamespaceContext,
+}
+
+fn is_name_start_char(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_' || !c.is_ascii()
+}
+
+fn is_name_char(c: char) -> bool {
+    is_name_start_char(c) || c.is_ascii_digit() || c == '-' || c == '.'
+}
+
+impl<'a> SelectorParser<'a> {
+    fn error<T>(&self, message: &str) -> Result<T, SelectorError> {
+        Err(SelectorError {
+            position: self.pos,
+            message: message.into(),
+        })
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn skip_whitespaces(&mut self) {
+        let rest = self.rest();
+
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespaces();
+        self.rest().chars().next()
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespaces();
+
+        if self.rest().starts_with(token) {
+            self.pos += token.len();
+
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), SelectorError> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            self.error(&format!("expected `{}`", token))
+        }
+    }
+
+    fn parse_selector(&mut self) -> Result<Selector, SelectorError> {
+        let mut steps = vec![];
+
+        if self.eat("//") {
+            steps.push(descendant_or_self());
+        } else {
+            self.eat("/");
+        }
+
+        loop {
+            steps.push(self.parse_step()?);
+
+            if self.eat("//") {
+                steps.push(descendant_or_self());
+            } else if !self.eat("/") {
+                break;
+            }
+        }
+
+        if self.peek().is_some() {
+            return self.error("unexpected character");
+        }
+
+        Ok(Selector { steps })
+    }
+
+    fn parse_step(&mut self) -> Result<Step, SelectorError> {
+        if self.eat("..") {
+            return self.error("the parent axis is not supported");
+        }
+
+        if self.eat(".") {
+            return Ok(Step {
+                axis: Axis::SelfNode,
+                test: NameTest::Any,
+                predicates: vec![],
+            });
+        }
+
+        let test = self.parse_name_test(false)?;
+        let predicates = self.parse_predicates()?;
+
+        Ok(Step {
+            axis: Axis::Child,
+            test,
+            predicates,
+        })
+    }
+
+    fn parse_predicates(&mut self) -> Result<Vec<Predicate>, SelectorError> {
+        let mut predicates = vec![];
+
+        while self.eat("[") {
+            predicates.push(self.parse_or()?);
+
+            self.expect("]")?;
+        }
+
+        Ok(predicates)
+    }
+
+    fn parse_child(&mut self) -> Result<Predicate, SelectorError> {
+        let test = self.parse_name_test(false)?;
+        let predicates = self.parse_predicates()?;
+        let comparison = self.parse_comparison()?;
+
+        Ok(Predicate::Child {
+            test,
+            predicates,
+            comparison,
+        })
+    }
+
+    fn parse_name(&mut self) -> Result<&'a str, SelectorError> {
+        self.skip_whitespaces();
+
+        let rest = self.rest();
+
+        match rest.chars().next() {
+            Some(c) if is_name_start_char(c) => {}
+            _ => return self.error("expected a name"),
+        }
+
+        let len = rest.find(|c| !is_name_char(c)).unwrap_or(rest.len());
+        let start = self.pos;
+
+        self.pos += len;
+
+        Ok(&self.input[start..self.pos])
+    }
+
+    /// Unprefixed attribute names have no namespace, unlike element names.
+    fn parse_name_test(&mut self, is_attribute: bool) -> Result<NameTest, SelectorError> {
+        if self.eat("*") {
+            return Ok(NameTest::Any);
+        }
+
+        let start = self.pos;
+        let name = self.parse_name()?;
+
+        // No whitespaces are allowed in qualified names
+        if !self.rest().starts_with(':') {
+            return Ok(NameTest::Name {
+                namespace: if is_attribute {
+                    None
+                } else {
+                    self.namespaces.default_element_namespace.clone()
+                },
+                local_name: Some(name.into()),
+            });
+        }
+
+        let namespace = match self.namespaces.lookup(name) {
+            Some(namespace) => namespace,
+            None => {
+                let message = format!("the `{}` prefix is not declared", name);
+
+                self.pos = start;
+
+                return self.error(&message);
+            }
+        };
+
+        self.pos += 1;
+
+        let local_name = if self.rest().starts_with('*') {
+            self.pos += 1;
+
+            None
+        } else {
+            Some(self.parse_name()?.into())
+        };
+
+        Ok(NameTest::Name {
+            namespace: Some(namespace),
+            local_name,
+        })
+    }
+
+    fn parse_comparison(&mut self) -> Result<Option<(Operator, JsWord)>, SelectorError> {
+        let operator = if self.eat("!=") {
+            Operator::NotEqual
+        } else if self.eat("=") {
+            Operator::Equal
+        } else {
+            return Ok(None);
+        };
+
+        self.skip_whitespaces();
+
+        let quote = match self.rest().chars().next() {
+            Some(quote @ ('\'' | '"')) => quote,
+            _ => return self.error("expected a string literal"),
+        };
+        let value = &self.rest()[1..];
+        let len = match value.find(quote) {
+            Some(len) => len,
+            None => return self.error("unterminated string literal"),
+        };
+        let value = JsWord::from(&value[..len]);
+
+        self.pos += len + 2;
+
+        Ok(Some((operator, value)))
+    }
+
+    fn parse_or(&mut self) -> Result<Predicate, SelectorError> {
+        let mut left = self.parse_and()?;
+
+        while self.eat_keyword("or") {
+            let right = self.parse_and()?;
+
+            left = Predicate::Or(Box::new(left), Box::new(right));
+        }
+
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<Predicate, SelectorError> {
+        let mut left = self.parse_unary()?;
+
+        while self.eat_keyword("and") {
+            let right = self.parse_unary()?;
+
+            left = Predicate::And(Box::new(left), Box::new(right));
+        }
+
+        Ok(left)
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        self.skip_whitespaces();
+
+        let rest = self.rest();
+
+        if rest.starts_with(keyword) && !rest[keyword.len()..].starts_with(is_name_char) {
+            self.pos += keyword.len();
+
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse_unary(&mut self) -> Result<Predicate, SelectorError> {
+        match self.peek() {
+            Some('@') => {
+                self.pos += 1;
+
+                let test = self.parse_name_test(true)?;
+                let comparison = self.parse_comparison()?;
+
+                Ok(Predicate::Attribute { test, comparison })
+            }
+            Some('(') => {
+                self.pos += 1;
+
+                let predicate = self.parse_or()?;
+
+                self.expect(")")?;
+
+                Ok(predicate)
+            }
+            Some(c) if c.is_ascii_digit() => {
+                let rest = self.rest();
+                let len = rest
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(rest.len());
+                let position = match rest[..len].parse() {
+                    Ok(position) => position,
+                    Err(..) => return self.error("invalid position"),
+                };
+
+                self.pos += len;
+
+                Ok(Predicate::Position(position))
+            }
+            Some(c) if is_name_start_char(c) => {
+                let start = self.pos;
+                let name = self.parse_name()?;
+
+                if !self.eat("(") {
+                    self.pos = start;
+
+                    return self.parse_child();
+                }
+
+                match name {
+                    "not" => {
+                        let predicate = self.parse_or()?;
+
+                        self.expect(")")?;
+
+                        Ok(Predicate::Not(Box::new(predicate)))
+                    }
+                    "last" => {
+                        self.expect(")")?;
+
+                        Ok(Predicate::Last)
+                    }
+                    _ => {
+                        let message = format!("the `{}` function is not supported", name);
+
+                        self.pos = start;
+
+                        self.error(&message)
+                    }
+                }
+            }
+            Some('*') => self.parse_child(),
+            _ => self.error("expected a predicate"),
+        }
+    }
+}
+
+fn descendant_or_self() -> Step {
+    Step {
+        axis: Axis::DescendantOrSelf,
+        test: NameTest::Any,
+        predicates: vec![],
+    }
+}
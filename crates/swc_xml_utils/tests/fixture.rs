@@ -0,0 +1,127 @@
+#![deny(warnings)]
+
+use std::{
+    fs::read_to_string,
+    path::{Path, PathBuf},
+};
+
+use swc_common::SourceMapper;
+use swc_xml_ast::Document;
+use swc_xml_codegen::{
+    writer::basic::{BasicXmlWriter, BasicXmlWriterConfig},
+    CodeGenerator, CodegenConfig, Emit,
+};
+use swc_xml_parser::parse_file_as_document;
+use swc_xml_utils::{modify, remove, select, set_attribute, NamespaceContext, Selector};
+use testing::NormalizedOutput;
+
+/// Reads `selectors.txt`, where `xmlns:prefix=uri` and `xmlns=uri` lines
+/// declare the prefixes of the following selectors.
+fn read_selectors(dir: &Path) -> Vec<(String, Result<Selector, String>)> {
+    let selectors = read_to_string(dir.join("selectors.txt")).expect("failed to read selectors");
+    let mut namespaces = NamespaceContext::default();
+    let mut result = vec![];
+
+    for line in selectors.lines().filter(|line| !line.trim().is_empty()) {
+        if let Some(declaration) = line.strip_prefix("xmlns") {
+            match declaration.split_once('=').unwrap() {
+                ("", uri) => {
+                    namespaces = namespaces.with_default_element_namespace(uri);
+                }
+                (prefix, uri) => {
+                    namespaces = namespaces.with_prefix(prefix.trim_start_matches(':'), uri);
+                }
+            }
+
+            continue;
+        }
+
+        let selector = Selector::parse(line, &namespaces).map_err(|err| err.to_string());
+
+        result.push((line.to_string(), selector));
+    }
+
+    result
+}
+
+#[testing::fixture("tests/fixture/**/input.xml")]
+fn test_select(input: PathBuf) {
+    let dir = input.parent().unwrap();
+    let output = dir.join("output.txt");
+
+    testing::run_test(false, |cm, _| {
+        let fm = cm.load_file(&input).unwrap();
+        let mut errors = vec![];
+        let document: Document =
+            parse_file_as_document(&fm, Default::default(), &mut errors).unwrap();
+        let mut result = String::new();
+
+        for (source, selector) in read_selectors(dir) {
+            result.push_str(&format!("{}\n", source));
+
+            let selector = match selector {
+                Ok(selector) => selector,
+                Err(err) => {
+                    result.push_str(&format!("  error: {}\n\n", err));
+
+                    continue;
+                }
+            };
+
+            for element in select(&document, &selector) {
+                let snippet = cm.span_to_snippet(element.span).unwrap();
+                let first_line = snippet.lines().next().unwrap_or_default();
+
+                result.push_str(&format!("  {}\n", first_line));
+            }
+
+            result.push('\n');
+        }
+
+        NormalizedOutput::new_raw(result)
+            .compare_to_file(&output)
+            .unwrap();
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+/// Removes the elements selected by the first selector, and marks the ones
+/// selected by the others with a `selected` attribute.
+#[testing::fixture("tests/remove/**/input.xml")]
+fn test_remove(input: PathBuf) {
+    let dir = input.parent().unwrap();
+    let output = dir.join("output.xml");
+
+    testing::run_test(false, |cm, _| {
+        let fm = cm.load_file(&input).unwrap();
+        let mut errors = vec![];
+        let mut document: Document =
+            parse_file_as_document(&fm, Default::default(), &mut errors).unwrap();
+        let mut selectors = read_selectors(dir).into_iter();
+
+        let (_, selector) = selectors.next().unwrap();
+
+        remove(&mut document, &selector.unwrap());
+
+        for (source, selector) in selectors {
+            modify(&mut document, &selector.unwrap(), |element| {
+                set_attribute(element, "selected", &source);
+            });
+        }
+
+        let mut xml_str = String::new();
+        let wr = BasicXmlWriter::new(&mut xml_str, None, BasicXmlWriterConfig::default());
+        let mut gen = CodeGenerator::new(wr, CodegenConfig::default());
+
+        gen.emit(&document).unwrap();
+
+        NormalizedOutput::new_raw(xml_str)
+            .compare_to_file(&output)
+            .unwrap();
+
+        Ok(())
+    })
+    .unwrap();
+}
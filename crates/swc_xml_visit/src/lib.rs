@@ -36,9 +36,70 @@ define!({
         pub name: Option<JsWord>,
         pub public_id: Option<JsWord>,
         pub system_id: Option<JsWord>,
+        pub internal_subset: Vec<DtdDeclaration>,
         pub raw: Option<JsWord>,
     }
 
+    pub enum DtdDeclaration {
+        Element(ElementDeclaration),
+        AttributeList(AttributeListDeclaration),
+        Entity(EntityDeclaration),
+        Notation(NotationDeclaration),
+        ParameterEntityReference(ParameterEntityReference),
+        Comment(Comment),
+        ProcessingInstruction(ProcessingInstruction),
+    }
+
+    pub struct ElementDeclaration {
+        pub span: Span,
+        pub name: JsWord,
+        pub content: JsWord,
+    }
+
+    pub struct AttributeListDeclaration {
+        pub span: Span,
+        pub element_name: JsWord,
+        pub attributes: Vec<AttributeDefinition>,
+    }
+
+    pub struct AttributeDefinition {
+        pub span: Span,
+        pub name: JsWord,
+        pub attribute_type: JsWord,
+        pub default_kind: Option<AttributeDefaultKind>,
+        pub default_value: Option<JsWord>,
+        pub raw_default_value: Option<JsWord>,
+    }
+
+    pub enum AttributeDefaultKind {
+        Required,
+        Implied,
+        Fixed,
+    }
+
+    pub struct EntityDeclaration {
+        pub span: Span,
+        pub is_parameter: bool,
+        pub name: JsWord,
+        pub value: Option<JsWord>,
+        pub raw_value: Option<JsWord>,
+        pub public_id: Option<JsWord>,
+        pub system_id: Option<JsWord>,
+        pub notation: Option<JsWord>,
+    }
+
+    pub struct NotationDeclaration {
+        pub span: Span,
+        pub name: JsWord,
+        pub public_id: Option<JsWord>,
+        pub system_id: Option<JsWord>,
+    }
+
+    pub struct ParameterEntityReference {
+        pub span: Span,
+        pub name: JsWord,
+    }
+
     pub enum Namespace {
         HTML,
         MATHML,
@@ -658,8 +658,23 @@ export interface JscConfig {
      * a name of the npm package can be resolved, or absolute path to .wasm binary.
      *
      * Second parameter of tuple is JSON based configuration for the plugin.
+     *
+     * Optional third parameter is a list of files or directories the plugin can
+     * resolve and read through the host.
+     *
+     * Optional fourth parameter limits the resources the plugin can use.
+     * `memory` is in bytes, `fuel` is the number of wasm instructions and
+     * `timeout` is in milliseconds. The transform fails if the plugin runs
+     * out of them.
      */
-    plugins?: Array<[string, Record<string, any>]>
+    plugins?: Array<
+      [
+        string,
+        Record<string, any>,
+        string[]?,
+        { memory?: number; fuel?: number; timeout?: number }?
+      ]
+    >
   },
 
   baseUrl?: string
@@ -668,6 +683,18 @@ export interface JscConfig {
     [from: string]: string[]
   }
 
+  /**
+   * Rules to rewrite module specifiers of imports, exports, `import()` and
+   * `require()` calls. Rules are applied in order, before `paths`, and each
+   * rule is applied to the result of the previous ones.
+   *
+   * `replacement` can refer to capture groups of `pattern` using `$1`.
+   */
+  rewriteImports?: Array<{
+    pattern: string,
+    replacement: string
+  }>
+
   minify?: JsMinifyOptions;
 
   preserveAllComments?: boolean;
@@ -796,6 +823,12 @@ export interface TransformConfig {
    */
   decoratorMetadata?: boolean;
 
+  /**
+   * Keeps `null` and `undefined` of union types in decorator metadata, like
+   * `tsc` does with `strictNullChecks`.
+   */
+  strictNullChecks?: boolean;
+
   treatConstEnumAsEnum?: boolean;
 
   useDefineForClassFields?: boolean;
@@ -850,6 +883,18 @@ export interface ReactConfig {
    * Declares the module specifier to be used for importing the `jsx` and `jsxs` factory functions when using `runtime` 'automatic'
    */
   importSource?: string
+
+  /**
+   * Hoist JSX elements which never change to the module scope.
+   * Ignored if `development` is `true`. Defaults to false.
+   */
+  constantElements?: boolean;
+
+  /**
+   * Create react elements as object literals using the `jsx` helper instead of `React.createElement`.
+   * Ignored if `development` is `true`. Defaults to false.
+   */
+  inlineElements?: boolean;
 }
 /**
  *  - `import { DEBUG } from '@ember/env-flags';`
@@ -1070,6 +1115,12 @@ export interface Es6Config extends BaseModuleConfig {
   type: "es6";
 }
 
+/**
+ * Emits ES modules or CommonJS modules per file, like `"module": "nodenext"`
+ * of TypeScript. `.mts` / `.mjs` files are ES modules, `.cts` / `.cjs` files
+ * are CommonJS modules and other files follow the `type` field of the nearest
+ * `package.json`.
+ */
 export interface NodeNextConfig extends BaseModuleConfig {
   type: "nodenext";
 }